@@ -5,7 +5,7 @@ use rustc_hash::{FxHashMap, FxHashSet};
 use scc::hash_map::Entry;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(tag = "state", content = "hash", rename_all = "kebab-case")]
@@ -54,6 +54,11 @@ pub struct ActionContext {
     #[serde(skip)]
     pub named_mutexes: scc::HashMap<String, Arc<Mutex<()>>>,
 
+    /// Active semaphores for tasks to acquire permits from.
+    /// @mutable
+    #[serde(skip)]
+    pub named_semaphores: scc::HashMap<String, Arc<Semaphore>>,
+
     /// Dependency edges that were intentionally ignored by graph options.
     #[serde(default, skip_serializing_if = "FxHashMap::is_empty")]
     pub ignored_dependencies: FxHashMap<Target, FxHashSet<Target>>,
@@ -70,6 +75,9 @@ pub struct ActionContext {
 
     /// Files that have currently been changed.
     pub changed_files: FxHashSet<WorkspaceRelativePathBuf>,
+
+    /// Trace the file system access of tasks (via `--trace-fs`).
+    pub trace_fs: bool,
 }
 
 impl ActionContext {
@@ -84,6 +92,19 @@ impl ActionContext {
         }
     }
 
+    /// The first task to create the semaphore determines the number of permits,
+    /// which are validated to be the same across tasks when building the graph.
+    pub async fn get_or_create_semaphore(&self, name: &str, permits: usize) -> Arc<Semaphore> {
+        match self.named_semaphores.entry_async(name.to_owned()).await {
+            Entry::Occupied(entry) => Arc::clone(&entry),
+            Entry::Vacant(entry) => {
+                let semaphore = Arc::new(Semaphore::new(permits));
+                entry.insert_entry(Arc::clone(&semaphore));
+                semaphore
+            }
+        }
+    }
+
     pub fn get_target_prefix<T: AsRef<Target>>(&self, target: T) -> String {
         target.as_ref().to_prefix(
             self.primary_targets
@@ -47,6 +47,24 @@ impl ActionGraph {
         self.nodes.len()
     }
 
+    /// Return the indices of the direct dependencies for each node.
+    pub fn get_dependency_indices(&self) -> FxHashMap<usize, Vec<usize>> {
+        let graph = self.graph.graph();
+
+        graph
+            .node_indices()
+            .map(|index| {
+                let mut deps = graph
+                    .neighbors_directed(index, Direction::Outgoing)
+                    .map(|dep_index| dep_index.index())
+                    .collect::<Vec<_>>();
+                deps.sort();
+
+                (index.index(), deps)
+            })
+            .collect()
+    }
+
     pub fn group_priorities(&self, topo_indices: Vec<NodeIndex>) -> BTreeMap<u8, Vec<NodeIndex>> {
         let mut groups = BTreeMap::default();
 
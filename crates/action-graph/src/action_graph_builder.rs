@@ -1,5 +1,6 @@
 use crate::action_graph::ActionGraph;
 use crate::action_graph_error::ActionGraphError;
+use crate::job_balancer::{TaskTimings, balance_by_duration};
 use daggy::{Dag, Walker};
 use miette::IntoDiagnostic;
 use moon_action::{
@@ -12,7 +13,7 @@ use moon_app_context::AppContext;
 use moon_common::path::{PathExt, WorkspaceRelativePathBuf};
 use moon_common::{Id, color, is_ci};
 use moon_config::{EnvMap, PipelineActionSwitch, TaskDependencyConfig, TaskDependencyType};
-use moon_exec_plan::{ExecutionPlan, TargetsBlock};
+use moon_exec_plan::{ExecutionPlan, JobBalance, TargetsBlock};
 use moon_pdk_api::{DefineRequirementsInput, LocateDependenciesRootInput};
 use moon_project::{Project, ProjectError};
 use moon_query::{Criteria, build_query};
@@ -61,6 +62,7 @@ pub struct RunRequirements {
     pub interactive: bool,           // Entire pipeline is interactive
     pub job: Option<usize>,          // Current job index
     pub job_total: Option<usize>,    // Total amount of jobs
+    pub job_balance: JobBalance,     // How to distribute tasks across jobs
     pub skip_affected: bool,         // Skip all affected checks
 }
 
@@ -75,6 +77,7 @@ impl Default for RunRequirements {
             interactive: false,
             job: None,
             job_total: None,
+            job_balance: JobBalance::Count,
             skip_affected: false,
         }
     }
@@ -144,6 +147,7 @@ pub struct ActionGraphBuilder<'query> {
     ignored_dependents: FxHashSet<Target>,
     passthrough_targets: FxHashSet<Target>,
     primary_targets: FxHashSet<Target>,
+    task_timings: TaskTimings,
 
     // Serial ordering edges added by `try_link_requirements`. Tracked so the
     // serial subtree walk doesn't follow them as if they were real dependency
@@ -172,6 +176,7 @@ impl<'query> ActionGraphBuilder<'query> {
             passthrough_targets: FxHashSet::default(),
             primary_targets: FxHashSet::default(),
             serial_edges: FxHashSet::default(),
+            task_timings: TaskTimings::default(),
             changed_files: None,
             workspace_graph,
         })
@@ -288,6 +293,10 @@ impl<'query> ActionGraphBuilder<'query> {
         Ok(())
     }
 
+    pub fn set_task_timings(&mut self, timings: TaskTimings) {
+        self.task_timings = timings;
+    }
+
     pub async fn track_affected(
         &mut self,
         upstream: UpstreamScope,
@@ -670,9 +679,35 @@ impl<'query> ActionGraphBuilder<'query> {
                 tasks = new_tasks;
             }
 
-            // Then slice and partition the tasks based on the job index and total
-            let size = tasks.len().div_ceil(job_total);
-            let (start, stop) =
+            // Balance the tasks based on their duration, and keep tasks
+            // that depend on other tasks within the same job
+            if matches!(reqs.job_balance, JobBalance::Duration) {
+                let items = tasks
+                    .iter()
+                    .map(|task| (task.target.clone(), self.get_task_closure(task, &reqs)))
+                    .collect::<Vec<_>>();
+
+                let mut jobs = balance_by_duration(&items, &self.task_timings, job_total);
+                let mut indices = mem::take(&mut jobs[job_index]).into_iter().peekable();
+
+                debug!(
+                    job = job_index,
+                    total = job_total,
+                    "Balancing {} tasks across jobs by duration",
+                    tasks.len(),
+                );
+
+                tasks = tasks
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(index, task)| indices.next_if_eq(&index).map(|_| task))
+                    .collect();
+
+                partition.size = Some(tasks.len());
+            } else {
+                // Then slice and partition the tasks based on the job index and total
+                let size = tasks.len().div_ceil(job_total);
+                let (start, stop) =
                 // beginning
                 if job_index == 0 {
                     (0, size)
@@ -686,15 +721,16 @@ impl<'query> ActionGraphBuilder<'query> {
                     ((size * job_index), (size * (job_index + 1)))
                 };
 
-            if tasks.get(start).is_some() {
-                if tasks.get(stop).is_some() {
-                    tasks = tasks[start..stop].to_vec();
-                } else {
-                    tasks = tasks[start..].to_vec();
+                if tasks.get(start).is_some() {
+                    if tasks.get(stop).is_some() {
+                        tasks = tasks[start..stop].to_vec();
+                    } else {
+                        tasks = tasks[start..].to_vec();
+                    }
                 }
-            }
 
-            partition.size = Some(size);
+                partition.size = Some(size);
+            }
         }
 
         for task in tasks {
@@ -706,6 +742,19 @@ impl<'query> ActionGraphBuilder<'query> {
         Ok(partition)
     }
 
+    // The task and the dependencies that will be ran alongside it
+    fn get_task_closure(&self, task: &Task, reqs: &RunRequirements) -> FxHashSet<Target> {
+        let mut closure = FxHashSet::from_iter([task.target.clone()]);
+
+        closure.extend(match reqs.dependencies {
+            UpstreamScope::None => vec![],
+            UpstreamScope::Direct => self.workspace_graph.tasks.dependencies_of(task),
+            UpstreamScope::Deep => self.workspace_graph.tasks.deep_dependencies_of(task),
+        });
+
+        closure
+    }
+
     #[instrument(skip(self, plan))]
     pub async fn run_tasks_with_plan(
         &mut self,
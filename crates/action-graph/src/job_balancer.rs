@@ -0,0 +1,164 @@
+use moon_common::Id;
+use moon_task::Target;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::cmp::Reverse;
+use std::time::Duration;
+
+/// The duration assumed for tasks when no timings are known at all.
+const DEFAULT_TASK_DURATION: Duration = Duration::from_secs(1);
+
+/// Known durations of tasks, typically loaded from previous runs,
+/// and used for balancing targets across jobs.
+#[derive(Debug, Default)]
+pub struct TaskTimings {
+    /// Durations of specific targets.
+    pub targets: FxHashMap<Target, Duration>,
+
+    /// Durations bucketed by task ID. Used as a fallback
+    /// when a target does not have its own duration.
+    pub tasks: FxHashMap<Id, Duration>,
+}
+
+impl TaskTimings {
+    pub fn get(&self, target: &Target) -> Option<Duration> {
+        self.targets.get(target).copied().or_else(|| {
+            target
+                .get_task_id()
+                .ok()
+                .and_then(|task_id| self.tasks.get(task_id).copied())
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty() && self.tasks.is_empty()
+    }
+}
+
+fn find_root(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+
+    while parents[root] != root {
+        root = parents[root];
+    }
+
+    parents[index] = root;
+    root
+}
+
+/// Group items where the closure (the item's target and its dependencies) of one
+/// item contains the primary target of another item, as the dependent must run in
+/// the same job as its dependency. Items that only share dependencies are not grouped,
+/// and those dependencies are ran in each job that requires them. Returns the
+/// indices of each group, and the combined closure of the group.
+pub fn group_by_primary_target(
+    items: &[(Target, FxHashSet<Target>)],
+) -> Vec<(Vec<usize>, FxHashSet<Target>)> {
+    let mut parents = (0..items.len()).collect::<Vec<_>>();
+    let primaries = items
+        .iter()
+        .enumerate()
+        .map(|(index, (target, _))| (target, index))
+        .collect::<FxHashMap<_, _>>();
+
+    for (index, (_, closure)) in items.iter().enumerate() {
+        for target in closure {
+            let Some(other) = primaries.get(target) else {
+                continue;
+            };
+
+            let a = find_root(&mut parents, *other);
+            let b = find_root(&mut parents, index);
+
+            // Always keep the lowest index as the root,
+            // so that groups are ordered deterministically
+            if a != b {
+                parents[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut groups = FxHashMap::<usize, (Vec<usize>, FxHashSet<Target>)>::default();
+
+    for (index, (_, closure)) in items.iter().enumerate() {
+        let root = find_root(&mut parents, index);
+        let group = groups.entry(root).or_default();
+
+        group.0.push(index);
+        group.1.extend(closure.iter().cloned());
+    }
+
+    let mut groups = groups.into_values().collect::<Vec<_>>();
+    groups.sort_by_key(|group| group.0[0]);
+    groups
+}
+
+/// Distribute items (a primary target and its closure) across the provided amount of
+/// jobs, so that the total duration of each job is as even as possible. Groups are placed
+/// longest first (longest processing time first) into the job that would finish the
+/// soonest, where dependencies already assigned to a job don't add to its duration,
+/// and are otherwise replicated. Returns the item indices for each job.
+pub fn balance_by_duration(
+    items: &[(Target, FxHashSet<Target>)],
+    timings: &TaskTimings,
+    job_total: usize,
+) -> Vec<Vec<usize>> {
+    if job_total == 0 {
+        return vec![];
+    }
+
+    // Targets without a known duration are assumed to be average
+    let known = items
+        .iter()
+        .flat_map(|(_, closure)| closure)
+        .collect::<FxHashSet<_>>()
+        .into_iter()
+        .filter_map(|target| timings.get(target))
+        .collect::<Vec<_>>();
+    let fallback = if known.is_empty() {
+        DEFAULT_TASK_DURATION
+    } else {
+        known.iter().sum::<Duration>() / known.len() as u32
+    };
+    let get_cost = |target: &Target| timings.get(target).unwrap_or(fallback);
+
+    let mut groups = group_by_primary_target(items)
+        .into_iter()
+        .map(|(indices, closure)| {
+            let cost = closure.iter().map(get_cost).sum::<Duration>();
+
+            (indices, closure, cost)
+        })
+        .collect::<Vec<_>>();
+
+    let mut jobs = vec![(Duration::ZERO, FxHashSet::<Target>::default(), vec![]); job_total];
+
+    // Stable sort, so groups of equal cost retain their order
+    groups.sort_by_key(|group| Reverse(group.2));
+
+    for (indices, closure, _) in groups {
+        let (job, cost) = jobs
+            .iter_mut()
+            .map(|job| {
+                let cost = closure
+                    .iter()
+                    .filter(|target| !job.1.contains(*target))
+                    .map(get_cost)
+                    .sum::<Duration>();
+
+                (job, cost)
+            })
+            .min_by_key(|(job, cost)| job.0 + *cost)
+            .expect("at least 1 job");
+
+        job.0 += cost;
+        job.1.extend(closure);
+        job.2.extend(indices);
+    }
+
+    jobs.into_iter()
+        .map(|(_, _, mut indices)| {
+            indices.sort();
+            indices
+        })
+        .collect()
+}
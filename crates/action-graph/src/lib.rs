@@ -1,8 +1,10 @@
 mod action_graph;
 mod action_graph_builder;
 mod action_graph_error;
+mod job_balancer;
 
 pub use action_graph::*;
 pub use action_graph_builder::*;
 pub use action_graph_error::*;
+pub use job_balancer::*;
 pub use moon_graph_utils::*;
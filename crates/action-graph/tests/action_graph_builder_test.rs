@@ -2,19 +2,20 @@ mod utils;
 
 use moon_action::*;
 use moon_action_context::TargetState;
-use moon_action_graph::{ActionGraph, ActionGraphBuilderOptions, RunRequirements};
+use moon_action_graph::{ActionGraph, ActionGraphBuilderOptions, RunRequirements, TaskTimings};
 use moon_affected::{AffectedBy, DownstreamScope, UpstreamScope};
 use moon_common::{Id, path::WorkspaceRelativePathBuf};
 use moon_config::{
     EnvMap, PROTO_CLI_VERSION, PipelineActionSwitch, TaskDependencyConfig, TaskOptionRunInCI,
     UnresolvedVersionSpec, Version, VersionSpec,
 };
-use moon_exec_plan::{ExecutionPlan, GraphBlock, TargetsBlock};
+use moon_exec_plan::{ExecutionPlan, GraphBlock, JobBalance, TargetsBlock};
 use moon_graph_utils::*;
 use moon_task::{Target, TargetLocator, Task, TaskFileInput};
 use moon_toolchain::ToolchainSpec;
 use rustc_hash::{FxHashMap, FxHashSet};
 use starbase_sandbox::{assert_snapshot, create_sandbox};
+use std::time::Duration;
 use utils::ActionGraphContainer;
 
 fn create_task(project: &str, id: &str) -> Task {
@@ -3991,6 +3992,159 @@ mod action_graph_builder {
             assert_eq!(context.primary_targets.len(), 2);
             assert_snapshot!(graph.to_dot());
         }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn partitions_by_duration() {
+            let sandbox = create_sandbox("tasks");
+            let mut container = ActionGraphContainer::new(sandbox.path());
+            let mut sizes = vec![];
+
+            for job in 0..3 {
+                let mut builder = container
+                    .create_builder(container.create_workspace_graph().await)
+                    .await;
+
+                builder.set_task_timings(TaskTimings {
+                    targets: FxHashMap::from_iter((1..=10).map(|index| {
+                        (
+                            Target::parse(&format!("partition:task-{index}")).unwrap(),
+                            Duration::from_secs(if index == 1 { 600 } else { 10 }),
+                        )
+                    })),
+                    ..Default::default()
+                });
+
+                builder
+                    .run_tasks(
+                        vec![TargetLocator::parse("partition:task-*").unwrap()],
+                        RunRequirements {
+                            job: Some(job),
+                            job_total: Some(3),
+                            job_balance: JobBalance::Duration,
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                    .unwrap();
+
+                let (context, _) = builder.build();
+
+                if job == 0 {
+                    assert_eq!(
+                        context.primary_targets,
+                        FxHashSet::from_iter([Target::parse("partition:task-1").unwrap()])
+                    );
+                }
+
+                sizes.push(context.primary_targets.len());
+            }
+
+            assert_eq!(sizes, [1, 5, 4]);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn partitions_by_duration_with_shared_deps() {
+            let sandbox = create_sandbox("tasks");
+            let mut container = ActionGraphContainer::new(sandbox.path());
+            let mut jobs = vec![];
+
+            for job in 0..2 {
+                let mut builder = container
+                    .create_builder(container.create_workspace_graph().await)
+                    .await;
+
+                builder
+                    .run_tasks(
+                        [
+                            "deps:parallel",
+                            "deps:chain1",
+                            "deps:serial",
+                            "deps:parent1",
+                        ]
+                        .into_iter()
+                        .map(|target| TargetLocator::Qualified(Target::parse(target).unwrap()))
+                        .collect::<Vec<_>>(),
+                        RunRequirements {
+                            job: Some(job),
+                            job_total: Some(2),
+                            job_balance: JobBalance::Duration,
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                    .unwrap();
+
+                let (context, _) = builder.build();
+
+                jobs.push(context.primary_targets);
+            }
+
+            // Parallel and serial share deps, so are cheaper to run in the same
+            // job, while the other targets are balanced into the other job
+            assert_eq!(
+                jobs[0],
+                FxHashSet::from_iter([
+                    Target::parse("deps:chain1").unwrap(),
+                    Target::parse("deps:parent1").unwrap(),
+                ])
+            );
+            assert_eq!(
+                jobs[1],
+                FxHashSet::from_iter([
+                    Target::parse("deps:parallel").unwrap(),
+                    Target::parse("deps:serial").unwrap(),
+                ])
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn partitions_by_duration_with_dependent_targets() {
+            let sandbox = create_sandbox("tasks");
+            let mut container = ActionGraphContainer::new(sandbox.path());
+            let mut jobs = vec![];
+
+            for job in 0..2 {
+                let mut builder = container
+                    .create_builder(container.create_workspace_graph().await)
+                    .await;
+
+                builder
+                    .run_tasks(
+                        ["deps:chain1", "deps:chain3", "deps:a", "deps:b"]
+                            .into_iter()
+                            .map(|target| TargetLocator::Qualified(Target::parse(target).unwrap()))
+                            .collect::<Vec<_>>(),
+                        RunRequirements {
+                            job: Some(job),
+                            job_total: Some(2),
+                            job_balance: JobBalance::Duration,
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                    .unwrap();
+
+                let (context, _) = builder.build();
+
+                jobs.push(context.primary_targets);
+            }
+
+            // Chain 3 is a dependency of chain 1, so must run in the same job
+            assert_eq!(
+                jobs[0],
+                FxHashSet::from_iter([
+                    Target::parse("deps:chain1").unwrap(),
+                    Target::parse("deps:chain3").unwrap(),
+                ])
+            );
+            assert_eq!(
+                jobs[1],
+                FxHashSet::from_iter([
+                    Target::parse("deps:a").unwrap(),
+                    Target::parse("deps:b").unwrap(),
+                ])
+            );
+        }
     }
 
     mod setup_env {
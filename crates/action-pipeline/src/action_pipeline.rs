@@ -2,11 +2,14 @@ use crate::event_emitter::{Event, EventEmitter};
 use crate::job::Job;
 use crate::job_context::JobContext;
 use crate::job_dispatcher::JobDispatcher;
+use crate::job_resources::{ResourceCapacity, ResourcePool};
 use crate::subscribers::cleanup_subscriber::CleanupSubscriber;
 use crate::subscribers::console_subscriber::ConsoleSubscriber;
+use crate::subscribers::history_subscriber::HistorySubscriber;
 use crate::subscribers::notifications_subscriber::NotificationsSubscriber;
 use crate::subscribers::reports_subscriber::ReportsSubscriber;
 // use crate::subscribers::telemetry_subscriber::TelemetrySubscriber;
+use crate::subscribers::trace_subscriber::TraceSubscriber;
 use crate::subscribers::webhooks_subscriber::WebhooksSubscriber;
 use miette::IntoDiagnostic;
 use moon_action::{Action, ActionNode, ActionPipelineStatus};
@@ -16,10 +19,11 @@ use moon_app_context::AppContext;
 use moon_common::{color, is_remote, is_test_env};
 use moon_console::Level;
 use moon_daemon_client::DaemonClient;
-use moon_process::{ProcessRegistry, SignalType};
+use moon_process::{ProcessRegistry, ProcessScope, SignalType};
 use moon_workspace_graph::WorkspaceGraph;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::mem;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, Semaphore, mpsc};
@@ -29,10 +33,13 @@ use tracing::{debug, instrument, warn};
 
 pub struct ActionPipeline {
     pub bail: bool,
+    pub cancel_token: Option<CancellationToken>,
     pub concurrency: usize,
+    pub process_scope: Option<ProcessScope>,
     pub quiet: bool,
     pub report_name: String,
     pub summary: Option<Level>,
+    pub trace_path: Option<PathBuf>,
 
     // State
     actions: Vec<Action>,
@@ -60,14 +67,17 @@ impl ActionPipeline {
             actions: vec![],
             app_context,
             bail: false,
+            cancel_token: None,
             concurrency: num_cpus::get(),
             daemon_client,
             duration: None,
             emitter: Arc::new(EventEmitter::default()),
+            process_scope: None,
             quiet: false,
             report_name: "runReport.json".into(),
             status: ActionPipelineStatus::Pending,
             summary: None,
+            trace_path: None,
             workspace_graph,
         }
     }
@@ -153,6 +163,8 @@ impl ActionPipeline {
         // Create job context
         let abort_token = CancellationToken::new();
         let cancel_token = CancellationToken::new();
+        let resource_capacity =
+            ResourceCapacity::detect(&self.app_context.workspace_config.pipeline.resources);
 
         let job_context = JobContext {
             abort_token: abort_token.clone(),
@@ -161,12 +173,16 @@ impl ActionPipeline {
             completed_jobs: Arc::new(RwLock::new(FxHashSet::default())),
             daemon_client: self.daemon_client.clone(),
             emitter: Arc::clone(&self.emitter),
+            resources: Arc::new(RwLock::new(ResourcePool::new(resource_capacity))),
             result_sender: sender,
             semaphore: Arc::new(Semaphore::new(self.concurrency)),
             running_jobs: Arc::new(RwLock::new(FxHashMap::default())),
             workspace_graph: self.workspace_graph.clone(),
         };
 
+        // Track dependencies so that reports can analyze the graph
+        let mut dependencies = action_graph.get_dependency_indices();
+
         // Monitor signals and ctrl+c
         let signal_handle = self.monitor_signals(cancel_token.clone());
 
@@ -182,7 +198,14 @@ impl ActionPipeline {
 
         while let Some(mut action) = receiver.recv().await {
             if job_context.should_abort(&action) {
-                process_registry.terminate_running();
+                // When scoped, only terminate our own processes, as other
+                // pipelines may be running in the same process
+                if let Some(scope) = &self.process_scope {
+                    process_registry.terminate_scope(scope).await;
+                } else {
+                    process_registry.terminate_running();
+                }
+
                 abort_token.cancel();
             }
 
@@ -196,6 +219,10 @@ impl ActionPipeline {
                 error = Some(action.get_error());
             }
 
+            if let Some(deps) = dependencies.remove(&action.node_index) {
+                action.dependencies = deps;
+            }
+
             actions.push(action);
 
             if abort_token.is_cancelled() {
@@ -230,7 +257,11 @@ impl ActionPipeline {
         }
 
         // Wait for running child processes to exit
-        process_registry.wait_for_running_to_shutdown().await;
+        if let Some(scope) = &self.process_scope {
+            process_registry.wait_for_scope_to_shutdown(scope).await;
+        } else {
+            process_registry.wait_for_running_to_shutdown().await;
+        }
 
         // Abort any running actions in progress
         if !matches!(self.status, ActionPipelineStatus::Completed) {
@@ -268,6 +299,7 @@ impl ActionPipeline {
         let priority_groups = action_graph.group_priorities(node_indices);
         let app_context = Arc::clone(&self.app_context);
         let action_context = Arc::clone(&self.action_context);
+        let process_scope = self.process_scope.clone();
 
         debug!(total_jobs = node_count, "Dispatching jobs in the pipeline");
 
@@ -324,12 +356,15 @@ impl ActionPipeline {
                 }
 
                 // Otherwise run the action topologically
-                job_handles.spawn(dispatch_job_with_permit(
-                    node.to_owned(),
-                    node_index.index(),
-                    job_context.clone(),
-                    Arc::clone(&app_context),
-                    Arc::clone(&action_context),
+                job_handles.spawn(dispatch_job_in_scope(
+                    process_scope.clone(),
+                    dispatch_job_with_permit(
+                        node.to_owned(),
+                        node_index.index(),
+                        job_context.clone(),
+                        Arc::clone(&app_context),
+                        Arc::clone(&action_context),
+                    ),
                 ));
 
                 // Run this in isolation by exhausting the current list of handles
@@ -372,12 +407,15 @@ impl ActionPipeline {
                     Some((node.to_owned(), node_index.index()))
                 })
                 .for_each(|(node, node_index)| {
-                    job_handles.spawn(dispatch_job(
-                        node,
-                        node_index,
-                        job_context.clone(),
-                        Arc::clone(&app_context),
-                        Arc::clone(&action_context),
+                    job_handles.spawn(dispatch_job_in_scope(
+                        process_scope.clone(),
+                        dispatch_job(
+                            node,
+                            node_index,
+                            job_context.clone(),
+                            Arc::clone(&app_context),
+                            Arc::clone(&action_context),
+                        ),
                     ));
                 });
 
@@ -386,16 +424,37 @@ impl ActionPipeline {
     }
 
     fn monitor_signals(&self, cancel_token: CancellationToken) -> JoinHandle<SignalType> {
+        let external_token = self.cancel_token.clone().unwrap_or_default();
+        let process_scope = self.process_scope.clone();
+
         tokio::spawn(async move {
-            let mut receiver = ProcessRegistry::instance().receive_signal();
+            let process_registry = ProcessRegistry::instance();
+            let mut receiver = process_registry.receive_signal();
 
-            if let Ok(signal) = receiver.recv().await {
-                cancel_token.cancel();
+            tokio::select! {
+                result = receiver.recv() => {
+                    if let Ok(signal) = result {
+                        cancel_token.cancel();
 
-                debug!("Received signal, shutting down pipeline");
+                        debug!("Received signal, shutting down pipeline");
 
-                return signal;
-            }
+                        return signal;
+                    }
+                }
+                _ = external_token.cancelled() => {
+                    cancel_token.cancel();
+
+                    debug!("Pipeline was cancelled, shutting down");
+
+                    // Jobs are not dropped when cancelled, so terminate the
+                    // processes they are waiting on
+                    if let Some(scope) = process_scope {
+                        process_registry.terminate_scope(&scope).await;
+                    }
+
+                    return SignalType::Interrupt;
+                }
+            };
 
             SignalType::Interrupt
         })
@@ -408,6 +467,7 @@ impl ActionPipeline {
             self.emitter
                 .subscribe(ConsoleSubscriber::new(
                     Arc::clone(&self.app_context.console),
+                    Arc::clone(&self.app_context.cache_engine),
                     self.summary,
                 ))
                 .await;
@@ -423,6 +483,23 @@ impl ActionPipeline {
             ))
             .await;
 
+        if let Some(trace_path) = &self.trace_path {
+            debug!(file = ?trace_path, "Subscribing trace export");
+
+            self.emitter
+                .subscribe(TraceSubscriber::new(trace_path))
+                .await;
+        }
+
+        debug!("Subscribing run history");
+
+        self.emitter
+            .subscribe(HistorySubscriber::new(
+                Arc::clone(&self.app_context.cache_engine),
+                &self.app_context.workspace_config.pipeline.history_retention,
+            ))
+            .await;
+
         // For security and privacy purposes, only send webhooks in a remote environment
         if (is_remote() || is_test_env())
             && let Some(webhook_url) = &self.app_context.workspace_config.notifier.webhook_url
@@ -533,6 +610,13 @@ async fn dispatch_job_with_permit(
     drop(permit);
 }
 
+async fn dispatch_job_in_scope(scope: Option<ProcessScope>, job: impl Future<Output = ()>) {
+    match scope {
+        Some(scope) => scope.run(job).await,
+        None => job.await,
+    };
+}
+
 #[instrument(skip_all)]
 async fn exhaust_job_handles<T: 'static>(set: &mut JoinSet<T>, job_context: &JobContext) -> bool {
     while set.join_next().await.is_some() {
@@ -1,4 +1,5 @@
 use crate::event_emitter::EventEmitter;
+use crate::job_resources::ResourcePool;
 use moon_action::Action;
 use moon_daemon_client::DaemonClient;
 use moon_workspace_graph::WorkspaceGraph;
@@ -28,6 +29,9 @@ pub struct JobContext {
     /// Internal pipeline event emitter
     pub emitter: Arc<EventEmitter>,
 
+    /// Resources reserved by running jobs (used by the dispatcher)
+    pub resources: Arc<RwLock<ResourcePool>>,
+
     /// Sends results to the parent pipeline
     pub result_sender: Sender<Action>,
 
@@ -48,6 +52,7 @@ impl JobContext {
 
     pub async fn mark_completed(&self, index: NodeIndex) {
         self.running_jobs.write().await.remove(&index);
+        self.resources.write().await.release(index);
         self.completed_jobs.write().await.insert(index);
     }
 
@@ -1,6 +1,7 @@
 use crate::job_context::JobContext;
 use moon_action::ActionNode;
 use moon_action_graph::{ActionGraph, ActionGraphType};
+use moon_task::TaskOptionResources;
 use petgraph::prelude::*;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::BTreeMap;
@@ -74,6 +75,62 @@ impl<'graph> JobDispatcher<'graph> {
         // Otherwise do nothing
         None
     }
+
+    pub fn get_required_resources(&self, node: &ActionNode) -> Option<TaskOptionResources> {
+        let ActionNode::RunTask(inner) = node else {
+            return None;
+        };
+
+        // Persistent tasks never complete, and would never release their resources
+        if inner.persistent {
+            return None;
+        }
+
+        self.context
+            .workspace_graph
+            .get_task(&inner.target)
+            .ok()
+            .and_then(|task| task.options.resources.clone())
+    }
+
+    pub async fn reserve_resources(
+        &self,
+        index: NodeIndex,
+        node: &ActionNode,
+        required: &TaskOptionResources,
+    ) -> bool {
+        let ActionNode::RunTask(inner) = node else {
+            return true;
+        };
+
+        let mut pool = self.context.resources.write().await;
+
+        if pool.try_reserve(index, required) {
+            debug!(
+                index = index.index(),
+                target = inner.target.as_str(),
+                cpu = required.cpu,
+                memory = required.memory,
+                "Reserved resources for task",
+            );
+
+            return true;
+        }
+
+        debug!(
+            index = index.index(),
+            target = inner.target.as_str(),
+            required_cpu = required.cpu,
+            required_memory = required.memory,
+            available_cpu = pool.get_available_cpu(),
+            available_memory = pool.get_available_memory(),
+            capacity_cpu = pool.capacity.cpu,
+            capacity_memory = pool.capacity.memory,
+            "Not enough resources available for task, deferring dispatch",
+        );
+
+        false
+    }
 }
 
 // This is based on the `Topo` struct from petgraph!
@@ -83,6 +140,8 @@ impl JobDispatcher<'_> {
         // Avoid repeatedly traversing the same blocked dependency subgraph
         // while a prerequisite action is still running.
         let mut traversed = FxHashSet::default();
+        // Whether a job was deferred because not enough resources were available
+        let mut deferred_for_resources = false;
 
         // Loop based on priority groups, from critical to low
         {
@@ -124,7 +183,22 @@ impl JobDispatcher<'_> {
 
                                 continue;
                             }
+                        }
+
+                        // If the task requires resources, only dispatch when enough
+                        // capacity is available. Once a job has been deferred, don't
+                        // dispatch lower priority jobs that require resources either,
+                        // otherwise they may continuously take the capacity that the
+                        // deferred job is waiting for, and starve it.
+                        if let Some(required) = self.get_required_resources(node)
+                            && (deferred_for_resources
+                                || !self.reserve_resources(index, node, &required).await)
+                        {
+                            deferred_for_resources = true;
+                            continue;
+                        }
 
+                        if id > 0 && node.is_standard() {
                             self.context.running_jobs.write().await.insert(index, id);
                         }
                     }
@@ -155,6 +229,7 @@ impl JobDispatcher<'_> {
 mod tests {
     use super::*;
     use crate::event_emitter::EventEmitter;
+    use crate::job_resources::{ResourceCapacity, ResourcePool};
     use moon_action::{Action, ActionNode, RunTaskNode, SyncProjectNode};
     use moon_action_graph::{ActionGraph, ActionGraphType};
     use moon_common::Id;
@@ -177,6 +252,10 @@ mod tests {
             completed_jobs: Arc::new(RwLock::new(FxHashSet::default())),
             daemon_client: None,
             emitter: Arc::new(EventEmitter::default()),
+            resources: Arc::new(RwLock::new(ResourcePool::new(ResourceCapacity {
+                cpu: 1,
+                memory: None,
+            }))),
             result_sender: sender,
             running_jobs: Arc::new(RwLock::new(FxHashMap::default())),
             semaphore: Arc::new(Semaphore::new(1)),
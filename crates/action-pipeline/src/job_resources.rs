@@ -0,0 +1,293 @@
+use moon_common::parse_byte_size;
+use moon_config::PipelineResourcesConfig;
+use moon_task::TaskOptionResources;
+use petgraph::graph::NodeIndex;
+use rustc_hash::FxHashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, trace};
+
+// cgroups v1 represents "no limit" as a page aligned `i64::MAX`
+const CGROUP_V1_UNLIMITED: u64 = 1 << 62;
+
+/// The amount of CPU cores and memory (in bytes) that tasks can reserve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResourceCapacity {
+    pub cpu: u32,
+
+    /// When `None`, memory could not be detected and is unlimited.
+    pub memory: Option<u64>,
+}
+
+impl ResourceCapacity {
+    /// Create the capacity from the workspace config, falling back to the
+    /// limits of the current cgroup, and then to the system's resources.
+    pub fn detect(config: &PipelineResourcesConfig) -> Self {
+        let cores = num_cpus::get() as u32;
+
+        let cpu = config
+            .cpu
+            .or_else(|| detect_cgroup_cpu().map(|cpu| cpu.min(cores)))
+            .unwrap_or(cores)
+            .max(1);
+
+        let memory = config
+            .memory
+            .as_deref()
+            .and_then(parse_byte_size)
+            .or_else(detect_cgroup_memory)
+            .or_else(detect_system_memory);
+
+        debug!(cpu, memory, "Detected resource capacity for the pipeline");
+
+        Self { cpu, memory }
+    }
+}
+
+/// Tracks the resources that are currently reserved by running jobs,
+/// so that jobs are only dispatched when enough capacity is available.
+#[derive(Debug)]
+pub struct ResourcePool {
+    pub capacity: ResourceCapacity,
+    reserved: FxHashMap<NodeIndex, (u32, u64)>,
+}
+
+impl ResourcePool {
+    pub fn new(capacity: ResourceCapacity) -> Self {
+        Self {
+            capacity,
+            reserved: FxHashMap::default(),
+        }
+    }
+
+    pub fn get_available_cpu(&self) -> u32 {
+        self.capacity
+            .cpu
+            .saturating_sub(self.reserved.values().map(|(cpu, _)| cpu).sum())
+    }
+
+    pub fn get_available_memory(&self) -> Option<u64> {
+        self.capacity.memory.map(|memory| {
+            memory.saturating_sub(self.reserved.values().map(|(_, memory)| memory).sum())
+        })
+    }
+
+    /// Reserve the required resources for the job if they are available.
+    /// Requirements that exceed the entire capacity are reduced to the
+    /// capacity, so that the job is ran once all other jobs have released.
+    pub fn try_reserve(&mut self, index: NodeIndex, required: &TaskOptionResources) -> bool {
+        let cpu = required.cpu.unwrap_or(0).min(self.capacity.cpu);
+        let memory = match (required.memory, self.capacity.memory) {
+            (Some(required), Some(capacity)) => required.min(capacity),
+            _ => 0,
+        };
+
+        if cpu > self.get_available_cpu()
+            || self
+                .get_available_memory()
+                .is_some_and(|available| memory > available)
+        {
+            return false;
+        }
+
+        self.reserved.insert(index, (cpu, memory));
+
+        true
+    }
+
+    pub fn release(&mut self, index: NodeIndex) {
+        if let Some((cpu, memory)) = self.reserved.remove(&index) {
+            trace!(
+                index = index.index(),
+                cpu,
+                memory,
+                "Released resources of job",
+            );
+        }
+    }
+}
+
+fn get_cgroup_dirs() -> Vec<PathBuf> {
+    let root = PathBuf::from("/sys/fs/cgroup");
+    let mut dirs = vec![];
+
+    // cgroups v2 has a single unified hierarchy: `0::/path`
+    if let Ok(content) = fs::read_to_string("/proc/self/cgroup") {
+        for line in content.lines() {
+            if let Some(path) = line.strip_prefix("0::") {
+                let path = path.trim().trim_start_matches('/');
+
+                if !path.is_empty() {
+                    dirs.push(root.join(path));
+                }
+            }
+        }
+    }
+
+    dirs.push(root);
+    dirs
+}
+
+fn read_file(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok()
+}
+
+fn detect_cgroup_cpu() -> Option<u32> {
+    for dir in get_cgroup_dirs() {
+        if let Some(cpu) = read_file(&dir.join("cpu.max")).and_then(|c| parse_cgroup_cpu_max(&c)) {
+            return Some(cpu);
+        }
+    }
+
+    let v1_dir = PathBuf::from("/sys/fs/cgroup/cpu");
+
+    parse_cgroup_cpu_quota(
+        &read_file(&v1_dir.join("cpu.cfs_quota_us"))?,
+        &read_file(&v1_dir.join("cpu.cfs_period_us"))?,
+    )
+}
+
+fn detect_cgroup_memory() -> Option<u64> {
+    for dir in get_cgroup_dirs() {
+        if let Some(memory) =
+            read_file(&dir.join("memory.max")).and_then(|c| parse_cgroup_memory_max(&c))
+        {
+            return Some(memory);
+        }
+    }
+
+    read_file(Path::new("/sys/fs/cgroup/memory/memory.limit_in_bytes"))
+        .and_then(|c| parse_cgroup_memory_max(&c))
+}
+
+fn detect_system_memory() -> Option<u64> {
+    parse_meminfo_total(&read_file(Path::new("/proc/meminfo"))?)
+}
+
+/// Parse the v2 `cpu.max` file, in the format of `<quota> <period>`.
+fn parse_cgroup_cpu_max(content: &str) -> Option<u32> {
+    let (quota, period) = content.trim().split_once(' ')?;
+
+    parse_cgroup_cpu_quota(quota, period)
+}
+
+fn parse_cgroup_cpu_quota(quota: &str, period: &str) -> Option<u32> {
+    // "max" (v2) or "-1" (v1) is unlimited
+    let quota: u64 = quota.trim().parse().ok()?;
+    let period: u64 = period.trim().parse().ok()?;
+
+    if period == 0 {
+        return None;
+    }
+
+    // Fractional quotas can still utilize the partial core
+    Some(quota.div_ceil(period).max(1) as u32)
+}
+
+fn parse_cgroup_memory_max(content: &str) -> Option<u64> {
+    // "max" (v2) is unlimited
+    let memory: u64 = content.trim().parse().ok()?;
+
+    if memory >= CGROUP_V1_UNLIMITED {
+        return None;
+    }
+
+    Some(memory)
+}
+
+fn parse_meminfo_total(content: &str) -> Option<u64> {
+    let line = content.lines().find(|line| line.starts_with("MemTotal:"))?;
+    let kb: u64 = line
+        .trim_start_matches("MemTotal:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_pool(cpu: u32, memory: Option<u64>) -> ResourcePool {
+        ResourcePool::new(ResourceCapacity { cpu, memory })
+    }
+
+    fn resources(cpu: u32, memory: u64) -> TaskOptionResources {
+        TaskOptionResources {
+            cpu: Some(cpu),
+            memory: Some(memory),
+        }
+    }
+
+    #[test]
+    fn reserves_until_capacity_is_exhausted() {
+        let mut pool = create_pool(8, Some(16));
+
+        assert!(pool.try_reserve(NodeIndex::new(0), &resources(4, 8)));
+        assert!(pool.try_reserve(NodeIndex::new(1), &resources(2, 8)));
+        assert!(!pool.try_reserve(NodeIndex::new(2), &resources(2, 1)));
+        assert!(pool.try_reserve(NodeIndex::new(3), &resources(2, 0)));
+        assert!(!pool.try_reserve(NodeIndex::new(4), &resources(1, 0)));
+
+        assert_eq!(pool.get_available_cpu(), 0);
+        assert_eq!(pool.get_available_memory(), Some(0));
+
+        pool.release(NodeIndex::new(0));
+
+        assert!(pool.try_reserve(NodeIndex::new(2), &resources(2, 1)));
+        assert_eq!(pool.get_available_cpu(), 2);
+        assert_eq!(pool.get_available_memory(), Some(7));
+    }
+
+    #[test]
+    fn always_reserves_without_requirements() {
+        let mut pool = create_pool(1, Some(1));
+
+        assert!(pool.try_reserve(NodeIndex::new(0), &resources(1, 1)));
+        assert!(pool.try_reserve(NodeIndex::new(1), &TaskOptionResources::default()));
+    }
+
+    #[test]
+    fn ignores_memory_when_capacity_is_unknown() {
+        let mut pool = create_pool(4, None);
+
+        assert!(pool.try_reserve(NodeIndex::new(0), &resources(1, u64::MAX)));
+        assert!(pool.try_reserve(NodeIndex::new(1), &resources(1, u64::MAX)));
+        assert_eq!(pool.get_available_memory(), None);
+    }
+
+    #[test]
+    fn reduces_requirements_larger_than_capacity() {
+        let mut pool = create_pool(4, Some(8));
+
+        assert!(pool.try_reserve(NodeIndex::new(0), &resources(16, 64)));
+        assert!(!pool.try_reserve(NodeIndex::new(1), &resources(1, 0)));
+
+        pool.release(NodeIndex::new(0));
+
+        assert!(pool.try_reserve(NodeIndex::new(1), &resources(1, 0)));
+        assert!(!pool.try_reserve(NodeIndex::new(0), &resources(16, 64)));
+    }
+
+    #[test]
+    fn parses_cgroup_files() {
+        assert_eq!(parse_cgroup_cpu_max("200000 100000\n"), Some(2));
+        assert_eq!(parse_cgroup_cpu_max("150000 100000"), Some(2));
+        assert_eq!(parse_cgroup_cpu_max("10000 100000"), Some(1));
+        assert_eq!(parse_cgroup_cpu_max("max 100000"), None);
+        assert_eq!(parse_cgroup_cpu_quota("-1", "100000"), None);
+
+        assert_eq!(parse_cgroup_memory_max("8589934592\n"), Some(8589934592));
+        assert_eq!(parse_cgroup_memory_max("max"), None);
+        assert_eq!(parse_cgroup_memory_max("9223372036854771712"), None);
+
+        assert_eq!(
+            parse_meminfo_total("MemTotal:       16318612 kB\nMemFree:  1 kB"),
+            Some(16318612 * 1024)
+        );
+    }
+}
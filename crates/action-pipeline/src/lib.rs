@@ -8,6 +8,7 @@ mod event_emitter;
 mod job;
 mod job_context;
 mod job_dispatcher;
+mod job_resources;
 pub mod reports;
 mod subscribers;
 
@@ -0,0 +1,188 @@
+use moon_action::{Action, ActionNode, ActionStatus};
+use moon_time::chrono::NaiveDateTime;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// A single run of a task, as persisted in the history log.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskRunRecord {
+    pub target: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+
+    pub status: ActionStatus,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<Duration>,
+
+    /// Total number of times the task was executed, including retries.
+    pub attempts: usize,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+
+    /// Whether the task failed at first, but passed after a retry.
+    pub flaky: bool,
+
+    pub created_at: NaiveDateTime,
+}
+
+impl TaskRunRecord {
+    /// Create a record from a task action. Returns `None` for other actions,
+    /// or for tasks that never finished.
+    pub fn from_action(action: &Action) -> Option<Self> {
+        let ActionNode::RunTask(node) = &*action.node else {
+            return None;
+        };
+
+        if matches!(action.status, ActionStatus::Running) {
+            return None;
+        }
+
+        Some(Self {
+            target: node.target.to_string(),
+            hash: action.operations.get_hash().map(|hash| hash.to_owned()),
+            status: action.status,
+            duration: action.duration,
+            attempts: action
+                .operations
+                .iter()
+                .filter(|op| op.meta.is_task_execution())
+                .count(),
+            exit_code: action.get_exit_code(),
+            flaky: action.flaky,
+            created_at: action.created_at,
+        })
+    }
+
+    pub fn was_executed(&self) -> bool {
+        matches!(self.status, ActionStatus::Passed | ActionStatus::Failed)
+    }
+}
+
+/// Aggregated statistics for a single task, derived from its run history.
+#[derive(Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskHistorySummary {
+    pub target: String,
+
+    /// Total number of recorded runs, including cache hits.
+    pub runs: usize,
+
+    /// Number of runs that were hydrated from the cache.
+    pub cached: usize,
+
+    /// Number of runs that failed, after exhausting all retries.
+    pub failed: usize,
+
+    /// Number of runs that passed only after a retry.
+    pub passed_after_retry: usize,
+
+    /// Hashes that have both passed and failed, which means the
+    /// task produced a different outcome for the exact same inputs.
+    pub inconsistent_hashes: Vec<String>,
+
+    /// Median duration of executed (non-cached) runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p50: Option<Duration>,
+
+    /// 95th percentile duration of executed (non-cached) runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p95: Option<Duration>,
+
+    pub last_run_at: Option<NaiveDateTime>,
+}
+
+impl TaskHistorySummary {
+    pub fn is_flaky(&self) -> bool {
+        self.passed_after_retry > 0 || !self.inconsistent_hashes.is_empty()
+    }
+}
+
+/// Return the duration at the provided percentile, using the nearest-rank method.
+/// The list of durations must be sorted.
+pub fn calculate_percentile(durations: &[Duration], percentile: f64) -> Option<Duration> {
+    if durations.is_empty() {
+        return None;
+    }
+
+    let rank = ((percentile / 100.0) * durations.len() as f64).ceil() as usize;
+
+    durations.get(rank.clamp(1, durations.len()) - 1).copied()
+}
+
+/// Aggregate the records into a summary per task, sorted by target.
+pub fn summarize_history(records: &[TaskRunRecord]) -> Vec<TaskHistorySummary> {
+    let mut targets: BTreeMap<&str, Vec<&TaskRunRecord>> = BTreeMap::new();
+
+    for record in records {
+        targets.entry(&record.target).or_default().push(record);
+    }
+
+    targets
+        .into_iter()
+        .map(|(target, records)| {
+            let mut summary = TaskHistorySummary {
+                target: target.to_owned(),
+                runs: records.len(),
+                ..Default::default()
+            };
+            let mut durations = vec![];
+            let mut outcomes: FxHashMap<&str, (bool, bool)> = FxHashMap::default();
+
+            for record in records {
+                match record.status {
+                    ActionStatus::Cached | ActionStatus::CachedFromRemote => {
+                        summary.cached += 1;
+                    }
+                    ActionStatus::Failed | ActionStatus::TimedOut => {
+                        summary.failed += 1;
+                    }
+                    _ => {}
+                };
+
+                if record.flaky {
+                    summary.passed_after_retry += 1;
+                }
+
+                if record.was_executed() {
+                    if let Some(duration) = record.duration {
+                        durations.push(duration);
+                    }
+
+                    if let Some(hash) = &record.hash {
+                        let outcome = outcomes.entry(hash.as_str()).or_default();
+
+                        if matches!(record.status, ActionStatus::Passed) {
+                            outcome.0 = true;
+                        } else {
+                            outcome.1 = true;
+                        }
+                    }
+                }
+
+                if summary
+                    .last_run_at
+                    .is_none_or(|last| last < record.created_at)
+                {
+                    summary.last_run_at = Some(record.created_at);
+                }
+            }
+
+            summary.inconsistent_hashes = outcomes
+                .into_iter()
+                .filter_map(|(hash, (passed, failed))| (passed && failed).then(|| hash.to_owned()))
+                .collect();
+            summary.inconsistent_hashes.sort();
+
+            durations.sort();
+            summary.p50 = calculate_percentile(&durations, 50.0);
+            summary.p95 = calculate_percentile(&durations, 95.0);
+            summary
+        })
+        .collect()
+}
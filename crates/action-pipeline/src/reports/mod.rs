@@ -1 +1,4 @@
 pub mod estimate;
+pub mod history;
+pub mod outputs;
+pub mod trace;
@@ -0,0 +1,41 @@
+use moon_action::{Action, ActionNode};
+use moon_cache::{StateEngine, TaskOutputsManifest};
+use starbase_utils::json;
+use std::collections::BTreeMap;
+
+pub const OUTPUTS_FILE_NAME: &str = "outputs.json";
+
+/// Collect the outputs of every task that ran (or was hydrated) in the
+/// pipeline, keyed by target. Each task records its own outputs in its
+/// state directory, which are only included if they match the hash of
+/// this run, as skipped or failed tasks don't record outputs.
+pub fn collect_task_outputs(
+    state: &StateEngine,
+    actions: &[Action],
+) -> BTreeMap<String, TaskOutputsManifest> {
+    let mut outputs = BTreeMap::default();
+
+    for action in actions {
+        let ActionNode::RunTask(node) = &*action.node else {
+            continue;
+        };
+
+        let Some(hash) = action.operations.get_hash() else {
+            continue;
+        };
+
+        let outputs_path = state.get_target_dir(&node.target).join(OUTPUTS_FILE_NAME);
+
+        if !outputs_path.exists() {
+            continue;
+        }
+
+        if let Ok(manifest) = json::read_file::<TaskOutputsManifest>(&outputs_path)
+            && manifest.hash == hash
+        {
+            outputs.insert(node.target.to_string(), manifest);
+        }
+    }
+
+    outputs
+}
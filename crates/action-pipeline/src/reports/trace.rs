@@ -0,0 +1,293 @@
+use moon_action::{Action, ActionStatus};
+use moon_time::chrono::{NaiveDateTime, TimeDelta};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
+use std::time::Duration;
+use tracing::{debug, instrument};
+
+/// A single action on the critical path.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalPathAction {
+    pub label: String,
+    pub node_index: usize,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalPath {
+    /// Actions on the path, starting from the furthest dependency.
+    pub actions: Vec<CriticalPathAction>,
+
+    /// Combined duration of all actions on the path, which is the
+    /// minimum wall-clock time of the pipeline, regardless of concurrency.
+    pub duration: Duration,
+}
+
+impl CriticalPath {
+    /// Calculate the chain of dependent actions with the longest combined duration.
+    #[instrument(name = "calculate_critical_path", skip_all)]
+    pub fn calculate(actions: &[Action]) -> Self {
+        debug!("Calculating the critical path through the action graph");
+
+        let actions_by_index = actions
+            .iter()
+            .map(|action| (action.node_index, action))
+            .collect::<FxHashMap<_, _>>();
+        let mut chains = FxHashMap::default();
+        let mut visiting = FxHashSet::default();
+        let mut end = None;
+
+        for action in actions {
+            let chain = calculate_chain(
+                action.node_index,
+                &actions_by_index,
+                &mut chains,
+                &mut visiting,
+            );
+
+            if end.is_none_or(|(_, duration)| chain.0 > duration) {
+                end = Some((action.node_index, chain.0));
+            }
+        }
+
+        let Some((mut index, duration)) = end else {
+            return Self::default();
+        };
+
+        // Walk the chain back to its first dependency
+        let mut path = vec![];
+
+        loop {
+            let action = actions_by_index[&index];
+
+            path.push(CriticalPathAction {
+                label: action.label.clone(),
+                node_index: index,
+                duration: action.duration.unwrap_or_default(),
+            });
+
+            match chains.get(&index).and_then(|chain| chain.1) {
+                Some(dep_index) => index = dep_index,
+                None => break,
+            };
+        }
+
+        path.reverse();
+
+        Self {
+            actions: path,
+            duration,
+        }
+    }
+
+    pub fn contains(&self, node_index: usize) -> bool {
+        self.actions
+            .iter()
+            .any(|action| action.node_index == node_index)
+    }
+}
+
+// Returns the longest duration of the chain that ends with the provided
+// action (inclusive), and the dependency that precedes it in the chain
+fn calculate_chain(
+    index: usize,
+    actions: &FxHashMap<usize, &Action>,
+    chains: &mut FxHashMap<usize, (Duration, Option<usize>)>,
+    visiting: &mut FxHashSet<usize>,
+) -> (Duration, Option<usize>) {
+    if let Some(chain) = chains.get(&index) {
+        return *chain;
+    }
+
+    let Some(action) = actions.get(&index) else {
+        return (Duration::ZERO, None);
+    };
+
+    // Shouldn't happen in a DAG, but avoid recursing forever
+    if !visiting.insert(index) {
+        return (Duration::ZERO, None);
+    }
+
+    let mut longest: (Duration, Option<usize>) = (Duration::ZERO, None);
+
+    for dep_index in &action.dependencies {
+        if !actions.contains_key(dep_index) {
+            continue;
+        }
+
+        let dep_chain = calculate_chain(*dep_index, actions, chains, visiting);
+
+        if longest.1.is_none() || dep_chain.0 > longest.0 {
+            longest = (dep_chain.0, Some(*dep_index));
+        }
+    }
+
+    let chain = (longest.0 + action.duration.unwrap_or_default(), longest.1);
+
+    visiting.remove(&index);
+    chains.insert(index, chain);
+    chain
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceEventArgs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_index: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ActionStatus>,
+}
+
+/// An event in the Chrome Trace Event format, which can be loaded
+/// into `chrome://tracing` or Perfetto.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TraceEvent {
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cat: Option<String>,
+
+    /// The phase, either `X` (complete) or `M` (metadata).
+    pub ph: String,
+
+    /// Timestamp in microseconds, relative to the start of the pipeline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts: Option<u64>,
+
+    /// Duration in microseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dur: Option<u64>,
+
+    pub pid: u32,
+
+    pub tid: usize,
+
+    pub args: TraceEventArgs,
+}
+
+impl TraceEvent {
+    fn thread_name(tid: usize, name: String) -> Self {
+        TraceEvent {
+            name: "thread_name".into(),
+            cat: None,
+            ph: "M".into(),
+            ts: None,
+            dur: None,
+            pid: 1,
+            tid,
+            args: TraceEventArgs {
+                name: Some(name),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trace {
+    pub trace_events: Vec<TraceEvent>,
+    pub display_time_unit: String,
+}
+
+impl Trace {
+    /// Generate a trace with a track for each concurrency slot that was
+    /// occupied during the run, and a separate track for the critical path.
+    #[instrument(name = "generate_trace", skip_all)]
+    pub fn generate(actions: &[Action], critical_path: &CriticalPath) -> Self {
+        debug!("Generating a trace of the pipeline");
+
+        let mut spans = actions
+            .iter()
+            .filter_map(|action| {
+                let start = action.started_at?;
+                let duration = action.duration.unwrap_or_default();
+
+                Some((start, duration, action))
+            })
+            .collect::<Vec<_>>();
+
+        spans.sort_by_key(|span| (span.0, span.2.node_index));
+
+        let Some(pipeline_start) = spans.first().map(|span| span.0) else {
+            return Self::new(vec![]);
+        };
+
+        let mut events = vec![];
+        let mut critical_events = vec![];
+        let mut slots: Vec<NaiveDateTime> = vec![];
+
+        for (start, duration, action) in spans {
+            let end = start + TimeDelta::from_std(duration).unwrap_or_default();
+
+            // Place into the first slot that was free when the action started
+            let tid = match slots.iter().position(|slot_end| *slot_end <= start) {
+                Some(tid) => {
+                    slots[tid] = end;
+                    tid
+                }
+                None => {
+                    slots.push(end);
+                    slots.len() - 1
+                }
+            };
+
+            let event = TraceEvent {
+                name: action.label.clone(),
+                cat: action.label.split('(').next().map(|cat| cat.to_owned()),
+                ph: "X".into(),
+                ts: Some(
+                    (start - pipeline_start)
+                        .num_microseconds()
+                        .unwrap_or_default()
+                        .max(0) as u64,
+                ),
+                dur: Some(duration.as_micros() as u64),
+                pid: 1,
+                tid,
+                args: TraceEventArgs {
+                    node_index: Some(action.node_index),
+                    status: Some(action.status),
+                    ..Default::default()
+                },
+            };
+
+            if critical_path.contains(action.node_index) {
+                critical_events.push(event.clone());
+            }
+
+            events.push(event);
+        }
+
+        // The critical path is rendered on its own track after all slots
+        let critical_tid = slots.len();
+
+        for event in &mut critical_events {
+            event.tid = critical_tid;
+        }
+
+        events.extend(critical_events);
+        events.extend(
+            (0..slots.len()).map(|tid| TraceEvent::thread_name(tid, format!("Slot {}", tid + 1))),
+        );
+        events.push(TraceEvent::thread_name(
+            critical_tid,
+            "Critical path".into(),
+        ));
+
+        Self::new(events)
+    }
+
+    fn new(trace_events: Vec<TraceEvent>) -> Self {
+        Trace {
+            trace_events,
+            display_time_unit: "ms".into(),
+        }
+    }
+}
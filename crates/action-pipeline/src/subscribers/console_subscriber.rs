@@ -1,16 +1,43 @@
 use crate::event_emitter::{Event, Subscriber};
 use async_trait::async_trait;
-use moon_console::{Console, Level, PipelineReportItem};
+use moon_cache::CacheEngine;
+use moon_console::{Console, Level, PipelineReportItem, RemoteCacheReportItem};
 use std::sync::Arc;
 
 pub struct ConsoleSubscriber {
+    cache_engine: Arc<CacheEngine>,
     console: Arc<Console>,
     summary: Option<Level>,
 }
 
 impl ConsoleSubscriber {
-    pub fn new(console: Arc<Console>, summary: Option<Level>) -> Self {
-        Self { console, summary }
+    pub fn new(
+        console: Arc<Console>,
+        cache_engine: Arc<CacheEngine>,
+        summary: Option<Level>,
+    ) -> Self {
+        Self {
+            cache_engine,
+            console,
+            summary,
+        }
+    }
+
+    fn get_remote_cache_items(&self) -> Vec<RemoteCacheReportItem> {
+        self.cache_engine
+            .storage
+            .get_remote_stats()
+            .into_iter()
+            .map(|(id, stats)| RemoteCacheReportItem {
+                backend: id.to_string(),
+                bytes_downloaded: stats.bytes_downloaded,
+                bytes_uploaded: stats.bytes_uploaded,
+                errors: stats.errors,
+                hits: stats.hits,
+                latency: stats.get_average_latency(),
+                misses: stats.misses,
+            })
+            .collect()
     }
 }
 
@@ -30,6 +57,7 @@ impl Subscriber for ConsoleSubscriber {
             } => {
                 let item = PipelineReportItem {
                     duration: *duration,
+                    remote_cache: self.get_remote_cache_items(),
                     summary: self.summary,
                     status: **status,
                 };
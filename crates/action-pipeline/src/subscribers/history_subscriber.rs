@@ -0,0 +1,64 @@
+use crate::event_emitter::{Event, Subscriber};
+use crate::reports::history::TaskRunRecord;
+use async_trait::async_trait;
+use moon_cache::CacheEngine;
+use moon_time::chrono::TimeDelta;
+use moon_time::now_timestamp;
+use std::sync::Arc;
+use tracing::debug;
+
+pub struct HistorySubscriber {
+    cache_engine: Arc<CacheEngine>,
+    retention: String,
+}
+
+impl HistorySubscriber {
+    pub fn new(cache_engine: Arc<CacheEngine>, retention: &str) -> Self {
+        HistorySubscriber {
+            cache_engine,
+            retention: retention.to_owned(),
+        }
+    }
+
+    fn prune(&self) -> miette::Result<()> {
+        let retention = self.cache_engine.parse_lifetime(&self.retention)?;
+        let retention = TimeDelta::from_std(retention)
+            .map_err(|error| miette::miette!("Invalid history retention: {error}"))?;
+
+        self.cache_engine
+            .history
+            .prune(&(now_timestamp() - retention).date())?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Subscriber for HistorySubscriber {
+    async fn on_emit<'data>(&mut self, event: &Event<'data>) -> miette::Result<()> {
+        if let Event::PipelineCompleted { actions, .. } = event {
+            let records = actions
+                .iter()
+                .filter_map(TaskRunRecord::from_action)
+                .collect::<Vec<_>>();
+
+            if records.is_empty() {
+                return Ok(());
+            }
+
+            debug!("Appending {} task runs to history", records.len());
+
+            let date = now_timestamp().date();
+            let is_new_segment = !self.cache_engine.history.get_segment_path(&date).exists();
+
+            self.cache_engine.history.append(&date, &records)?;
+
+            // Only prune once a day, when a new segment has been started
+            if is_new_segment {
+                self.prune()?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 pub mod cleanup_subscriber;
 pub mod console_subscriber;
+pub mod history_subscriber;
 pub mod notifications_subscriber;
 pub mod reports_subscriber;
 // pub mod telemetry_subscriber;
+pub mod trace_subscriber;
 pub mod webhooks_subscriber;
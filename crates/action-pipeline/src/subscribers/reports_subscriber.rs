@@ -1,10 +1,14 @@
 use crate::event_emitter::{Event, Subscriber};
 use crate::reports::estimate::Estimate;
+use crate::reports::outputs::{OUTPUTS_FILE_NAME, collect_task_outputs};
+use crate::reports::trace::CriticalPath;
 use async_trait::async_trait;
 use moon_action::{Action, ActionPipelineStatus};
 use moon_action_context::ActionContext;
-use moon_cache::CacheEngine;
+use moon_cache::{CacheEngine, StorageStats};
+use moon_common::Id;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::debug;
@@ -23,6 +27,14 @@ pub struct RunReport<'data> {
     /// compared to another product or baseline.
     pub comparison_estimate: Estimate,
 
+    /// The chain of dependent actions with the longest combined duration.
+    pub critical_path: CriticalPath,
+
+    /// Requests made to each remote cache backend, keyed by backend.
+    /// Uploads that are still queued when the pipeline completes are
+    /// not included.
+    pub remote_cache: BTreeMap<Id, StorageStats>,
+
     pub status: &'data ActionPipelineStatus,
 }
 
@@ -65,10 +77,24 @@ impl Subscriber for ReportsSubscriber {
                 context: &self.action_context,
                 duration,
                 comparison_estimate: estimate,
+                critical_path: CriticalPath::calculate(actions),
+                remote_cache: self
+                    .cache_engine
+                    .storage
+                    .get_remote_stats()
+                    .into_iter()
+                    .collect(),
                 status,
             };
 
             self.cache_engine.write(&self.report_name, &report)?;
+
+            debug!("Creating outputs report");
+
+            self.cache_engine.write(
+                OUTPUTS_FILE_NAME,
+                &collect_task_outputs(&self.cache_engine.state, actions),
+            )?;
         }
 
         Ok(())
@@ -0,0 +1,37 @@
+use crate::event_emitter::{Event, Subscriber};
+use crate::reports::trace::{CriticalPath, Trace};
+use async_trait::async_trait;
+use starbase_utils::json;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+pub struct TraceSubscriber {
+    trace_path: PathBuf,
+}
+
+impl TraceSubscriber {
+    pub fn new(trace_path: &Path) -> Self {
+        TraceSubscriber {
+            trace_path: trace_path.to_path_buf(),
+        }
+    }
+}
+
+#[async_trait]
+impl Subscriber for TraceSubscriber {
+    async fn on_emit<'data>(&mut self, event: &Event<'data>) -> miette::Result<()> {
+        if let Event::PipelineCompleted { actions, .. } = event {
+            debug!(file = ?self.trace_path, "Writing pipeline trace");
+
+            let critical_path = CriticalPath::calculate(actions);
+
+            json::write_file(
+                &self.trace_path,
+                &Trace::generate(actions, &critical_path),
+                false,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use moon_action::*;
+use moon_action_pipeline::reports::history::*;
+use moon_time::chrono::NaiveDateTime;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn create_record(target: &str, hash: &str, status: ActionStatus, secs: u64) -> TaskRunRecord {
+    TaskRunRecord {
+        target: target.into(),
+        hash: Some(hash.into()),
+        status,
+        duration: Some(Duration::from_secs(secs)),
+        attempts: 1,
+        exit_code: Some(if status == ActionStatus::Failed { 1 } else { 0 }),
+        flaky: false,
+        created_at: NaiveDateTime::default(),
+    }
+}
+
+mod history {
+    use super::*;
+
+    #[test]
+    fn creates_records_for_tasks_only() {
+        let action = Action {
+            node: Arc::new(ActionNode::sync_workspace()),
+            status: ActionStatus::Passed,
+            ..Action::default()
+        };
+
+        assert_eq!(TaskRunRecord::from_action(&action), None);
+
+        let action = Action {
+            node: Arc::new(ActionNode::run_task(RunTaskNode::new("app:build".into()))),
+            status: ActionStatus::Passed,
+            duration: Some(Duration::from_secs(3)),
+            flaky: true,
+            ..Action::default()
+        };
+        let record = TaskRunRecord::from_action(&action).unwrap();
+
+        assert_eq!(record.target, "app:build");
+        assert_eq!(record.status, ActionStatus::Passed);
+        assert_eq!(record.duration, Some(Duration::from_secs(3)));
+        assert!(record.flaky);
+    }
+
+    #[test]
+    fn calculates_percentiles() {
+        let durations = (1..=20).map(Duration::from_secs).collect::<Vec<_>>();
+
+        assert_eq!(calculate_percentile(&[], 50.0), None);
+        assert_eq!(
+            calculate_percentile(&durations, 50.0),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            calculate_percentile(&durations, 95.0),
+            Some(Duration::from_secs(19))
+        );
+        assert_eq!(
+            calculate_percentile(&durations[0..1], 95.0),
+            Some(Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn summarizes_per_target() {
+        let summaries = summarize_history(&[
+            create_record("b:test", "h1", ActionStatus::Passed, 1),
+            create_record("a:build", "h1", ActionStatus::Passed, 4),
+            create_record("a:build", "h2", ActionStatus::Cached, 0),
+            create_record("a:build", "h3", ActionStatus::Passed, 2),
+        ]);
+
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].target, "a:build");
+        assert_eq!(summaries[0].runs, 3);
+        assert_eq!(summaries[0].cached, 1);
+        assert_eq!(summaries[0].p50, Some(Duration::from_secs(2)));
+        assert_eq!(summaries[0].p95, Some(Duration::from_secs(4)));
+        assert!(!summaries[0].is_flaky());
+        assert_eq!(summaries[1].target, "b:test");
+    }
+
+    #[test]
+    fn detects_flaky_from_retries() {
+        let mut record = create_record("a:test", "h1", ActionStatus::Passed, 1);
+        record.attempts = 2;
+        record.flaky = true;
+
+        let summaries = summarize_history(&[record]);
+
+        assert_eq!(summaries[0].passed_after_retry, 1);
+        assert!(summaries[0].is_flaky());
+    }
+
+    #[test]
+    fn detects_flaky_from_inconsistent_outcomes() {
+        let summaries = summarize_history(&[
+            create_record("a:test", "h1", ActionStatus::Passed, 1),
+            create_record("a:test", "h1", ActionStatus::Failed, 1),
+            create_record("a:test", "h2", ActionStatus::Failed, 1),
+        ]);
+
+        assert_eq!(summaries[0].failed, 2);
+        assert_eq!(summaries[0].inconsistent_hashes, ["h1"]);
+        assert!(summaries[0].is_flaky());
+    }
+}
@@ -0,0 +1,80 @@
+use moon_action::*;
+use moon_action_pipeline::reports::outputs::*;
+use moon_cache::{StateEngine, TaskOutputsManifest};
+use moon_common::Id;
+use starbase_sandbox::create_empty_sandbox;
+use starbase_utils::json;
+use std::sync::Arc;
+
+fn create_action(node: ActionNode, hash: Option<&str>) -> Action {
+    let mut operations = vec![];
+
+    if let Some(hash) = hash {
+        let mut operation = Operation::hash_generation();
+        operation.meta.set_hash(hash);
+        operations.push(operation);
+    }
+
+    Action {
+        node: Arc::new(node),
+        operations: OperationList(operations),
+        status: ActionStatus::Passed,
+        ..Action::default()
+    }
+}
+
+fn record_outputs(state: &StateEngine, target: &str, hash: &str) {
+    json::write_file(
+        state.get_target_dir(&target.into()).join(OUTPUTS_FILE_NAME),
+        &TaskOutputsManifest {
+            hash: hash.into(),
+            target: target.into(),
+            ..Default::default()
+        },
+        false,
+    )
+    .unwrap();
+}
+
+mod outputs {
+    use super::*;
+
+    #[test]
+    fn collects_outputs_of_tasks_for_this_run() {
+        let sandbox = create_empty_sandbox();
+        let state = StateEngine::new(sandbox.path()).unwrap();
+
+        record_outputs(&state, "a:build", "a1");
+        record_outputs(&state, "b:build", "b0");
+
+        let outputs = collect_task_outputs(
+            &state,
+            &[
+                create_action(ActionNode::sync_workspace(), None),
+                create_action(
+                    ActionNode::sync_project(SyncProjectNode {
+                        project_id: Id::raw("a"),
+                    }),
+                    None,
+                ),
+                create_action(
+                    ActionNode::run_task(RunTaskNode::new("a:build".into())),
+                    Some("a1"),
+                ),
+                // Recorded by a previous run
+                create_action(
+                    ActionNode::run_task(RunTaskNode::new("b:build".into())),
+                    Some("b1"),
+                ),
+                // No outputs recorded
+                create_action(
+                    ActionNode::run_task(RunTaskNode::new("c:build".into())),
+                    Some("c1"),
+                ),
+            ],
+        );
+
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs.get("a:build").unwrap().hash, "a1");
+    }
+}
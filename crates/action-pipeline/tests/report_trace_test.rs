@@ -0,0 +1,147 @@
+use moon_action::*;
+use moon_action_pipeline::reports::trace::*;
+use moon_time::chrono::{NaiveDateTime, TimeDelta};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn create_action(index: usize, target: &str, deps: &[usize], start_secs: i64, secs: u64) -> Action {
+    let node = ActionNode::run_task(RunTaskNode::new(target.into()));
+
+    Action {
+        dependencies: deps.to_vec(),
+        duration: Some(Duration::from_secs(secs)),
+        label: node.label(),
+        node: Arc::new(node),
+        node_index: index,
+        started_at: Some(NaiveDateTime::default() + TimeDelta::seconds(start_secs)),
+        status: ActionStatus::Passed,
+        ..Action::default()
+    }
+}
+
+// 0 (2s) <- 1 (5s) <- 3 (1s)
+// 0 (2s) <- 2 (1s) <- 3 (1s)
+fn create_actions() -> Vec<Action> {
+    vec![
+        create_action(0, "a:build", &[], 0, 2),
+        create_action(1, "b:build", &[0], 2, 5),
+        create_action(2, "c:build", &[0], 2, 1),
+        create_action(3, "d:build", &[1, 2], 7, 1),
+    ]
+}
+
+mod critical_path {
+    use super::*;
+
+    #[test]
+    fn empty_for_no_actions() {
+        assert_eq!(CriticalPath::calculate(&[]), CriticalPath::default());
+    }
+
+    #[test]
+    fn follows_longest_chain() {
+        let path = CriticalPath::calculate(&create_actions());
+
+        assert_eq!(path.duration, Duration::from_secs(8));
+        assert_eq!(
+            path.actions
+                .iter()
+                .map(|action| action.node_index)
+                .collect::<Vec<_>>(),
+            [0, 1, 3]
+        );
+        assert_eq!(path.actions[1].label, "RunTask(b:build)");
+    }
+
+    #[test]
+    fn ignores_missing_dependencies() {
+        let path = CriticalPath::calculate(&[
+            create_action(1, "b:build", &[0], 0, 3),
+            create_action(2, "c:build", &[], 0, 4),
+        ]);
+
+        assert_eq!(path.duration, Duration::from_secs(4));
+        assert_eq!(path.actions.len(), 1);
+        assert_eq!(path.actions[0].node_index, 2);
+    }
+}
+
+mod trace {
+    use super::*;
+
+    #[test]
+    fn assigns_concurrent_actions_to_slots() {
+        let actions = create_actions();
+        let trace = Trace::generate(&actions, &CriticalPath::calculate(&actions));
+
+        let tids = trace
+            .trace_events
+            .iter()
+            .filter(|event| event.ph == "X" && event.tid < 2)
+            .map(|event| (event.args.node_index.unwrap(), event.tid))
+            .collect::<Vec<_>>();
+
+        // 1 and 2 run in parallel, while 0 and 3 reuse the first free slot
+        assert_eq!(tids, [(0, 0), (1, 0), (2, 1), (3, 0)]);
+    }
+
+    #[test]
+    fn uses_relative_microseconds() {
+        let actions = create_actions();
+        let trace = Trace::generate(&actions, &CriticalPath::default());
+        let event = &trace.trace_events[3];
+
+        assert_eq!(event.name, "RunTask(d:build)");
+        assert_eq!(event.cat.as_deref(), Some("RunTask"));
+        assert_eq!(event.ts, Some(7_000_000));
+        assert_eq!(event.dur, Some(1_000_000));
+    }
+
+    #[test]
+    fn includes_critical_path_track() {
+        let actions = create_actions();
+        let trace = Trace::generate(&actions, &CriticalPath::calculate(&actions));
+
+        let critical = trace
+            .trace_events
+            .iter()
+            .filter(|event| event.ph == "X" && event.tid == 2)
+            .map(|event| event.args.node_index.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(critical, [0, 1, 3]);
+
+        let names = trace
+            .trace_events
+            .iter()
+            .filter(|event| event.ph == "M")
+            .map(|event| (event.tid, event.args.name.clone().unwrap()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            [
+                (0, "Slot 1".to_owned()),
+                (1, "Slot 2".to_owned()),
+                (2, "Critical path".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_actions_that_never_started() {
+        let mut actions = create_actions();
+        actions[3].started_at = None;
+
+        let trace = Trace::generate(&actions, &CriticalPath::default());
+
+        assert_eq!(
+            trace
+                .trace_events
+                .iter()
+                .filter(|event| event.ph == "X")
+                .count(),
+            3
+        );
+    }
+}
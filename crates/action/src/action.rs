@@ -1,5 +1,7 @@
 use crate::action_node::ActionNode;
 use crate::operation_list::OperationList;
+use crate::task_check_result::TaskCheckResult;
+use crate::task_test_result::TaskTestResults;
 use moon_time::chrono::NaiveDateTime;
 use moon_time::now_timestamp;
 use serde::{Deserialize, Serialize};
@@ -40,8 +42,16 @@ pub enum ActionStatus {
 pub struct Action {
     pub allow_failure: bool,
 
+    /// Results of the task checks that were executed, if any.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<TaskCheckResult>,
+
     pub created_at: NaiveDateTime,
 
+    /// Node indices of the actions this action depends on.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<usize>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<Duration>,
 
@@ -71,13 +81,19 @@ pub struct Action {
     pub start_time: Option<Instant>,
 
     pub status: ActionStatus,
+
+    /// Results parsed from the task's test reports, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tests: Option<TaskTestResults>,
 }
 
 impl Action {
     pub fn new(node: ActionNode) -> Self {
         Action {
             allow_failure: false,
+            checks: vec![],
             created_at: now_timestamp(),
+            dependencies: vec![],
             duration: None,
             error: None,
             error_report: None,
@@ -90,6 +106,7 @@ impl Action {
             started_at: None,
             start_time: None,
             status: ActionStatus::Running,
+            tests: None,
         }
     }
 
@@ -3,9 +3,13 @@ mod action_node;
 mod operation;
 mod operation_list;
 mod operation_meta;
+mod task_check_result;
+mod task_test_result;
 
 pub use action::*;
 pub use action_node::*;
 pub use operation::*;
 pub use operation_list::*;
 pub use operation_meta::*;
+pub use task_check_result::*;
+pub use task_test_result::*;
@@ -284,6 +284,10 @@ impl Operation {
         Self::new(OperationMeta::MutexAcquisition)
     }
 
+    pub fn semaphore_acquisition() -> Self {
+        Self::new(OperationMeta::SemaphoreAcquisition)
+    }
+
     pub fn output_hydration() -> Self {
         Self::new(OperationMeta::OutputHydration(Default::default()))
     }
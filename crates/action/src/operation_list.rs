@@ -31,6 +31,7 @@ impl OperationList {
             op.meta.is_archive_creation()
                 || op.meta.is_hash_generation()
                 || op.meta.is_mutex_acquisition()
+                || op.meta.is_semaphore_acquisition()
         })
     }
 
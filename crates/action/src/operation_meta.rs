@@ -70,6 +70,7 @@ pub enum OperationMeta {
     ArchiveCreation,
     HashGeneration(Box<OperationMetaHash>),
     MutexAcquisition,
+    SemaphoreAcquisition,
 }
 
 impl OperationMeta {
@@ -89,6 +90,10 @@ impl OperationMeta {
         matches!(self, Self::MutexAcquisition)
     }
 
+    pub fn is_semaphore_acquisition(&self) -> bool {
+        matches!(self, Self::SemaphoreAcquisition)
+    }
+
     pub fn is_output_hydration(&self) -> bool {
         matches!(self, Self::OutputHydration(_))
     }
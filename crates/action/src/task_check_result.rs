@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskCheckOutcome {
+    /// Condition passed. When all conditions pass, the task is skipped.
+    ConditionMet,
+    /// Condition failed, so the task will run as normal.
+    ConditionUnmet,
+    /// Fingerprint was captured and included in the task hash.
+    Fingerprinted,
+    /// Fingerprint was captured but not hashed, as hashing is disabled.
+    FingerprintIgnored,
+    /// Fingerprint failed, which fails the task.
+    FingerprintFailed,
+    /// Requirement passed.
+    RequirementMet,
+    /// Requirement failed, which fails the task.
+    RequirementFailed,
+}
+
+impl TaskCheckOutcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::FingerprintFailed | Self::RequirementFailed)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ConditionMet => "condition met",
+            Self::ConditionUnmet => "condition unmet",
+            Self::Fingerprinted => "fingerprinted",
+            Self::FingerprintIgnored => "fingerprint ignored",
+            Self::FingerprintFailed => "fingerprint failed",
+            Self::RequirementMet => "requirement met",
+            Self::RequirementFailed => "requirement failed",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCheckResult {
+    /// The script that was executed.
+    pub script: String,
+
+    pub outcome: TaskCheckOutcome,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+
+    /// Hash of the captured output, for fingerprint checks only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub timed_out: bool,
+}
+
+impl TaskCheckResult {
+    pub fn new(script: impl AsRef<str>, outcome: TaskCheckOutcome) -> Self {
+        Self {
+            script: script.as_ref().to_owned(),
+            outcome,
+            exit_code: None,
+            fingerprint: None,
+            timed_out: false,
+        }
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
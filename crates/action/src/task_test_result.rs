@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskTestStatus {
+    Passed,
+    /// Failed an assertion, or errored while running.
+    Failed,
+    /// Skipped, or marked as todo.
+    Skipped,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTestCase {
+    pub name: String,
+
+    /// The suite (or class) the test belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suite: Option<String>,
+
+    pub status: TaskTestStatus,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<Duration>,
+
+    /// The failure or skip message, if provided by the report.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl TaskTestCase {
+    pub fn new(name: impl AsRef<str>, status: TaskTestStatus) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            suite: None,
+            status,
+            duration: None,
+            message: None,
+        }
+    }
+
+    pub fn get_label(&self) -> String {
+        match &self.suite {
+            Some(suite) if !suite.is_empty() => format!("{suite} › {}", self.name),
+            _ => self.name.clone(),
+        }
+    }
+}
+
+/// Results of the test reports that a task wrote, aggregated across all reports.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTestResults {
+    pub failed: usize,
+
+    pub passed: usize,
+
+    /// Workspace relative paths of the parsed report files.
+    pub reports: Vec<String>,
+
+    pub skipped: usize,
+
+    pub tests: Vec<TaskTestCase>,
+}
+
+impl TaskTestResults {
+    pub fn add_test(&mut self, test: TaskTestCase) {
+        match test.status {
+            TaskTestStatus::Passed => self.passed += 1,
+            TaskTestStatus::Failed => self.failed += 1,
+            TaskTestStatus::Skipped => self.skipped += 1,
+        };
+
+        self.tests.push(test);
+    }
+
+    pub fn get_failed_tests(&self) -> impl Iterator<Item = &TaskTestCase> {
+        self.tests
+            .iter()
+            .filter(|test| test.status == TaskTestStatus::Failed)
+    }
+
+    pub fn get_total(&self) -> usize {
+        self.tests.len()
+    }
+
+    pub fn has_failed(&self) -> bool {
+        self.failed > 0
+    }
+}
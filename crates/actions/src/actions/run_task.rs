@@ -32,6 +32,8 @@ pub async fn run_task(
     action.flaky = result.operations.is_flaky();
     action.status = result.operations.get_final_status();
     action.operations = result.operations;
+    action.checks = result.checks;
+    action.tests = result.tests;
 
     if action.has_failed() && action.allow_failure {
         warn!(
@@ -57,6 +57,22 @@ pub fn with_shared_exec_args(attr: TokenStream, item: TokenStream) -> TokenStrea
             )]
             pub summary: Option<Option<crate::app_options::SummaryOption>>
         },
+        quote! {
+            #[arg(
+                long,
+                env = "MOON_TRACE",
+                help = "Write a trace (Chrome Trace Event JSON) of the pipeline to this file, viewable in Perfetto"
+            )]
+            pub trace: Option<std::path::PathBuf>
+        },
+        quote! {
+            #[arg(
+                long,
+                env = "MOON_TRACE_FS",
+                help = "Trace the file system access of tasks, and report files read or written that are not declared as inputs or outputs"
+            )]
+            pub trace_fs: bool
+        },
         // GRAPH
         quote! {
             #[arg(
@@ -95,6 +111,24 @@ pub fn with_shared_exec_args(attr: TokenStream, item: TokenStream) -> TokenStrea
             )]
             pub job_total: Option<usize>
         },
+        quote! {
+            #[arg(
+                long,
+                env = "MOON_JOB_BALANCE",
+                help = "Distribute tasks across jobs by count, or by historical duration",
+                help_heading = super::HEADING_PARALLELISM,
+            )]
+            pub balance: Option<crate::commands::exec::JobBalance>
+        },
+        quote! {
+            #[arg(
+                long,
+                env = "MOON_JOB_TIMINGS",
+                help = "Relative path to a timings (JSON) file, used when balancing jobs by duration",
+                help_heading = super::HEADING_PARALLELISM,
+            )]
+            pub timings: Option<std::path::PathBuf>
+        },
     ];
 
     if params.passthrough {
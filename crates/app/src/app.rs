@@ -1,6 +1,7 @@
 use crate::app_options::*;
 use crate::commands::action_graph::ActionGraphArgs;
 use crate::commands::bin::BinArgs;
+use crate::commands::cache::CacheCommands;
 use crate::commands::check::CheckArgs;
 use crate::commands::ci::CiArgs;
 use crate::commands::clean::CleanArgs;
@@ -13,6 +14,7 @@ use crate::commands::ext::ExtArgs;
 use crate::commands::extension::ExtensionCommands;
 use crate::commands::generate::GenerateArgs;
 use crate::commands::hash::HashArgs;
+use crate::commands::history::HistoryArgs;
 use crate::commands::init::InitArgs;
 use crate::commands::mcp::McpArgs;
 use crate::commands::migrate::MigrateCommands;
@@ -24,6 +26,7 @@ use crate::commands::run::RunArgs;
 use crate::commands::sync::SyncCommands;
 use crate::commands::task::TaskArgs;
 use crate::commands::task_graph::TaskGraphArgs;
+use crate::commands::task_outputs::TaskOutputsArgs;
 use crate::commands::tasks::TasksArgs;
 use crate::commands::template::TemplateArgs;
 use crate::commands::templates::TemplatesArgs;
@@ -60,6 +63,12 @@ pub enum Commands {
     )]
     Bin(BinArgs),
 
+    #[command(name = "cache", about = "Inspect and manage the cache.")]
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+
     #[command(
         alias = "c",
         name = "check",
@@ -133,6 +142,12 @@ pub enum Commands {
     )]
     Hash(HashArgs),
 
+    #[command(
+        name = "history",
+        about = "Display the run history of tasks, including durations and flakiness."
+    )]
+    History(HistoryArgs),
+
     #[command(name = "init", about = "Initialize a new moon repository.")]
     Init(InitArgs),
 
@@ -214,6 +229,12 @@ pub enum Commands {
     )]
     TaskGraph(TaskGraphArgs),
 
+    #[command(
+        name = "task-outputs",
+        about = "List or extract the cached outputs of a task, without running it."
+    )]
+    TaskOutputs(TaskOutputsArgs),
+
     #[command(name = "tasks", about = "Display a table of all tasks.")]
     Tasks(TasksArgs),
 
@@ -39,6 +39,31 @@ pub enum AppError {
     )]
     MissingHashManifest(String),
 
+    #[diagnostic(
+        code(app::invalid_task_outputs_hash),
+        help = "A hash is a 64 character hexadecimal string."
+    )]
+    #[error("Invalid task outputs hash {}.", .0.style(Style::Hash))]
+    InvalidTaskOutputsHash(String),
+
+    #[diagnostic(code(app::missing_task_outputs))]
+    #[error(
+        "Unable to find cached outputs for task {} with hash {}.",
+        .target.style(Style::Label),
+        .hash.style(Style::Hash),
+    )]
+    MissingTaskOutputs { target: String, hash: String },
+
+    #[diagnostic(
+        code(app::missing_task_outputs_hash),
+        help = "Run the task first, or pass a hash with --hash."
+    )]
+    #[error(
+        "Task {} has not been run successfully, unable to determine which outputs to use.",
+        .0.style(Style::Label),
+    )]
+    MissingTaskOutputsHash(String),
+
     #[diagnostic(code(app::missing_home_dir))]
     #[error("Unable to determine your home directory.")]
     MissingHomeDir,
@@ -0,0 +1,13 @@
+pub mod remote;
+
+use clap::Subcommand;
+use remote::CacheRemoteCommands;
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum CacheCommands {
+    #[command(name = "remote", about = "Inspect the remote cache.")]
+    Remote {
+        #[command(subcommand)]
+        command: CacheRemoteCommands,
+    },
+}
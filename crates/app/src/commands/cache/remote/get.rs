@@ -0,0 +1,184 @@
+use crate::session::{MoonSession, SessionResult};
+use clap::Args;
+use iocraft::prelude::element;
+use moon_cache::{ContentHash, Digest, Manifest};
+use moon_common::format_error_chain;
+use moon_console::ui::*;
+use starbase_utils::{fs, json};
+use std::collections::BTreeMap;
+use tracing::instrument;
+
+#[derive(Args, Clone, Debug)]
+pub struct CacheRemoteGetArgs {
+    #[arg(required = true, help = "Task hash to fetch the manifest for")]
+    hash: String,
+
+    #[arg(long, help = "Print in JSON format")]
+    json: bool,
+}
+
+fn format_size(digest: Option<&Digest>) -> String {
+    digest
+        .map(|digest| format!("{} bytes", digest.size))
+        .unwrap_or_else(|| "--".into())
+}
+
+#[instrument(skip(session))]
+pub async fn get(session: MoonSession, args: CacheRemoteGetArgs) -> SessionResult {
+    let cache_engine = session.get_cache_engine()?;
+
+    // The remote keys manifests by the digest of the local hash manifest,
+    // so use it when available, which also allows partial hashes
+    let digest = match cache_engine.hash.find_manifest_path(&args.hash)? {
+        Some(manifest_path) => Digest {
+            hash: ContentHash::from_hex(fs::file_name(&manifest_path).replace(".json", ""))?,
+            size: fs::metadata(&manifest_path)?.len() as i64,
+        },
+        None => Digest {
+            hash: ContentHash::from_hex(&args.hash)?,
+            size: 0,
+        },
+    };
+
+    cache_engine.storage.connect_backends().await?;
+
+    let mut manifests: BTreeMap<String, Option<Manifest>> = BTreeMap::default();
+    let mut failed = false;
+
+    for backend in cache_engine.storage.get_remote_backends() {
+        if !backend.is_readable() {
+            continue;
+        }
+
+        let id = backend.get_id().to_string();
+
+        match backend.retrieve_manifest(digest.clone()).await {
+            Ok(manifest) => {
+                manifests.insert(id, manifest);
+            }
+            Err(error) => {
+                failed = true;
+
+                session.console.render_err(element! {
+                    Container {
+                        Notice(title: id, variant: Variant::Failure) {
+                            StyledText(content: format_error_chain(&error))
+                        }
+                    }
+                })?;
+            }
+        };
+    }
+
+    if args.json {
+        session
+            .console
+            .out
+            .write_line(json::format(&manifests, true)?)?;
+
+        return Ok(if failed { Some(1) } else { None });
+    }
+
+    if manifests.is_empty() && !failed {
+        session.console.render(element! {
+            Container {
+                Notice(variant: Variant::Caution) {
+                    StyledText(content: "No readable remote backends are configured")
+                }
+            }
+        })?;
+
+        return Ok(None);
+    }
+
+    for (id, manifest) in manifests {
+        let Some(manifest) = manifest else {
+            session.console.render(element! {
+                Container {
+                    Notice(title: id, variant: Variant::Caution) {
+                        StyledText(
+                            content: format!(
+                                "No manifest found for hash <hash>{}</hash>",
+                                digest.hash
+                            )
+                        )
+                    }
+                }
+            })?;
+
+            continue;
+        };
+
+        session.console.render(element! {
+            Container {
+                Section(title: id) {
+                    Entry(
+                        name: "Hash",
+                        value: element! {
+                            StyledText(
+                                content: digest.hash.to_string(),
+                                style: Style::Hash
+                            )
+                        }.into_any()
+                    )
+                    Entry(
+                        name: "Exit code",
+                        content: manifest.exit_code.to_string(),
+                    )
+                    Entry(
+                        name: "Stdout",
+                        content: format_size(manifest.stdout_digest.as_ref()),
+                    )
+                    Entry(
+                        name: "Stderr",
+                        content: format_size(manifest.stderr_digest.as_ref()),
+                    )
+                    Entry(
+                        name: "Files",
+                        no_children: manifest.files.is_empty()
+                    ) {
+                        List {
+                            #(manifest.files.iter().map(|file| {
+                                element! {
+                                    ListItem {
+                                        StyledText(
+                                            content: format!(
+                                                "{} <mutedlight>({})</mutedlight>",
+                                                file.path,
+                                                format_size(file.digest.as_ref()),
+                                            ),
+                                            style: Style::File
+                                        )
+                                    }
+                                }
+                            }))
+                        }
+                    }
+                    Entry(
+                        name: "Symlinks",
+                        no_children: manifest.symlinks.is_empty()
+                    ) {
+                        List {
+                            #(manifest.symlinks.iter().map(|symlink| {
+                                element! {
+                                    ListItem {
+                                        StyledText(
+                                            content: format!(
+                                                "{} <mutedlight>-> {}</mutedlight>",
+                                                symlink.path,
+                                                symlink.target,
+                                            ),
+                                            style: Style::File
+                                        )
+                                    }
+                                }
+                            }))
+                        }
+                    }
+                }
+            }
+        })?;
+    }
+
+    Ok(if failed { Some(1) } else { None })
+}
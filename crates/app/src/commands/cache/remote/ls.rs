@@ -0,0 +1,159 @@
+use crate::session::{MoonSession, SessionResult};
+use clap::Args;
+use iocraft::prelude::{Size, element};
+use moon_action_pipeline::reports::history::TaskRunRecord;
+use moon_cache::{ContentHash, Digest};
+use moon_console::ui::{
+    Container, Notice, Style, StyledText, Table, TableCol, TableHeader, TableRow, Variant,
+};
+use rustc_hash::FxHashSet;
+use serde::Serialize;
+use starbase_utils::{fs, json};
+use tracing::instrument;
+
+#[derive(Args, Clone, Debug)]
+pub struct CacheRemoteLsArgs {
+    #[arg(long, default_value_t = 20, help = "Maximum number of hashes to check")]
+    limit: usize,
+
+    #[arg(long, help = "Print in JSON format")]
+    json: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RemoteHashItem {
+    target: String,
+    hash: String,
+    /// Remote backends that have a manifest for the hash.
+    stored_in: Vec<String>,
+    /// Remote backends that failed to respond.
+    errored_in: Vec<String>,
+}
+
+#[instrument(skip(session))]
+pub async fn ls(session: MoonSession, args: CacheRemoteLsArgs) -> SessionResult {
+    let cache_engine = session.get_cache_engine()?;
+    let records = cache_engine.history.load::<TaskRunRecord>(None)?;
+
+    // Most recent hash for each task, newest first
+    let mut seen = FxHashSet::default();
+    let mut items = vec![];
+
+    for record in records.into_iter().rev() {
+        if items.len() >= args.limit {
+            break;
+        }
+
+        let Some(hash) = record.hash else {
+            continue;
+        };
+
+        if seen.insert(record.target.clone()) {
+            items.push(RemoteHashItem {
+                target: record.target,
+                hash,
+                stored_in: vec![],
+                errored_in: vec![],
+            });
+        }
+    }
+
+    cache_engine.storage.connect_backends().await?;
+
+    let backends = cache_engine.storage.get_remote_backends();
+
+    for item in &mut items {
+        let Ok(hash) = ContentHash::from_hex(&item.hash) else {
+            continue;
+        };
+        let manifest_path = cache_engine.hash.get_manifest_path(&item.hash);
+        let digest = Digest {
+            hash,
+            size: if manifest_path.exists() {
+                fs::metadata(&manifest_path)?.len() as i64
+            } else {
+                0
+            },
+        };
+
+        for backend in &backends {
+            if !backend.is_readable() {
+                continue;
+            }
+
+            match backend.retrieve_manifest(digest.clone()).await {
+                Ok(Some(_)) => item.stored_in.push(backend.get_id().to_string()),
+                Ok(None) => {}
+                Err(_) => item.errored_in.push(backend.get_id().to_string()),
+            };
+        }
+    }
+
+    if args.json {
+        session
+            .console
+            .out
+            .write_line(json::format(&items, true)?)?;
+
+        return Ok(None);
+    }
+
+    if items.is_empty() {
+        session.console.render(element! {
+            Container {
+                Notice(variant: Variant::Caution) {
+                    StyledText(content: "No task hashes have been recorded in the run history.")
+                }
+            }
+        })?;
+
+        return Ok(None);
+    }
+
+    let target_width = items.iter().fold(0, |acc, item| acc.max(item.target.len()));
+
+    session.console.render(element! {
+        Container {
+            Table(
+                headers: vec![
+                    TableHeader::new("Task", Size::Length((target_width + 5) as u32)),
+                    TableHeader::new("Hash", Size::Length(18)),
+                    TableHeader::new("Remote", Size::Auto),
+                ]
+            ) {
+                #(items.into_iter().enumerate().map(|(i, item)| {
+                    let (remote, style) = if !item.stored_in.is_empty() {
+                        (item.stored_in.join(", "), Style::Success)
+                    } else if !item.errored_in.is_empty() {
+                        (format!("errored ({})", item.errored_in.join(", ")), Style::Failure)
+                    } else {
+                        ("missing".to_owned(), Style::Muted)
+                    };
+
+                    element! {
+                        TableRow(row: i as i32) {
+                            TableCol(col: 0) {
+                                StyledText(
+                                    content: &item.target,
+                                    style: Style::Label
+                                )
+                            }
+                            TableCol(col: 1) {
+                                StyledText(
+                                    content: item.hash.get(0..12).unwrap_or(&item.hash),
+                                    style: Style::Hash
+                                )
+                            }
+                            TableCol(col: 2) {
+                                StyledText(content: remote, style: style)
+                            }
+                        }
+                    }
+                }))
+            }
+        }
+    })?;
+
+    Ok(None)
+}
@@ -0,0 +1,29 @@
+pub mod get;
+pub mod ls;
+pub mod status;
+
+use clap::Subcommand;
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum CacheRemoteCommands {
+    #[command(
+        name = "get",
+        about = "Fetch and inspect the manifest stored for a task hash.",
+        long_about = "Fetch the cache manifest stored for a task hash from each remote backend, and inspect its outputs. Blobs are not downloaded."
+    )]
+    Get(get::CacheRemoteGetArgs),
+
+    #[command(
+        name = "ls",
+        about = "List recently ran task hashes and whether the remote has them.",
+        long_about = "List the hash of recently ran tasks, as recorded in the local run history, and check whether each remote backend has a manifest for it."
+    )]
+    Ls(ls::CacheRemoteLsArgs),
+
+    #[command(
+        name = "status",
+        about = "Check connectivity and capabilities of the remote backends.",
+        long_about = "Connect to each configured remote backend, and display its capabilities, and whether it's readable and writable."
+    )]
+    Status,
+}
@@ -0,0 +1,111 @@
+use crate::session::{MoonSession, SessionResult};
+use iocraft::prelude::element;
+use moon_common::format_error_chain;
+use moon_console::ui::*;
+use moon_time::elapsed;
+use std::time::Instant;
+use tracing::instrument;
+
+fn format_bool(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+fn format_list<T: std::fmt::Debug>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|item| format!("{item:?}").to_lowercase())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[instrument(skip(session))]
+pub async fn status(session: MoonSession) -> SessionResult {
+    let cache_engine = session.get_cache_engine()?;
+    let backends = cache_engine.storage.get_remote_backends();
+
+    if backends.is_empty() {
+        session.console.render(element! {
+            Container {
+                Notice(variant: Variant::Caution) {
+                    StyledText(content: "Remote caching is not configured")
+                }
+            }
+        })?;
+
+        return Ok(None);
+    }
+
+    let mut failed = false;
+
+    for backend in backends {
+        // Connect each backend individually (instead of through the storage)
+        // so that connection errors are reported instead of logged
+        let started = Instant::now();
+        let result = backend.connect().await;
+        let latency = started.elapsed();
+        let capabilities = backend.get_capabilities();
+
+        if result.is_err() {
+            failed = true;
+        }
+
+        session.console.render(element! {
+            Container {
+                Section(title: backend.get_id().as_str()) {
+                    Entry(
+                        name: "Connection",
+                        value: element! {
+                            StyledText(
+                                content: match &result {
+                                    Ok(_) => format!("connected in {}", elapsed(latency)),
+                                    Err(error) => format_error_chain(error),
+                                },
+                                style: if result.is_ok() {
+                                    Style::Success
+                                } else {
+                                    Style::Failure
+                                }
+                            )
+                        }.into_any()
+                    )
+                    Entry(
+                        name: "Readable",
+                        content: format_bool(backend.is_readable()),
+                    )
+                    Entry(
+                        name: "Writable",
+                        content: format_bool(backend.is_writable()),
+                    )
+                }
+                Section(title: "Capabilities") {
+                    Entry(
+                        name: "Digest functions",
+                        content: format_list(&capabilities.digest_functions),
+                    )
+                    Entry(
+                        name: "Compressors",
+                        content: format_list(&capabilities.supported_compressors),
+                    )
+                    Entry(
+                        name: "Batch compressors",
+                        content: format_list(&capabilities.supported_batch_update_compressors),
+                    )
+                    Entry(
+                        name: "Max batch size",
+                        content: if capabilities.max_batch_total_size_bytes == 0 {
+                            "unlimited".to_owned()
+                        } else {
+                            format!("{} bytes", capabilities.max_batch_total_size_bytes)
+                        },
+                    )
+                    Entry(
+                        name: "Stores manifests",
+                        content: format_bool(capabilities.store_manifests),
+                    )
+                }
+            }
+        })?;
+    }
+
+    Ok(if failed { Some(1) } else { None })
+}
@@ -41,6 +41,10 @@ pub async fn server(session: MoonSession) -> SessionResult {
             // Loaded in the background within the workspace watcher,
             // otherwise it causes this command to block for too long
             workspace_graph: Default::default(),
+            workspace_graph_data: None,
+            workspace_graph_generation: 0,
+            watcher_generation: 0,
+            task_inputs: Default::default(),
         },
         vec![Box::new(WorkspaceWatcher::new(session))],
     )
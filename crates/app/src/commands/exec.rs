@@ -1,13 +1,15 @@
 use crate::app_options::AffectedOption;
 // use crate::app_error::AppError;
-use crate::helpers::run_action_pipeline;
+use crate::helpers::{create_action_pipeline, run_action_pipeline};
 use crate::prompts::select_targets;
 use crate::queries::changed_files::{QueryChangedFilesOptions, query_changed_files};
+use crate::queries::task_timings::{QueryTaskTimingsOptions, query_task_timings};
 use crate::session::{MoonSession, SessionResult};
 use ci_env::CiOutput;
 use clap::{Args, ValueEnum};
 use iocraft::prelude::element;
-use moon_action::Action;
+use miette::IntoDiagnostic;
+use moon_action::{Action, ActionNode};
 use moon_action_context::ActionContext;
 use moon_action_graph::{ActionGraph, ActionGraphBuilderOptions, RunRequirements};
 use moon_affected::{DownstreamScope, UpstreamScope};
@@ -16,7 +18,10 @@ use moon_cache::CacheMode;
 use moon_common::{apply_style_tags, is_ci, is_test_env, path::WorkspaceRelativePathBuf};
 use moon_console::ui::{Container, Notice, SelectOption, SelectProps, StyledText, Variant};
 use moon_console::{Console, Level};
+use moon_daemon::start_file_watcher;
 use moon_exec_plan::{ExecutionPlan, TargetsBlock};
+use moon_file_watcher::FileEvent;
+use moon_process::{ProcessRegistry, ProcessScope};
 use moon_task::{Target, TargetLocator};
 use moon_vcs::ChangedStatus;
 use petgraph::graph::NodeIndex;
@@ -24,8 +29,17 @@ use rustc_hash::{FxHashMap, FxHashSet};
 use starbase_utils::json;
 use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, instrument, warn};
 
+/// How long to wait for more file events after the watcher emits
+/// one, so that a single save (or a `git checkout`) is one re-run.
+const WATCH_SETTLE_TIMEOUT: Duration = Duration::from_millis(100);
+
 #[derive(Clone, Debug, Default, PartialEq, ValueEnum)]
 pub enum OnFailure {
     #[default]
@@ -46,6 +60,22 @@ impl fmt::Display for OnFailure {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum JobBalance {
+    #[default]
+    Count,
+    Duration,
+}
+
+impl From<JobBalance> for moon_exec_plan::JobBalance {
+    fn from(value: JobBalance) -> Self {
+        match value {
+            JobBalance::Count => Self::Count,
+            JobBalance::Duration => Self::Duration,
+        }
+    }
+}
+
 #[with_affected_args]
 #[with_shared_exec_args(passthrough)]
 #[derive(Args, Clone, Debug, Default)]
@@ -83,6 +113,14 @@ pub struct ExecArgs {
         help_heading = super::HEADING_WORKFLOW,
     )]
     pub query: Option<String>,
+
+    #[arg(
+        long,
+        short = 'w',
+        help = "Watch for file changes and re-run affected tasks",
+        help_heading = super::HEADING_WORKFLOW,
+    )]
+    pub watch: bool,
 }
 
 #[instrument(skip(session))]
@@ -143,6 +181,14 @@ pub async fn exec(session: MoonSession, args: ExecArgs) -> SessionResult {
     Ok(exit_code)
 }
 
+/// A re-run of affected tasks in watch mode, running in the background.
+struct WatchRun {
+    cancel_token: CancellationToken,
+    changed_files: FxHashSet<WorkspaceRelativePathBuf>,
+    handle: JoinHandle<()>,
+    targets: FxHashSet<Target>,
+}
+
 pub struct ExecWorkflow {
     args: ExecArgs,
     console: Arc<Console>,
@@ -213,7 +259,8 @@ impl ExecWorkflow {
         // files when combined with `--affected --force`. Instead, forcing only
         // bypasses the affected *selection* filter (see `skip_affected` below),
         // so unaffected tasks still run while affected files are still passed.
-        if self.args.force {
+        // Tracing file system access also requires tasks to actually run.
+        if self.args.force || self.args.trace_fs {
             self.session
                 .get_cache_engine()?
                 .force_mode(CacheMode::Write);
@@ -275,12 +322,19 @@ impl ExecWorkflow {
                 }
             })?;
 
-            return Ok(if self.affected { None } else { Some(1) });
+            if !self.args.watch {
+                return Ok(if self.affected { None } else { Some(1) });
+            }
         }
 
         // Step 3
         self.display_affected(&action_context)?;
 
+        // Step 5 (replaces 4)
+        if self.args.watch {
+            return self.watch(action_context, action_graph).await;
+        }
+
         // Step 4
         let results = self
             .execute_action_pipeline(action_context, action_graph)
@@ -478,6 +532,33 @@ impl ExecWorkflow {
         // Insert targets into the graph
         let job = self.get_job();
         let job_total = self.get_job_total();
+        let job_balance = self.get_job_balance();
+
+        if job.is_some()
+            && job_total.is_some()
+            && job_balance == moon_exec_plan::JobBalance::Duration
+        {
+            let timings = query_task_timings(
+                &*self.session.get_cache_engine()?,
+                QueryTaskTimingsOptions {
+                    timings_file: self.args.timings.as_ref().map(|file| {
+                        if file.is_absolute() {
+                            file.to_path_buf()
+                        } else {
+                            self.session.working_dir.join(file)
+                        }
+                    }),
+                },
+            )?;
+
+            if timings.is_empty() {
+                warn!(
+                    "Balancing jobs by duration, but no task timings were found, falling back to an equal duration for each task"
+                );
+            }
+
+            action_graph_builder.set_task_timings(timings);
+        }
 
         let partition = action_graph_builder
             .run_tasks_with_plan(
@@ -491,6 +572,7 @@ impl ExecWorkflow {
                     interactive: self.args.interactive,
                     job,
                     job_total,
+                    job_balance,
                     // Forcing runs tasks even when not affected, but still
                     // tracks affected files for the `affectedFiles` option.
                     skip_affected: !self.affected || self.args.force,
@@ -507,6 +589,7 @@ impl ExecWorkflow {
         {
             self.print(format!("Job index: <property>{index}</property>"))?;
             self.print(format!("Job total: <property>{total}</property>"))?;
+            self.print(format!("Job balance: <property>{job_balance}</property>"))?;
             self.print(format!(
                 "Partition size: <property>{}</property>",
                 partition.size.unwrap_or_default()
@@ -603,6 +686,7 @@ impl ExecWorkflow {
         );
 
         action_context.passthrough_args = self.args.passthrough.clone();
+        action_context.trace_fs = self.args.trace_fs;
 
         let results =
             run_action_pipeline(&self.session, action_context, action_graph, self.summary).await?;
@@ -610,6 +694,336 @@ impl ExecWorkflow {
         Ok(results)
     }
 
+    // Step 5
+    async fn watch(
+        &mut self,
+        action_context: ActionContext,
+        action_graph: ActionGraph,
+    ) -> miette::Result<Option<u8>> {
+        // Resolve the tasks to re-run before the initial pipeline starts,
+        // as persistent tasks are never re-ran, they are kept alive instead
+        let watch_targets = self.resolve_watch_targets().await?;
+
+        self.print_step("Executing action pipeline")?;
+
+        // The initial run is never cancelled, as it owns the persistent tasks
+        let initial_handle = self.spawn_action_pipeline(
+            self.prepare_action_context(action_context),
+            action_graph,
+            CancellationToken::new(),
+        );
+
+        self.print_step("Watching for file changes")?;
+
+        let (event_tx, mut event_rx) = broadcast::channel::<FileEvent>(1024);
+        let (shutdown_tx, shutdown_rx) = broadcast::channel::<()>(1);
+        let watcher_handle = tokio::spawn(start_file_watcher(
+            self.session.workspace_root.clone(),
+            event_tx,
+            shutdown_rx,
+        ));
+
+        let mut signal_rx = ProcessRegistry::instance().receive_signal();
+        let mut runs: Vec<WatchRun> = vec![];
+
+        loop {
+            let mut changed_files = FxHashSet::default();
+
+            tokio::select! {
+                _ = signal_rx.recv() => {
+                    debug!("Received signal, shutting down watcher");
+                    break;
+                }
+                result = event_rx.recv() => match result {
+                    Ok(event) => {
+                        changed_files.insert(event.path);
+                    }
+                    Err(RecvError::Lagged(count)) => {
+                        warn!("File change event receiver lagged by {count} events");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => {
+                        break;
+                    }
+                },
+            };
+
+            // Events from a single debounce window arrive in a burst
+            while let Ok(Ok(event)) = timeout(WATCH_SETTLE_TIMEOUT, event_rx.recv()).await {
+                changed_files.insert(event.path);
+            }
+
+            if let Some(file) = changed_files.iter().find(|file| is_config_file(file)) {
+                self.render_watch_notice(
+                    format!(
+                        "Configuration file <file>{file}</file> changed, restart to apply the changes."
+                    ),
+                    Variant::Caution,
+                )?;
+            }
+
+            // Cancel in-flight runs that share tasks with the new run,
+            // and carry over their changed files, so that the tasks
+            // affected by them are not lost when re-running
+            let mut affected = self
+                .build_watch_action_graph(&watch_targets, changed_files.clone())
+                .await;
+
+            if let Ok(Some((_, _, targets))) = &affected {
+                let mut carried = false;
+
+                for run in std::mem::take(&mut runs) {
+                    if run.handle.is_finished() {
+                        continue;
+                    }
+
+                    if run.targets.is_disjoint(targets) {
+                        runs.push(run);
+                        continue;
+                    }
+
+                    debug!(
+                        targets = ?run.targets.iter().map(|target| target.as_str()).collect::<Vec<_>>(),
+                        "Cancelling in-flight run, as its tasks were affected again",
+                    );
+
+                    run.cancel_token.cancel();
+                    let _ = run.handle.await;
+
+                    changed_files.extend(run.changed_files);
+                    carried = true;
+                }
+
+                if carried {
+                    affected = self
+                        .build_watch_action_graph(&watch_targets, changed_files.clone())
+                        .await;
+                }
+            }
+
+            let (action_context, action_graph, targets) = match affected {
+                Ok(Some(result)) => result,
+                Ok(None) => {
+                    debug!(
+                        files = ?changed_files.iter().map(|file| file.as_str()).collect::<Vec<_>>(),
+                        "No tasks affected by changed files",
+                    );
+
+                    continue;
+                }
+                Err(error) => {
+                    // Don't exit the watcher, the next change may fix it
+                    self.console.err.write_line(format!("{error:?}"))?;
+
+                    continue;
+                }
+            };
+
+            self.render_watch_notice(
+                format!(
+                    "Files changed, re-running {} affected {}",
+                    targets.len(),
+                    if targets.len() == 1 { "task" } else { "tasks" }
+                ),
+                Variant::Info,
+            )?;
+
+            let cancel_token = CancellationToken::new();
+
+            runs.push(WatchRun {
+                handle: self.spawn_action_pipeline(
+                    self.prepare_action_context(action_context),
+                    action_graph,
+                    cancel_token.clone(),
+                ),
+                cancel_token,
+                changed_files,
+                targets,
+            });
+        }
+
+        // Pipelines receive the same signal, so wait for them to shutdown
+        for run in runs {
+            let _ = run.handle.await;
+        }
+
+        let _ = initial_handle.await;
+        let _ = shutdown_tx.send(());
+
+        if watcher_handle.is_finished() {
+            watcher_handle.await.into_diagnostic()??;
+        }
+
+        Ok(None)
+    }
+
+    async fn resolve_watch_targets(&mut self) -> miette::Result<Vec<Target>> {
+        let mut action_graph_builder = self
+            .session
+            .build_action_graph_with_options(ActionGraphBuilderOptions::new(false))
+            .await?;
+
+        if let Some(query_input) = &self.args.query {
+            action_graph_builder.set_query(query_input)?;
+        }
+
+        let partition = action_graph_builder
+            .run_tasks_with_plan(
+                &self.plan,
+                RunRequirements {
+                    ci: self.ci_env,
+                    ci_check: self.ci_check,
+                    dependencies: UpstreamScope::None,
+                    dependents: DownstreamScope::None,
+                    interactive: self.args.interactive,
+                    skip_affected: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let workspace_graph = self.session.get_workspace_graph().await?;
+        let mut targets = vec![];
+
+        for target in partition.targets.into_values() {
+            if !workspace_graph.get_task(&target)?.is_persistent() {
+                targets.push(target);
+            }
+        }
+
+        targets.sort();
+
+        Ok(targets)
+    }
+
+    async fn build_watch_action_graph(
+        &self,
+        watch_targets: &[Target],
+        changed_files: FxHashSet<WorkspaceRelativePathBuf>,
+    ) -> miette::Result<Option<(ActionContext, ActionGraph, FxHashSet<Target>)>> {
+        if watch_targets.is_empty() {
+            return Ok(None);
+        }
+
+        let mut action_graph_builder = if self.get_no_actions() {
+            self.session
+                .build_action_graph_with_options(ActionGraphBuilderOptions::new(false))
+                .await?
+        } else {
+            self.session.build_action_graph().await?
+        };
+
+        let upstream = self.get_upstream();
+        let downstream = self.get_downstream();
+
+        action_graph_builder.set_changed_files(changed_files)?;
+        action_graph_builder
+            .track_affected(upstream, downstream, self.ci_env && self.ci_check)
+            .await?;
+
+        let partition = action_graph_builder
+            .run_tasks(
+                watch_targets
+                    .iter()
+                    .map(|target| TargetLocator::Qualified(target.to_owned()))
+                    .collect::<Vec<_>>(),
+                RunRequirements {
+                    ci: self.ci_env,
+                    ci_check: self.ci_check,
+                    dependencies: upstream,
+                    dependents: downstream,
+                    include_relations: self.get_include_relations(),
+                    interactive: self.args.interactive,
+                    skip_affected: false,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        if partition.targets.is_empty() {
+            return Ok(None);
+        }
+
+        let (mut action_context, action_graph) = action_graph_builder.build();
+
+        action_context.initial_targets.extend(
+            partition
+                .targets
+                .into_values()
+                .map(TargetLocator::Qualified),
+        );
+
+        // Include dependencies, as those are ran (and cancelled) as well
+        let targets = action_graph
+            .get_nodes()
+            .into_iter()
+            .filter_map(|node| match node {
+                ActionNode::RunTask(inner) => Some(inner.target.clone()),
+                _ => None,
+            })
+            .collect();
+
+        Ok(Some((action_context, action_graph, targets)))
+    }
+
+    fn prepare_action_context(&self, mut action_context: ActionContext) -> ActionContext {
+        if action_context.initial_targets.is_empty() {
+            action_context.initial_targets.extend(
+                self.get_targets()
+                    .into_iter()
+                    .map(|target| target.to_owned())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        action_context.passthrough_args = self.args.passthrough.clone();
+        action_context.trace_fs = self.args.trace_fs;
+        action_context
+    }
+
+    fn spawn_action_pipeline(
+        &self,
+        action_context: ActionContext,
+        action_graph: ActionGraph,
+        cancel_token: CancellationToken,
+    ) -> JoinHandle<()> {
+        let session = self.session.clone();
+        let console = Arc::clone(&self.console);
+        let summary = self.summary;
+
+        tokio::spawn(async move {
+            let result = async {
+                let mut pipeline = create_action_pipeline(&session, summary).await?;
+
+                // Scope processes to this run, so that cancelling or
+                // aborting it does not terminate the other runs
+                pipeline.cancel_token = Some(cancel_token);
+                pipeline.process_scope = Some(ProcessScope::new());
+                pipeline
+                    .run_with_context(action_graph, action_context)
+                    .await
+            }
+            .await;
+
+            // Failures are rendered but don't stop the watcher
+            if let Err(error) = result {
+                let _ = console.err.write_line(format!("{error:?}"));
+            }
+        })
+    }
+
+    fn render_watch_notice(&self, message: String, variant: Variant) -> miette::Result<()> {
+        self.console.render(element! {
+            Container {
+                Notice(variant: variant) {
+                    StyledText(content: message)
+                }
+            }
+        })?;
+
+        Ok(())
+    }
+
     fn should_print(&self) -> bool {
         !self.test_env
             && !self.console.out.is_quiet()
@@ -701,6 +1115,14 @@ impl ExecWorkflow {
         self.plan.pipeline.job.or(self.args.job)
     }
 
+    fn get_job_balance(&self) -> moon_exec_plan::JobBalance {
+        self.plan
+            .pipeline
+            .balance
+            .or(self.args.balance.map(Into::into))
+            .unwrap_or_default()
+    }
+
     fn get_job_total(&self) -> Option<usize> {
         self.plan.pipeline.job_total.or(self.args.job_total)
     }
@@ -744,3 +1166,7 @@ impl ExecWorkflow {
             .unwrap_or(UpstreamScope::Deep)
     }
 }
+
+fn is_config_file(file: &WorkspaceRelativePathBuf) -> bool {
+    file.as_str().starts_with(".moon/") || file.file_stem() == Some("moon")
+}
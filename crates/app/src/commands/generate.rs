@@ -9,14 +9,18 @@ use clap::{
     parser::ValueSource,
 };
 use iocraft::prelude::{View, Weight, element};
-use moon_codegen::{CodegenError, FileState, Template, TemplateContext};
+use moon_codegen::{
+    CodegenError, FileState, Template, TemplateContext, TemplateLock, TemplateLockEntry,
+    TemplatePlan,
+};
+use moon_common::path::RelativePathBuf;
 use moon_common::*;
 use moon_config::{TemplateVariable, TemplateVariableEnumDefault};
 use moon_console::{Console, ui::*};
 use rustc_hash::FxHashMap;
-use starbase_utils::json::{self, JsonValue, serde_json};
+use starbase_utils::json::{self, JsonValue};
 use std::path::PathBuf;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
 #[derive(Args, Clone, Debug)]
 pub struct GenerateArgs {
@@ -41,9 +45,26 @@ pub struct GenerateArgs {
     #[arg(long, help = "Force overwrite any existing files at the destination")]
     force: bool,
 
+    #[arg(long, help = "Print the plan of file operations in JSON format")]
+    json: bool,
+
+    #[arg(
+        long,
+        help = "Run the template's post-generate hooks without confirming (required with --json)"
+    )]
+    run_hooks: bool,
+
     #[arg(long, help = "Create a new template")]
     template: bool,
 
+    #[arg(
+        long,
+        value_name = "DEST",
+        help = "Update a previously generated destination with the latest template, merging changes",
+        conflicts_with_all = ["dest", "template"]
+    )]
+    update: Option<String>,
+
     // Variable args (after --)
     #[arg(last = true, help = "Arguments to define as variable values")]
     vars: Vec<String>,
@@ -83,12 +104,43 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> SessionResult
 
     generator.load_templates().await?;
 
-    let id = select_identifier(&session.console, &args.id, || {
+    // When updating, templates are inherited from the destination's lock file
+    let update_lock = match &args.update {
+        Some(dest) => {
+            let dest = resolve_dest_path(&session, dest, None)?.1;
+            let lock = TemplateLock::load(&dest)?;
+
+            if lock.templates.is_empty() {
+                return Err(CodegenError::MissingTemplateLock(dest).into());
+            }
+
+            Some((dest, lock))
+        }
+        None => None,
+    };
+
+    let id_arg = match (&args.id, &update_lock) {
+        (None, Some((_, lock))) if lock.templates.len() == 1 => {
+            lock.templates.keys().next().cloned()
+        }
+        _ => args.id.clone(),
+    };
+
+    let id = select_identifier(&session.console, &id_arg, || {
         Ok(SelectProps {
-            label: "Which template to generate?".into(),
+            label: if update_lock.is_some() {
+                "Which template to update?".into()
+            } else {
+                "Which template to generate?".into()
+            },
             options: generator
                 .templates
                 .iter()
+                .filter(|(id, _)| {
+                    update_lock
+                        .as_ref()
+                        .is_none_or(|(_, lock)| lock.templates.contains_key(*id))
+                })
                 .map(|(id, template)| {
                     SelectOption::new(id).description(&template.config.description)
                 })
@@ -98,33 +150,50 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> SessionResult
     })
     .await?;
 
+    let previous = match &update_lock {
+        Some((dest, lock)) => {
+            let Some(entry) = lock.templates.get(&id) else {
+                return Err(CodegenError::MissingTemplateInLock {
+                    id: id.to_string(),
+                    dest: dest.to_owned(),
+                }
+                .into());
+            };
+
+            Some(entry)
+        }
+        None => None,
+    };
+
     // Create the template instance
     let mut template = generator.get_template(&id)?;
     let mut has_prompts = !template.config.variables.is_empty();
 
-    console.render(element! {
-        Container{
-            Section(title: template.id.as_str()) {
-                StyledText(
-                    content: if args.dry_run {
-                        format!("{} <invalid>(dry run)</invalid>", template.config.title)
-                    } else {
-                        template.config.title.clone()
-                    },
-                    weight: Weight::Bold
-                )
-                StyledText(content: &template.config.description)
+    if !args.json {
+        console.render(element! {
+            Container{
+                Section(title: template.id.as_str()) {
+                    StyledText(
+                        content: if args.dry_run {
+                            format!("{} <invalid>(dry run)</invalid>", template.config.title)
+                        } else {
+                            template.config.title.clone()
+                        },
+                        weight: Weight::Bold
+                    )
+                    StyledText(content: &template.config.description)
+                }
             }
-        }
-    })?;
+        })?;
+    }
 
     // Gather variables
-    let mut context = gather_variables(&args, &template, console).await?;
+    let mut context = gather_variables(&args, &template, console, previous).await?;
     context.insert("working_dir", &session.working_dir);
     context.insert("workspace_root", &session.workspace_root);
 
     // Determine the destination path
-    let relative_dest = match &args.dest {
+    let relative_dest = match args.update.as_ref().or(args.dest.as_ref()) {
         Some(dest) => dest.to_owned(),
         None => {
             if let Some(dest) = &template.config.destination {
@@ -157,13 +226,8 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> SessionResult
             }
         }
     };
-    let relative_from_root = relative_dest.starts_with('/');
-    let relative_dest = template.interpolate_path(&PathBuf::from(relative_dest), &context)?;
-    let dest = relative_dest.to_logical_path(if relative_from_root {
-        &session.workspace_root
-    } else {
-        &session.working_dir
-    });
+    let (relative_dest, dest) =
+        resolve_dest_path(&session, &relative_dest, Some((&mut template, &context)))?;
 
     debug!(dest = ?dest, "Destination path set");
 
@@ -174,75 +238,121 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> SessionResult
     // Load template files and determine when to overwrite
     template.load_files(&dest, &context)?;
 
-    for file in template.files.values_mut() {
-        if file.is_skipped() {
-            file.state = FileState::Skip;
-            continue;
-        }
-
-        if file.dest_path.exists() {
-            if args.force || file.is_forced() {
-                file.state = FileState::Replace;
+    let lock_entry = TemplateLockEntry::new(
+        &template,
+        &dest,
+        &context,
+        generator.get_template_revision(&template).await?,
+    )?;
+
+    // Or merge with the previous render when updating
+    if let Some(previous) = previous {
+        debug!(
+            template = template.id.as_str(),
+            from = ?previous.revision,
+            to = ?lock_entry.revision,
+            "Updating previously generated template",
+        );
+
+        template.update_files(&dest, previous)?;
+    } else {
+        for file in template.files.values_mut() {
+            if file.is_skipped() {
+                file.state = FileState::Skip;
                 continue;
             }
 
-            // Merge files when applicable
-            if file.is_mergeable().is_some() {
-                let mut index = 2;
+            if file.dest_path.exists() {
+                if args.force || file.is_forced() {
+                    file.state = FileState::Replace;
+                    continue;
+                }
+
+                // Merge files when applicable
+                if file.is_mergeable().is_some() {
+                    let mut index = 2;
+                    has_prompts = true;
+
+                    console
+                        .render_prompt(element! {
+                            Select(
+                                label: format!(
+                                    "File <path>{}</path> already exists, what to do?",
+                                    file.dest_path.display()
+                                ),
+                                default_index: 2,
+                                on_index: &mut index,
+                                options: vec![
+                                    SelectOption::new("Keep existing file"),
+                                    SelectOption::new("Merge new file into existing file"),
+                                    SelectOption::new("Replace existing with new file"),
+                                ]
+                            )
+                        })
+                        .await?;
+
+                    file.state = match index {
+                        0 => FileState::Skip,
+                        1 => FileState::Merge,
+                        _ => FileState::Replace,
+                    };
+
+                    continue;
+                }
+
+                // Confirm whether to replace file
+                let mut confirmed = false;
                 has_prompts = true;
 
                 console
                     .render_prompt(element! {
-                        Select(
+                        Confirm(
                             label: format!(
-                                "File <path>{}</path> already exists, what to do?",
+                                "File <path>{}</path> already exists, overwrite?",
                                 file.dest_path.display()
                             ),
-                            default_index: 2,
-                            on_index: &mut index,
-                            options: vec![
-                                SelectOption::new("Keep existing file"),
-                                SelectOption::new("Merge new file into existing file"),
-                                SelectOption::new("Replace existing with new file"),
-                            ]
+                            on_confirm: &mut confirmed
                         )
                     })
                     .await?;
 
-                file.state = match index {
-                    0 => FileState::Skip,
-                    1 => FileState::Merge,
-                    _ => FileState::Replace,
-                };
-
-                continue;
-            }
-
-            // Confirm whether to replace file
-            let mut confirmed = false;
-            has_prompts = true;
-
-            console
-                .render_prompt(element! {
-                    Confirm(
-                        label: format!(
-                            "File <path>{}</path> already exists, overwrite?",
-                            file.dest_path.display()
-                        ),
-                        on_confirm: &mut confirmed
-                    )
-                })
-                .await?;
-
-            if confirmed {
-                file.state = FileState::Replace;
+                if confirmed {
+                    file.state = FileState::Replace;
+                }
             }
         }
     }
 
+    // Plan the file operations before writing, so that
+    // diffs are against the existing destination files
+    let plan = if args.dry_run || args.json {
+        Some(TemplatePlan::new(&template, &dest)?)
+    } else {
+        None
+    };
+
     // Generate the files in the destination and print the results
     if !args.dry_run {
         generator.generate(&template)?;
+
+        let mut lock = TemplateLock::load(&dest)?;
+        lock.templates.insert(template.id.clone(), lock_entry);
+        lock.save(&dest)?;
+
+        if should_run_hooks(&args, &template, &context, console).await? {
+            // Output is captured in JSON mode so that stdout remains parseable
+            template
+                .run_post_generate_hooks(&dest, &context, !args.json)
+                .await?;
+        }
+    }
+
+    if args.json
+        && let Some(plan) = &plan
+    {
+        console.out.write_line(json::format(plan, true)?)?;
+
+        return Ok(None);
     }
 
     console.render(element! {
@@ -261,6 +371,8 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> SessionResult
                         FileState::Merge => ("merged", "---➤", "success"),
                         FileState::Replace => ("replaced", "-➤", "failure"),
                         FileState::Skip => ("skipped", "--➤", "invalid"),
+                        FileState::Update if file.conflicted => ("conflict", "-➤", "failure"),
+                        FileState::Update => ("updated", "--➤", "success"),
                     };
 
                     element! {
@@ -297,9 +409,129 @@ pub async fn generate(session: MoonSession, args: GenerateArgs) -> SessionResult
         }
     })?;
 
+    if args.dry_run
+        && let Some(plan) = &plan
+    {
+        for file in &plan.files {
+            if let Some(diff) = &file.diff {
+                render_diff(console, diff)?;
+                console.out.write_newline()?;
+            }
+        }
+    }
+
+    let conflicted_count = template
+        .files
+        .values()
+        .filter(|file| file.conflicted)
+        .count();
+
+    if conflicted_count > 0 {
+        console.render(element! {
+            Container {
+                Notice(variant: Variant::Caution) {
+                    StyledText(content: format!(
+                        "Unable to merge all template changes, resolve the conflict markers in {conflicted_count} file(s)",
+                    ))
+                }
+            }
+        })?;
+    }
+
     Ok(None)
 }
 
+fn render_diff(console: &Console, diff: &str) -> miette::Result<()> {
+    for (index, line) in diff.lines().enumerate() {
+        console.out.write_line(if index < 2 {
+            color::muted_light(line)
+        } else if line.starts_with("@@") {
+            color::muted(line)
+        } else if line.starts_with('+') {
+            color::success(line)
+        } else if line.starts_with('-') {
+            color::failure(line)
+        } else {
+            line.to_owned()
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Hooks run arbitrary commands, so they're only ran without consent when the
+/// template was loaded from the local file system. Otherwise the user must confirm,
+/// or pass `--run-hooks`, which is always required when not interactive (`--json`).
+async fn should_run_hooks(
+    args: &GenerateArgs,
+    template: &Template,
+    context: &TemplateContext,
+    console: &Console,
+) -> miette::Result<bool> {
+    let hooks = template.get_post_generate_hooks(context)?;
+
+    if hooks.is_empty() || args.run_hooks {
+        return Ok(!hooks.is_empty());
+    }
+
+    if args.json {
+        warn!(
+            template = template.id.as_str(),
+            "Skipping {} post-generate hooks, pass --run-hooks to run them",
+            hooks.len(),
+        );
+
+        return Ok(false);
+    }
+
+    if !template.has_remote_hooks() {
+        return Ok(true);
+    }
+
+    let mut confirmed = false;
+
+    console
+        .render_prompt(element! {
+            Confirm(
+                label: format!(
+                    "Template <id>{}</id> was not loaded from the file system, run its post-generate hooks?",
+                    template.id
+                ),
+                description: Some(hooks.join("\n")),
+                on_confirm: &mut confirmed
+            )
+        })
+        .await?;
+
+    if !confirmed {
+        debug!(
+            template = template.id.as_str(),
+            "Post-generate hooks were not confirmed, skipping them"
+        );
+    }
+
+    Ok(confirmed)
+}
+
+fn resolve_dest_path(
+    session: &MoonSession,
+    value: &str,
+    interpolate: Option<(&mut Template, &TemplateContext)>,
+) -> miette::Result<(RelativePathBuf, PathBuf)> {
+    let relative_from_root = value.starts_with('/');
+    let relative_dest = match interpolate {
+        Some((template, context)) => template.interpolate_path(&PathBuf::from(value), context)?,
+        None => RelativePathBuf::from(value),
+    };
+    let dest = relative_dest.to_logical_path(if relative_from_root {
+        &session.workspace_root
+    } else {
+        &session.working_dir
+    });
+
+    Ok((relative_dest, dest))
+}
+
 fn is_numeric(value: &str) -> bool {
     value
         .as_bytes()
@@ -392,7 +624,12 @@ pub fn parse_args_into_variables(
                 );
             }
             TemplateVariable::Object(_) => {
-                debug!("Skipping object based arguments");
+                command = command.arg(
+                    Arg::new(name)
+                        .long(name)
+                        .action(ArgAction::Set)
+                        .value_parser(StringValueParser::new()),
+                );
             }
             TemplateVariable::String(_) => {
                 command = command.arg(
@@ -438,8 +675,15 @@ pub fn parse_args_into_variables(
                                 "Setting array variable"
                             );
 
-                            for item in value {
-                                list.push(parse_arg_into_json(item)?);
+                            // A single JSON array is used as the entire list
+                            if let [item] = value.as_slice()
+                                && let JsonValue::Array(items) = parse_arg_into_json(item)?
+                            {
+                                list.extend(items);
+                            } else {
+                                for item in value {
+                                    list.push(parse_arg_into_json(item)?);
+                                }
                             }
                         }
 
@@ -483,7 +727,13 @@ pub fn parse_args_into_variables(
                         }
                     }
                     TemplateVariable::Object(_) => {
-                        vars.insert(name, &FxHashMap::<String, JsonValue>::default());
+                        if let Some(value) = matches.get_one::<String>(arg_name) {
+                            debug!(name, value, "Setting object variable");
+
+                            let data: JsonValue = json::parse(value)?;
+
+                            vars.insert(name, &data);
+                        }
                     }
                     TemplateVariable::String(_) => {
                         if let Some(value) = matches.get_one::<String>(arg_name) {
@@ -521,17 +771,42 @@ pub fn parse_args_into_variables(
         }
     };
 
+    // Validate values against the type and constraints of each variable
+    for (name, cfg) in config {
+        if let Some(value) = vars.get(name)
+            && let Some(message) = cfg.validate_value(value)
+        {
+            return Err(CodegenError::FailedToParseArgs {
+                error: miette::miette!("Invalid value for --{name}: {message}"),
+            }
+            .into());
+        }
+    }
+
     Ok(vars)
 }
 
-#[instrument(skip(template, console))]
+#[instrument(skip(template, console, previous))]
 pub async fn gather_variables(
     args: &GenerateArgs,
     template: &Template,
     console: &Console,
+    previous: Option<&TemplateLockEntry>,
 ) -> miette::Result<TemplateContext> {
     let mut context = parse_args_into_variables(&args.vars, &template.config.variables)?;
 
+    // Inherit the values from the previous generation when updating,
+    // so that only new variables are prompted for
+    if let Some(previous) = previous {
+        for (name, value) in &previous.variables {
+            if !context.contains_key(name) && template.config.variables.contains_key(name) {
+                debug!(name, value = ?value, "Inheriting variable from template lock");
+
+                context.insert(name, value);
+            }
+        }
+    }
+
     debug!("Gathering variable values from defaults and user prompts");
 
     let mut variables = template.config.variables.iter().collect::<Vec<_>>();
@@ -547,49 +822,64 @@ pub async fn gather_variables(
 
         let required = config.is_required();
 
+        // Use the default value when the conditions for prompting are not met
+        let skip_prompts = if config.is_when_met(|name| context.get(name)) {
+            skip_prompts
+        } else {
+            debug!(
+                name,
+                "Not prompting for variable as its conditions were not met"
+            );
+
+            true
+        };
+
         match config {
             TemplateVariable::Array(cfg) => {
                 let value = if skip_prompts || cfg.prompt.is_none() {
                     cfg.default.clone()
                 } else {
-                    let mut value = String::new();
-
-                    console
-                        .render_prompt(element! {
-                            Input(
-                                label: cfg.prompt.as_ref().unwrap(),
-                                description: Some("As a JSON string".into()),
-                                on_value: &mut value,
-                                validate: move |input: String| {
-                                    let input = if input.is_empty() {
-                                        if required {
-                                            return Some("A value is required".into());
-                                        } else {
-                                            "[]"
+                    let mut list = vec![];
+
+                    // Prompt for each item until an empty value is entered
+                    loop {
+                        let mut value = String::new();
+                        let first = list.is_empty();
+
+                        console
+                            .render_prompt(element! {
+                                Input(
+                                    label: cfg.prompt.as_ref().unwrap(),
+                                    description: Some(format!(
+                                        "Item {}, as a JSON value or string, leave empty to finish",
+                                        list.len() + 1
+                                    )),
+                                    on_value: &mut value,
+                                    validate: move |input: String| {
+                                        if input.is_empty() {
+                                            return if required && first {
+                                                Some("At least 1 item is required".into())
+                                            } else {
+                                                None
+                                            };
                                         }
-                                    } else {
-                                        &input
-                                    };
 
-                                    match serde_json::from_str::<JsonValue>(input) {
-                                        Ok(data) => if data.is_array() {
-                                            None
-                                        } else {
-                                            Some("Must be an array".into())
-                                        },
-                                        Err(error) => Some(format!("Invalid JSON: {error}")),
+                                        parse_arg_into_json(&input)
+                                            .err()
+                                            .map(|error| format!("Invalid JSON: {error}"))
                                     }
-                                }
-                            )
-                        })
-                        .await?;
+                                )
+                            })
+                            .await?;
 
-                    let data: JsonValue = json::parse(value)?;
+                        if value.is_empty() {
+                            break;
+                        }
 
-                    match data {
-                        JsonValue::Array(inner) => inner,
-                        _ => vec![],
+                        list.push(parse_arg_into_json(&value)?);
                     }
+
+                    list
                 };
 
                 debug!(name, value = ?value, "Setting array variable");
@@ -622,6 +912,7 @@ pub async fn gather_variables(
                 let value = if skip_prompts || cfg.prompt.is_none() {
                     cfg.default
                 } else {
+                    let validator = cfg.clone();
                     let mut value = String::new();
 
                     console
@@ -635,11 +926,7 @@ pub async fn gather_variables(
                                         return Some("A number is required".into());
                                     };
 
-                                    if required && number == 0 {
-                                        Some("A non-zero value is required".into())
-                                    } else {
-                                        None
-                                    }
+                                    validator.validate_value(number)
                                 }
                             )
                         })
@@ -656,44 +943,52 @@ pub async fn gather_variables(
                 let value = if skip_prompts || cfg.prompt.is_none() {
                     cfg.default.clone()
                 } else {
-                    let mut value = String::new();
-
-                    console
-                        .render_prompt(element! {
-                            Input(
-                                label: cfg.prompt.as_ref().unwrap(),
-                                description: Some("As a JSON string".into()),
-                                on_value: &mut value,
-                                validate: move |input: String| {
-                                    let input = if input.is_empty() {
-                                        if required {
-                                            return Some("A value is required".into());
-                                        } else {
-                                            "{}"
+                    let mut map = FxHashMap::default();
+
+                    // Prompt for each entry until an empty value is entered
+                    loop {
+                        let mut value = String::new();
+                        let first = map.is_empty();
+
+                        console
+                            .render_prompt(element! {
+                                Input(
+                                    label: cfg.prompt.as_ref().unwrap(),
+                                    description: Some(format!(
+                                        "Entry {}, as key=value, leave empty to finish",
+                                        map.len() + 1
+                                    )),
+                                    on_value: &mut value,
+                                    validate: move |input: String| {
+                                        if input.is_empty() {
+                                            return if required && first {
+                                                Some("At least 1 entry is required".into())
+                                            } else {
+                                                None
+                                            };
                                         }
-                                    } else {
-                                        &input
-                                    };
 
-                                    match serde_json::from_str::<JsonValue>(input) {
-                                        Ok(data) => if data.is_object() {
-                                            None
-                                        } else {
-                                            Some("Must be an object".into())
-                                        },
-                                        Err(error) => Some(format!("Invalid JSON: {error}")),
+                                        match input.split_once('=') {
+                                            Some((key, value)) if !key.trim().is_empty() => {
+                                                parse_arg_into_json(value.trim())
+                                                    .err()
+                                                    .map(|error| format!("Invalid JSON: {error}"))
+                                            }
+                                            _ => Some("Must be in the format of key=value".into()),
+                                        }
                                     }
-                                }
-                            )
-                        })
-                        .await?;
+                                )
+                            })
+                            .await?;
 
-                    let data: JsonValue = json::parse(value)?;
+                        let Some((key, value)) = value.split_once('=') else {
+                            break;
+                        };
 
-                    match data {
-                        JsonValue::Object(inner) => FxHashMap::from_iter(inner),
-                        _ => FxHashMap::default(),
+                        map.insert(key.trim().to_owned(), parse_arg_into_json(value.trim())?);
                     }
+
+                    map
                 };
 
                 debug!(name, value = ?value, "Setting object variable");
@@ -704,6 +999,7 @@ pub async fn gather_variables(
                 let value = if skip_prompts || cfg.prompt.is_none() {
                     cfg.default.clone()
                 } else {
+                    let validator = cfg.clone();
                     let mut value = String::new();
 
                     console
@@ -712,13 +1008,7 @@ pub async fn gather_variables(
                                 label: cfg.prompt.as_ref().unwrap(),
                                 default_value: &cfg.default,
                                 on_value: &mut value,
-                                validate: move |input: String| {
-                                    if required && input.is_empty() {
-                                        Some("A value is required".into())
-                                    } else {
-                                        None
-                                    }
-                                }
+                                validate: move |input: String| validator.validate_value(&input)
                             )
                         })
                         .await?;
@@ -0,0 +1,148 @@
+use crate::session::{MoonSession, SessionResult};
+use clap::Args;
+use iocraft::prelude::{Size, element};
+use miette::IntoDiagnostic;
+use moon_action_pipeline::reports::history::{TaskRunRecord, summarize_history};
+use moon_console::ui::{
+    Container, Notice, Style, StyledText, Table, TableCol, TableHeader, TableRow, Variant,
+};
+use moon_time::chrono::TimeDelta;
+use moon_time::{elapsed, now_timestamp, parse_duration};
+use starbase_utils::json;
+use std::time::Duration;
+use tracing::instrument;
+
+#[derive(Args, Clone, Debug)]
+pub struct HistoryArgs {
+    #[arg(long, help = "Filter tasks based on this target pattern")]
+    filter: Option<String>,
+
+    #[arg(long, help = "Only include tasks that have been flaky")]
+    flaky: bool,
+
+    #[arg(
+        long,
+        default_value = "30 days",
+        help = "Only include runs within this duration"
+    )]
+    since: String,
+
+    #[arg(long, help = "Print in JSON format")]
+    json: bool,
+}
+
+fn format_duration(duration: Option<Duration>) -> String {
+    duration.map(elapsed).unwrap_or_else(|| "--".into())
+}
+
+#[instrument(skip(session))]
+pub async fn history(session: MoonSession, args: HistoryArgs) -> SessionResult {
+    let since = parse_duration(&args.since)
+        .map_err(|error| miette::miette!("Invalid duration for --since: {error}"))?;
+    let since_date = TimeDelta::from_std(since)
+        .ok()
+        .and_then(|delta| now_timestamp().checked_sub_signed(delta))
+        .map(|timestamp| timestamp.date());
+
+    let records = session
+        .get_cache_engine()?
+        .history
+        .load::<TaskRunRecord>(since_date.as_ref())?;
+
+    let mut summaries = summarize_history(&records);
+
+    if let Some(filter) = &args.filter {
+        let pattern = regex::Regex::new(&format!("(?i){filter}")).into_diagnostic()?;
+
+        summaries.retain(|summary| pattern.is_match(&summary.target));
+    }
+
+    if args.flaky {
+        summaries.retain(|summary| summary.is_flaky());
+    }
+
+    if args.json {
+        session
+            .console
+            .out
+            .write_line(json::format(&summaries, true)?)?;
+
+        return Ok(None);
+    }
+
+    if summaries.is_empty() {
+        session.console.render(element! {
+            Container {
+                Notice(variant: Variant::Caution) {
+                    StyledText(content: format!("No task runs recorded within the last <shell>{}</shell>.", args.since))
+                }
+            }
+        })?;
+
+        return Ok(None);
+    }
+
+    let target_width = summaries
+        .iter()
+        .fold(0, |acc, summary| acc.max(summary.target.len()));
+
+    session.console.render(element! {
+        Container {
+            Table(
+                headers: vec![
+                    TableHeader::new("Task", Size::Length((target_width + 5) as u32)),
+                    TableHeader::new("Runs", Size::Length(8)),
+                    TableHeader::new("Cached", Size::Length(8)),
+                    TableHeader::new("Failed", Size::Length(8)),
+                    TableHeader::new("Flaky", Size::Length(8)),
+                    TableHeader::new("p50", Size::Length(12)),
+                    TableHeader::new("p95", Size::Length(12)),
+                ]
+            ) {
+                #(summaries.into_iter().enumerate().map(|(i, summary)| {
+                    let flaky = summary.passed_after_retry + summary.inconsistent_hashes.len();
+
+                    element! {
+                        TableRow(row: i as i32) {
+                            TableCol(col: 0) {
+                                StyledText(
+                                    content: &summary.target,
+                                    style: Style::Label
+                                )
+                            }
+                            TableCol(col: 1) {
+                                StyledText(content: summary.runs.to_string())
+                            }
+                            TableCol(col: 2) {
+                                StyledText(
+                                    content: summary.cached.to_string(),
+                                    style: Style::Muted
+                                )
+                            }
+                            TableCol(col: 3) {
+                                StyledText(
+                                    content: summary.failed.to_string(),
+                                    style: if summary.failed > 0 { Style::Failure } else { Style::Muted }
+                                )
+                            }
+                            TableCol(col: 4) {
+                                StyledText(
+                                    content: flaky.to_string(),
+                                    style: if flaky > 0 { Style::Caution } else { Style::Muted }
+                                )
+                            }
+                            TableCol(col: 5) {
+                                StyledText(content: format_duration(summary.p50))
+                            }
+                            TableCol(col: 6) {
+                                StyledText(content: format_duration(summary.p95))
+                            }
+                        }
+                    }
+                }))
+            }
+        }
+    })?;
+
+    Ok(None)
+}
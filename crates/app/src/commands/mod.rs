@@ -1,5 +1,6 @@
 pub mod action_graph;
 pub mod bin;
+pub mod cache;
 pub mod check;
 pub mod ci;
 pub mod clean;
@@ -13,6 +14,7 @@ pub mod extension;
 pub mod generate;
 pub mod graph;
 pub mod hash;
+pub mod history;
 pub mod init;
 pub mod mcp;
 pub mod migrate;
@@ -26,6 +28,7 @@ pub mod sync;
 pub mod syncs;
 pub mod task;
 pub mod task_graph;
+pub mod task_outputs;
 pub mod tasks;
 pub mod teardown;
 pub mod template;
@@ -1,5 +1,6 @@
 pub mod affected;
 pub mod changed_files;
+pub mod path;
 pub mod projects;
 pub mod tasks;
 
@@ -22,6 +23,12 @@ pub enum QueryCommands {
     )]
     ChangedFiles(changed_files::QueryChangedFilesArgs),
 
+    #[command(
+        name = "path",
+        about = "Query all dependency paths between two projects or tasks."
+    )]
+    Path(path::QueryPathArgs),
+
     #[command(
         name = "projects",
         about = "Query for projects within the project graph."
@@ -0,0 +1,85 @@
+use crate::session::{MoonSession, SessionResult};
+use clap::Args;
+use moon_common::Id;
+use moon_config::{DependencyScope, TaskDependencyType};
+use moon_task::Target;
+use moon_workspace_graph::{GraphConnections, GraphData, GraphPath};
+use serde::Serialize;
+use starbase_utils::json;
+use tracing::instrument;
+
+#[derive(Args, Clone, Debug)]
+pub struct QueryPathArgs {
+    #[arg(help = "Project ID or task target to start from")]
+    from: String,
+
+    #[arg(help = "Project ID or task target to end at (a dependency of the start)")]
+    to: String,
+
+    #[arg(
+        long,
+        help = "Return all simple paths, instead of only the shortest paths"
+    )]
+    all: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPathResult<K, E> {
+    pub from: K,
+    pub to: K,
+    pub paths: Vec<GraphPath<K, E>>,
+    pub strongly_connected_components: Vec<Vec<K>>,
+}
+
+#[instrument(skip(session))]
+pub async fn path(session: MoonSession, args: QueryPathArgs) -> SessionResult {
+    let workspace_graph = session.get_workspace_graph().await?;
+
+    // Targets always contain a scope separator, while project IDs do not
+    let output = if args.from.contains(':') || args.to.contains(':') {
+        let task_graph = &workspace_graph.tasks;
+        let from = task_graph.get_unexpanded(&Target::parse(&args.from)?)?;
+        let to = task_graph.get_unexpanded(&Target::parse(&args.to)?)?;
+        let from_index = task_graph.get_node_index(from);
+        let to_index = task_graph.get_node_index(to);
+
+        json::format(
+            &QueryPathResult::<Target, TaskDependencyType> {
+                from: from.target.clone(),
+                to: to.target.clone(),
+                paths: if args.all {
+                    task_graph.simple_paths_between(from_index, to_index)
+                } else {
+                    task_graph.shortest_paths_between(from_index, to_index)
+                },
+                strongly_connected_components: task_graph.strongly_connected_components(),
+            },
+            true,
+        )?
+    } else {
+        let project_graph = &workspace_graph.projects;
+        let from = project_graph.get_unexpanded(&args.from)?;
+        let to = project_graph.get_unexpanded(&args.to)?;
+        let from_index = project_graph.get_node_index(from);
+        let to_index = project_graph.get_node_index(to);
+
+        json::format(
+            &QueryPathResult::<Id, DependencyScope> {
+                from: from.id.clone(),
+                to: to.id.clone(),
+                paths: if args.all {
+                    project_graph.simple_paths_between(from_index, to_index)
+                } else {
+                    project_graph.shortest_paths_between(from_index, to_index)
+                },
+                strongly_connected_components: project_graph.strongly_connected_components(),
+            },
+            true,
+        )?
+    };
+
+    session.console.out.write_line(output)?;
+
+    Ok(None)
+}
@@ -19,6 +19,14 @@ pub struct RunArgs {
         help_heading = super::HEADING_WORKFLOW,
     )]
     query: Option<String>,
+
+    #[arg(
+        long,
+        short = 'w',
+        help = "Watch for file changes and re-run affected tasks",
+        help_heading = super::HEADING_WORKFLOW,
+    )]
+    watch: bool,
 }
 
 #[instrument(skip(session))]
@@ -30,6 +38,7 @@ pub async fn run(session: MoonSession, args: RunArgs) -> SessionResult {
         exec.targets = args.targets;
         exec.on_failure = OnFailure::Bail;
         exec.query = args.query;
+        exec.watch = args.watch;
         exec
     })
     .await
@@ -3,7 +3,7 @@ use crate::prompts::select_target;
 use crate::session::{MoonSession, SessionResult};
 use clap::Args;
 use iocraft::prelude::{View, element};
-use moon_action::{ActionNode, RunTaskNode};
+use moon_action::{ActionNode, RunTaskNode, TaskCheckOutcome, TaskCheckResult};
 use moon_action_context::ActionContext;
 use moon_common::is_test_env;
 use moon_console::ui::{
@@ -12,8 +12,10 @@ use moon_console::ui::{
 };
 use moon_process::Command;
 use moon_project::Project;
-use moon_task::{Target, Task};
+use moon_task::{Target, Task, TaskCheck, TaskCheckType};
+use moon_task_runner::TaskRunCacheState;
 use moon_task_runner::command_builder::CommandBuilder;
+use serde::Serialize;
 use starbase_utils::json;
 use tracing::instrument;
 
@@ -24,6 +26,20 @@ pub struct TaskArgs {
 
     #[arg(long, help = "Print in JSON format")]
     json: bool,
+
+    #[arg(
+        long,
+        help = "Explain the task's checks and their results from the last run"
+    )]
+    explain_checks: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskChecksExplanation<'task> {
+    checks: &'task [TaskCheck],
+    explanation: Option<String>,
+    results: Vec<TaskCheckResult>,
 }
 
 #[instrument(skip(session))]
@@ -53,6 +69,10 @@ pub async fn task(session: MoonSession, args: TaskArgs) -> SessionResult {
     let task = workspace_graph.get_task(&target)?;
     let console = &session.console;
 
+    if args.explain_checks {
+        return explain_checks(&session, &task, args.json).await;
+    }
+
     if args.json {
         console.out.write_line(json::format(&task, true)?)?;
 
@@ -70,6 +90,9 @@ pub async fn task(session: MoonSession, args: TaskArgs) -> SessionResult {
     if task.is_persistent() {
         modes.push("persistent");
     }
+    if task.options.sandbox {
+        modes.push("sandboxed");
+    }
 
     let toolchains = project.get_enabled_toolchains_for_task(&task);
 
@@ -385,6 +408,144 @@ pub async fn task(session: MoonSession, args: TaskArgs) -> SessionResult {
     Ok(None)
 }
 
+async fn explain_checks(session: &MoonSession, task: &Task, json: bool) -> SessionResult {
+    let state = session
+        .get_cache_engine()?
+        .state
+        .load_target_state::<TaskRunCacheState>(&task.target)?;
+    let results = state.data.checks;
+    let explanation = explain_check_results(&results);
+
+    if json {
+        session.console.out.write_line(json::format(
+            &TaskChecksExplanation {
+                checks: &task.checks,
+                explanation,
+                results,
+            },
+            true,
+        )?)?;
+
+        return Ok(None);
+    }
+
+    session.console.render(element! {
+        Container {
+            Section(title: "Checks") {
+                #(task.checks.is_empty().then(|| {
+                    element! {
+                        StyledText(
+                            content: "No checks have been configured for this task.",
+                            style: Style::MutedLight
+                        )
+                    }
+                }))
+                #(task.checks.iter().map(|check| {
+                    let result = results
+                        .iter()
+                        .find(|result| result.script == check.get_script());
+
+                    element! {
+                        Entry(
+                            name: match check.get_type() {
+                                TaskCheckType::Condition => "Condition",
+                                TaskCheckType::Fingerprint => "Fingerprint",
+                                TaskCheckType::Requirement => "Requirement",
+                            },
+                            value: element! {
+                                StyledText(
+                                    content: check.get_script(),
+                                    style: Style::Shell
+                                )
+                            }.into_any()
+                        ) {
+                            List {
+                                ListItem {
+                                    StyledText(
+                                        content: result
+                                            .map(format_check_result)
+                                            .unwrap_or_else(|| "not executed in the last run".into()),
+                                        style: if result.is_some_and(|res| res.outcome.is_failure()) {
+                                            Style::Failure
+                                        } else {
+                                            Style::MutedLight
+                                        }
+                                    )
+                                }
+                            }
+                        }
+                    }
+                }))
+            }
+            #(explanation.map(|explanation| {
+                element! {
+                    Section(title: "Explanation") {
+                        StyledText(content: explanation)
+                    }
+                }
+            }))
+        }
+    })?;
+
+    Ok(None)
+}
+
+fn format_check_result(result: &TaskCheckResult) -> String {
+    let mut comments = vec![];
+
+    if let Some(code) = result.exit_code {
+        comments.push(format!("exit code {code}"));
+    }
+
+    if result.timed_out {
+        comments.push("timed out".into());
+    }
+
+    if let Some(fingerprint) = &result.fingerprint {
+        comments.push(fingerprint.to_owned());
+    }
+
+    if comments.is_empty() {
+        result.outcome.label().into()
+    } else {
+        format!("{} ({})", result.outcome.label(), comments.join(", "))
+    }
+}
+
+fn explain_check_results(results: &[TaskCheckResult]) -> Option<String> {
+    if let Some(failed) = results.iter().find(|result| result.outcome.is_failure()) {
+        return Some(format!(
+            "The last run failed because the check \"{}\" was unsuccessful.",
+            failed.script
+        ));
+    }
+
+    let conditions = results
+        .iter()
+        .filter(|result| {
+            matches!(
+                result.outcome,
+                TaskCheckOutcome::ConditionMet | TaskCheckOutcome::ConditionUnmet
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if conditions.is_empty() {
+        return None;
+    }
+
+    Some(
+        if conditions
+            .iter()
+            .all(|result| result.outcome == TaskCheckOutcome::ConditionMet)
+        {
+            "The last run was skipped because all conditions were met.".into()
+        } else {
+            "The last run was not skipped because not all conditions were met.".into()
+        },
+    )
+}
+
 async fn build_command(
     session: &MoonSession,
     project: &Project,
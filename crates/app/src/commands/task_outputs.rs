@@ -0,0 +1,211 @@
+use crate::app_error::AppError;
+use crate::prompts::select_target;
+use crate::session::{MoonSession, SessionResult};
+use clap::Args;
+use iocraft::prelude::{Size, element};
+use moon_cache::{ContentHash, TaskOutputsSource};
+use moon_console::ui::{
+    Container, Notice, SelectOption, SelectProps, Style, StyledText, Table, TableCol, TableHeader,
+    TableRow, Variant,
+};
+use moon_task::Target;
+use moon_task_runner::output_extractor::OutputExtractor;
+use starbase_utils::json;
+use std::path::PathBuf;
+use tracing::instrument;
+
+#[derive(Args, Clone, Debug)]
+pub struct TaskOutputsArgs {
+    #[arg(help = "Task target to inspect")]
+    target: Option<Target>,
+
+    #[arg(
+        long,
+        help = "Hash of the run to use. Defaults to the last successful run"
+    )]
+    hash: Option<String>,
+
+    #[arg(
+        long,
+        help = "Extract the cached outputs into this directory instead of listing them"
+    )]
+    extract: Option<PathBuf>,
+
+    #[arg(long, help = "Print in JSON format")]
+    json: bool,
+}
+
+#[instrument(skip(session))]
+pub async fn task_outputs(session: MoonSession, args: TaskOutputsArgs) -> SessionResult {
+    let workspace_graph = session.get_workspace_graph().await?;
+
+    let target = select_target(&session.console, &args.target, || {
+        Ok(SelectProps {
+            label: "Which task's outputs to view?".into(),
+            options: workspace_graph
+                .get_tasks_unexpanded()?
+                .into_iter()
+                .filter(|task| task.has_outputs())
+                .map(|task| {
+                    SelectOption::new(&task.target).description_opt(task.description.clone())
+                })
+                .collect(),
+            ..Default::default()
+        })
+    })
+    .await?;
+
+    let task = workspace_graph.get_task(&target)?;
+    let app_context = session.get_app_context().await?;
+    let extractor = OutputExtractor::new(&app_context, &task)?;
+
+    let hash = match &args.hash {
+        // The hash is used in file paths, so must be a real hash
+        Some(hash) if ContentHash::from_hex(hash).is_err() => {
+            return Err(AppError::InvalidTaskOutputsHash(hash.to_owned()).into());
+        }
+        Some(hash) => hash.to_owned(),
+        None => extractor
+            .get_last_hash()?
+            .ok_or_else(|| AppError::MissingTaskOutputsHash(target.to_string()))?,
+    };
+
+    let missing_error = || AppError::MissingTaskOutputs {
+        target: target.to_string(),
+        hash: hash.clone(),
+    };
+
+    if let Some(dir) = &args.extract {
+        let dest_dir = session.working_dir.join(dir);
+
+        if !extractor.extract(&hash, &dest_dir).await? {
+            return Err(missing_error().into());
+        }
+
+        session.console.render(element! {
+            Container {
+                Notice(variant: Variant::Success) {
+                    StyledText(
+                        content: format!(
+                            "Extracted outputs of <id>{target}</id> (<hash>{}</hash>) to <path>{}</path>",
+                            &hash[0..8.min(hash.len())],
+                            dest_dir.display(),
+                        ),
+                    )
+                }
+            }
+        })?;
+
+        return Ok(None);
+    }
+
+    let Some(outputs) = extractor.load_manifest(&hash).await? else {
+        return Err(missing_error().into());
+    };
+
+    if args.json {
+        session
+            .console
+            .out
+            .write_line(json::format(&outputs, true)?)?;
+
+        return Ok(None);
+    }
+
+    if outputs.files.is_empty() && outputs.symlinks.is_empty() {
+        session.console.render(element! {
+            Container {
+                Notice(variant: Variant::Info) {
+                    StyledText(content: "No outputs were cached for this task.")
+                }
+            }
+        })?;
+
+        return Ok(None);
+    }
+
+    let path_width = outputs
+        .files
+        .iter()
+        .map(|file| file.path.as_str().len())
+        .chain(outputs.symlinks.iter().map(|link| link.path.as_str().len()))
+        .max()
+        .unwrap_or_default()
+        .max(4);
+
+    session.console.render(element! {
+        Container {
+            Table(
+                headers: vec![
+                    TableHeader::new("Path", Size::Length((path_width + 5) as u32)),
+                    TableHeader::new("Size", Size::Length(15)),
+                    TableHeader::new("Executable", Size::Length(12)).hide_below(100),
+                    TableHeader::new("Digest", Size::Auto).hide_below(120),
+                ]
+            ) {
+                #(outputs.files.iter().enumerate().map(|(i, file)| {
+                    element! {
+                        TableRow(row: i as i32) {
+                            TableCol(col: 0) {
+                                StyledText(
+                                    content: file.path.as_str(),
+                                    style: Style::File
+                                )
+                            }
+                            TableCol(col: 1) {
+                                StyledText(
+                                    content: format!("{} bytes", file.size),
+                                )
+                            }
+                            TableCol(col: 2) {
+                                StyledText(
+                                    content: if file.executable { "Yes" } else { "No" },
+                                )
+                            }
+                            TableCol(col: 3) {
+                                StyledText(
+                                    content: file.digest.as_deref().unwrap_or_default(),
+                                    style: Style::Hash
+                                )
+                            }
+                        }
+                    }
+                }))
+                #(outputs.symlinks.iter().enumerate().map(|(i, link)| {
+                    element! {
+                        TableRow(row: (outputs.files.len() + i) as i32) {
+                            TableCol(col: 0) {
+                                StyledText(
+                                    content: link.path.as_str(),
+                                    style: Style::File
+                                )
+                            }
+                            TableCol(col: 1) {
+                                StyledText(
+                                    content: format!("-> {}", link.target),
+                                    style: Style::MutedLight
+                                )
+                            }
+                        }
+                    }
+                }))
+            }
+            StyledText(
+                content: format!(
+                    "{} files totaling {} bytes, {} (<hash>{}</hash>)",
+                    outputs.files.len(),
+                    outputs.get_total_size(),
+                    match outputs.source {
+                        TaskOutputsSource::Fresh => "created by a run",
+                        TaskOutputsSource::Local => "from the local cache",
+                        TaskOutputsSource::Remote => "from the remote cache",
+                    },
+                    outputs.hash,
+                ),
+                style: Style::MutedLight
+            )
+        }
+    })?;
+
+    Ok(None)
+}
@@ -70,12 +70,10 @@ pub fn append_plugin_to_config_file(
     Ok(path.to_path_buf())
 }
 
-pub async fn run_action_pipeline(
+pub async fn create_action_pipeline(
     session: &MoonSession,
-    action_context: ActionContext,
-    action_graph: ActionGraph,
     summary: Option<Level>,
-) -> miette::Result<Vec<Action>> {
+) -> miette::Result<ActionPipeline> {
     let mut pipeline = ActionPipeline::new(
         session.get_app_context().await?,
         session.get_workspace_graph().await?,
@@ -89,17 +87,21 @@ pub async fn run_action_pipeline(
     pipeline.summary = summary;
 
     match &session.cli.command {
-        Commands::Check(_) => {
+        Commands::Check(cmd) => {
             pipeline.bail = true;
+            pipeline.trace_path = cmd.trace.clone();
         }
-        Commands::Ci(_) => {
+        Commands::Ci(cmd) => {
             pipeline.report_name = "ciReport.json".into();
+            pipeline.trace_path = cmd.trace.clone();
         }
         Commands::Exec(cmd) => {
             pipeline.bail = cmd.on_failure == OnFailure::Bail;
+            pipeline.trace_path = cmd.trace.clone();
         }
-        Commands::Run(_) => {
+        Commands::Run(cmd) => {
             pipeline.bail = true;
+            pipeline.trace_path = cmd.trace.clone();
         }
         Commands::Setup | Commands::Sync { .. } => {
             pipeline.summary = Some(Level::Two);
@@ -107,7 +109,22 @@ pub async fn run_action_pipeline(
         _ => {}
     };
 
-    let results = pipeline
+    // Relative to the current working directory
+    if let Some(trace_path) = &pipeline.trace_path {
+        pipeline.trace_path = Some(session.working_dir.join(trace_path));
+    }
+
+    Ok(pipeline)
+}
+
+pub async fn run_action_pipeline(
+    session: &MoonSession,
+    action_context: ActionContext,
+    action_graph: ActionGraph,
+    summary: Option<Level>,
+) -> miette::Result<Vec<Action>> {
+    let results = create_action_pipeline(session, summary)
+        .await?
         .run_with_context(action_graph, action_context)
         .await?;
 
@@ -1,5 +1,6 @@
 pub mod changed_files;
 pub mod projects;
+pub mod task_timings;
 pub mod tasks;
 
 use miette::IntoDiagnostic;
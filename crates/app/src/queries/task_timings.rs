@@ -0,0 +1,106 @@
+use moon_action_graph::TaskTimings;
+use moon_action_pipeline::reports::estimate::TaskEstimate;
+use moon_action_pipeline::reports::history::{TaskRunRecord, summarize_history};
+use moon_cache::CacheEngine;
+use moon_common::Id;
+use moon_task::Target;
+use moon_time::chrono::TimeDelta;
+use moon_time::now_timestamp;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use starbase_utils::json;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// How far back to look in the run history.
+const HISTORY_DAYS: i64 = 30;
+
+#[derive(Clone, Debug, Default)]
+pub struct QueryTaskTimingsOptions {
+    /// Path to a timings file, that maps targets (or task IDs)
+    /// to their duration in milliseconds.
+    pub timings_file: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RunReportEstimates {
+    comparison_estimate: RunReportTaskEstimates,
+}
+
+#[derive(Deserialize)]
+struct RunReportTaskEstimates {
+    tasks: FxHashMap<String, TaskEstimate>,
+}
+
+/// Load known task durations from, in order of precedence: the provided timings file,
+/// the p50 of each target in the run history, and the task estimates of the last run
+/// report. Estimates are bucketed by task ID, so they are only used as a fallback.
+pub fn query_task_timings(
+    cache_engine: &CacheEngine,
+    options: QueryTaskTimingsOptions,
+) -> miette::Result<TaskTimings> {
+    debug!("Querying for task timings");
+
+    let mut timings = TaskTimings::default();
+
+    // Last run report
+    let report_path = cache_engine.resolve_path("runReport.json");
+
+    if report_path.exists() {
+        match json::read_file::<RunReportEstimates>(&report_path) {
+            Ok(report) => {
+                for (task_id, estimate) in report.comparison_estimate.tasks {
+                    if estimate.count == 0 {
+                        continue;
+                    }
+
+                    if let Ok(id) = Id::new(task_id) {
+                        timings
+                            .tasks
+                            .insert(id, estimate.total / estimate.count as u32);
+                    }
+                }
+            }
+            Err(error) => {
+                warn!(
+                    report = ?report_path,
+                    "Failed to parse run report for task estimates: {error}",
+                );
+            }
+        };
+    }
+
+    // Run history
+    let since = now_timestamp()
+        .checked_sub_signed(TimeDelta::days(HISTORY_DAYS))
+        .map(|timestamp| timestamp.date());
+
+    let records = cache_engine.history.load::<TaskRunRecord>(since.as_ref())?;
+
+    for summary in summarize_history(&records) {
+        if let (Ok(target), Some(p50)) = (Target::parse(&summary.target), summary.p50) {
+            timings.targets.insert(target, p50);
+        }
+    }
+
+    // Timings file
+    if let Some(timings_file) = options.timings_file {
+        debug!(file = ?timings_file, "Loading task timings from file");
+
+        let data: FxHashMap<String, u64> = json::read_file(&timings_file)?;
+
+        for (key, millis) in data {
+            let duration = Duration::from_millis(millis);
+
+            if key.contains(':') {
+                timings.targets.insert(Target::parse(&key)?, duration);
+            } else {
+                timings.tasks.insert(Id::new(key)?, duration);
+            }
+        }
+    }
+
+    Ok(timings)
+}
@@ -8,11 +8,14 @@ use moon_api::Launchpad;
 use moon_app_context::AppContext;
 use moon_cache::{CacheContext, CacheEngine};
 use moon_cache_local::LocalStorage;
-use moon_cache_remote::{GrpcRemoteStorage, HttpRemoteStorage};
+use moon_cache_remote::{
+    GrpcRemoteStorage, HttpRemoteStorage, S3RemoteStorage, TrackedRemoteStorage,
+};
 use moon_codegen::CodeGenerator;
-use moon_common::{is_docker, is_formatted_output, is_test_env};
+use moon_common::{format_error_chain, is_docker, is_formatted_output, is_test_env};
 use moon_config::{
-    ExtensionsConfig, InheritedTasksManager, RemoteApi, ToolchainsConfig, WorkspaceConfig,
+    ExtensionsConfig, InheritedTasksManager, RemoteApi, ToolchainsConfig, VcsClient,
+    WorkspaceConfig,
 };
 use moon_config_loader::ConfigLoader;
 use moon_console::{Console, MoonReporter, create_console_theme};
@@ -25,11 +28,14 @@ use moon_process::ProcessRegistry;
 use moon_project_graph::ProjectGraph;
 use moon_task_graph::TaskGraph;
 use moon_toolchain_plugin::*;
-use moon_vcs::{BoxedVcs, git::Git};
+use moon_vcs::mercurial::{HgFlavor, Mercurial};
+use moon_vcs::{BoxedVcs, git::Git, jujutsu::Jujutsu};
 use moon_workspace::{WorkspaceBuilder, WorkspaceBuilderAsync, WorkspaceBuilderContext};
 use moon_workspace_graph::WorkspaceGraph;
 use proto_core::ProtoEnvironment;
+use serde::Serialize;
 use starbase::{AppExitCode, AppResult, AppSession};
+use starbase_utils::json;
 use std::env;
 use std::fmt;
 use std::path::PathBuf;
@@ -62,6 +68,7 @@ pub struct MoonSession {
     pub(crate) toolchain_registry: OnceCell<Arc<ToolchainRegistry>>,
     pub(crate) vcs_adapter: OnceLock<Arc<BoxedVcs>>,
     pub(crate) workspace_graph: OnceCell<Arc<WorkspaceGraph>>,
+    pub(crate) workspace_graph_data: OnceLock<Arc<Vec<u8>>>,
 
     // Configs
     pub extensions_config: Arc<ExtensionsConfig>,
@@ -99,6 +106,7 @@ impl MoonSession {
             working_dir: PathBuf::new(),
             workspace_config: Arc::new(WorkspaceConfig::default()),
             workspace_graph: OnceCell::new(),
+            workspace_graph_data: OnceLock::new(),
             workspace_root: PathBuf::new(),
             vcs_adapter: OnceLock::new(),
             cli,
@@ -243,14 +251,19 @@ impl MoonSession {
             if context.remote_config.is_enabled() {
                 match context.remote_config.api {
                     RemoteApi::Grpc => {
-                        engine
-                            .storage
-                            .add_remote_backend(GrpcRemoteStorage::new(context.clone())?);
+                        engine.storage.add_remote_backend(TrackedRemoteStorage::new(
+                            GrpcRemoteStorage::new(context.clone())?,
+                        ));
                     }
                     RemoteApi::Http => {
-                        engine
-                            .storage
-                            .add_remote_backend(HttpRemoteStorage::new(context.clone())?);
+                        engine.storage.add_remote_backend(TrackedRemoteStorage::new(
+                            HttpRemoteStorage::new(context.clone())?,
+                        ));
+                    }
+                    RemoteApi::S3 => {
+                        engine.storage.add_remote_backend(TrackedRemoteStorage::new(
+                            S3RemoteStorage::new(context.clone())?,
+                        ));
                     }
                 };
             }
@@ -331,13 +344,32 @@ impl MoonSession {
         if self.vcs_adapter.get().is_none() {
             let config = &self.workspace_config.vcs;
 
-            let git: BoxedVcs = Box::new(Git::load(
-                &self.workspace_root,
-                &config.default_branch,
-                &config.remote_candidates,
-            )?);
+            let vcs: BoxedVcs = match config.client {
+                VcsClient::Git => Box::new(Git::load(
+                    &self.workspace_root,
+                    &config.default_branch,
+                    &config.remote_candidates,
+                )?),
+                VcsClient::Jujutsu => Box::new(Jujutsu::load(
+                    &self.workspace_root,
+                    &config.default_branch,
+                    &config.remote_candidates,
+                )?),
+                VcsClient::Mercurial => Box::new(Mercurial::load(
+                    HgFlavor::Mercurial,
+                    &self.workspace_root,
+                    &config.default_branch,
+                    &config.remote_candidates,
+                )?),
+                VcsClient::Sapling => Box::new(Mercurial::load(
+                    HgFlavor::Sapling,
+                    &self.workspace_root,
+                    &config.default_branch,
+                    &config.remote_candidates,
+                )?),
+            };
 
-            let _ = self.vcs_adapter.set(Arc::new(git));
+            let _ = self.vcs_adapter.set(Arc::new(vcs));
         }
 
         Ok(self.vcs_adapter.get().map(Arc::clone).unwrap())
@@ -385,19 +417,74 @@ impl MoonSession {
         )
     }
 
+    /// When running as the daemon, serialize the builder so that clients
+    /// can load the graph without locating and loading projects themselves.
+    fn serialize_workspace_graph<T: Serialize>(&self, builder: &T) -> miette::Result<()> {
+        if self.is_daemon_server_command() {
+            let _ = self
+                .workspace_graph_data
+                .set(Arc::new(json::format(builder, false)?.into_bytes()));
+        }
+
+        Ok(())
+    }
+
+    async fn load_serialized_workspace_graph(&self) -> Option<Vec<u8>> {
+        let mut client = self.connect_to_daemon().await.ok()??;
+
+        match client.get_workspace_graph().await {
+            Ok(data) => data,
+            Err(error) => {
+                debug!(
+                    error = format_error_chain(&error),
+                    "Failed to load workspace graph from daemon, building it instead"
+                );
+
+                None
+            }
+        }
+    }
+
     async fn load_workspace_graph(&self) -> miette::Result<Arc<WorkspaceGraph>> {
+        let data = self.load_serialized_workspace_graph().await;
         let context = self.create_workspace_graph_context().await?;
 
         let workspace_graph = Arc::new(if self.workspace_config.experiments.async_graph_building {
-            WorkspaceBuilderAsync::new_with_cache(context)
-                .await?
-                .build()
-                .await?
+            let mut builder = None;
+
+            if let Some(data) = &data {
+                match WorkspaceBuilderAsync::new_from_serialized(context.clone(), data) {
+                    Ok(inner) => builder = Some(inner),
+                    Err(error) => log_serialized_workspace_graph_error(error),
+                };
+            }
+
+            let builder = match builder {
+                Some(inner) => inner,
+                None => WorkspaceBuilderAsync::new_with_cache(context).await?,
+            };
+
+            self.serialize_workspace_graph(&builder)?;
+
+            builder.build().await?
         } else {
-            WorkspaceBuilder::new_with_cache(context)
-                .await?
-                .build()
-                .await?
+            let mut builder = None;
+
+            if let Some(data) = &data {
+                match WorkspaceBuilder::new_from_serialized(context.clone(), data) {
+                    Ok(inner) => builder = Some(inner),
+                    Err(error) => log_serialized_workspace_graph_error(error),
+                };
+            }
+
+            let builder = match builder {
+                Some(inner) => inner,
+                None => WorkspaceBuilder::new_with_cache(context).await?,
+            };
+
+            self.serialize_workspace_graph(&builder)?;
+
+            builder.build().await?
         });
 
         // Update the plugin registries with the graph
@@ -565,3 +652,10 @@ impl fmt::Debug for MoonSession {
             .finish()
     }
 }
+
+fn log_serialized_workspace_graph_error(error: miette::Report) {
+    debug!(
+        error = format_error_chain(&error),
+        "Failed to deserialize workspace graph from daemon, building it instead"
+    );
+}
@@ -35,6 +35,9 @@ impl MoonSession {
             if let Ok(app_context) = session.get_app_context().await {
                 let mut state = state.write().await;
                 state.app_context = app_context;
+
+                // Hashing settings may have changed
+                state.task_inputs.clear();
             }
         })
     }
@@ -45,12 +48,24 @@ impl MoonSession {
         let session = self.clone();
 
         tokio::spawn(async move {
+            let generation = state.read().await.watcher_generation;
+
             if let Ok(graph) = session.get_workspace_graph().await
                 && let Ok(app_context) = session.get_app_context().await
             {
                 let mut state = state.write().await;
+
+                // Another change was observed while building,
+                // so this graph is already stale
+                if state.watcher_generation != generation {
+                    return;
+                }
+
                 state.app_context = app_context;
                 state.workspace_graph = graph;
+                state.workspace_graph_data = session.workspace_graph_data.get().cloned();
+                state.workspace_graph_generation = generation;
+                state.task_inputs.clear();
             }
         })
     }
@@ -63,6 +78,7 @@ impl MoonSession {
         self.project_graph.take();
         self.task_graph.take();
         self.workspace_graph.take();
+        self.workspace_graph_data.take();
     }
 
     pub fn reset_vcs(&mut self) {
@@ -44,7 +44,11 @@ impl WorkspaceWatcher {
 
 #[async_trait]
 impl FileWatcher<AtomicDaemonState> for WorkspaceWatcher {
-    async fn on_init(&mut self, _state: AtomicDaemonState) -> miette::Result<()> {
+    async fn on_init(&mut self, state: AtomicDaemonState) -> miette::Result<()> {
+        // Build the graphs in the background, so that they're
+        // ready to be served by the time a client requests them
+        self.graph_handle = Some(self.session.rebuild_graphs(state));
+
         Ok(())
     }
 
@@ -143,6 +147,14 @@ impl WorkspaceWatcher {
             handle.abort();
         }
 
+        // Stop serving the stale graph and hashes while rebuilding
+        {
+            let mut state = state.write().await;
+            state.watcher_generation += 1;
+            state.workspace_graph_data = None;
+            state.task_inputs.clear();
+        }
+
         // Ensure the cache/state files are cleared before rebuilding
         let cache_engine = self.session.get_cache_engine()?;
 
@@ -6,8 +6,13 @@ mod headers;
 mod http_remote_storage;
 mod http_tls;
 mod remote_error;
+mod s3_remote_storage;
+mod s3_signer;
+mod tracked_remote_storage;
 
 pub use compressable_blob::*;
 pub use grpc_remote_storage::*;
 pub use http_remote_storage::*;
 pub use remote_error::*;
+pub use s3_remote_storage::*;
+pub use tracked_remote_storage::*;
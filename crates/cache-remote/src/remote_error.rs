@@ -78,6 +78,17 @@ pub enum RemoteError {
     #[error("The HTTP based remote service is currently not supported, use gRPC instead.")]
     NoHttpClient,
 
+    #[diagnostic(code(remote::s3::invalid_endpoint))]
+    #[error("Invalid S3 endpoint {endpoint}, must be a fully-qualified URL.")]
+    S3InvalidEndpoint { endpoint: String },
+
+    #[diagnostic(code(remote::s3::missing_config))]
+    #[error(
+        "The S3 remote service requires a bucket to be configured with the {} setting.",
+        "remote.s3.bucket"
+    )]
+    S3MissingConfig,
+
     #[diagnostic(code(remote::unsupported_protocol))]
     #[error("Unknown remote host protocol, only gRPC is supported.")]
     UnknownHostProtocol,
@@ -0,0 +1,588 @@
+use crate::compressable_blob::CompressableBlob;
+use crate::grpc_remote_storage::is_upload_allowed;
+use crate::headers::extract_headers;
+use crate::http_tls::*;
+use crate::remote_error::RemoteError;
+use crate::s3_signer::*;
+use async_trait::async_trait;
+use bazel_remote_apis::build::bazel::remote::execution::v2::ActionResult;
+use miette::IntoDiagnostic;
+use moon_blob::{BlobInput, BlobOutput, Bytes};
+use moon_cache_storage::{
+    CacheCapabilities, CacheContext, Manifest, StorageBackend, check_blob_integrity,
+};
+use moon_common::{Id, color, is_ci, is_remote};
+use moon_config::{RemoteCompression, RemoteS3Config};
+use moon_hash::Digest;
+use moon_time::chrono::Utc;
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Client, Method, Response, Url};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{debug, error, warn};
+
+/// Object metadata that records the compression of a blob.
+const COMPRESSION_HEADER: &str = "x-amz-meta-moon-compression";
+
+/// An HTTP client that signs every request for a specific bucket.
+#[derive(Debug)]
+struct S3Client {
+    client: Client,
+    credentials: Option<S3Credentials>,
+    region: String,
+}
+
+impl S3Client {
+    async fn send(
+        &self,
+        method: Method,
+        url: Url,
+        mut headers: HeaderMap,
+        body: Option<Bytes>,
+    ) -> Result<Response, reqwest::Error> {
+        // Without credentials, requests are sent anonymously,
+        // which works for buckets with public access
+        if let Some(credentials) = &self.credentials {
+            let payload_hash = match &body {
+                Some(body) => hash_payload(body),
+                None => EMPTY_PAYLOAD_HASH.to_owned(),
+            };
+
+            sign_request(
+                credentials,
+                &self.region,
+                method.as_str(),
+                &url,
+                &mut headers,
+                &payload_hash,
+                Utc::now(),
+            );
+        }
+
+        let mut request = self.client.request(method, url).headers(headers);
+
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+
+        request.send().await
+    }
+}
+
+#[derive(Debug)]
+pub struct S3RemoteStorage {
+    context: CacheContext,
+    id: Id,
+
+    // Bucket
+    base_url: Url,
+    config: RemoteS3Config,
+    credentials: Option<S3Credentials>,
+
+    // States
+    capabilities: OnceLock<CacheCapabilities>,
+    client: OnceLock<Arc<S3Client>>,
+
+    // Every object is a separate request, so limit the
+    // amount of parallels, for the same reasons as HTTP.
+    semaphore: Arc<Semaphore>,
+}
+
+impl S3RemoteStorage {
+    pub fn new(context: CacheContext) -> miette::Result<Self> {
+        let Some(config) = context.remote_config.s3.clone() else {
+            return Err(RemoteError::S3MissingConfig.into());
+        };
+
+        let endpoint = match context.remote_config.host.as_deref() {
+            Some(host) if !host.is_empty() => host.to_owned(),
+            _ => format!("https://s3.{}.amazonaws.com", config.region),
+        };
+
+        Ok(Self {
+            base_url: build_base_url(&endpoint, &config)?,
+            capabilities: OnceLock::new(),
+            client: OnceLock::new(),
+            config,
+            context,
+            credentials: S3Credentials::from_env(),
+            id: Id::raw("s3-remote-cache"),
+            semaphore: Arc::new(Semaphore::new(100)),
+        })
+    }
+
+    fn create_client(&self, headers: HeaderMap) -> miette::Result<Client> {
+        let config = &self.context.remote_config;
+        let mut client = Client::builder()
+            .user_agent("moon")
+            .tcp_keepalive(Duration::from_secs(60))
+            .default_headers(headers);
+
+        if let Some(mtls) = &config.mtls {
+            client = create_mtls_config(client, mtls, &self.context.workspace_root)?
+        } else if let Some(tls) = &config.tls {
+            client = create_tls_config(client, tls, &self.context.workspace_root)?
+        } else if self.base_url.scheme() == "https" {
+            client = create_native_tls_config(client)?;
+        }
+
+        let client = client
+            .build()
+            .map_err(|error| map_error("create_client", error, self.context.remote_debug))?;
+
+        Ok(client)
+    }
+
+    fn get_client(&self) -> Arc<S3Client> {
+        Arc::clone(self.client.get_or_init(|| {
+            Arc::new(S3Client {
+                client: Client::new(),
+                credentials: self.credentials.clone(),
+                region: self.config.region.clone(),
+            })
+        }))
+    }
+
+    fn get_object_url(&self, path: &str, hash: &str) -> Url {
+        build_object_url(
+            &self.base_url,
+            &self.config,
+            &self.context.remote_config.cache.instance_name,
+            path,
+            hash,
+        )
+    }
+}
+
+#[async_trait]
+impl StorageBackend for S3RemoteStorage {
+    fn get_capabilities(&self) -> &CacheCapabilities {
+        self.capabilities.get_or_init(CacheCapabilities::default)
+    }
+
+    fn get_id(&self) -> &Id {
+        &self.id
+    }
+
+    fn is_readable(&self) -> bool {
+        self.context.remote_config.is_enabled() && self.client.get().is_some()
+    }
+
+    fn is_writable(&self) -> bool {
+        self.context.remote_config.is_enabled()
+            && is_upload_allowed(
+                self.client.get().is_some(),
+                is_ci(),
+                self.context.remote_config.cache.local_read_only,
+            )
+    }
+
+    async fn connect(&self) -> miette::Result<()> {
+        let config = &self.context.remote_config;
+
+        if is_remote() && config.is_localhost() {
+            warn!(
+                storage = self.get_id().as_str(),
+                host = &config.host,
+                "Remote service is configured with a localhost endpoint, but we are in a CI environment; disabling service",
+            );
+
+            return Ok(());
+        }
+
+        debug!(
+            storage = self.get_id().as_str(),
+            instance = &config.cache.instance_name,
+            "Connecting to S3 bucket {} at {} {}",
+            color::id(&self.config.bucket),
+            color::url(self.base_url.as_str()),
+            if self.credentials.is_some() {
+                "(with credentials)"
+            } else {
+                "(anonymous)"
+            }
+        );
+
+        // Extract headers and abort early if not enabled
+        let Some(headers) = extract_headers(config)? else {
+            return Ok(());
+        };
+
+        if config.cache.compression.is_enabled() {
+            debug!("Blobs will be compressed with {}", config.cache.compression);
+        }
+
+        let _ = self.client.set(Arc::new(S3Client {
+            client: self.create_client(headers)?,
+            credentials: self.credentials.clone(),
+            region: self.config.region.clone(),
+        }));
+
+        Ok(())
+    }
+
+    async fn retrieve_manifest(&self, digest: Digest) -> miette::Result<Option<Manifest>> {
+        match self
+            .get_client()
+            .send(
+                Method::GET,
+                self.get_object_url("ac", &digest.hash),
+                HeaderMap::new(),
+                None,
+            )
+            .await
+        {
+            Ok(response) => {
+                let status = response.status();
+
+                if status.is_success() {
+                    let bytes =
+                        response
+                            .bytes()
+                            .await
+                            .map_err(|error| RemoteError::HttpCallFailed {
+                                error: Box::new(error),
+                            })?;
+                    let result: ActionResult = serde_json::from_slice(&bytes).into_diagnostic()?;
+
+                    Ok(Some(Manifest::from_bazel_action_result(result)?))
+                } else if status.as_u16() == 404 {
+                    Ok(None)
+                } else {
+                    Err(map_response_error(
+                        "retrieve_manifest",
+                        response,
+                        self.context.remote_debug,
+                    )
+                    .into())
+                }
+            }
+            Err(error) => {
+                Err(map_error("retrieve_manifest", error, self.context.remote_debug).into())
+            }
+        }
+    }
+
+    async fn store_manifest(&self, digest: Digest, manifest: Manifest) -> miette::Result<()> {
+        let body =
+            serde_json::to_vec(&manifest.into_bazel_action_result(false)).into_diagnostic()?;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+
+        match self
+            .get_client()
+            .send(
+                Method::PUT,
+                self.get_object_url("ac", &digest.hash),
+                headers,
+                Some(Bytes::from(body)),
+            )
+            .await
+        {
+            Ok(response) => {
+                if response.status().is_success() {
+                    Ok(())
+                } else {
+                    Err(
+                        map_response_error("store_manifest", response, self.context.remote_debug)
+                            .into(),
+                    )
+                }
+            }
+            Err(error) => Err(map_error("store_manifest", error, self.context.remote_debug).into()),
+        }
+    }
+
+    async fn find_missing_blobs(&self, blob_digests: Vec<Digest>) -> miette::Result<Vec<Digest>> {
+        let mut set = JoinSet::<miette::Result<Option<Digest>>>::new();
+        let debug_enabled = self.context.remote_debug;
+
+        for digest in blob_digests {
+            let client = self.get_client();
+            let url = self.get_object_url("cas", &digest.hash);
+            let semaphore = self.semaphore.clone();
+
+            set.spawn(async move {
+                let Ok(_permit) = semaphore.acquire().await else {
+                    return Ok(Some(digest));
+                };
+
+                match client.send(Method::HEAD, url, HeaderMap::new(), None).await {
+                    // Anything other than a success is treated as missing,
+                    // so that the blob is uploaded again
+                    Ok(response) => Ok(if response.status().is_success() {
+                        None
+                    } else {
+                        Some(digest)
+                    }),
+                    Err(error) => Err(map_error("find_missing_blobs", error, debug_enabled).into()),
+                }
+            });
+        }
+
+        let mut missing = vec![];
+
+        while let Some(result) = set.join_next().await {
+            if let Some(digest) = result.into_diagnostic()?? {
+                missing.push(digest);
+            }
+        }
+
+        Ok(missing)
+    }
+
+    async fn retrieve_blobs(
+        &self,
+        blob_digests: Vec<Digest>,
+        _stream: bool,
+    ) -> miette::Result<Vec<BlobOutput>> {
+        let mut set = JoinSet::<miette::Result<Option<BlobOutput>>>::new();
+        let debug_enabled = self.context.remote_debug;
+
+        for digest in blob_digests {
+            let client = self.get_client();
+            let url = self.get_object_url("cas", &digest.hash);
+            let semaphore = self.semaphore.clone();
+
+            set.spawn(async move {
+                let Ok(_permit) = semaphore.acquire().await else {
+                    return Ok(None);
+                };
+
+                match client.send(Method::GET, url, HeaderMap::new(), None).await {
+                    Ok(response) => {
+                        let status = response.status();
+
+                        if status.is_success() {
+                            let compression = get_compression_from_header(
+                                response.headers().get(COMPRESSION_HEADER),
+                            );
+
+                            let Ok(bytes) = response.bytes().await else {
+                                return Ok(None);
+                            };
+
+                            let mut blob = CompressableBlob::new(digest, bytes.to_vec());
+                            blob.compression = compression;
+                            blob.decompress()?;
+
+                            if !check_blob_integrity(&blob.digest, &blob.bytes)? {
+                                return Ok(None);
+                            }
+
+                            return Ok(Some(BlobOutput::from(blob.inner)));
+                        }
+
+                        Err(map_response_error("retrieve_blobs", response, debug_enabled).into())
+                    }
+                    Err(error) => Err(map_error("retrieve_blobs", error, debug_enabled).into()),
+                }
+            });
+        }
+
+        let mut blobs = vec![];
+
+        while let Some(result) = set.join_next().await {
+            if let Some(blob) = result.into_diagnostic()?? {
+                blobs.push(blob);
+            }
+        }
+
+        Ok(blobs)
+    }
+
+    async fn store_blobs(
+        &self,
+        blob_inputs: Vec<BlobInput>,
+        _stream: bool,
+    ) -> miette::Result<Vec<Digest>> {
+        let mut set = JoinSet::<miette::Result<Option<Digest>>>::new();
+        let compression = self.context.remote_config.cache.compression;
+        let debug_enabled = self.context.remote_debug;
+
+        for input in blob_inputs {
+            let client = self.get_client();
+            let url = self.get_object_url("cas", &input.digest.hash);
+            let semaphore = self.semaphore.clone();
+
+            set.spawn(async move {
+                let Ok(_permit) = semaphore.acquire().await else {
+                    return Ok(None);
+                };
+
+                let mut blob = CompressableBlob::from_blob(input.into_blob()?);
+                blob.compress(compression)?;
+
+                let mut headers = HeaderMap::new();
+
+                if compression.is_enabled() {
+                    headers.insert(
+                        COMPRESSION_HEADER,
+                        HeaderValue::from_str(&compression.to_string()).into_diagnostic()?,
+                    );
+                }
+
+                match client
+                    .send(Method::PUT, url, headers, Some(blob.inner.bytes))
+                    .await
+                {
+                    Ok(response) => {
+                        let status = response.status();
+
+                        if status.is_success() {
+                            return Ok(Some(blob.inner.digest));
+                        }
+
+                        Err(map_response_error("store_blobs", response, debug_enabled).into())
+                    }
+                    Err(error) => Err(map_error("store_blobs", error, debug_enabled).into()),
+                }
+            });
+        }
+
+        let mut digests = vec![];
+
+        while let Some(result) = set.join_next().await {
+            if let Some(digest) = result.into_diagnostic()?? {
+                digests.push(digest);
+            }
+        }
+
+        Ok(digests)
+    }
+}
+
+fn map_error(method: &str, error: reqwest::Error, debug: bool) -> RemoteError {
+    if debug {
+        error!("{method}: {:#?}", error);
+    }
+
+    RemoteError::HttpCallFailed {
+        error: Box::new(error),
+    }
+}
+
+fn map_response_error(method: &str, res: reqwest::Response, debug: bool) -> RemoteError {
+    if debug {
+        error!("{method}: {:#?}", res);
+    }
+
+    RemoteError::HttpRequestFailed {
+        status: Box::new(res.status()),
+    }
+}
+
+fn get_compression_from_header(value: Option<&HeaderValue>) -> RemoteCompression {
+    match value.and_then(|value| value.to_str().ok()) {
+        Some("zstd") => RemoteCompression::Zstd,
+        _ => RemoteCompression::None,
+    }
+}
+
+fn build_base_url(endpoint: &str, config: &RemoteS3Config) -> miette::Result<Url> {
+    let invalid = || RemoteError::S3InvalidEndpoint {
+        endpoint: endpoint.to_owned(),
+    };
+
+    let mut url = Url::parse(endpoint).map_err(|_| invalid())?;
+
+    if !config.path_style {
+        let host = format!("{}.{}", config.bucket, url.host_str().ok_or_else(invalid)?);
+
+        url.set_host(Some(&host)).map_err(|_| invalid())?;
+    }
+
+    Ok(url)
+}
+
+fn build_object_url(
+    base_url: &Url,
+    config: &RemoteS3Config,
+    instance_name: &str,
+    path: &str,
+    hash: &str,
+) -> Url {
+    let mut segments = vec![];
+
+    if config.path_style {
+        segments.push(uri_encode(&config.bucket, true));
+    }
+
+    if let Some(prefix) = &config.prefix {
+        let prefix = prefix.trim_matches('/');
+
+        if !prefix.is_empty() {
+            segments.push(uri_encode(prefix, false));
+        }
+    }
+
+    if !instance_name.is_empty() {
+        segments.push(uri_encode(instance_name, true));
+    }
+
+    segments.push(path.to_owned());
+    segments.push(hash.to_owned());
+
+    let mut url = base_url.clone();
+    url.set_path(&format!(
+        "{}/{}",
+        base_url.path().trim_end_matches('/'),
+        segments.join("/")
+    ));
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_config(path_style: bool, prefix: Option<&str>) -> RemoteS3Config {
+        RemoteS3Config {
+            bucket: "cache".into(),
+            path_style,
+            prefix: prefix.map(|prefix| prefix.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn builds_virtual_hosted_url() {
+        let config = create_config(false, None);
+        let base_url = build_base_url("https://s3.us-east-1.amazonaws.com", &config).unwrap();
+
+        assert_eq!(
+            build_object_url(&base_url, &config, "main", "cas", "abc123").as_str(),
+            "https://cache.s3.us-east-1.amazonaws.com/main/cas/abc123"
+        );
+    }
+
+    #[test]
+    fn builds_path_style_url() {
+        let config = create_config(true, None);
+        let base_url = build_base_url("http://localhost:9000", &config).unwrap();
+
+        assert_eq!(
+            build_object_url(&base_url, &config, "main", "ac", "abc123").as_str(),
+            "http://localhost:9000/cache/main/ac/abc123"
+        );
+    }
+
+    #[test]
+    fn builds_url_with_prefix() {
+        let config = create_config(true, Some("/team/moon/"));
+        let base_url = build_base_url("http://localhost:9000", &config).unwrap();
+
+        assert_eq!(
+            build_object_url(&base_url, &config, "", "cas", "abc123").as_str(),
+            "http://localhost:9000/cache/team/moon/cas/abc123"
+        );
+    }
+
+    #[test]
+    fn errors_on_invalid_endpoint() {
+        assert!(build_base_url("not a url", &create_config(true, None)).is_err());
+    }
+}
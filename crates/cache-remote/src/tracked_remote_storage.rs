@@ -0,0 +1,174 @@
+use async_trait::async_trait;
+use moon_blob::{BlobInput, BlobOutput};
+use moon_cache_storage::{CacheCapabilities, Manifest, StorageBackend, StorageStats};
+use moon_common::Id;
+use moon_hash::Digest;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Default)]
+struct RemoteStats {
+    blobs_downloaded: AtomicU64,
+    blobs_uploaded: AtomicU64,
+    bytes_downloaded: AtomicU64,
+    bytes_uploaded: AtomicU64,
+    errors: AtomicU64,
+    hits: AtomicU64,
+    latency_nanos: AtomicU64,
+    misses: AtomicU64,
+    requests: AtomicU64,
+}
+
+impl RemoteStats {
+    fn track<T>(&self, started: Instant, result: &miette::Result<T>) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.latency_nanos
+            .fetch_add(started.elapsed().as_nanos() as u64, Ordering::Relaxed);
+
+        if result.is_err() {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn snapshot(&self) -> StorageStats {
+        StorageStats {
+            blobs_downloaded: self.blobs_downloaded.load(Ordering::Relaxed),
+            blobs_uploaded: self.blobs_uploaded.load(Ordering::Relaxed),
+            bytes_downloaded: self.bytes_downloaded.load(Ordering::Relaxed),
+            bytes_uploaded: self.bytes_uploaded.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            hits: self.hits.load(Ordering::Relaxed),
+            latency: Duration::from_nanos(self.latency_nanos.load(Ordering::Relaxed)),
+            misses: self.misses.load(Ordering::Relaxed),
+            requests: self.requests.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Wraps a remote storage backend and counts the requests it serves
+/// (hits, misses, transferred bytes, and latency), without changing
+/// the behavior of the backend itself.
+#[derive(Debug)]
+pub struct TrackedRemoteStorage<T: StorageBackend> {
+    backend: T,
+    stats: RemoteStats,
+}
+
+impl<T: StorageBackend> TrackedRemoteStorage<T> {
+    pub fn new(backend: T) -> Self {
+        Self {
+            backend,
+            stats: RemoteStats::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl<T: StorageBackend> StorageBackend for TrackedRemoteStorage<T> {
+    fn get_id(&self) -> &Id {
+        self.backend.get_id()
+    }
+
+    fn get_capabilities(&self) -> &CacheCapabilities {
+        self.backend.get_capabilities()
+    }
+
+    fn get_stats(&self) -> Option<StorageStats> {
+        Some(self.stats.snapshot())
+    }
+
+    fn is_readable(&self) -> bool {
+        self.backend.is_readable()
+    }
+
+    fn is_writable(&self) -> bool {
+        self.backend.is_writable()
+    }
+
+    async fn connect(&self) -> miette::Result<()> {
+        self.backend.connect().await
+    }
+
+    async fn retrieve_manifest(&self, digest: Digest) -> miette::Result<Option<Manifest>> {
+        let started = Instant::now();
+        let result = self.backend.retrieve_manifest(digest).await;
+
+        self.stats.track(started, &result);
+
+        match &result {
+            Ok(Some(_)) => {
+                self.stats.hits.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(None) => {
+                self.stats.misses.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(_) => {}
+        };
+
+        result
+    }
+
+    async fn store_manifest(&self, digest: Digest, manifest: Manifest) -> miette::Result<()> {
+        let started = Instant::now();
+        let result = self.backend.store_manifest(digest, manifest).await;
+
+        self.stats.track(started, &result);
+
+        result
+    }
+
+    async fn find_missing_blobs(&self, blob_digests: Vec<Digest>) -> miette::Result<Vec<Digest>> {
+        let started = Instant::now();
+        let result = self.backend.find_missing_blobs(blob_digests).await;
+
+        self.stats.track(started, &result);
+
+        result
+    }
+
+    async fn store_blobs(
+        &self,
+        blob_inputs: Vec<BlobInput>,
+        stream: bool,
+    ) -> miette::Result<Vec<Digest>> {
+        let started = Instant::now();
+        let result = self.backend.store_blobs(blob_inputs, stream).await;
+
+        self.stats.track(started, &result);
+
+        if let Ok(digests) = &result {
+            self.stats
+                .blobs_uploaded
+                .fetch_add(digests.len() as u64, Ordering::Relaxed);
+            self.stats.bytes_uploaded.fetch_add(
+                digests.iter().map(|digest| digest.size as u64).sum(),
+                Ordering::Relaxed,
+            );
+        }
+
+        result
+    }
+
+    async fn retrieve_blobs(
+        &self,
+        blob_digests: Vec<Digest>,
+        stream: bool,
+    ) -> miette::Result<Vec<BlobOutput>> {
+        let started = Instant::now();
+        let result = self.backend.retrieve_blobs(blob_digests, stream).await;
+
+        self.stats.track(started, &result);
+
+        if let Ok(blobs) = &result {
+            self.stats
+                .blobs_downloaded
+                .fetch_add(blobs.len() as u64, Ordering::Relaxed);
+            self.stats.bytes_downloaded.fetch_add(
+                blobs.iter().map(|blob| blob.digest.size as u64).sum(),
+                Ordering::Relaxed,
+            );
+        }
+
+        result
+    }
+}
@@ -0,0 +1,309 @@
+use httpmock::prelude::*;
+use moon_blob::{BlobContent, BlobInput, Bytes};
+use moon_cache_remote::S3RemoteStorage;
+use moon_cache_storage::{CacheContext, Manifest, StorageBackend};
+use moon_config::{RemoteApi, RemoteCompression, RemoteConfig, RemoteS3Config};
+use moon_env_var::GlobalEnvBag;
+use moon_hash::Digest;
+use starbase_sandbox::{Sandbox, create_empty_sandbox};
+use std::sync::Arc;
+
+const BUCKET: &str = "moon-bucket";
+const INSTANCE: &str = "moon-test";
+
+fn create_remote_config(host: String) -> RemoteConfig {
+    let mut remote = RemoteConfig {
+        api: RemoteApi::S3,
+        host: Some(host),
+        s3: Some(RemoteS3Config {
+            bucket: BUCKET.into(),
+            path_style: true,
+            prefix: Some("ci".into()),
+            region: "us-east-1".into(),
+        }),
+        ..Default::default()
+    };
+    remote.cache.instance_name = INSTANCE.to_owned();
+    remote
+}
+
+fn create_storage_with_config(sandbox: &Sandbox, remote: RemoteConfig) -> S3RemoteStorage {
+    // Every test uses the same credentials, so this is safe to share
+    let env_bag = GlobalEnvBag::instance();
+    env_bag.set("AWS_ACCESS_KEY_ID", "minioadmin");
+    env_bag.set("AWS_SECRET_ACCESS_KEY", "minioadmin");
+
+    let mut context = CacheContext::new(sandbox.path());
+    context.remote_config = Arc::new(remote);
+
+    S3RemoteStorage::new(context).unwrap()
+}
+
+fn create_storage(sandbox: &Sandbox, host: String) -> S3RemoteStorage {
+    create_storage_with_config(sandbox, create_remote_config(host))
+}
+
+fn digest_of(bytes: &[u8]) -> Digest {
+    Digest::from_bytes(bytes).unwrap()
+}
+
+fn object_path(path: &str, digest: &Digest) -> String {
+    format!("/{BUCKET}/ci/{INSTANCE}/{path}/{}", digest.hash)
+}
+
+mod s3_remote_storage {
+    use super::*;
+
+    #[test]
+    fn errors_without_s3_config() {
+        let sandbox = create_empty_sandbox();
+        let mut context = CacheContext::new(sandbox.path());
+        context.remote_config = Arc::new(RemoteConfig {
+            api: RemoteApi::S3,
+            ..Default::default()
+        });
+
+        assert!(S3RemoteStorage::new(context).is_err());
+    }
+
+    mod connect {
+        use super::*;
+
+        #[tokio::test]
+        async fn enables_backend_without_requests() {
+            let server = MockServer::start_async().await;
+            let sandbox = create_empty_sandbox();
+            let storage = create_storage(&sandbox, server.base_url());
+
+            storage.connect().await.unwrap();
+
+            assert!(storage.is_readable());
+        }
+    }
+
+    mod manifests {
+        use super::*;
+
+        #[tokio::test]
+        async fn stores_signed_manifest() {
+            let server = MockServer::start_async().await;
+            let digest = digest_of(b"action");
+            let mock = server.mock(|when, then| {
+                when.method(PUT)
+                    .path(object_path("ac", &digest))
+                    .header("content-type", "application/json")
+                    .header_exists("x-amz-date")
+                    .header_exists("x-amz-content-sha256")
+                    .header_matches(
+                        "authorization",
+                        "^AWS4-HMAC-SHA256 Credential=minioadmin/\\d{8}/us-east-1/s3/aws4_request, SignedHeaders=content-type;host;x-amz-content-sha256;x-amz-date, Signature=[0-9a-f]{64}$",
+                    )
+                    .body_includes("\"exitCode\":2");
+                then.status(200);
+            });
+            let sandbox = create_empty_sandbox();
+            let storage = create_storage(&sandbox, server.base_url());
+
+            storage
+                .store_manifest(
+                    digest,
+                    Manifest {
+                        exit_code: 2,
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+
+            mock.assert_calls_async(1).await;
+        }
+
+        #[tokio::test]
+        async fn retrieves_manifest() {
+            let server = MockServer::start_async().await;
+            let digest = digest_of(b"action");
+            let mock = server.mock(|when, then| {
+                when.method(GET)
+                    .path(object_path("ac", &digest))
+                    .header_exists("authorization");
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .body(serde_json::json!({ "exitCode": 7 }).to_string());
+            });
+            let sandbox = create_empty_sandbox();
+            let storage = create_storage(&sandbox, server.base_url());
+
+            let manifest = storage.retrieve_manifest(digest).await.unwrap().unwrap();
+
+            mock.assert_calls_async(1).await;
+            assert_eq!(manifest.exit_code, 7);
+        }
+
+        #[tokio::test]
+        async fn retrieve_returns_none_on_404() {
+            let server = MockServer::start_async().await;
+            let digest = digest_of(b"missing");
+            server.mock(|when, then| {
+                when.method(GET).path(object_path("ac", &digest));
+                then.status(404);
+            });
+            let sandbox = create_empty_sandbox();
+            let storage = create_storage(&sandbox, server.base_url());
+
+            assert!(storage.retrieve_manifest(digest).await.unwrap().is_none());
+        }
+
+        #[tokio::test]
+        async fn retrieve_errors_on_forbidden() {
+            let server = MockServer::start_async().await;
+            let digest = digest_of(b"forbidden");
+            server.mock(|when, then| {
+                when.method(GET).path(object_path("ac", &digest));
+                then.status(403);
+            });
+            let sandbox = create_empty_sandbox();
+            let storage = create_storage(&sandbox, server.base_url());
+
+            assert!(storage.retrieve_manifest(digest).await.is_err());
+        }
+    }
+
+    mod blobs {
+        use super::*;
+
+        #[tokio::test]
+        async fn stores_blob() {
+            let server = MockServer::start_async().await;
+            let content = b"inline blob";
+            let digest = digest_of(content);
+            let mock = server.mock(|when, then| {
+                when.method(PUT)
+                    .path(object_path("cas", &digest))
+                    .header("x-amz-content-sha256", digest.hash.as_str())
+                    .header_missing("x-amz-meta-moon-compression")
+                    .body("inline blob");
+                then.status(200);
+            });
+            let sandbox = create_empty_sandbox();
+            let storage = create_storage(&sandbox, server.base_url());
+
+            let source = BlobInput {
+                content: BlobContent::Inline(Bytes::from_static(content)),
+                digest: digest.clone(),
+            };
+            let stored = storage.store_blobs(vec![source], false).await.unwrap();
+
+            mock.assert_calls_async(1).await;
+            assert_eq!(stored, vec![digest]);
+        }
+
+        #[tokio::test]
+        async fn stores_compressed_blob() {
+            let server = MockServer::start_async().await;
+            let content = b"compressed blob";
+            let digest = digest_of(content);
+            let mock = server.mock(|when, then| {
+                when.method(PUT)
+                    .path(object_path("cas", &digest))
+                    .header("x-amz-meta-moon-compression", "zstd")
+                    .is_true(|request| {
+                        zstd::decode_all(request.body().to_vec().as_slice()).unwrap()
+                            == b"compressed blob"
+                    });
+                then.status(200);
+            });
+            let sandbox = create_empty_sandbox();
+            let mut remote = create_remote_config(server.base_url());
+            remote.cache.compression = RemoteCompression::Zstd;
+            let storage = create_storage_with_config(&sandbox, remote);
+
+            let source = BlobInput {
+                content: BlobContent::Inline(Bytes::from_static(content)),
+                digest: digest.clone(),
+            };
+            let stored = storage.store_blobs(vec![source], false).await.unwrap();
+
+            mock.assert_calls_async(1).await;
+            assert_eq!(stored, vec![digest]);
+        }
+
+        #[tokio::test]
+        async fn retrieves_blob() {
+            let server = MockServer::start_async().await;
+            let content = "downloaded";
+            let digest = digest_of(content.as_bytes());
+            let mock = server.mock(|when, then| {
+                when.method(GET).path(object_path("cas", &digest));
+                then.status(200).body(content);
+            });
+            let sandbox = create_empty_sandbox();
+            let storage = create_storage(&sandbox, server.base_url());
+
+            let blobs = storage.retrieve_blobs(vec![digest], false).await.unwrap();
+
+            mock.assert_calls_async(1).await;
+            assert_eq!(blobs.len(), 1);
+            assert_eq!(blobs[0].content.get_bytes().unwrap(), content.as_bytes());
+        }
+
+        #[tokio::test]
+        async fn retrieves_compressed_blob() {
+            let server = MockServer::start_async().await;
+            let content = "downloaded and compressed";
+            let digest = digest_of(content.as_bytes());
+            server.mock(|when, then| {
+                when.method(GET).path(object_path("cas", &digest));
+                then.status(200)
+                    .header("x-amz-meta-moon-compression", "zstd")
+                    .body(zstd::encode_all(content.as_bytes(), 1).unwrap());
+            });
+            let sandbox = create_empty_sandbox();
+            let storage = create_storage(&sandbox, server.base_url());
+
+            let blobs = storage.retrieve_blobs(vec![digest], false).await.unwrap();
+
+            assert_eq!(blobs.len(), 1);
+            assert_eq!(blobs[0].content.get_bytes().unwrap(), content.as_bytes());
+        }
+
+        #[tokio::test]
+        async fn discards_corrupted_blob() {
+            let server = MockServer::start_async().await;
+            let digest = digest_of(b"expected");
+            server.mock(|when, then| {
+                when.method(GET).path(object_path("cas", &digest));
+                then.status(200).body("corrupted");
+            });
+            let sandbox = create_empty_sandbox();
+            let storage = create_storage(&sandbox, server.base_url());
+
+            let blobs = storage.retrieve_blobs(vec![digest], false).await.unwrap();
+
+            assert!(blobs.is_empty());
+        }
+
+        #[tokio::test]
+        async fn finds_missing_blobs() {
+            let server = MockServer::start_async().await;
+            let a = digest_of(b"a");
+            let b = digest_of(b"b");
+            server.mock(|when, then| {
+                when.method(Method::HEAD).path(object_path("cas", &a));
+                then.status(200);
+            });
+            server.mock(|when, then| {
+                when.method(Method::HEAD).path(object_path("cas", &b));
+                then.status(404);
+            });
+            let sandbox = create_empty_sandbox();
+            let storage = create_storage(&sandbox, server.base_url());
+
+            let missing = storage
+                .find_missing_blobs(vec![a, b.clone()])
+                .await
+                .unwrap();
+
+            assert_eq!(missing, vec![b]);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use httpmock::prelude::*;
+use moon_blob::{BlobContent, BlobInput, Bytes};
+use moon_cache_remote::{HttpRemoteStorage, TrackedRemoteStorage};
+use moon_cache_storage::{CacheContext, StorageBackend};
+use moon_config::RemoteConfig;
+use moon_hash::Digest;
+use starbase_sandbox::{Sandbox, create_empty_sandbox};
+use std::sync::Arc;
+
+const INSTANCE: &str = "moon-test";
+
+fn create_storage(sandbox: &Sandbox, host: String) -> TrackedRemoteStorage<HttpRemoteStorage> {
+    let mut remote = RemoteConfig {
+        host: Some(host),
+        ..Default::default()
+    };
+    remote.cache.instance_name = INSTANCE.to_owned();
+
+    let mut context = CacheContext::new(sandbox.path());
+    context.remote_config = Arc::new(remote);
+
+    TrackedRemoteStorage::new(HttpRemoteStorage::new(context).unwrap())
+}
+
+fn digest_of(bytes: &[u8]) -> Digest {
+    Digest::from_bytes(bytes).unwrap()
+}
+
+mod tracked_remote_storage {
+    use super::*;
+
+    #[tokio::test]
+    async fn starts_empty() {
+        let server = MockServer::start_async().await;
+        let sandbox = create_empty_sandbox();
+        let storage = create_storage(&sandbox, server.base_url());
+
+        let stats = storage.get_stats().unwrap();
+
+        assert!(stats.is_empty());
+        assert_eq!(stats.hits, 0);
+        assert_eq!(stats.misses, 0);
+    }
+
+    #[tokio::test]
+    async fn counts_hits_and_misses() {
+        let server = MockServer::start_async().await;
+        let hit = digest_of(b"hit");
+        let miss = digest_of(b"miss");
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{INSTANCE}/ac/{}", hit.hash));
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(serde_json::json!({ "exitCode": 0 }).to_string());
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{INSTANCE}/ac/{}", miss.hash));
+            then.status(404);
+        });
+        let sandbox = create_empty_sandbox();
+        let storage = create_storage(&sandbox, server.base_url());
+
+        assert!(storage.retrieve_manifest(hit).await.unwrap().is_some());
+        assert!(storage.retrieve_manifest(miss).await.unwrap().is_none());
+
+        let stats = storage.get_stats().unwrap();
+
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.requests, 2);
+        assert_eq!(stats.errors, 0);
+    }
+
+    #[tokio::test]
+    async fn counts_errors() {
+        let server = MockServer::start_async().await;
+        let digest = digest_of(b"error");
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{INSTANCE}/ac/{}", digest.hash));
+            then.status(500);
+        });
+        let sandbox = create_empty_sandbox();
+        let storage = create_storage(&sandbox, server.base_url());
+
+        assert!(storage.retrieve_manifest(digest).await.is_err());
+
+        let stats = storage.get_stats().unwrap();
+
+        assert_eq!(stats.hits, 0);
+        assert_eq!(stats.misses, 0);
+        assert_eq!(stats.requests, 1);
+        assert_eq!(stats.errors, 1);
+    }
+
+    #[tokio::test]
+    async fn counts_transferred_bytes() {
+        let server = MockServer::start_async().await;
+        let content = b"transferred";
+        let digest = digest_of(content);
+        server.mock(|when, then| {
+            when.method(PUT)
+                .path(format!("/{INSTANCE}/cas/{}", digest.hash));
+            then.status(200);
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/{INSTANCE}/cas/{}", digest.hash));
+            then.status(200).body(content);
+        });
+        let sandbox = create_empty_sandbox();
+        let storage = create_storage(&sandbox, server.base_url());
+
+        storage
+            .store_blobs(
+                vec![BlobInput {
+                    content: BlobContent::Inline(Bytes::from_static(content)),
+                    digest: digest.clone(),
+                }],
+                false,
+            )
+            .await
+            .unwrap();
+        storage.retrieve_blobs(vec![digest], false).await.unwrap();
+
+        let stats = storage.get_stats().unwrap();
+
+        assert_eq!(stats.blobs_uploaded, 1);
+        assert_eq!(stats.bytes_uploaded, content.len() as u64);
+        assert_eq!(stats.blobs_downloaded, 1);
+        assert_eq!(stats.bytes_downloaded, content.len() as u64);
+        assert_eq!(stats.requests, 2);
+    }
+}
@@ -2,10 +2,12 @@ mod capabilities;
 mod helpers;
 mod storage;
 mod storage_backend;
+mod storage_stats;
 
 pub use capabilities::*;
 pub use helpers::*;
 pub use storage::*;
 pub use storage_backend::*;
+pub use storage_stats::*;
 
 pub use moon_manifest::*;
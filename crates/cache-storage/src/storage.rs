@@ -1,4 +1,5 @@
 use crate::storage_backend::{BoxedStorageBackend, StorageBackend};
+use crate::storage_stats::StorageStats;
 use miette::IntoDiagnostic;
 use moon_blob::{BlobCleanStats, BlobContent, BlobInput, BlobOutput};
 use moon_common::{Id, format_error_chain, is_daemon_env};
@@ -174,6 +175,19 @@ impl Storage {
         })
     }
 
+    /// Return the stats of every remote backend that has served a request.
+    pub fn get_remote_stats(&self) -> Vec<(Id, StorageStats)> {
+        self.remote_backends
+            .iter()
+            .filter_map(|backend| {
+                backend
+                    .get_stats()
+                    .filter(|stats| !stats.is_empty())
+                    .map(|stats| (backend.get_id().to_owned(), stats))
+            })
+            .collect()
+    }
+
     pub fn is_local_enabled(&self) -> bool {
         !self.local_backends.is_empty()
     }
@@ -1,5 +1,6 @@
 use crate::capabilities::CacheCapabilities;
 use crate::helpers::{Batch, create_batches};
+use crate::storage_stats::StorageStats;
 use async_trait::async_trait;
 use miette::IntoDiagnostic;
 use moon_blob::{BlobCleanStats, BlobInput, BlobOutput};
@@ -41,6 +42,12 @@ where
     fn is_readable(&self) -> bool;
     fn is_writable(&self) -> bool;
 
+    /// Return a snapshot of the requests this backend has served so far,
+    /// if the backend tracks them. Only remote backends track requests.
+    fn get_stats(&self) -> Option<StorageStats> {
+        None
+    }
+
     /// Garbage-collect this backend's storage, evicting entries past `lifetime`
     /// (and any blobs they were the last to reference). Remote backends manage
     /// their own eviction server-side, so this defaults to a no-op.
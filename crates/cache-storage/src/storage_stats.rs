@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A snapshot of the requests a storage backend has served during the
+/// current process.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StorageStats {
+    /// Number of blobs downloaded from the backend.
+    pub blobs_downloaded: u64,
+
+    /// Number of blobs uploaded to the backend.
+    pub blobs_uploaded: u64,
+
+    /// Total (uncompressed) size of all downloaded blobs.
+    pub bytes_downloaded: u64,
+
+    /// Total (uncompressed) size of all uploaded blobs.
+    pub bytes_uploaded: u64,
+
+    /// Number of requests that failed with an error.
+    pub errors: u64,
+
+    /// Number of manifest lookups that found an entry.
+    pub hits: u64,
+
+    /// Combined time spent waiting on the backend.
+    pub latency: Duration,
+
+    /// Number of manifest lookups that found nothing.
+    pub misses: u64,
+
+    /// Number of requests made to the backend.
+    pub requests: u64,
+}
+
+impl StorageStats {
+    pub fn get_average_latency(&self) -> Duration {
+        if self.requests == 0 {
+            Duration::ZERO
+        } else {
+            self.latency.div_f64(self.requests as f64)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests == 0
+    }
+}
@@ -1,4 +1,6 @@
+use crate::cache_lease::CacheLease;
 use crate::hash_engine::HashEngine;
+use crate::history_engine::HistoryEngine;
 use crate::state_engine::StateEngine;
 use crate::{merge_clean_results, resolve_path};
 use miette::IntoDiagnostic;
@@ -29,6 +31,9 @@ pub struct CacheEngine {
     /// Manages reading and writing of content hashable items.
     pub hash: HashEngine,
 
+    /// Manages the append-only history of runs.
+    pub history: HistoryEngine,
+
     /// Manages states of projects, tasks, tools, and more.
     pub state: StateEngine,
 
@@ -70,6 +75,7 @@ impl CacheEngine {
 
         Ok(CacheEngine {
             hash,
+            history: HistoryEngine::new(dir)?,
             state: StateEngine::new(dir)?,
             storage: Storage::new(context.clone()),
             temp_dir: dir.join("temp"),
@@ -115,6 +121,7 @@ impl CacheEngine {
         let mut dirs = vec![&self.hash.hashes_dir, &self.hash.outputs_dir, &locks_dir];
 
         if all {
+            dirs.push(&self.history.history_dir);
             dirs.push(&self.state.states_dir);
             dirs.push(&self.temp_dir);
         }
@@ -139,16 +146,37 @@ impl CacheEngine {
     }
 
     pub fn create_lock<T: AsRef<str>>(&self, name: T) -> miette::Result<FileLock> {
+        let mut guard = fs::lock_file(self.get_lock_path(name))?;
+        guard.remove_on_unlock();
+
+        Ok(guard)
+    }
+
+    /// Acquire a lease with the provided name, blocking until it has been released
+    /// by its current holder. Unlike locks, the lease file is kept when released.
+    pub fn create_lease<T: AsRef<str>>(&self, name: T) -> miette::Result<CacheLease> {
+        CacheLease::acquire(&self.get_lock_path(name))
+    }
+
+    /// Try to acquire a lease with the provided name without blocking,
+    /// and return `None` if it's currently held by this or another process.
+    pub fn try_create_lease<T: AsRef<str>>(&self, name: T) -> miette::Result<Option<CacheLease>> {
+        CacheLease::try_acquire(&self.get_lock_path(name))
+    }
+
+    /// Return true if the lock is currently held, by this or another process.
+    pub fn is_locked<T: AsRef<str>>(&self, name: T) -> bool {
+        fs::is_file_locked(self.get_lock_path(name))
+    }
+
+    fn get_lock_path<T: AsRef<str>>(&self, name: T) -> PathBuf {
         let mut name = encode_component(name.as_ref());
 
         if !name.ends_with(".lock") {
             name.push_str(".lock");
         }
 
-        let mut guard = fs::lock_file(self.cache_dir.join("locks").join(name))?;
-        guard.remove_on_unlock();
-
-        Ok(guard)
+        self.cache_dir.join("locks").join(name)
     }
 
     pub async fn hash_files(
@@ -0,0 +1,61 @@
+use starbase_utils::fs::{self, FsError};
+use std::fs::{File, TryLockError};
+use std::path::{Path, PathBuf};
+use tracing::trace;
+
+/// An exclusive lock on a file, that is released when dropped.
+///
+/// Unlike [`FileLock`](starbase_utils::fs::FileLock) created with
+/// [`CacheEngine.create_lock`](crate::CacheEngine::create_lock), the lock file
+/// is never removed. Another process may have opened the file before it was
+/// removed, and would then lock a different inode than a process that creates
+/// the file anew, allowing both processes to hold the lease at the same time.
+#[derive(Debug)]
+pub struct CacheLease {
+    pub file: File,
+    pub path: PathBuf,
+}
+
+impl CacheLease {
+    /// Acquire the lease, blocking until it has been released by its current holder.
+    pub fn acquire(path: &Path) -> miette::Result<Self> {
+        let file = fs::create_file_if_missing(path)?;
+
+        fs::acquire_exclusive_lock(path, &file)?;
+
+        Ok(Self {
+            file,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Try to acquire the lease without blocking, and return `None`
+    /// if it's currently held by this or another process.
+    pub fn try_acquire(path: &Path) -> miette::Result<Option<Self>> {
+        let file = fs::create_file_if_missing(path)?;
+
+        match file.try_lock() {
+            Ok(()) => {
+                trace!(file = ?path, "Acquired exclusive lock");
+
+                Ok(Some(Self {
+                    file,
+                    path: path.to_path_buf(),
+                }))
+            }
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(error)) => Err(FsError::Lock {
+                path: path.to_path_buf(),
+                error: Box::new(error),
+            }
+            .into()),
+        }
+    }
+}
+
+impl Drop for CacheLease {
+    fn drop(&mut self) {
+        // Closing the file releases the lock regardless
+        let _ = fs::release_lock(&self.path, &self.file);
+    }
+}
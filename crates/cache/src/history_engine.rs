@@ -0,0 +1,135 @@
+use crate::cache_lease::CacheLease;
+use moon_time::chrono::NaiveDate;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use starbase_utils::{fs, json};
+use std::path::{Path, PathBuf};
+use tracing::{debug, instrument, warn};
+
+#[derive(Debug)]
+pub struct HistoryEngine {
+    /// The `.moon/cache/history` directory. Stores an append-only log
+    /// of records, segmented into a JSONL file per day.
+    pub history_dir: PathBuf,
+}
+
+impl HistoryEngine {
+    pub fn new(cache_dir: &Path) -> miette::Result<HistoryEngine> {
+        let history_dir = cache_dir.join("history");
+
+        debug!(
+            history_dir = ?history_dir,
+            "Creating history engine",
+        );
+
+        fs::create_dir_all(&history_dir)?;
+
+        Ok(HistoryEngine { history_dir })
+    }
+
+    pub fn get_segment_path(&self, date: &NaiveDate) -> PathBuf {
+        self.history_dir
+            .join(format!("{}.jsonl", date.format("%Y-%m-%d")))
+    }
+
+    /// Append the records to the segment of the provided date. Each record
+    /// is written as a single line, and existing lines are never modified.
+    #[instrument(skip(self, records))]
+    pub fn append<T: Serialize>(&self, date: &NaiveDate, records: &[T]) -> miette::Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let mut data = String::new();
+
+        for record in records {
+            data.push_str(&json::format(record, false)?);
+            data.push('\n');
+        }
+
+        let path = self.get_segment_path(date);
+
+        debug!(segment = ?path, "Appending {} records to history", records.len());
+
+        // Multiple processes may be running at the same time
+        let _lease = CacheLease::acquire(&self.history_dir.join("append.lock"))?;
+
+        fs::append_file(&path, data)?;
+
+        Ok(())
+    }
+
+    /// Remove all segments before the provided date, and return
+    /// the number of segments that were removed.
+    #[instrument(skip(self))]
+    pub fn prune(&self, before: &NaiveDate) -> miette::Result<usize> {
+        let mut count = 0;
+
+        for (date, path) in self.get_segments()? {
+            if &date < before {
+                fs::remove_file(&path)?;
+                count += 1;
+            }
+        }
+
+        if count > 0 {
+            debug!("Pruned {} history segments before {}", count, before);
+        }
+
+        Ok(count)
+    }
+
+    /// Load all records from segments on or after the provided date,
+    /// in the order they were appended. Lines that fail to parse,
+    /// for example from a partial write, are skipped.
+    #[instrument(skip(self))]
+    pub fn load<T: DeserializeOwned>(&self, since: Option<&NaiveDate>) -> miette::Result<Vec<T>> {
+        let mut records = vec![];
+
+        for (date, path) in self.get_segments()? {
+            if since.is_some_and(|since| &date < since) {
+                continue;
+            }
+
+            for (index, line) in fs::read_file(&path)?.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                match json::parse(line) {
+                    Ok(record) => records.push(record),
+                    Err(error) => {
+                        warn!(
+                            segment = ?path,
+                            line = index + 1,
+                            "Failed to parse history record, skipping: {error}",
+                        );
+                    }
+                };
+            }
+        }
+
+        Ok(records)
+    }
+
+    fn get_segments(&self) -> miette::Result<Vec<(NaiveDate, PathBuf)>> {
+        let mut segments = vec![];
+
+        for entry in fs::read_dir(&self.history_dir)? {
+            let path = entry.path();
+
+            if let Some(date) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".jsonl"))
+                .and_then(|name| NaiveDate::parse_from_str(name, "%Y-%m-%d").ok())
+            {
+                segments.push((date, path));
+            }
+        }
+
+        segments.sort_by_key(|segment| segment.0);
+
+        Ok(segments)
+    }
+}
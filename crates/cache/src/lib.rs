@@ -1,9 +1,13 @@
 mod cache_engine;
+mod cache_lease;
 mod hash_engine;
+mod history_engine;
 mod state_engine;
 
 pub use cache_engine::*;
+pub use cache_lease::*;
 pub use hash_engine::*;
+pub use history_engine::*;
 pub use moon_cache_item::*;
 pub use moon_cache_storage::*;
 pub use moon_cas::CasStore;
@@ -231,4 +231,39 @@ mod cache_engine {
             }
         }
     }
+
+    mod leases {
+        use super::*;
+
+        #[test]
+        fn cant_acquire_a_held_lease() {
+            let sandbox = create_empty_sandbox();
+            let engine = create_engine(&sandbox);
+
+            let lease = engine.try_create_lease("task-abc").unwrap();
+
+            assert!(lease.is_some());
+            assert!(engine.is_locked("task-abc"));
+            assert!(engine.try_create_lease("task-abc").unwrap().is_none());
+            assert!(engine.try_create_lease("task-xyz").unwrap().is_some());
+
+            drop(lease);
+
+            assert!(!engine.is_locked("task-abc"));
+            assert!(engine.try_create_lease("task-abc").unwrap().is_some());
+        }
+
+        #[test]
+        fn keeps_lease_file_when_released() {
+            let sandbox = create_empty_sandbox();
+            let engine = create_engine(&sandbox);
+
+            let lease = engine.create_lease("task-abc").unwrap();
+            let path = lease.path.clone();
+
+            drop(lease);
+
+            assert!(path.exists());
+        }
+    }
 }
@@ -0,0 +1,108 @@
+use moon_cache::*;
+use moon_time::chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use starbase_sandbox::create_empty_sandbox;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Record {
+    id: u32,
+}
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 1, day).unwrap()
+}
+
+mod history_engine {
+    use super::*;
+
+    #[test]
+    fn creates_history_dir() {
+        let sandbox = create_empty_sandbox();
+        let engine = CacheEngine::new(CacheContext::new(sandbox.path())).unwrap();
+
+        assert!(engine.history.history_dir.exists());
+    }
+
+    #[test]
+    fn appends_to_daily_segments() {
+        let sandbox = create_empty_sandbox();
+        let engine = HistoryEngine::new(sandbox.path()).unwrap();
+
+        engine
+            .append(&date(1), &[Record { id: 1 }, Record { id: 2 }])
+            .unwrap();
+        engine.append(&date(1), &[Record { id: 3 }]).unwrap();
+        engine.append(&date(2), &[Record { id: 4 }]).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(engine.get_segment_path(&date(1))).unwrap(),
+            "{\"id\":1}\n{\"id\":2}\n{\"id\":3}\n"
+        );
+        assert!(engine.get_segment_path(&date(2)).exists());
+    }
+
+    #[test]
+    fn loads_in_order() {
+        let sandbox = create_empty_sandbox();
+        let engine = HistoryEngine::new(sandbox.path()).unwrap();
+
+        engine.append(&date(3), &[Record { id: 3 }]).unwrap();
+        engine.append(&date(1), &[Record { id: 1 }]).unwrap();
+        engine.append(&date(2), &[Record { id: 2 }]).unwrap();
+
+        assert_eq!(
+            engine.load::<Record>(None).unwrap(),
+            [Record { id: 1 }, Record { id: 2 }, Record { id: 3 }]
+        );
+        assert_eq!(
+            engine.load::<Record>(Some(&date(2))).unwrap(),
+            [Record { id: 2 }, Record { id: 3 }]
+        );
+    }
+
+    #[test]
+    fn skips_invalid_lines_and_files() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            "history/2025-01-01.jsonl",
+            "{\"id\":1}\n{\"id\":\n\n{\"id\":2}\n",
+        );
+        sandbox.create_file("history/unknown.jsonl", "{\"id\":3}\n");
+
+        let engine = HistoryEngine::new(sandbox.path()).unwrap();
+
+        assert_eq!(
+            engine.load::<Record>(None).unwrap(),
+            [Record { id: 1 }, Record { id: 2 }]
+        );
+    }
+
+    #[test]
+    fn keeps_lock_file_after_append() {
+        let sandbox = create_empty_sandbox();
+        let engine = HistoryEngine::new(sandbox.path()).unwrap();
+
+        engine.append(&date(1), &[Record { id: 1 }]).unwrap();
+
+        assert!(engine.history_dir.join("append.lock").exists());
+    }
+
+    #[test]
+    fn prunes_segments_before_date() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("history/unknown.jsonl", "");
+
+        let engine = HistoryEngine::new(sandbox.path()).unwrap();
+
+        engine.append(&date(1), &[Record { id: 1 }]).unwrap();
+        engine.append(&date(2), &[Record { id: 2 }]).unwrap();
+        engine.append(&date(3), &[Record { id: 3 }]).unwrap();
+
+        assert_eq!(engine.prune(&date(2)).unwrap(), 1);
+        assert_eq!(
+            engine.load::<Record>(None).unwrap(),
+            [Record { id: 2 }, Record { id: 3 }]
+        );
+        assert!(sandbox.path().join("history/unknown.jsonl").exists());
+    }
+}
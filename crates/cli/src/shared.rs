@@ -1,6 +1,7 @@
 use crate::lookup::*;
 use clap::Parser;
 use mimalloc::MiMalloc;
+use moon_app::commands::cache::{CacheCommands, remote::CacheRemoteCommands};
 use moon_app::commands::daemon::DaemonCommands;
 use moon_app::commands::debug::DebugCommands;
 use moon_app::commands::docker::DockerCommands;
@@ -193,6 +194,19 @@ pub async fn run_cli(args: Vec<OsString>) -> MainResult {
                     commands::action_graph::action_graph(session, args).await
                 }
                 Commands::Bin(args) => commands::bin::bin(session, args).await,
+                Commands::Cache { command } => match command {
+                    CacheCommands::Remote { command } => match command {
+                        CacheRemoteCommands::Get(args) => {
+                            commands::cache::remote::get::get(session, args).await
+                        }
+                        CacheRemoteCommands::Ls(args) => {
+                            commands::cache::remote::ls::ls(session, args).await
+                        }
+                        CacheRemoteCommands::Status => {
+                            commands::cache::remote::status::status(session).await
+                        }
+                    },
+                },
                 Commands::Ci(args) => commands::ci::ci(session, args).await,
                 Commands::Check(args) => commands::check::check(session, args).await,
                 Commands::Clean(args) => commands::clean::clean(session, args).await,
@@ -234,6 +248,7 @@ pub async fn run_cli(args: Vec<OsString>) -> MainResult {
                 },
                 Commands::Generate(args) => commands::generate::generate(session, args).await,
                 Commands::Hash(args) => commands::hash::hash(session, args).await,
+                Commands::History(args) => commands::history::history(session, args).await,
                 Commands::Init(args) => commands::init::init(session, args).await,
                 Commands::Mcp(args) => commands::mcp::mcp(session, args).await,
                 Commands::Migrate { command, .. } => match command {
@@ -251,6 +266,7 @@ pub async fn run_cli(args: Vec<OsString>) -> MainResult {
                     QueryCommands::ChangedFiles(args) => {
                         commands::query::changed_files::changed_files(session, args).await
                     }
+                    QueryCommands::Path(args) => commands::query::path::path(session, args).await,
                     QueryCommands::Projects(args) => {
                         commands::query::projects::projects(session, args).await
                     }
@@ -275,6 +291,9 @@ pub async fn run_cli(args: Vec<OsString>) -> MainResult {
                 },
                 Commands::Task(args) => commands::task::task(session, args).await,
                 Commands::TaskGraph(args) => commands::task_graph::task_graph(session, args).await,
+                Commands::TaskOutputs(args) => {
+                    commands::task_outputs::task_outputs(session, args).await
+                }
                 Commands::Tasks(args) => commands::tasks::tasks(session, args).await,
                 Commands::Teardown => commands::teardown::teardown(session).await,
                 Commands::Template(args) => commands::template::template(session, args).await,
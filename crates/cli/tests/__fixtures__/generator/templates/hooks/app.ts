@@ -0,0 +1,4 @@
+---
+if: not library
+---
+export const name = '{{ name }}';
@@ -0,0 +1,4 @@
+---
+if: library
+---
+export * from './lib';
@@ -4,11 +4,12 @@ use moon_common::path::standardize_separators;
 use moon_config::{
     TemplateVariable, TemplateVariableArraySetting, TemplateVariableBoolSetting,
     TemplateVariableEnumSetting, TemplateVariableEnumValue, TemplateVariableNumberSetting,
-    TemplateVariableStringSetting,
+    TemplateVariableObjectSetting, TemplateVariableStringSetting, schematic,
 };
 use moon_test_utils::{create_moon_sandbox, predicates::prelude::*};
 use rustc_hash::FxHashMap;
 use starbase_sandbox::assert_snapshot;
+use starbase_utils::json::serde_json;
 use std::fs;
 
 mod generate {
@@ -119,6 +120,121 @@ mod generate {
         assert!(!sandbox.path().join("test/template.yml").exists());
     }
 
+    #[test]
+    fn prints_diffs_of_existing_files_when_dryrun() {
+        let sandbox = create_moon_sandbox("generator");
+
+        sandbox.create_file("test/file.txt", "1\n");
+
+        let assert = sandbox.run_bin(|cmd| {
+            cmd.arg("generate")
+                .arg("standard")
+                .arg("--to")
+                .arg("./test")
+                .arg("--dry-run")
+                .arg("--force");
+        });
+
+        let output = assert.output();
+
+        assert!(predicate::str::contains("--- a/file.txt").eval(&output));
+        assert!(predicate::str::contains("-1").eval(&output));
+        assert!(predicate::str::contains("+2").eval(&output));
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("test/file.txt")).unwrap(),
+            "1\n"
+        );
+    }
+
+    #[test]
+    fn prints_a_json_plan() {
+        let sandbox = create_moon_sandbox("generator");
+
+        let assert = sandbox.run_bin(|cmd| {
+            cmd.arg("generate")
+                .arg("standard")
+                .arg("--to")
+                .arg("./test")
+                .arg("--dry-run")
+                .arg("--json");
+        });
+
+        let plan: serde_json::Value = serde_json::from_str(assert.stdout().trim()).unwrap();
+
+        assert_eq!(plan["template"], "standard");
+        assert!(
+            plan["files"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|file| file["path"] == "file.txt"
+                    && file["state"] == "create"
+                    && file["hash"].as_str().unwrap().len() == 64)
+        );
+        assert!(!sandbox.path().join("test").exists());
+    }
+
+    #[test]
+    fn writes_a_template_lock_file() {
+        let sandbox = create_moon_sandbox("generator");
+
+        sandbox
+            .run_bin(|cmd| {
+                cmd.arg("generate")
+                    .arg("standard")
+                    .arg("--to")
+                    .arg("./test");
+            })
+            .success();
+
+        let lock = fs::read_to_string(sandbox.path().join("test/.moon-template.lock")).unwrap();
+
+        assert!(lock.contains("\"standard\""));
+        assert!(lock.contains("\"file.txt\": \"2\\n\""));
+    }
+
+    #[test]
+    fn updates_files_by_merging_changes() {
+        let sandbox = create_moon_sandbox("generator");
+
+        sandbox.run_bin(|cmd| {
+            cmd.arg("generate")
+                .arg("standard")
+                .arg("--to")
+                .arg("./test");
+        });
+
+        sandbox.create_file("test/file.txt", "1\n2\n");
+        sandbox.create_file(
+            "templates/standard/file.txt",
+            "{% set my_var = 2 %}\n{{ my_var }}\n3\n",
+        );
+
+        let assert = sandbox.run_bin(|cmd| {
+            cmd.arg("generate").arg("--update").arg("./test");
+        });
+
+        assert.success().stdout(predicate::str::contains("updated"));
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("test/file.txt")).unwrap(),
+            "1\n2\n3\n"
+        );
+    }
+
+    #[test]
+    fn errors_when_updating_without_a_lock_file() {
+        let sandbox = create_moon_sandbox("generator");
+
+        let assert = sandbox.run_bin(|cmd| {
+            cmd.arg("generate").arg("--update").arg("./test");
+        });
+
+        assert
+            .failure()
+            .stderr(predicate::str::contains("no template lock file"));
+    }
+
     #[test]
     fn overwrites_existing_files_when_forced() {
         let sandbox = create_moon_sandbox("generator");
@@ -482,6 +598,149 @@ mod generate {
         }
     }
 
+    mod lifecycle {
+        use super::*;
+
+        #[test]
+        fn excludes_files_by_condition() {
+            let sandbox = create_moon_sandbox("generator");
+
+            sandbox
+                .run_bin(|cmd| {
+                    cmd.arg("generate")
+                        .arg("hooks")
+                        .arg("--to")
+                        .arg("./test")
+                        .arg("--defaults");
+                })
+                .success();
+
+            assert!(sandbox.path().join("./test/app.ts").exists());
+            assert!(!sandbox.path().join("./test/index.ts").exists());
+        }
+
+        #[test]
+        fn includes_files_by_condition() {
+            let sandbox = create_moon_sandbox("generator");
+
+            sandbox
+                .run_bin(|cmd| {
+                    cmd.arg("generate")
+                        .arg("hooks")
+                        .arg("--to")
+                        .arg("./test")
+                        .arg("--defaults")
+                        .arg("--")
+                        .arg("--library");
+                })
+                .success();
+
+            assert!(!sandbox.path().join("./test/app.ts").exists());
+            assert!(sandbox.path().join("./test/index.ts").exists());
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn runs_post_generate_hooks_in_dest() {
+            let sandbox = create_moon_sandbox("generator");
+
+            sandbox
+                .run_bin(|cmd| {
+                    cmd.arg("generate")
+                        .arg("hooks")
+                        .arg("--to")
+                        .arg("./test")
+                        .arg("--defaults")
+                        .arg("--")
+                        .arg("--name")
+                        .arg("bar");
+                })
+                .success();
+
+            assert_eq!(
+                fs::read_to_string(sandbox.path().join("./test/hooks.txt")).unwrap(),
+                "bar\n"
+            );
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn doesnt_run_hooks_when_dryrun() {
+            let sandbox = create_moon_sandbox("generator");
+
+            sandbox
+                .run_bin(|cmd| {
+                    cmd.arg("generate")
+                        .arg("hooks")
+                        .arg("--to")
+                        .arg("./test")
+                        .arg("--defaults")
+                        .arg("--dry-run");
+                })
+                .success();
+
+            assert!(!sandbox.path().join("./test/hooks.txt").exists());
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn doesnt_run_hooks_in_json_mode_without_flag() {
+            let sandbox = create_moon_sandbox("generator");
+
+            sandbox
+                .run_bin(|cmd| {
+                    cmd.arg("generate")
+                        .arg("hooks")
+                        .arg("--to")
+                        .arg("./test")
+                        .arg("--defaults")
+                        .arg("--json");
+                })
+                .success();
+
+            assert!(!sandbox.path().join("./test/hooks.txt").exists());
+
+            sandbox
+                .run_bin(|cmd| {
+                    cmd.arg("generate")
+                        .arg("hooks")
+                        .arg("--to")
+                        .arg("./other")
+                        .arg("--defaults")
+                        .arg("--json")
+                        .arg("--run-hooks");
+                })
+                .success();
+
+            assert!(sandbox.path().join("./other/hooks.txt").exists());
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn quotes_variables_in_hooks() {
+            let sandbox = create_moon_sandbox("generator");
+
+            sandbox
+                .run_bin(|cmd| {
+                    cmd.arg("generate")
+                        .arg("hooks")
+                        .arg("--to")
+                        .arg("./test")
+                        .arg("--defaults")
+                        .arg("--")
+                        .arg("--name")
+                        .arg("bar; touch pwned");
+                })
+                .success();
+
+            assert!(!sandbox.path().join("./test/pwned").exists());
+            assert_eq!(
+                fs::read_to_string(sandbox.path().join("./test/hooks.txt")).unwrap(),
+                "bar; touch pwned\n"
+            );
+        }
+    }
+
     mod cli_args {
         use super::*;
 
@@ -521,6 +780,25 @@ mod generate {
                     ..Default::default()
                 }),
             );
+            vars.insert(
+                "object".into(),
+                TemplateVariable::Object(TemplateVariableObjectSetting::default()),
+            );
+            vars.insert(
+                "range".into(),
+                TemplateVariable::Number(TemplateVariableNumberSetting {
+                    min: Some(1),
+                    max: Some(10),
+                    ..Default::default()
+                }),
+            );
+            vars.insert(
+                "slug".into(),
+                TemplateVariable::String(TemplateVariableStringSetting {
+                    pattern: Some(schematic::RegexSetting::new("^[a-z-]+$").unwrap()),
+                    ..Default::default()
+                }),
+            );
             vars.insert(
                 "multienum".into(),
                 TemplateVariable::Enum(TemplateVariableEnumSetting {
@@ -580,6 +858,23 @@ mod generate {
                 );
             }
 
+            #[test]
+            fn sets_var_from_json() {
+                let context = parse_args_into_variables(
+                    &["--array".into(), "[\"abc\", 123]".into()],
+                    &create_vars(),
+                )
+                .unwrap();
+
+                assert_eq!(
+                    context.get("array").unwrap(),
+                    &Value::Array(vec![
+                        Value::String("abc".into()),
+                        Value::Number(Number::from(123)),
+                    ])
+                );
+            }
+
             #[test]
             #[should_panic(expected = "a value is required")]
             fn errors_when_no_value() {
@@ -587,6 +882,38 @@ mod generate {
             }
         }
 
+        mod object {
+            use super::*;
+
+            #[test]
+            fn nothing_when_no_matching_arg() {
+                let context = parse_args_into_variables(&[], &create_vars()).unwrap();
+
+                assert!(!context.contains_key("object"));
+            }
+
+            #[test]
+            fn sets_var_from_json() {
+                let context = parse_args_into_variables(
+                    &["--object".into(), "{\"key\": \"value\"}".into()],
+                    &create_vars(),
+                )
+                .unwrap();
+
+                assert_eq!(
+                    context.get("object").unwrap(),
+                    &serde_json::json!({ "key": "value" })
+                );
+            }
+
+            #[test]
+            #[should_panic(expected = "Must be an object")]
+            fn errors_if_not_an_object() {
+                parse_args_into_variables(&["--object".into(), "[1, 2]".into()], &create_vars())
+                    .unwrap();
+            }
+        }
+
         mod bool {
             use super::*;
 
@@ -675,6 +1002,15 @@ mod generate {
             fn errors_when_no_value() {
                 parse_args_into_variables(&["--number".into()], &create_vars()).unwrap();
             }
+
+            #[test]
+            #[should_panic(
+                expected = "Invalid value for --range: Must be less than or equal to 10"
+            )]
+            fn errors_when_out_of_range() {
+                parse_args_into_variables(&["--range".into(), "11".into()], &create_vars())
+                    .unwrap();
+            }
         }
 
         mod string {
@@ -701,6 +1037,13 @@ mod generate {
             fn errors_when_no_value() {
                 parse_args_into_variables(&["--string".into()], &create_vars()).unwrap();
             }
+
+            #[test]
+            #[should_panic(expected = "Invalid value for --slug: Must match the pattern ^[a-z-]+$")]
+            fn errors_when_not_matching_pattern() {
+                parse_args_into_variables(&["--slug".into(), "Some Slug".into()], &create_vars())
+                    .unwrap();
+            }
         }
 
         mod enum_single {
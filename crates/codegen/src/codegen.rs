@@ -6,7 +6,7 @@ use moon_common::path::{PathExt, RelativePathBuf, locate_config_dir};
 use moon_config::{GeneratorConfig, PartialTemplateConfig, TemplateLocator};
 use moon_config_loader::ConfigLoader;
 use moon_env::MoonEnvironment;
-use moon_process::{Command, Output};
+use moon_process::{Command, Output, output_to_trimmed_string};
 use moon_time::now_millis;
 use rustc_hash::FxHashMap;
 use starbase_archive::Archiver;
@@ -23,6 +23,7 @@ pub struct CodeGenerator<'app> {
     pub templates: FxHashMap<Id, Template>,
     pub template_locations: Vec<PathBuf>,
 
+    locators: FxHashMap<PathBuf, TemplateLocator>,
     moon_env: Arc<MoonEnvironment>,
     workspace_root: &'app Path,
 }
@@ -40,6 +41,7 @@ impl<'app> CodeGenerator<'app> {
 
         CodeGenerator {
             config,
+            locators: FxHashMap::default(),
             moon_env,
             templates: FxHashMap::default(),
             template_locations: vec![],
@@ -68,8 +70,9 @@ impl<'app> CodeGenerator<'app> {
 
                 debug!(root = ?template_root, "Found a template, attempting to load");
 
-                let template =
+                let mut template =
                     Template::new(Id::clean(fs::file_name(&template_root))?, template_root)?;
+                template.locator = self.locators.get(location).cloned();
 
                 if let Some(existing_template) = self.templates.get(&template.id) {
                     return Err(CodegenError::DuplicateTemplate {
@@ -194,6 +197,36 @@ impl<'app> CodeGenerator<'app> {
         Ok(())
     }
 
+    /// Resolve the revision of the location the template was loaded from,
+    /// which is the checked out commit for Git, and the version for npm.
+    #[instrument(skip_all)]
+    pub async fn get_template_revision(
+        &self,
+        template: &Template,
+    ) -> miette::Result<Option<String>> {
+        let revision = match &template.locator {
+            Some(TemplateLocator::Git { revision, .. }) => {
+                let output = Command::new("git")
+                    .args(["rev-parse", "HEAD"])
+                    .cwd(&template.root)
+                    .no_shell()
+                    .exec_capture_output()
+                    .await?;
+                let commit = output_to_trimmed_string(&output.stdout);
+
+                Some(if commit.is_empty() {
+                    revision.to_owned()
+                } else {
+                    commit
+                })
+            }
+            Some(TemplateLocator::Npm { version, .. }) => Some(version.to_string()),
+            _ => None,
+        };
+
+        Ok(revision)
+    }
+
     #[instrument(skip_all)]
     async fn resolve_template_locations(&mut self) -> miette::Result<()> {
         let mut locations = vec![];
@@ -203,6 +236,8 @@ impl<'app> CodeGenerator<'app> {
         debug!("Resolving template locations to absolute file paths");
 
         for locator in &self.config.templates {
+            let start_index = locations.len();
+
             match locator {
                 TemplateLocator::File { path } => {
                     locations.push(
@@ -299,6 +334,11 @@ impl<'app> CodeGenerator<'app> {
                     locations.push(template_location);
                 }
             }
+
+            for location in &locations[start_index..] {
+                self.locators
+                    .insert(location.to_owned(), locator.to_owned());
+            }
         }
 
         while let Some(future) = set.join_next().await {
@@ -34,6 +34,25 @@ pub enum CodegenError {
     )]
     MissingTemplate(String),
 
+    #[diagnostic(
+        code(codegen::lock::missing),
+        help = "Only destinations that were generated into can be updated."
+    )]
+    #[error(
+        "Unable to update, no template lock file {} found in {}.",
+        crate::TEMPLATE_LOCK_FILE.style(Style::File),
+        .0.style(Style::Path),
+    )]
+    MissingTemplateLock(PathBuf),
+
+    #[diagnostic(code(codegen::lock::missing_template))]
+    #[error(
+        "Unable to update, the template {} was not generated into {}.",
+        .id.style(Style::Id),
+        .dest.style(Style::Path),
+    )]
+    MissingTemplateInLock { id: String, dest: PathBuf },
+
     #[diagnostic(code(codegen::template::duplicate))]
     #[error(
         "Found multiple templates with the same name {}.\nOriginal template at {}.\nCurrent template at {}.",
@@ -69,6 +88,29 @@ pub enum CodegenError {
         error: Box<tera::Error>,
     },
 
+    #[diagnostic(code(codegen::template_file::condition_failed))]
+    #[error(
+        "Failed to evaluate the {} condition of template file {}.",
+        "if".style(Style::Property),
+        .path.style(Style::Path),
+    )]
+    EvaluateConditionFailed {
+        path: PathBuf,
+        #[source]
+        error: Box<tera::Error>,
+    },
+
+    #[diagnostic(code(codegen::template::hook_failed))]
+    #[error(
+        "Failed to interpolate variables into post-generate hook {}.",
+        .command.style(Style::Shell),
+    )]
+    RenderHookFailed {
+        command: String,
+        #[source]
+        error: Box<tera::Error>,
+    },
+
     #[diagnostic(code(codegen::template_file::interpolate_path))]
     #[error(
         "Failed to interpolate variables into template file path {}.",
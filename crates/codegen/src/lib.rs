@@ -3,13 +3,21 @@ mod codegen;
 mod codegen_error;
 mod filters;
 mod funcs;
+mod merge;
 mod template;
 mod template_file;
+mod template_lock;
+mod template_plan;
+mod unified_diff;
 
 pub use asset_file::*;
 pub use codegen::*;
 pub use codegen_error::*;
+pub use merge::*;
 pub use template::*;
 pub use template_file::*;
+pub use template_lock::*;
+pub use template_plan::*;
 pub use tera;
 pub use tera::Context as TemplateContext;
+pub use unified_diff::*;
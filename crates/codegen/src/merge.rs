@@ -0,0 +1,118 @@
+pub const CONFLICT_START: &str = "<<<<<<< current";
+pub const CONFLICT_SEPARATOR: &str = "=======";
+pub const CONFLICT_END: &str = ">>>>>>> template";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct MergedContent {
+    pub content: String,
+    pub conflicts: usize,
+}
+
+impl MergedContent {
+    pub fn has_conflicts(&self) -> bool {
+        self.conflicts > 0
+    }
+}
+
+/// Merge the changes between the original rendered content (base) and
+/// the newly rendered content (next) into the content that currently exists
+/// at the destination (current), using a line-based three-way merge.
+///
+/// When both sides changed the same lines, the region is wrapped in Git-style
+/// conflict markers, with the current lines first and the template lines second.
+pub fn merge_content(base: &str, current: &str, next: &str) -> MergedContent {
+    let base_lines = base.split_inclusive('\n').collect::<Vec<_>>();
+    let current_lines = current.split_inclusive('\n').collect::<Vec<_>>();
+    let next_lines = next.split_inclusive('\n').collect::<Vec<_>>();
+
+    let current_matches = match_lines(&base_lines, &current_lines);
+    let next_matches = match_lines(&base_lines, &next_lines);
+
+    let mut merged = MergedContent::default();
+    let (mut b, mut c, mut n) = (0, 0, 0);
+
+    while b < base_lines.len() || c < current_lines.len() || n < next_lines.len() {
+        // Line is unchanged on both sides
+        if b < base_lines.len() && current_matches[b] == Some(c) && next_matches[b] == Some(n) {
+            merged.content.push_str(base_lines[b]);
+            b += 1;
+            c += 1;
+            n += 1;
+            continue;
+        }
+
+        // Find the next line that is unchanged on both sides,
+        // everything up until that point is a changed chunk
+        let (b_end, c_end, n_end) = (b..base_lines.len())
+            .find_map(
+                |index| match (current_matches[index], next_matches[index]) {
+                    (Some(c_index), Some(n_index)) => Some((index, c_index, n_index)),
+                    _ => None,
+                },
+            )
+            .unwrap_or((base_lines.len(), current_lines.len(), next_lines.len()));
+
+        let base_chunk = &base_lines[b..b_end];
+        let current_chunk = &current_lines[c..c_end];
+        let next_chunk = &next_lines[n..n_end];
+
+        if current_chunk == base_chunk || current_chunk == next_chunk {
+            merged.content.extend(next_chunk.iter().copied());
+        } else if next_chunk == base_chunk {
+            merged.content.extend(current_chunk.iter().copied());
+        } else {
+            merged.conflicts += 1;
+
+            push_marker(&mut merged.content, CONFLICT_START);
+            push_chunk(&mut merged.content, current_chunk);
+            push_marker(&mut merged.content, CONFLICT_SEPARATOR);
+            push_chunk(&mut merged.content, next_chunk);
+            push_marker(&mut merged.content, CONFLICT_END);
+        }
+
+        b = b_end;
+        c = c_end;
+        n = n_end;
+    }
+
+    merged
+}
+
+fn push_marker(content: &mut String, marker: &str) {
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+
+    content.push_str(marker);
+    content.push('\n');
+}
+
+fn push_chunk(content: &mut String, chunk: &[&str]) {
+    for line in chunk {
+        content.push_str(line);
+    }
+}
+
+/// Diff the base lines against the other lines, and return
+/// the matching other index for each base line.
+fn match_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = Vec::with_capacity(base.len());
+    let mut other_index = 0;
+
+    for result in diff::slice(base, other) {
+        match result {
+            diff::Result::Left(_) => {
+                matches.push(None);
+            }
+            diff::Result::Both(_, _) => {
+                matches.push(Some(other_index));
+                other_index += 1;
+            }
+            diff::Result::Right(_) => {
+                other_index += 1;
+            }
+        };
+    }
+
+    matches
+}
@@ -1,14 +1,18 @@
 use crate::asset_file::AssetFile;
 use crate::codegen_error::CodegenError;
-use crate::template_file::{FileState, MergeType, TemplateFile};
+use crate::merge::merge_content;
+use crate::template_file::{FileState, MergeType, TemplateFile, to_relative_dest_path};
+use crate::template_lock::TemplateLockEntry;
 use crate::{filters, funcs};
 use miette::IntoDiagnostic;
 use moon_common::Id;
 use moon_common::path::{RelativePathBuf, to_virtual_string};
-use moon_config::{TemplateConfig, schematic::helpers::strip_bom};
+use moon_config::{TemplateConfig, TemplateLocator, schematic::helpers::strip_bom};
 use moon_config_loader::ConfigLoader;
+use moon_process::{Command, get_default_shell};
 use regex::Regex;
 use serde::Serialize;
+use starbase_shell::Quotable;
 use starbase_utils::{fs, json, yaml};
 use std::collections::BTreeMap;
 use std::mem;
@@ -17,6 +21,8 @@ use std::sync::LazyLock;
 use tera::{Context, Tera};
 use tracing::{debug, instrument};
 
+const HOOK_TEMPLATE_NAME: &str = "__post_generate_hook";
+
 static PATH_VAR: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\[([A-Za-z0-9_]+)(?:\s*\|\s*([^\]]+))?\]").unwrap());
 
@@ -28,6 +34,7 @@ pub struct Template {
     pub engine: Tera,
     pub files: BTreeMap<RelativePathBuf, TemplateFile>,
     pub id: Id,
+    pub locator: Option<TemplateLocator>,
     pub root: PathBuf,
     pub templates: Vec<Template>, // Extending
 }
@@ -57,6 +64,7 @@ impl Template {
             config,
             engine,
             files: BTreeMap::new(),
+            locator: None,
             root,
             templates: vec![],
         })
@@ -215,12 +223,196 @@ impl Template {
                 )?;
             }
 
+            // Exclude the file entirely when its condition is not met
+            if let Some(condition) = file
+                .config
+                .as_ref()
+                .and_then(|cfg| cfg.if_condition.as_deref())
+                && !self.evaluate_condition(&file, condition, context)?
+            {
+                debug!(
+                    template = self.id.as_str(),
+                    file = file.name.as_str(),
+                    condition,
+                    "Excluding template file as its condition was not met",
+                );
+
+                continue;
+            }
+
             self.files.insert(file.name.clone(), file);
         }
 
         Ok(())
     }
 
+    /// Update previously generated files by merging the changes between the
+    /// original render (recorded in the lock) and the new render, into the
+    /// current destination files. Conflicting changes are marked in the content.
+    #[instrument(skip_all)]
+    pub fn update_files(&mut self, dest: &Path, lock: &TemplateLockEntry) -> miette::Result<()> {
+        for file in self.files.values_mut() {
+            if file.is_skipped() {
+                file.state = FileState::Skip;
+                continue;
+            }
+
+            let base = lock
+                .files
+                .get(&to_relative_dest_path(&file.dest_path, dest)?);
+
+            if !file.dest_path.exists() {
+                // Was previously generated but has since been removed,
+                // so respect that and don't bring it back
+                file.state = if base.is_some() {
+                    FileState::Skip
+                } else {
+                    FileState::Create
+                };
+
+                continue;
+            }
+
+            let current = fs::read_file(&file.dest_path)?;
+            let merged = merge_content(
+                base.map(|base| base.as_str()).unwrap_or_default(),
+                &current,
+                &file.content,
+            );
+
+            debug!(
+                file = file.name.as_str(),
+                conflicts = merged.conflicts,
+                "Merged template file changes with destination",
+            );
+
+            if merged.content == current {
+                file.state = FileState::Skip;
+            } else {
+                file.state = FileState::Update;
+                file.conflicted = merged.has_conflicts();
+                file.content = merged.content;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate a frontmatter `if` expression by wrapping it in a Tera
+    /// conditional block, so that the full expression syntax is supported.
+    fn evaluate_condition(
+        &mut self,
+        file: &TemplateFile,
+        condition: &str,
+        context: &Context,
+    ) -> miette::Result<bool> {
+        let result = self
+            .engine
+            .render_str(&format!("{{% if {condition} %}}true{{% endif %}}"), context)
+            .map_err(|error| CodegenError::EvaluateConditionFailed {
+                path: file.source_path.clone(),
+                error: Box::new(error),
+            })?;
+
+        Ok(result == "true")
+    }
+
+    /// Return the post-generate hook commands of this template and all
+    /// templates in the extends chain (extended first), with variables
+    /// interpolated into each command. Interpolated values are quoted for
+    /// the shell, so that they're passed as a single argument, and can't
+    /// inject other commands.
+    pub fn get_post_generate_hooks(&self, context: &Context) -> miette::Result<Vec<String>> {
+        fn collect_hooks(template: &Template, hooks: &mut Vec<String>) {
+            for extended in &template.templates {
+                collect_hooks(extended, hooks);
+            }
+
+            for hook in &template.config.hooks.post_generate {
+                if !hooks.contains(hook) {
+                    hooks.push(hook.to_owned());
+                }
+            }
+        }
+
+        let mut hooks = vec![];
+        let mut commands = vec![];
+
+        collect_hooks(self, &mut hooks);
+
+        if hooks.is_empty() {
+            return Ok(commands);
+        }
+
+        // Use a separate engine, so that escaping doesn't apply to template files
+        let mut engine = self.engine.clone();
+        engine.autoescape_on(vec![HOOK_TEMPLATE_NAME]);
+        engine.set_escape_fn(quote_hook_value);
+
+        for hook in hooks {
+            commands.push(
+                engine
+                    .add_raw_template(HOOK_TEMPLATE_NAME, &hook)
+                    .and_then(|_| engine.render(HOOK_TEMPLATE_NAME, context))
+                    .map_err(|error| CodegenError::RenderHookFailed {
+                        command: hook.clone(),
+                        error: Box::new(error),
+                    })?,
+            );
+        }
+
+        Ok(commands)
+    }
+
+    /// Return true if this template, or a template in its extends chain, has
+    /// post-generate hooks, and was not loaded from the local file system
+    /// (a `file` or `glob` locator). Such hooks should not run without consent.
+    pub fn has_remote_hooks(&self) -> bool {
+        let is_remote = !self.config.hooks.post_generate.is_empty()
+            && self.locator.as_ref().is_some_and(|locator| {
+                !matches!(
+                    locator,
+                    TemplateLocator::File { .. } | TemplateLocator::Glob { .. }
+                )
+            });
+
+        is_remote
+            || self
+                .templates
+                .iter()
+                .any(|extended| extended.has_remote_hooks())
+    }
+
+    /// Run the post-generate hooks in the destination, one after another.
+    /// When streaming, output is piped to the current terminal, otherwise
+    /// it's captured and discarded. Fails on the first unsuccessful hook.
+    #[instrument(skip(self, context))]
+    pub async fn run_post_generate_hooks(
+        &self,
+        dest: &Path,
+        context: &Context,
+        stream: bool,
+    ) -> miette::Result<()> {
+        for hook in self.get_post_generate_hooks(context)? {
+            debug!(
+                template = self.id.as_str(),
+                command = &hook,
+                "Running post-generate hook",
+            );
+
+            let mut command = Command::new_script(&hook);
+            command.cwd(dest);
+
+            if stream {
+                command.exec_stream_output().await?;
+            } else {
+                command.exec_capture_output().await?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Tera *does not* support iterating over the context, so we're unable
     /// to interpolate a path ourselves. Instead, let's use Tera and its
     /// template rendering to handle this.
@@ -284,6 +476,45 @@ impl Template {
         Ok(())
     }
 
+    /// Return the content that will be written to the destination path,
+    /// which differs from the rendered content when merging. Merged content
+    /// is formatted according to the destination's `.editorconfig`.
+    pub fn get_write_content(&self, file: &TemplateFile) -> miette::Result<String> {
+        if file.state == FileState::Merge {
+            match file.is_mergeable() {
+                Some(MergeType::Json) => {
+                    let prev: json::JsonValue = json::read_file(&file.dest_path)?;
+                    let next: json::JsonValue = json::parse(&file.content)?;
+                    let editor_config = fs::get_editor_config_props(&file.dest_path)?;
+
+                    let mut content = json::format_with_identation(
+                        &json::merge(&prev, &next),
+                        &editor_config.indent,
+                    )?;
+                    editor_config.apply_eof(&mut content);
+
+                    return Ok(content);
+                }
+                Some(MergeType::Yaml) => {
+                    let prev: yaml::YamlValue = yaml::read_file(&file.dest_path)?;
+                    let next: yaml::YamlValue = yaml::parse(&file.content)?;
+                    let editor_config = fs::get_editor_config_props(&file.dest_path)?;
+
+                    let mut content = yaml::format_with_identation(
+                        &yaml::merge(&prev, &next),
+                        &editor_config.indent,
+                    )?;
+                    editor_config.apply_eof(&mut content);
+
+                    return Ok(content);
+                }
+                _ => {}
+            };
+        }
+
+        Ok(file.content.clone())
+    }
+
     /// Write the template file to the defined destination path.
     pub fn write_file(&self, file: &TemplateFile) -> miette::Result<()> {
         match file.state {
@@ -293,26 +524,6 @@ impl Template {
                     to = ?file.dest_path,
                     "Merging template file with destination",
                 );
-
-                match file.is_mergeable() {
-                    Some(MergeType::Json) => {
-                        let prev: json::JsonValue = json::read_file(&file.dest_path)?;
-                        let next: json::JsonValue = json::parse(&file.content)?;
-
-                        json::write_file_with_config(
-                            &file.dest_path,
-                            &json::merge(&prev, &next),
-                            true,
-                        )?;
-                    }
-                    Some(MergeType::Yaml) => {
-                        let prev: yaml::YamlValue = yaml::read_file(&file.dest_path)?;
-                        let next: yaml::YamlValue = yaml::parse(&file.content)?;
-
-                        yaml::write_file_with_config(&file.dest_path, &yaml::merge(&prev, &next))?;
-                    }
-                    _ => {}
-                };
             }
             FileState::Replace => {
                 debug!(
@@ -320,8 +531,14 @@ impl Template {
                     to = ?file.dest_path,
                     "Overwriting with template file",
                 );
-
-                fs::write_file(&file.dest_path, &file.content)?;
+            }
+            FileState::Update => {
+                debug!(
+                    file = file.name.as_str(),
+                    to = ?file.dest_path,
+                    conflicted = file.conflicted,
+                    "Updating with merged template file",
+                );
             }
             _ => {
                 debug!(
@@ -329,11 +546,19 @@ impl Template {
                     to = ?file.dest_path,
                     "Writing template file",
                 );
-
-                fs::write_file(&file.dest_path, &file.content)?;
             }
         };
 
+        // Use the same content as dry runs and plans
+        fs::write_file(&file.dest_path, self.get_write_content(file)?)?;
+
         Ok(())
     }
 }
+
+fn quote_hook_value(value: &str) -> String {
+    get_default_shell()
+        .build()
+        .create_quoter(Quotable::from(value))
+        .quote()
+}
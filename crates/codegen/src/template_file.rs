@@ -1,4 +1,4 @@
-use moon_common::path::RelativePathBuf;
+use moon_common::path::{RelativePathBuf, to_virtual_string};
 use moon_config::TemplateFrontmatterConfig;
 use moon_config_loader::ConfigLoader;
 use serde::Serialize;
@@ -19,6 +19,7 @@ pub enum FileState {
     Merge,
     Replace,
     Skip,
+    Update,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
@@ -26,6 +27,9 @@ pub struct TemplateFile {
     /// Frontmatter extracted into a config.
     pub config: Option<TemplateFrontmatterConfig>,
 
+    /// Whether the content contains conflict markers after updating.
+    pub conflicted: bool,
+
     /// Rendered and frontmatter-free file content.
     pub content: String,
 
@@ -50,6 +54,7 @@ impl TemplateFile {
         TemplateFile {
             raw: name.as_str().contains(".raw"),
             config: None,
+            conflicted: false,
             content: String::new(),
             dest_path: PathBuf::new(),
             name,
@@ -131,3 +136,7 @@ impl TemplateFile {
         !matches!(self.state, FileState::Skip)
     }
 }
+
+pub(crate) fn to_relative_dest_path(path: &Path, dest: &Path) -> miette::Result<String> {
+    to_virtual_string(path.strip_prefix(dest).unwrap_or(path))
+}
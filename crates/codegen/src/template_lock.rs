@@ -0,0 +1,97 @@
+use crate::template::Template;
+use crate::template_file::to_relative_dest_path;
+use moon_common::Id;
+use serde::{Deserialize, Serialize};
+use starbase_utils::json::{self, JsonValue};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tera::Context;
+use tracing::debug;
+
+pub const TEMPLATE_LOCK_FILE: &str = ".moon-template.lock";
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TemplateLockEntry {
+    /// Content of each file as it was originally rendered,
+    /// keyed by a path relative to the destination.
+    pub files: BTreeMap<String, String>,
+
+    /// Resolved revision of the template location, for Git and npm locators.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+
+    /// Values of the variables the template was rendered with.
+    pub variables: BTreeMap<String, JsonValue>,
+}
+
+impl TemplateLockEntry {
+    pub fn new(
+        template: &Template,
+        dest: &Path,
+        context: &Context,
+        revision: Option<String>,
+    ) -> miette::Result<Self> {
+        let mut entry = TemplateLockEntry {
+            revision,
+            ..Default::default()
+        };
+
+        for file in template.files.values() {
+            if file.is_skipped() {
+                continue;
+            }
+
+            entry.files.insert(
+                to_relative_dest_path(&file.dest_path, dest)?,
+                file.content.clone(),
+            );
+        }
+
+        // Internal variables are not recorded, so that updates
+        // always inherit the latest value from the template
+        for (name, config) in &template.config.variables {
+            if config.is_internal() {
+                continue;
+            }
+
+            if let Some(value) = context.get(name) {
+                entry.variables.insert(name.to_owned(), value.to_owned());
+            }
+        }
+
+        Ok(entry)
+    }
+}
+
+/// A lock file within a generated destination, that records the
+/// templates that were generated into it, so that they can be updated.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TemplateLock {
+    pub templates: BTreeMap<Id, TemplateLockEntry>,
+}
+
+impl TemplateLock {
+    pub fn load(dest: &Path) -> miette::Result<Self> {
+        let path = dest.join(TEMPLATE_LOCK_FILE);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        debug!(file = ?path, "Loading template lock file");
+
+        Ok(json::read_file(path)?)
+    }
+
+    pub fn save(&self, dest: &Path) -> miette::Result<()> {
+        let path = dest.join(TEMPLATE_LOCK_FILE);
+
+        debug!(file = ?path, "Writing template lock file");
+
+        json::write_file_with_config(path, self, true)?;
+
+        Ok(())
+    }
+}
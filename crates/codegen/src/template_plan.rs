@@ -0,0 +1,87 @@
+use crate::template::Template;
+use crate::template_file::{FileState, to_relative_dest_path};
+use crate::unified_diff::create_unified_diff;
+use moon_common::Id;
+use serde::Serialize;
+use starbase_utils::{fs, hash};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplatePlanFile {
+    /// Whether the file is a binary asset that is copied as-is.
+    pub asset: bool,
+
+    /// Whether the content contains conflict markers after updating.
+    pub conflicted: bool,
+
+    /// Unified diff between the existing destination file and the new content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+
+    /// SHA-256 hash of the content that will be written.
+    pub hash: String,
+
+    /// Path relative to the destination.
+    pub path: String,
+
+    pub state: FileState,
+}
+
+/// A plan of all file operations that generating a template will
+/// commit to the destination, without writing any files.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TemplatePlan {
+    pub dest: PathBuf,
+    pub files: Vec<TemplatePlanFile>,
+    pub template: Id,
+}
+
+impl TemplatePlan {
+    pub fn new(template: &Template, dest: &Path) -> miette::Result<Self> {
+        let mut files = vec![];
+
+        for file in template.files.values() {
+            let content = template.get_write_content(file)?;
+            let diff = if file.should_write() && file.dest_path.exists() {
+                create_unified_diff(
+                    &to_relative_dest_path(&file.dest_path, dest)?,
+                    &fs::read_file(&file.dest_path)?,
+                    &content,
+                )
+            } else {
+                None
+            };
+
+            files.push(TemplatePlanFile {
+                asset: false,
+                conflicted: file.conflicted,
+                diff,
+                hash: hash::sha256::from_bytes(&content),
+                path: to_relative_dest_path(&file.dest_path, dest)?,
+                state: file.state.clone(),
+            });
+        }
+
+        for asset in template.assets.values() {
+            files.push(TemplatePlanFile {
+                asset: true,
+                conflicted: false,
+                diff: None,
+                hash: hash::sha256::from_bytes(&asset.content),
+                path: to_relative_dest_path(&asset.dest_path, dest)?,
+                state: if asset.dest_path.exists() {
+                    FileState::Replace
+                } else {
+                    FileState::Create
+                },
+            });
+        }
+
+        Ok(TemplatePlan {
+            dest: dest.to_path_buf(),
+            files,
+            template: template.id.clone(),
+        })
+    }
+}
@@ -0,0 +1,116 @@
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+enum DiffLine<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Create a unified diff (with 3 lines of context) that transforms the
+/// previous content into the next content. Returns `None` if the content
+/// is the same.
+pub fn create_unified_diff(label: &str, prev: &str, next: &str) -> Option<String> {
+    if prev == next {
+        return None;
+    }
+
+    let prev_lines = prev.split_inclusive('\n').collect::<Vec<_>>();
+    let next_lines = next.split_inclusive('\n').collect::<Vec<_>>();
+    let lines = diff::slice(&prev_lines, &next_lines)
+        .into_iter()
+        .map(|result| match result {
+            diff::Result::Left(line) => DiffLine::Delete(line),
+            diff::Result::Both(line, _) => DiffLine::Equal(line),
+            diff::Result::Right(line) => DiffLine::Insert(line),
+        })
+        .collect::<Vec<_>>();
+
+    let mut output = format!("--- a/{label}\n+++ b/{label}\n");
+    let mut index = 0;
+    let mut prev_line = 0;
+    let mut next_line = 0;
+
+    while index < lines.len() {
+        // Find the next change, and track the line numbers up to it
+        let Some(change_index) =
+            (index..lines.len()).find(|i| !matches!(lines[*i], DiffLine::Equal(_)))
+        else {
+            break;
+        };
+
+        let skipped = change_index.saturating_sub(CONTEXT_LINES).max(index) - index;
+        prev_line += skipped;
+        next_line += skipped;
+
+        let start = index + skipped;
+        let mut end = change_index;
+
+        // Extend the hunk until there are enough unchanged lines
+        // after the last change to separate it from the next hunk
+        loop {
+            while end < lines.len() && !matches!(lines[end], DiffLine::Equal(_)) {
+                end += 1;
+            }
+
+            let equal_count = (end..lines.len())
+                .take_while(|i| matches!(lines[*i], DiffLine::Equal(_)))
+                .count();
+
+            if end + equal_count >= lines.len() || equal_count > CONTEXT_LINES * 2 {
+                end += equal_count.min(CONTEXT_LINES);
+                break;
+            }
+
+            end += equal_count;
+        }
+
+        let hunk = &lines[start..end];
+        let prev_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Insert(_)))
+            .count();
+        let next_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Delete(_)))
+            .count();
+
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            format_range(prev_line, prev_count),
+            format_range(next_line, next_count)
+        ));
+
+        for line in hunk {
+            let (prefix, content) = match line {
+                DiffLine::Equal(content) => (' ', content),
+                DiffLine::Delete(content) => ('-', content),
+                DiffLine::Insert(content) => ('+', content),
+            };
+
+            output.push(prefix);
+            output.push_str(content);
+
+            if !content.ends_with('\n') {
+                output.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+
+        prev_line += prev_count;
+        next_line += next_count;
+        index = end;
+    }
+
+    Some(output)
+}
+
+fn format_range(start: usize, count: usize) -> String {
+    // Ranges are 1-based, but an empty range points to the line before it
+    let start = if count == 0 { start } else { start + 1 };
+
+    if count == 1 {
+        start.to_string()
+    } else {
+        format!("{start},{count}")
+    }
+}
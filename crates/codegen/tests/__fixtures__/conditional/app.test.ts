@@ -0,0 +1,4 @@
+---
+if: type == 'app' and tests
+---
+test('{{ name }}');
@@ -0,0 +1,4 @@
+---
+if: type == 'library'
+---
+export * from './lib';
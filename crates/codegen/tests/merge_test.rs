@@ -0,0 +1,114 @@
+use moon_codegen::{MergedContent, merge_content};
+
+mod merge_content {
+    use super::*;
+
+    #[test]
+    fn returns_next_when_current_is_unchanged() {
+        assert_eq!(
+            merge_content("a\nb\nc\n", "a\nb\nc\n", "a\nB\nc\n"),
+            MergedContent {
+                content: "a\nB\nc\n".into(),
+                conflicts: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn returns_current_when_next_is_unchanged() {
+        assert_eq!(
+            merge_content("a\nb\nc\n", "a\nb\nc\nd\n", "a\nb\nc\n"),
+            MergedContent {
+                content: "a\nb\nc\nd\n".into(),
+                conflicts: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn merges_changes_in_different_regions() {
+        let merged = merge_content(
+            "one\ntwo\nthree\nfour\nfive\n",
+            "ONE\ntwo\nthree\nfour\nfive\n",
+            "one\ntwo\nthree\nfour\nFIVE\nsix\n",
+        );
+
+        assert_eq!(merged.content, "ONE\ntwo\nthree\nfour\nFIVE\nsix\n");
+        assert!(!merged.has_conflicts());
+    }
+
+    #[test]
+    fn merges_removed_lines() {
+        let merged = merge_content(
+            "one\ntwo\nthree\nfour\n",
+            "one\nthree\nfour\n",
+            "one\ntwo\nthree\nfour\nfive\n",
+        );
+
+        assert_eq!(merged.content, "one\nthree\nfour\nfive\n");
+        assert!(!merged.has_conflicts());
+    }
+
+    #[test]
+    fn doesnt_conflict_when_both_make_the_same_change() {
+        let merged = merge_content("a\nb\nc\n", "a\nB\nc\n", "a\nB\nc\n");
+
+        assert_eq!(merged.content, "a\nB\nc\n");
+        assert!(!merged.has_conflicts());
+    }
+
+    #[test]
+    fn marks_conflicts_when_both_change_the_same_lines() {
+        let merged = merge_content("a\nb\nc\n", "a\ncurrent\nc\n", "a\nnext\nc\n");
+
+        assert_eq!(
+            merged.content,
+            "a\n<<<<<<< current\ncurrent\n=======\nnext\n>>>>>>> template\nc\n"
+        );
+        assert_eq!(merged.conflicts, 1);
+    }
+
+    #[test]
+    fn marks_conflicts_when_both_insert_at_the_same_position() {
+        let merged = merge_content("a\n", "a\ncurrent\n", "a\nnext\n");
+
+        assert_eq!(
+            merged.content,
+            "a\n<<<<<<< current\ncurrent\n=======\nnext\n>>>>>>> template\n"
+        );
+        assert_eq!(merged.conflicts, 1);
+    }
+
+    #[test]
+    fn marks_each_conflicting_region() {
+        let merged = merge_content("a\nb\nc\nd\ne\n", "a\nB1\nc\nd\nE1\n", "a\nB2\nc\nd\nE2\n");
+
+        assert_eq!(merged.conflicts, 2);
+    }
+
+    #[test]
+    fn handles_missing_trailing_newlines() {
+        let merged = merge_content("a\nb", "a\ncurrent", "a\nnext");
+
+        assert_eq!(
+            merged.content,
+            "a\n<<<<<<< current\ncurrent\n=======\nnext\n>>>>>>> template\n"
+        );
+    }
+
+    #[test]
+    fn conflicts_entire_content_without_a_base() {
+        let merged = merge_content("", "current\n", "next\n");
+
+        assert_eq!(
+            merged.content,
+            "<<<<<<< current\ncurrent\n=======\nnext\n>>>>>>> template\n"
+        );
+        assert_eq!(merged.conflicts, 1);
+
+        let merged = merge_content("", "same\n", "same\n");
+
+        assert_eq!(merged.content, "same\n");
+        assert!(!merged.has_conflicts());
+    }
+}
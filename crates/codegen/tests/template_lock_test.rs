@@ -0,0 +1,76 @@
+use moon_codegen::{
+    TEMPLATE_LOCK_FILE, Template, TemplateContext, TemplateLock, TemplateLockEntry,
+};
+use moon_common::Id;
+use starbase_sandbox::{create_empty_sandbox, locate_fixture};
+use starbase_utils::json::JsonValue;
+
+fn create_template() -> Template {
+    Template::new(Id::raw("update"), locate_fixture("update")).unwrap()
+}
+
+fn create_context() -> TemplateContext {
+    let mut context = TemplateContext::new();
+    context.insert("name", "app");
+    context.insert("internal", "original");
+    context
+}
+
+mod template_lock {
+    use super::*;
+
+    #[test]
+    fn records_rendered_files_and_variables() {
+        let sandbox = create_empty_sandbox();
+        let mut template = create_template();
+        let context = create_context();
+
+        template.load_files(sandbox.path(), &context).unwrap();
+
+        let entry =
+            TemplateLockEntry::new(&template, sandbox.path(), &context, Some("1.2.3".into()))
+                .unwrap();
+
+        assert_eq!(entry.revision.as_deref(), Some("1.2.3"));
+        assert_eq!(
+            entry.files.keys().collect::<Vec<_>>(),
+            vec!["added.txt", "config.toml"]
+        );
+        assert_eq!(
+            entry.files["config.toml"],
+            "name = \"app\"\nversion = \"1.0.0\"\n\n[build]\nminify = true\n"
+        );
+        assert_eq!(
+            entry.variables.into_iter().collect::<Vec<_>>(),
+            vec![("name".into(), JsonValue::String("app".into()))]
+        );
+    }
+
+    #[test]
+    fn returns_empty_if_no_file() {
+        let sandbox = create_empty_sandbox();
+
+        assert_eq!(
+            TemplateLock::load(sandbox.path()).unwrap(),
+            TemplateLock::default()
+        );
+    }
+
+    #[test]
+    fn saves_and_loads_file() {
+        let sandbox = create_empty_sandbox();
+        let mut lock = TemplateLock::default();
+
+        lock.templates.insert(
+            Id::raw("update"),
+            TemplateLockEntry {
+                revision: Some("abc123".into()),
+                ..Default::default()
+            },
+        );
+        lock.save(sandbox.path()).unwrap();
+
+        assert!(sandbox.path().join(TEMPLATE_LOCK_FILE).exists());
+        assert_eq!(TemplateLock::load(sandbox.path()).unwrap(), lock);
+    }
+}
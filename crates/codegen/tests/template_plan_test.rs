@@ -0,0 +1,110 @@
+use moon_codegen::{FileState, Template, TemplateContext, TemplatePlan};
+use moon_common::Id;
+use starbase_sandbox::{create_empty_sandbox, locate_fixture};
+use starbase_utils::hash;
+
+fn load_template(dest: &std::path::Path) -> Template {
+    let mut context = TemplateContext::new();
+    context.insert("name", "app");
+
+    let mut template = Template::new(Id::raw("update"), locate_fixture("update")).unwrap();
+    template.load_files(dest, &context).unwrap();
+    template
+}
+
+mod template_plan {
+    use super::*;
+
+    #[test]
+    fn plans_all_files() {
+        let sandbox = create_empty_sandbox();
+        let mut template = load_template(sandbox.path());
+
+        for file in template.files.values_mut() {
+            if file.is_skipped() {
+                file.state = FileState::Skip;
+            }
+        }
+
+        let plan = TemplatePlan::new(&template, sandbox.path()).unwrap();
+
+        assert_eq!(plan.template, "update");
+        assert_eq!(plan.dest, sandbox.path());
+        assert_eq!(
+            plan.files
+                .iter()
+                .map(|file| (file.path.as_str(), &file.state))
+                .collect::<Vec<_>>(),
+            vec![
+                ("added.txt", &FileState::Create),
+                ("config.toml", &FileState::Create),
+                ("skipped.txt", &FileState::Skip),
+            ]
+        );
+        assert_eq!(
+            plan.files[0].hash,
+            hash::sha256::from_bytes("New file\n".as_bytes())
+        );
+        assert!(plan.files.iter().all(|file| file.diff.is_none()));
+    }
+
+    #[test]
+    fn includes_diff_against_existing_files() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("config.toml", "name = \"app\"\nversion = \"0.1.0\"\n");
+        sandbox.create_file("skipped.txt", "Existing\n");
+
+        let mut template = load_template(sandbox.path());
+
+        for file in template.files.values_mut() {
+            file.state = if file.is_skipped() {
+                FileState::Skip
+            } else {
+                FileState::Replace
+            };
+        }
+
+        let plan = TemplatePlan::new(&template, sandbox.path()).unwrap();
+
+        assert_eq!(plan.files[0].diff, None);
+        assert_eq!(
+            plan.files[1].diff.as_deref(),
+            Some(
+                "--- a/config.toml
++++ b/config.toml
+@@ -1,2 +1,5 @@
+ name = \"app\"
+-version = \"0.1.0\"
++version = \"1.0.0\"
++
++[build]
++minify = true
+"
+            )
+        );
+        assert_eq!(plan.files[2].diff, None);
+    }
+
+    #[test]
+    fn plans_merged_content() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("config.json", "{\"a\": 1}");
+
+        let mut template = load_template(sandbox.path());
+        let mut file = template.files.values().next().unwrap().clone();
+        file.name = "config.json".into();
+        file.dest_path = sandbox.path().join("config.json");
+        file.content = "{\"b\": 2}".into();
+        file.state = FileState::Merge;
+        template.files.clear();
+        template.files.insert(file.name.clone(), file);
+
+        let plan = TemplatePlan::new(&template, sandbox.path()).unwrap();
+
+        assert_eq!(
+            plan.files[0].hash,
+            hash::sha256::from_bytes("{\n  \"a\": 1,\n  \"b\": 2\n}\n".as_bytes())
+        );
+        assert!(plan.files[0].diff.is_some());
+    }
+}
@@ -1,8 +1,10 @@
-use moon_codegen::{CodeGenerator, Template, TemplateContext, TemplateFile};
+use moon_codegen::{
+    CodeGenerator, FileState, Template, TemplateContext, TemplateFile, TemplateLockEntry,
+};
 use moon_common::Id;
-use moon_config::{GeneratorConfig, TemplateFrontmatterConfig};
+use moon_config::{FilePath, GeneratorConfig, TemplateFrontmatterConfig, TemplateLocator};
 use moon_env::MoonEnvironment;
-use starbase_sandbox::{create_sandbox, locate_fixture};
+use starbase_sandbox::{create_empty_sandbox, create_sandbox, locate_fixture};
 use std::path::PathBuf;
 
 fn create_template_file() -> TemplateFile {
@@ -192,6 +194,274 @@ mod template {
         }
     }
 
+    mod conditions {
+        use super::*;
+
+        fn load_conditional_files(type_of: &str, tests: bool) -> Vec<String> {
+            let mut context = TemplateContext::new();
+            context.insert("name", "example");
+            context.insert("type", type_of);
+            context.insert("tests", &tests);
+
+            let mut template =
+                Template::new(Id::raw("conditional"), locate_fixture("conditional")).unwrap();
+            template.load_files(&PathBuf::from("."), &context).unwrap();
+            template.files.keys().map(|name| name.to_string()).collect()
+        }
+
+        #[test]
+        fn excludes_files_when_condition_fails() {
+            assert_eq!(load_conditional_files("app", false), vec!["always.txt"]);
+        }
+
+        #[test]
+        fn includes_files_when_condition_passes() {
+            assert_eq!(
+                load_conditional_files("library", false),
+                vec!["always.txt", "index.ts"]
+            );
+            assert_eq!(
+                load_conditional_files("app", true),
+                vec!["always.txt", "app.test.ts"]
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Failed to evaluate the if condition")]
+        fn errors_for_invalid_condition() {
+            let sandbox = create_empty_sandbox();
+            sandbox.create_file("template.yml", "title: Test\ndescription: Test");
+            sandbox.create_file("file.txt", "---\nif: type ==\n---\ncontent");
+
+            let mut template = Template::new(Id::raw("test"), sandbox.path().into()).unwrap();
+            template
+                .load_files(&PathBuf::from("."), &create_context())
+                .unwrap();
+        }
+    }
+
+    mod hooks {
+        use super::*;
+
+        fn create_conditional_template() -> (Template, TemplateContext) {
+            let mut context = TemplateContext::new();
+            context.insert("name", "example");
+            context.insert("type", "app");
+
+            (
+                Template::new(Id::raw("conditional"), locate_fixture("conditional")).unwrap(),
+                context,
+            )
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn interpolates_variables_into_commands() {
+            let (template, context) = create_conditional_template();
+
+            assert_eq!(
+                template.get_post_generate_hooks(&context).unwrap(),
+                vec!["echo example > hook.txt", "echo app >> hook.txt"]
+            );
+        }
+
+        #[cfg(unix)]
+        #[tokio::test]
+        async fn doesnt_run_injected_commands() {
+            let sandbox = create_empty_sandbox();
+            let (template, mut context) = create_conditional_template();
+            context.insert("name", "x; touch pwned");
+            context.insert("type", "$(touch pwned)");
+
+            template
+                .run_post_generate_hooks(sandbox.path(), &context, false)
+                .await
+                .unwrap();
+
+            assert!(!sandbox.path().join("pwned").exists());
+            assert_eq!(
+                std::fs::read_to_string(sandbox.path().join("hook.txt")).unwrap(),
+                "x; touch pwned\n$(touch pwned)\n"
+            );
+        }
+
+        #[test]
+        fn only_has_remote_hooks_for_non_file_locators() {
+            let (mut template, _) = create_conditional_template();
+
+            assert!(!template.has_remote_hooks());
+
+            template.locator = Some(TemplateLocator::File {
+                path: FilePath("./templates".into()),
+            });
+
+            assert!(!template.has_remote_hooks());
+
+            let mut extended = template.clone();
+            extended.locator = Some(TemplateLocator::Git {
+                remote_url: "github.com/org/templates".into(),
+                revision: "main".into(),
+            });
+            template.extend_template(extended);
+
+            assert!(template.has_remote_hooks());
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn includes_extended_hooks_first() {
+            let (mut template, context) = create_conditional_template();
+            let mut extended = template.clone();
+            extended.config.hooks.post_generate =
+                vec!["echo {{ name }} > hook.txt".into(), "npm install".into()];
+            template.extend_template(extended);
+
+            assert_eq!(
+                template.get_post_generate_hooks(&context).unwrap(),
+                vec![
+                    "echo example > hook.txt",
+                    "npm install",
+                    "echo app >> hook.txt"
+                ]
+            );
+        }
+
+        #[cfg(unix)]
+        #[tokio::test]
+        async fn runs_commands_in_destination() {
+            let sandbox = create_empty_sandbox();
+            let (template, context) = create_conditional_template();
+
+            template
+                .run_post_generate_hooks(sandbox.path(), &context, false)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                std::fs::read_to_string(sandbox.path().join("hook.txt")).unwrap(),
+                "example\napp\n"
+            );
+        }
+
+        #[cfg(unix)]
+        #[tokio::test]
+        #[should_panic]
+        async fn errors_when_a_command_fails() {
+            let sandbox = create_empty_sandbox();
+            let (mut template, context) = create_conditional_template();
+            template.config.hooks.post_generate = vec!["exit 1".into()];
+
+            template
+                .run_post_generate_hooks(sandbox.path(), &context, false)
+                .await
+                .unwrap();
+        }
+    }
+
+    mod update_files {
+        use super::*;
+
+        fn load_update_template(dest: &std::path::Path, name: &str) -> Template {
+            let mut context = TemplateContext::new();
+            context.insert("name", name);
+
+            let mut template = Template::new(Id::raw("update"), locate_fixture("update")).unwrap();
+            template.load_files(dest, &context).unwrap();
+            template
+        }
+
+        fn get_file<'a>(template: &'a Template, name: &str) -> &'a TemplateFile {
+            template.files.values().find(|f| f.name == name).unwrap()
+        }
+
+        #[test]
+        fn merges_changes_into_current_files() {
+            let sandbox = create_empty_sandbox();
+            let previous = load_update_template(sandbox.path(), "old");
+            let lock =
+                TemplateLockEntry::new(&previous, sandbox.path(), &TemplateContext::new(), None)
+                    .unwrap();
+
+            sandbox.create_file(
+                "config.toml",
+                "name = \"old\"\nversion = \"1.0.0\"\n\n[build]\nminify = false\n",
+            );
+
+            let mut template = load_update_template(sandbox.path(), "new");
+            template.update_files(sandbox.path(), &lock).unwrap();
+
+            let file = get_file(&template, "config.toml");
+
+            assert_eq!(file.state, FileState::Update);
+            assert!(!file.conflicted);
+            assert_eq!(
+                file.content,
+                "name = \"new\"\nversion = \"1.0.0\"\n\n[build]\nminify = false\n"
+            );
+        }
+
+        #[test]
+        fn marks_conflicting_changes() {
+            let sandbox = create_empty_sandbox();
+            let previous = load_update_template(sandbox.path(), "old");
+            let lock =
+                TemplateLockEntry::new(&previous, sandbox.path(), &TemplateContext::new(), None)
+                    .unwrap();
+
+            sandbox.create_file(
+                "config.toml",
+                "name = \"custom\"\nversion = \"1.0.0\"\n\n[build]\nminify = true\n",
+            );
+
+            let mut template = load_update_template(sandbox.path(), "new");
+            template.update_files(sandbox.path(), &lock).unwrap();
+
+            let file = get_file(&template, "config.toml");
+
+            assert_eq!(file.state, FileState::Update);
+            assert!(file.conflicted);
+            assert_eq!(
+                file.content,
+                "<<<<<<< current\nname = \"custom\"\n=======\nname = \"new\"\n>>>>>>> template\nversion = \"1.0.0\"\n\n[build]\nminify = true\n"
+            );
+        }
+
+        #[test]
+        fn skips_unchanged_and_removed_files() {
+            let sandbox = create_empty_sandbox();
+            let previous = load_update_template(sandbox.path(), "old");
+            let lock =
+                TemplateLockEntry::new(&previous, sandbox.path(), &TemplateContext::new(), None)
+                    .unwrap();
+
+            // config.toml was removed by the user, added.txt is unchanged
+            sandbox.create_file("added.txt", "New file\n");
+
+            let mut template = load_update_template(sandbox.path(), "new");
+            template.update_files(sandbox.path(), &lock).unwrap();
+
+            assert_eq!(get_file(&template, "added.txt").state, FileState::Skip);
+            assert_eq!(get_file(&template, "config.toml").state, FileState::Skip);
+            assert_eq!(get_file(&template, "skipped.txt").state, FileState::Skip);
+        }
+
+        #[test]
+        fn creates_files_new_to_the_template() {
+            let sandbox = create_empty_sandbox();
+            let mut previous = load_update_template(sandbox.path(), "old");
+            previous.files.retain(|name, _| name != "added.txt");
+
+            let lock =
+                TemplateLockEntry::new(&previous, sandbox.path(), &TemplateContext::new(), None)
+                    .unwrap();
+
+            let mut template = load_update_template(sandbox.path(), "new");
+            template.update_files(sandbox.path(), &lock).unwrap();
+
+            assert_eq!(get_file(&template, "added.txt").state, FileState::Create);
+        }
+    }
+
     mod interpolate_path {
         use super::*;
 
@@ -387,6 +657,36 @@ mod template {
         }
     }
 
+    mod write_file {
+        use super::*;
+
+        #[test]
+        fn writes_the_same_merged_content_as_planned() {
+            let sandbox = create_empty_sandbox();
+            sandbox.create_file(".editorconfig", "[*]\nindent_style = tab\n");
+            sandbox.create_file("package.json", "{\"name\":\"app\",\"private\":true}");
+
+            let mut file = TemplateFile::new("package.json".into(), PathBuf::from("."));
+            file.set_content("{\"name\":\"next\",\"version\":\"1.0.0\"}", sandbox.path())
+                .unwrap();
+            file.state = FileState::Merge;
+
+            let template = create_template();
+            let content = template.get_write_content(&file).unwrap();
+
+            template.write_file(&file).unwrap();
+
+            assert_eq!(
+                std::fs::read_to_string(sandbox.path().join("package.json")).unwrap(),
+                content
+            );
+            assert_eq!(
+                content,
+                "{\n\t\"name\": \"next\",\n\t\"private\": true,\n\t\"version\": \"1.0.0\"\n}\n"
+            );
+        }
+    }
+
     mod extending {
         use super::*;
         use starbase_sandbox::assert_snapshot;
@@ -0,0 +1,109 @@
+use moon_codegen::create_unified_diff;
+
+mod unified_diff {
+    use super::*;
+
+    #[test]
+    fn returns_none_when_equal() {
+        assert_eq!(create_unified_diff("file.txt", "a\nb\n", "a\nb\n"), None);
+    }
+
+    #[test]
+    fn creates_a_single_hunk() {
+        assert_eq!(
+            create_unified_diff("file.txt", "a\nb\nc\n", "a\nB\nc\nd\n").unwrap(),
+            "--- a/file.txt
++++ b/file.txt
+@@ -1,3 +1,4 @@
+ a
+-b
++B
+ c
++d
+"
+        );
+    }
+
+    #[test]
+    fn limits_context_and_splits_hunks() {
+        let prev = (1..=20).map(|i| format!("{i}\n")).collect::<String>();
+        let next = (1..=20)
+            .map(|i| match i {
+                2 => "two\n".to_owned(),
+                18 => "eighteen\n".to_owned(),
+                _ => format!("{i}\n"),
+            })
+            .collect::<String>();
+
+        assert_eq!(
+            create_unified_diff("file.txt", &prev, &next).unwrap(),
+            "--- a/file.txt
++++ b/file.txt
+@@ -1,5 +1,5 @@
+ 1
+-2
++two
+ 3
+ 4
+ 5
+@@ -15,6 +15,6 @@
+ 15
+ 16
+ 17
+-18
++eighteen
+ 19
+ 20
+"
+        );
+    }
+
+    #[test]
+    fn merges_nearby_changes_into_one_hunk() {
+        let prev = "1\n2\n3\n4\n5\n6\n7\n8\n";
+        let next = "one\n2\n3\n4\n5\n6\n7\neight\n";
+
+        assert_eq!(
+            create_unified_diff("file.txt", prev, next).unwrap(),
+            "--- a/file.txt
++++ b/file.txt
+@@ -1,8 +1,8 @@
+-1
++one
+ 2
+ 3
+ 4
+ 5
+ 6
+ 7
+-8
++eight
+"
+        );
+    }
+
+    #[test]
+    fn handles_empty_and_missing_newlines() {
+        assert_eq!(
+            create_unified_diff("file.txt", "", "a\n").unwrap(),
+            "--- a/file.txt
++++ b/file.txt
+@@ -0,0 +1 @@
++a
+"
+        );
+
+        assert_eq!(
+            create_unified_diff("file.txt", "a\nb", "a\nc").unwrap(),
+            "--- a/file.txt
++++ b/file.txt
+@@ -1,2 +1,2 @@
+ a
+-b
+\\ No newline at end of file
++c
+\\ No newline at end of file
+"
+        );
+    }
+}
@@ -1,9 +1,11 @@
+mod bytes;
 #[cfg(not(target_arch = "wasm32"))]
 mod env;
 mod id;
 mod macros;
 pub mod path;
 
+pub use bytes::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub use env::*;
 pub use id::*;
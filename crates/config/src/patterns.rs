@@ -18,15 +18,21 @@ pattern!(ENV_VAR_GLOB_DISTINCT, "^\\$([A-Z0-9_*]+)$"); // $ENV_*
 
 // Task tokens
 
-pattern!(TOKEN_FUNC, "@([a-z]+)\\(([0-9A-Za-z_-]+)\\)");
-pattern!(TOKEN_FUNC_DISTINCT, "^@([a-z]+)\\(([0-9A-Za-z_-]+)\\)$");
+pattern!(
+    TOKEN_FUNC,
+    "@([a-z]+)\\(([0-9A-Za-z_./-]+(?:,\\s*[0-9A-Za-z_./-]+)*)\\)"
+);
+pattern!(
+    TOKEN_FUNC_DISTINCT,
+    "^@([a-z]+)\\(([0-9A-Za-z_./-]+(?:,\\s*[0-9A-Za-z_./-]+)*)\\)$"
+);
 pattern!(
     TOKEN_VAR,
-    "\\$(arch|language|osFamily|os|projectAliases|projectAlias|projectChannel|projectId|projectLayer|projectName|projectTitle|projectOwner|projectRoot|projectSource|projectStack|project|target|taskId|taskToolchains|taskToolchain|taskType|task|timestamp|datetime|date|time|vcsBranch|vcsRepository|vcsRevision|workingDir|workspaceRoot)"
+    "\\$(arch|language|osFamily|os|projectAliases|projectAlias|projectChannel|projectId|projectLayer|projectName|projectTitle|projectOwner|projectRoot|projectSource|projectStack|projectVersion|project|target|taskId|taskToolchains|taskToolchain|taskType|task|timestamp|datetime|date|time|vcsBranch|vcsRepository|vcsRevision|vcsShortRevision|vcsTag|workingDir|workspaceRoot)"
 );
 pattern!(
     TOKEN_VAR_DISTINCT,
-    "^\\$(arch|language|osFamily|os|projectAliases|projectAlias|projectChannel|projectId|projectLayer|projectName|projectTitle|projectOwner|projectRoot|projectSource|projectStack|project|target|taskId|taskToolchains|taskToolchain|taskType|task|timestamp|datetime|date|time|vcsBranch|vcsRepository|vcsRevision|workingDir|workspaceRoot)$"
+    "^\\$(arch|language|osFamily|os|projectAliases|projectAlias|projectChannel|projectId|projectLayer|projectName|projectTitle|projectOwner|projectRoot|projectSource|projectStack|projectVersion|project|target|taskId|taskToolchains|taskToolchain|taskType|task|timestamp|datetime|date|time|vcsBranch|vcsRepository|vcsRevision|vcsShortRevision|vcsTag|workingDir|workspaceRoot)$"
 );
 
 pub fn merge_iter<I, V, C>(mut prev: I, next: I, _: &C) -> MergeResult<I>
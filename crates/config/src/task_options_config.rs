@@ -1,8 +1,13 @@
 use crate::merging::MergeStrategy;
-use crate::shapes::{FilePath, GlobPath, OneOrMany};
-use crate::{config_enum, config_struct, config_unit_enum, config_untagged_enum, generate_switch};
+use crate::shapes::{FilePath, GlobPath, OneOrMany, Output};
+use crate::{
+    config_enum, config_struct, config_unit_enum, config_untagged_enum, generate_switch, is_false,
+};
+use moon_common::parse_byte_size;
 use schematic::schema::{StringType, UnionType};
-use schematic::{Config, ConfigEnum, Schema, SchemaBuilder, Schematic, ValidateError};
+use schematic::{
+    Config, ConfigEnum, Schema, SchemaBuilder, Schematic, ValidateError, ValidateResult, validate,
+};
 use std::env::consts;
 
 fn validate_interactive<C>(
@@ -20,6 +25,29 @@ fn validate_interactive<C>(
     Ok(())
 }
 
+pub(crate) fn validate_byte_size<D, C>(
+    value: &str,
+    _data: &D,
+    _ctx: &C,
+    _finalize: bool,
+) -> ValidateResult {
+    if parse_byte_size(value).is_none() {
+        return Err(ValidateError::new(
+            "invalid size, expected a number with an optional unit, like \"512mb\" or \"8gib\"",
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_permits<D, C>(value: &u32, _data: &D, _ctx: &C, _finalize: bool) -> ValidateResult {
+    if *value == 0 {
+        return Err(ValidateError::new("at least 1 permit is required"));
+    }
+
+    Ok(())
+}
+
 config_enum!(
     /// The pattern in which affected files will be passed to the affected task.
     #[serde(expecting = "expected `args`, `env`, or a boolean")]
@@ -211,6 +239,42 @@ impl TaskPriority {
     }
 }
 
+config_struct!(
+    /// Resources that a task requires while running, which the action pipeline
+    /// reserves from the workspace capacity before running the task.
+    #[derive(Config)]
+    pub struct TaskOptionResourcesConfig {
+        /// The number of CPU cores the task utilizes.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub cpu: Option<u32>,
+
+        /// The amount of memory the task utilizes, for example "8gb" or "512mib".
+        #[setting(validate = validate_byte_size)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub memory: Option<String>,
+    }
+);
+
+config_struct!(
+    /// A named counting semaphore, that limits how many tasks using the
+    /// same semaphore can run concurrently.
+    #[derive(Config)]
+    pub struct TaskOptionSemaphoreConfig {
+        /// Share the permits with other moon processes running on the same
+        /// machine, instead of only within the current action pipeline.
+        #[serde(default, skip_serializing_if = "is_false")]
+        pub global: bool,
+
+        /// The name of the semaphore. Tasks with the same name share permits.
+        #[setting(validate = validate::not_empty)]
+        pub name: String,
+
+        /// The number of tasks that can hold a permit at the same time.
+        #[setting(default = 1, validate = validate_permits)]
+        pub permits: u32,
+    }
+);
+
 config_unit_enum!(
     /// A list of available shells on Unix.
     #[derive(ConfigEnum)]
@@ -370,6 +434,13 @@ config_struct!(
         #[serde(skip_serializing_if = "Option::is_none")]
         pub priority: Option<TaskPriority>,
 
+        /// The CPU and memory resources that the task requires while running.
+        /// The task will not be ran until these resources are available.
+        /// @since 2.6.0
+        #[setting(nested)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub resources: Option<TaskOptionResourcesConfig>,
+
         /// The number of times a failing task will be retried to succeed.
         #[setting(env = "MOON_RETRY_COUNT")]
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -394,11 +465,32 @@ config_struct!(
         #[serde(skip_serializing_if = "Option::is_none")]
         pub run_from_workspace_root: Option<bool>,
 
+        /// Runs the task in a hermetic sandbox, that only contains the task's
+        /// declared inputs and toolchain dependencies. Declared outputs are
+        /// copied back into the workspace after a successful run.
+        /// @since 2.6.0
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub sandbox: Option<bool>,
+
+        /// Acquires a permit from a named counting semaphore before running,
+        /// limiting how many tasks using the same semaphore run concurrently.
+        /// @since 2.6.0
+        #[setting(nested)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub semaphore: Option<TaskOptionSemaphoreConfig>,
+
         /// Runs the task within a shell. When not defined, runs the task
         /// directly while relying on native `PATH` resolution.
         #[serde(skip_serializing_if = "Option::is_none")]
         pub shell: Option<bool>,
 
+        /// A list of JUnit XML or TAP files (relative from the project root)
+        /// that the task writes test results to. After running, the files
+        /// are parsed into per-test results, and are cached as outputs.
+        /// @since 2.6.0
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub test_reports: Option<Vec<Output>>,
+
         /// The maximum time in seconds that a task can run before being cancelled.
         #[serde(skip_serializing_if = "Option::is_none")]
         pub timeout: Option<u64>,
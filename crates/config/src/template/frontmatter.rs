@@ -13,6 +13,12 @@ config_struct!(
         #[serde(default, skip_serializing_if = "is_false")]
         pub force: bool,
 
+        /// Only include this file when the expression, evaluated against
+        /// the template variables, is truthy.
+        #[setting(rename = "if")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub if_condition: Option<String>,
+
         /// Override the destination using a relative file path.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         pub to: Option<String>,
@@ -2,11 +2,11 @@ use crate::shapes::OneOrMany;
 use crate::{config_enum, config_struct, is_false};
 use moon_common::Id;
 use rustc_hash::FxHashMap;
-use schematic::{Config, ValidateError, validate};
+use schematic::{Config, RegexSetting, ValidateError, validate};
 use serde_json::Value;
 
 macro_rules! var_setting {
-    ($name:ident, $ty:ty) => {
+    ($name:ident, $ty:ty $(, { $($field:tt)* })?) => {
         config_struct!(
             /// Configuration for a template variable.
             #[derive(Config)]
@@ -30,6 +30,13 @@ macro_rules! var_setting {
                 /// Marks the variable as required, and will not accept an empty value.
                 #[serde(skip_serializing_if = "Option::is_none")]
                 pub required: Option<bool>,
+
+                /// Only prompt for the variable when other variables have the
+                /// configured values, otherwise the default value is used.
+                #[serde(skip_serializing_if = "FxHashMap::is_empty")]
+                pub when: FxHashMap<String, Value>,
+
+                $($($field)*)?
             }
         );
     };
@@ -37,9 +44,105 @@ macro_rules! var_setting {
 
 var_setting!(TemplateVariableArraySetting, Vec<Value>);
 var_setting!(TemplateVariableBoolSetting, bool);
-var_setting!(TemplateVariableNumberSetting, isize);
+var_setting!(TemplateVariableNumberSetting, isize, {
+    /// The maximum value allowed (inclusive).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<isize>,
+
+    /// The minimum value allowed (inclusive).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<isize>,
+});
 var_setting!(TemplateVariableObjectSetting, FxHashMap<String, Value>);
-var_setting!(TemplateVariableStringSetting, String);
+var_setting!(TemplateVariableStringSetting, String, {
+    /// The maximum number of characters allowed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<usize>,
+
+    /// The minimum number of characters allowed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<usize>,
+
+    /// A regex pattern that the value must match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<RegexSetting>,
+});
+
+impl TemplateVariableArraySetting {
+    pub fn validate_value(&self, value: &[Value]) -> Option<String> {
+        if self.required.is_some_and(|v| v) && value.is_empty() {
+            return Some("At least 1 item is required".into());
+        }
+
+        None
+    }
+}
+
+impl TemplateVariableNumberSetting {
+    pub fn validate_value(&self, value: isize) -> Option<String> {
+        if self.required.is_some_and(|v| v) && value == 0 {
+            return Some("A non-zero value is required".into());
+        }
+
+        if let Some(min) = self.min
+            && value < min
+        {
+            return Some(format!("Must be greater than or equal to {min}"));
+        }
+
+        if let Some(max) = self.max
+            && value > max
+        {
+            return Some(format!("Must be less than or equal to {max}"));
+        }
+
+        None
+    }
+}
+
+impl TemplateVariableObjectSetting {
+    pub fn validate_value(&self, value: &serde_json::Map<String, Value>) -> Option<String> {
+        if self.required.is_some_and(|v| v) && value.is_empty() {
+            return Some("At least 1 entry is required".into());
+        }
+
+        None
+    }
+}
+
+impl TemplateVariableStringSetting {
+    pub fn validate_value(&self, value: &str) -> Option<String> {
+        if value.is_empty() {
+            return if self.required.is_some_and(|v| v) {
+                Some("A value is required".into())
+            } else {
+                None
+            };
+        }
+
+        let length = value.chars().count();
+
+        if let Some(min) = self.min
+            && length < min
+        {
+            return Some(format!("Must be at least {min} characters"));
+        }
+
+        if let Some(max) = self.max
+            && length > max
+        {
+            return Some(format!("Must be at most {max} characters"));
+        }
+
+        if let Some(pattern) = &self.pattern
+            && !pattern.is_match(value)
+        {
+            return Some(format!("Must match the pattern {}", pattern.as_str()));
+        }
+
+        None
+    }
+}
 
 config_struct!(
     #[derive(Config)]
@@ -148,6 +251,11 @@ config_struct!(
         /// List of acceptable values for this variable.
         #[setting(nested)]
         pub values: Vec<TemplateVariableEnumValue>,
+
+        /// Only prompt for the variable when other variables have the
+        /// configured values, otherwise the default value is used.
+        #[serde(default, skip_serializing_if = "FxHashMap::is_empty")]
+        pub when: FxHashMap<String, Value>,
     }
 );
 
@@ -175,6 +283,33 @@ impl TemplateVariableEnumSetting {
     pub fn is_multiple(&self) -> bool {
         self.multiple.is_some_and(|v| v)
     }
+
+    pub fn validate_value(&self, value: &Value) -> Option<String> {
+        let values = self.get_values();
+        let is_valid = |item: &Value| {
+            item.as_str()
+                .is_some_and(|item| values.iter().any(|v| *v == item))
+        };
+
+        let valid = match value {
+            Value::Array(list) if self.is_multiple() => list.iter().all(is_valid),
+            _ if self.is_multiple() => false,
+            other => is_valid(other),
+        };
+
+        if valid {
+            None
+        } else {
+            Some(format!(
+                "Must be one of {}",
+                values
+                    .iter()
+                    .map(|v| v.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        }
+    }
 }
 
 config_enum!(
@@ -251,6 +386,37 @@ impl TemplateVariable {
         }
     }
 
+    pub fn get_when(&self) -> &FxHashMap<String, Value> {
+        match self {
+            Self::Array(cfg) => &cfg.when,
+            Self::Boolean(cfg) => &cfg.when,
+            Self::Enum(cfg) => &cfg.when,
+            Self::Number(cfg) => &cfg.when,
+            Self::Object(cfg) => &cfg.when,
+            Self::String(cfg) => &cfg.when,
+        }
+    }
+
+    /// Return true if all `when` conditions are met, using the provided
+    /// function to look up the current value of other variables. A condition
+    /// is met when the value is equal, when a list value contains it,
+    /// or when the condition is a list that contains the value.
+    pub fn is_when_met<'a>(&self, get_value: impl Fn(&str) -> Option<&'a Value>) -> bool {
+        self.get_when().iter().all(|(name, expected)| {
+            let Some(actual) = get_value(name) else {
+                return false;
+            };
+
+            actual == expected
+                || actual
+                    .as_array()
+                    .is_some_and(|list| list.contains(expected))
+                || expected
+                    .as_array()
+                    .is_some_and(|list| list.contains(actual))
+        })
+    }
+
     pub fn is_required(&self) -> bool {
         match self {
             Self::Array(cfg) => cfg.required,
@@ -262,8 +428,44 @@ impl TemplateVariable {
         }
         .is_some_and(|v| v)
     }
+
+    /// Validate a value against the type and constraints of the variable,
+    /// and return an error message if invalid.
+    pub fn validate_value(&self, value: &Value) -> Option<String> {
+        match self {
+            Self::Array(cfg) => match value {
+                Value::Array(list) => cfg.validate_value(list),
+                _ => Some("Must be an array".into()),
+            },
+            Self::Boolean(_) => (!value.is_boolean()).then(|| "Must be a boolean".into()),
+            Self::Enum(cfg) => cfg.validate_value(value),
+            Self::Number(cfg) => match value.as_i64() {
+                Some(number) => cfg.validate_value(number as isize),
+                None => Some("Must be a number".into()),
+            },
+            Self::Object(cfg) => match value {
+                Value::Object(map) => cfg.validate_value(map),
+                _ => Some("Must be an object".into()),
+            },
+            Self::String(cfg) => match value {
+                Value::String(string) => cfg.validate_value(string),
+                _ => Some("Must be a string".into()),
+            },
+        }
+    }
 }
 
+config_struct!(
+    /// Configures commands to run during the generation lifecycle.
+    #[derive(Config)]
+    pub struct TemplateHooksConfig {
+        /// A list of commands to run in the destination, after all files
+        /// have been generated. Commands support variable interpolation.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub post_generate: Vec<String>,
+    }
+);
+
 config_struct!(
     /// Configures a template and its files to be scaffolded.
     /// Docs: https://moonrepo.dev/docs/config/template
@@ -285,6 +487,10 @@ config_struct!(
         #[serde(default, skip_serializing_if = "OneOrMany::is_empty")]
         pub extends: OneOrMany<Id>,
 
+        /// Commands to run during the generation lifecycle.
+        #[setting(nested)]
+        pub hooks: TemplateHooksConfig,
+
         /// Overrides the identifier of the template, instead of using the folder name.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         pub id: Option<Id>,
@@ -1,3 +1,4 @@
+use crate::task_options_config::validate_byte_size;
 use crate::{config_enum, config_struct, is_false};
 use moon_common::Id;
 use schematic::{Config, env};
@@ -37,6 +38,24 @@ impl From<bool> for PipelineActionSwitch {
     }
 }
 
+config_struct!(
+    /// Configures the resource capacity that tasks can reserve while running.
+    #[derive(Config)]
+    pub struct PipelineResourcesConfig {
+        /// The number of CPU cores available to tasks. When not defined,
+        /// the CPU quota of the current cgroup is used, otherwise the
+        /// number of logical cores.
+        #[setting(env = "MOON_PIPELINE_RESOURCES_CPU")]
+        pub cpu: Option<u32>,
+
+        /// The amount of memory available to tasks, for example "16gb".
+        /// When not defined, the memory limit of the current cgroup is used,
+        /// otherwise the total system memory.
+        #[setting(env = "MOON_PIPELINE_RESOURCES_MEMORY", validate = validate_byte_size)]
+        pub memory: Option<String>,
+    }
+);
+
 config_struct!(
     /// Configures aspects of the action pipeline.
     #[derive(Config)]
@@ -50,6 +69,12 @@ config_struct!(
         #[setting(default = "7 days", env = "MOON_PIPELINE_CACHE_LIFETIME")]
         pub cache_lifetime: String,
 
+        /// The lifetime of task run history, before old daily segments
+        /// are pruned from the cache directory.
+        /// @since 2.6.0
+        #[setting(default = "30 days", env = "MOON_PIPELINE_HISTORY_RETENTION")]
+        pub history_retention: String,
+
         /// Automatically inherit color settings for all tasks being ran.
         #[setting(default = true)]
         pub inherit_colors_for_piped_tasks: bool,
@@ -70,6 +95,12 @@ config_struct!(
         #[serde(default, skip_serializing_if = "is_false")]
         pub log_running_command: bool,
 
+        /// The resource capacity that tasks with `options.resources` reserve
+        /// from, before they are ran.
+        /// @since 2.6.0
+        #[setting(nested)]
+        pub resources: PipelineResourcesConfig,
+
         /// Run the `SyncProject` actions in the pipeline for each owning project
         /// of a running task.
         /// @since 1.34.0
@@ -1,5 +1,6 @@
 use crate::shapes::FilePath;
 use crate::{config_struct, config_unit_enum, is_false};
+use moon_common::Id;
 use rustc_hash::FxHashMap;
 use schematic::{Config, ConfigEnum, ValidateError, ValidateResult, env};
 
@@ -16,6 +17,19 @@ fn path_is_required<D, C>(
     Ok(())
 }
 
+fn bucket_is_required<D, C>(
+    value: &str,
+    _data: &D,
+    _context: &C,
+    _finalize: bool,
+) -> ValidateResult {
+    if value.is_empty() {
+        return Err(ValidateError::new("bucket must not be empty"));
+    }
+
+    Ok(())
+}
+
 config_unit_enum!(
     /// The API format of the remote service.
     /// @since 1.32.0
@@ -26,6 +40,9 @@ config_unit_enum!(
         Grpc,
         /// HTTP(S) endpoints.
         Http,
+        /// S3-compatible object storage.
+        /// @since 2.6.0
+        S3,
     }
 );
 
@@ -64,6 +81,35 @@ impl RemoteCompression {
     }
 }
 
+config_struct!(
+    /// A rule that determines whether task outputs may be uploaded to the
+    /// remote cache. All conditions that are configured must match.
+    /// @since 2.6.0
+    #[derive(Config)]
+    pub struct RemoteCacheWriteRule {
+        /// Allow (`true`) or deny (`false`) uploads when the rule matches.
+        #[setting(default = true)]
+        pub allow: bool,
+
+        /// Match when the current branch matches one of these glob patterns.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub branches: Vec<String>,
+
+        /// Match when running in a CI environment (`true`) or locally (`false`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub ci: Option<bool>,
+
+        /// Match when on the default branch (`true`) or any other branch (`false`).
+        /// Pull requests never match the default branch.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub default_branch: Option<bool>,
+
+        /// Match when the task has at least one of these tags.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub tags: Vec<Id>,
+    }
+);
+
 config_struct!(
     /// Configures the action cache (AC) and content-addressable cache (CAS).
     /// @since 1.30.0
@@ -92,6 +138,43 @@ config_struct!(
         #[setting(parse_env = env::parse_bool)]
         #[serde(default, skip_serializing_if = "is_false")]
         pub verify_integrity: bool,
+
+        /// A list of rules that determine whether task outputs may be uploaded
+        /// to the remote cache. The first matching rule is applied, and if no
+        /// rules match, uploads are denied. When empty, uploads are allowed.
+        /// @since 2.6.0
+        #[setting(nested)]
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub write_policy: Vec<RemoteCacheWriteRule>,
+    }
+);
+
+config_struct!(
+    /// Configures an S3-compatible bucket to store blobs in.
+    /// Credentials are read from the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`,
+    /// and `AWS_SESSION_TOKEN` environment variables.
+    /// @since 2.6.0
+    #[derive(Config)]
+    #[config(env_prefix = "MOON_REMOTE_S3_")]
+    pub struct RemoteS3Config {
+        /// The name of the bucket.
+        #[setting(validate = bucket_is_required)]
+        pub bucket: String,
+
+        /// Use path-style requests (`host/bucket/key`) instead of
+        /// virtual-hosted-style requests (`bucket.host/key`). Required
+        /// by most self-hosted S3-compatible services, like MinIO.
+        #[setting(parse_env = env::parse_bool)]
+        #[serde(default, skip_serializing_if = "is_false")]
+        pub path_style: bool,
+
+        /// A prefix to prepend to all object keys, for sharing a bucket.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub prefix: Option<String>,
+
+        /// The region of the bucket, used for signing requests.
+        #[setting(default = "us-east-1")]
+        pub region: String,
     }
 );
 
@@ -172,7 +255,8 @@ config_struct!(
         pub cache: RemoteCacheConfig,
 
         /// The remote host to connect and send requests to.
-        /// Supports gRPC protocols.
+        /// Supports gRPC protocols. When using the S3 API, this is
+        /// the endpoint of the S3-compatible service.
         #[setting(env = "MOON_REMOTE_HOST")]
         #[serde(default, skip_serializing_if = "Option::is_none")]
         pub host: Option<String>,
@@ -183,6 +267,12 @@ config_struct!(
         #[serde(default, skip_serializing_if = "Option::is_none")]
         pub mtls: Option<RemoteMtlsConfig>,
 
+        /// Configures the bucket when using the S3 API.
+        /// @since 2.6.0
+        #[setting(nested)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub s3: Option<RemoteS3Config>,
+
         /// Connect to the host using server-only authentication with TLS.
         #[setting(nested)]
         #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -201,6 +291,7 @@ impl RemoteConfig {
 
     pub fn is_enabled(&self) -> bool {
         self.host.as_ref().is_some_and(|host| !host.is_empty())
+            || (self.api == RemoteApi::S3 && self.s3.is_some())
     }
 
     pub fn is_localhost(&self) -> bool {
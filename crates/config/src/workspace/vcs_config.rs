@@ -8,6 +8,9 @@ config_unit_enum!(
     pub enum VcsClient {
         #[default]
         Git,
+        Jujutsu,
+        Mercurial,
+        Sapling,
     }
 );
 
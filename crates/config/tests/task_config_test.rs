@@ -1509,6 +1509,159 @@ options:
                     r"
 options:
   os: unknown
+",
+                    load_config_from_code,
+                );
+            }
+        }
+
+        mod resources {
+            use super::*;
+            use moon_config::TaskOptionResourcesConfig;
+
+            #[test]
+            fn can_set_cpu_and_memory() {
+                let config = test_parse_config(
+                    r"
+options:
+  resources:
+    cpu: 4
+    memory: 8GB
+",
+                    load_config_from_code,
+                );
+
+                assert_eq!(
+                    config.options.resources,
+                    Some(TaskOptionResourcesConfig {
+                        cpu: Some(4),
+                        memory: Some("8GB".into()),
+                    })
+                );
+            }
+
+            #[test]
+            #[should_panic(expected = "invalid size")]
+            fn errors_for_invalid_memory() {
+                test_parse_config(
+                    r"
+options:
+  resources:
+    memory: lots
+",
+                    load_config_from_code,
+                );
+            }
+        }
+
+        mod semaphore {
+            use super::*;
+            use moon_config::TaskOptionSemaphoreConfig;
+
+            #[test]
+            fn defaults_to_a_single_local_permit() {
+                let config = test_parse_config(
+                    r"
+options:
+  semaphore:
+    name: db
+",
+                    load_config_from_code,
+                );
+
+                assert_eq!(
+                    config.options.semaphore,
+                    Some(TaskOptionSemaphoreConfig {
+                        global: false,
+                        name: "db".into(),
+                        permits: 1,
+                    })
+                );
+            }
+
+            #[test]
+            fn can_set_permits_and_global() {
+                let config = test_parse_config(
+                    r"
+options:
+  semaphore:
+    name: emulators
+    permits: 3
+    global: true
+",
+                    load_config_from_code,
+                );
+
+                assert_eq!(
+                    config.options.semaphore,
+                    Some(TaskOptionSemaphoreConfig {
+                        global: true,
+                        name: "emulators".into(),
+                        permits: 3,
+                    })
+                );
+            }
+
+            #[test]
+            #[should_panic(expected = "at least 1 permit is required")]
+            fn errors_for_zero_permits() {
+                test_parse_config(
+                    r"
+options:
+  semaphore:
+    name: db
+    permits: 0
+",
+                    load_config_from_code,
+                );
+            }
+
+            #[test]
+            #[should_panic(expected = "must not be empty")]
+            fn errors_for_empty_name() {
+                test_parse_config(
+                    r"
+options:
+  semaphore:
+    name: ''
+",
+                    load_config_from_code,
+                );
+            }
+        }
+
+        mod test_reports {
+            use super::*;
+
+            #[test]
+            fn supports_files_and_globs() {
+                let config = test_parse_config(
+                    r"
+options:
+  testReports:
+    - 'reports/junit.xml'
+    - '/coverage/*.tap'
+",
+                    load_config_from_code,
+                );
+
+                assert_eq!(
+                    config.options.test_reports,
+                    Some(vec![
+                        Output::parse("reports/junit.xml").unwrap(),
+                        Output::parse("/coverage/*.tap").unwrap(),
+                    ])
+                );
+            }
+
+            #[test]
+            #[should_panic(expected = "Failed to deserialize the untagged enum OutputShape")]
+            fn errors_for_invalid_type() {
+                test_parse_config(
+                    r"
+options:
+  testReports:
+    - 123
 ",
                     load_config_from_code,
                 );
@@ -16,7 +16,7 @@ mod template_config {
 
     #[test]
     #[should_panic(
-        expected = "unknown field `unknown`, expected one of `$schema`, `description`, `destination`, `extends`, `id`, `title`, `variables`"
+        expected = "unknown field `unknown`, expected one of `$schema`, `description`, `destination`, `extends`, `hooks`, `id`, `title`, `variables`"
     )]
     fn error_unknown_field() {
         test_load_config("template.yml", "unknown: 123", |path| {
@@ -77,6 +77,34 @@ mod template_config {
         }
     }
 
+    mod hooks {
+        use super::*;
+
+        #[test]
+        fn loads_post_generate() {
+            let config = test_load_config(
+                "template.yml",
+                "title: title\ndescription: description\nhooks:\n  postGenerate: ['pnpm install', 'moon sync projects']",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.hooks.post_generate,
+                vec!["pnpm install".to_owned(), "moon sync projects".to_owned()]
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "invalid type: string \"pnpm install\", expected a sequence")]
+        fn invalid_post_generate() {
+            test_load_config(
+                "template.yml",
+                "title: title\ndescription: description\nhooks:\n  postGenerate: 'pnpm install'",
+                load_config_from_root,
+            );
+        }
+    }
+
     mod variables {
         use super::*;
 
@@ -122,7 +150,8 @@ variables:
                     internal: false,
                     order: None,
                     prompt: Some("prompt".into()),
-                    required: Some(true)
+                    required: Some(true),
+                    ..Default::default()
                 })
             );
         }
@@ -168,7 +197,8 @@ variables:
                     internal: false,
                     order: None,
                     prompt: Some("prompt".into()),
-                    required: Some(true)
+                    required: Some(true),
+                    ..Default::default()
                 })
             );
         }
@@ -214,7 +244,8 @@ variables:
                     internal: false,
                     order: None,
                     prompt: Some("prompt".into()),
-                    required: Some(false)
+                    required: Some(false),
+                    ..Default::default()
                 })
             );
         }
@@ -265,7 +296,8 @@ variables:
                     internal: false,
                     order: None,
                     prompt: Some("prompt".into()),
-                    required: Some(true)
+                    required: Some(true),
+                    ..Default::default()
                 })
             );
         }
@@ -309,11 +341,118 @@ variables:
                     internal: false,
                     order: None,
                     prompt: None,
-                    required: None
+                    required: None,
+                    ..Default::default()
                 })
             );
         }
 
+        #[test]
+        fn loads_string_constraints() {
+            let config = test_load_config(
+                "template.yml",
+                r"
+title: title
+description: description
+variables:
+  str:
+    type: string
+    default: abc
+    min: 2
+    max: 10
+    pattern: '^[a-z]+$'
+",
+                load_config_from_root,
+            );
+
+            let TemplateVariable::String(cfg) = config.variables.get("str").unwrap() else {
+                panic!();
+            };
+
+            assert_eq!(cfg.min, Some(2));
+            assert_eq!(cfg.max, Some(10));
+            assert_eq!(cfg.pattern.as_ref().unwrap().as_str(), "^[a-z]+$");
+        }
+
+        #[test]
+        #[should_panic(expected = "regex parse error")]
+        fn invalid_string_pattern() {
+            test_load_config(
+                "template.yml",
+                r"
+title: title
+description: description
+variables:
+  str:
+    type: string
+    default: abc
+    pattern: '[a-z'
+",
+                load_config_from_root,
+            );
+        }
+
+        #[test]
+        fn loads_number_constraints() {
+            let config = test_load_config(
+                "template.yml",
+                r"
+title: title
+description: description
+variables:
+  num:
+    type: number
+    default: 5
+    min: -10
+    max: 10
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                *config.variables.get("num").unwrap(),
+                TemplateVariable::Number(TemplateVariableNumberSetting {
+                    default: 5,
+                    max: Some(10),
+                    min: Some(-10),
+                    ..Default::default()
+                })
+            );
+        }
+
+        #[test]
+        fn loads_when() {
+            let config = test_load_config(
+                "template.yml",
+                r"
+title: title
+description: description
+variables:
+  bool:
+    type: boolean
+    default: false
+    when:
+      type: library
+      features: [a, b]
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(
+                config.variables.get("bool").unwrap().get_when(),
+                &FxHashMap::from_iter([
+                    ("type".into(), JsonValue::String("library".into())),
+                    (
+                        "features".into(),
+                        JsonValue::Array(vec![
+                            JsonValue::String("a".into()),
+                            JsonValue::String("b".into())
+                        ])
+                    ),
+                ])
+            );
+        }
+
         #[test]
         #[should_panic(expected = "invalid type: integer `123`, expected a string")]
         fn invalid_string() {
@@ -368,6 +507,7 @@ variables:
                             value: "c".into()
                         })
                     ],
+                    ..Default::default()
                 })
             );
         }
@@ -412,6 +552,7 @@ variables:
                             value: "c".into()
                         })
                     ],
+                    ..Default::default()
                 })
             );
         }
@@ -492,4 +633,167 @@ variables:
     fn supports_toml() {
         load_template_config_in_format("toml");
     }
+
+    mod validate_value {
+        use super::*;
+
+        #[test]
+        fn string() {
+            let var = TemplateVariable::String(TemplateVariableStringSetting {
+                min: Some(2),
+                max: Some(4),
+                pattern: Some(schematic::RegexSetting::new("^[a-z]+$").unwrap()),
+                required: Some(true),
+                ..Default::default()
+            });
+
+            assert_eq!(var.validate_value(&JsonValue::String("abc".into())), None);
+            assert_eq!(
+                var.validate_value(&JsonValue::String("".into())),
+                Some("A value is required".into())
+            );
+            assert_eq!(
+                var.validate_value(&JsonValue::String("a".into())),
+                Some("Must be at least 2 characters".into())
+            );
+            assert_eq!(
+                var.validate_value(&JsonValue::String("abcde".into())),
+                Some("Must be at most 4 characters".into())
+            );
+            assert_eq!(
+                var.validate_value(&JsonValue::String("AB".into())),
+                Some("Must match the pattern ^[a-z]+$".into())
+            );
+            assert_eq!(
+                var.validate_value(&JsonValue::Bool(true)),
+                Some("Must be a string".into())
+            );
+        }
+
+        #[test]
+        fn number() {
+            let var = TemplateVariable::Number(TemplateVariableNumberSetting {
+                min: Some(-1),
+                max: Some(10),
+                ..Default::default()
+            });
+
+            assert_eq!(var.validate_value(&JsonValue::from(10)), None);
+            assert_eq!(
+                var.validate_value(&JsonValue::from(-2)),
+                Some("Must be greater than or equal to -1".into())
+            );
+            assert_eq!(
+                var.validate_value(&JsonValue::from(11)),
+                Some("Must be less than or equal to 10".into())
+            );
+            assert_eq!(
+                var.validate_value(&JsonValue::from(1.5)),
+                Some("Must be a number".into())
+            );
+        }
+
+        #[test]
+        fn collections() {
+            let array = TemplateVariable::Array(TemplateVariableArraySetting {
+                required: Some(true),
+                ..Default::default()
+            });
+
+            assert_eq!(array.validate_value(&JsonValue::from(vec![1])), None);
+            assert_eq!(
+                array.validate_value(&JsonValue::Array(vec![])),
+                Some("At least 1 item is required".into())
+            );
+            assert_eq!(
+                array.validate_value(&JsonValue::Object(Default::default())),
+                Some("Must be an array".into())
+            );
+
+            let object = TemplateVariable::Object(TemplateVariableObjectSetting::default());
+
+            assert_eq!(
+                object.validate_value(&JsonValue::Object(Default::default())),
+                None
+            );
+            assert_eq!(
+                object.validate_value(&JsonValue::Array(vec![])),
+                Some("Must be an object".into())
+            );
+        }
+
+        #[test]
+        fn enums() {
+            let mut setting = TemplateVariableEnumSetting {
+                values: vec![
+                    TemplateVariableEnumValue::String("a".into()),
+                    TemplateVariableEnumValue::String("b".into()),
+                ],
+                ..Default::default()
+            };
+
+            let var = TemplateVariable::Enum(setting.clone());
+
+            assert_eq!(var.validate_value(&JsonValue::from("a")), None);
+            assert_eq!(
+                var.validate_value(&JsonValue::from("c")),
+                Some("Must be one of a, b".into())
+            );
+
+            setting.multiple = Some(true);
+
+            let var = TemplateVariable::Enum(setting);
+
+            assert_eq!(var.validate_value(&JsonValue::from(vec!["a", "b"])), None);
+            assert_eq!(
+                var.validate_value(&JsonValue::from("a")),
+                Some("Must be one of a, b".into())
+            );
+        }
+    }
+
+    mod is_when_met {
+        use super::*;
+
+        fn create_var(when: &[(&str, JsonValue)]) -> TemplateVariable {
+            TemplateVariable::Boolean(TemplateVariableBoolSetting {
+                when: FxHashMap::from_iter(
+                    when.iter()
+                        .map(|(name, value)| (name.to_string(), value.clone())),
+                ),
+                ..Default::default()
+            })
+        }
+
+        #[test]
+        fn met_without_conditions() {
+            assert!(create_var(&[]).is_when_met(|_| None));
+        }
+
+        #[test]
+        fn matches_equal_values() {
+            let var = create_var(&[("type", JsonValue::from("library"))]);
+            let library = JsonValue::from("library");
+            let app = JsonValue::from("app");
+
+            assert!(var.is_when_met(|_| Some(&library)));
+            assert!(!var.is_when_met(|_| Some(&app)));
+            assert!(!var.is_when_met(|_| None));
+        }
+
+        #[test]
+        fn matches_list_values() {
+            let var = create_var(&[("type", JsonValue::from(vec!["app", "library"]))]);
+            let library = JsonValue::from("library");
+            let other = JsonValue::from("other");
+
+            assert!(var.is_when_met(|_| Some(&library)));
+            assert!(!var.is_when_met(|_| Some(&other)));
+
+            let var = create_var(&[("features", JsonValue::from("docs"))]);
+            let features = JsonValue::from(vec!["docs", "tests"]);
+
+            assert!(var.is_when_met(|_| Some(&features)));
+        }
+    }
 }
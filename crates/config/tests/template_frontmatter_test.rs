@@ -8,7 +8,7 @@ mod template_frontmatter {
 
     #[test]
     #[should_panic(
-        expected = "unknown field `title`, expected one of `$schema`, `force`, `to`, `skip`"
+        expected = "unknown field `title`, expected one of `$schema`, `force`, `if`, `to`, `skip`"
     )]
     fn error_unknown_field() {
         test_parse_config("title: test", |code| {
@@ -25,6 +25,7 @@ mod template_frontmatter {
         assert!(!config.force);
         assert!(!config.skip);
         assert_eq!(config.to, None);
+        assert_eq!(config.if_condition, None);
     }
 
     #[test]
@@ -69,4 +70,21 @@ mod template_frontmatter {
             ConfigLoader::parse_template_frontmatter_config(code)
         });
     }
+
+    #[test]
+    fn can_set_if() {
+        let config = test_parse_config("if: type == 'library'", |code| {
+            ConfigLoader::parse_template_frontmatter_config(code)
+        });
+
+        assert_eq!(config.if_condition, Some("type == 'library'".into()));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid type: boolean `true`, expected a string")]
+    fn invalid_if() {
+        test_parse_config("if: true", |code| {
+            ConfigLoader::parse_template_frontmatter_config(code)
+        });
+    }
 }
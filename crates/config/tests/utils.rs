@@ -314,12 +314,16 @@ pub fn load_tasks_config_in_format(format: &str) {
                 output_style: Some(TaskOutputStyle::Stream),
                 persistent: Some(true),
                 priority: None,
+                resources: None,
                 retry_count: Some(3),
                 run_deps_in_parallel: Some(false),
                 run_in_ci: Some(TaskOptionRunInCI::Enabled(true)),
                 run_in_sync_phase: None,
                 run_from_workspace_root: Some(false),
+                sandbox: None,
+                semaphore: None,
                 shell: Some(false),
+                test_reports: None,
                 timeout: Some(60),
                 unix_shell: Some(TaskUnixShell::Zsh),
                 windows_shell: Some(TaskWindowsShell::Pwsh)
@@ -432,7 +436,8 @@ pub fn load_template_config_in_format(format: &str) {
                         internal: false,
                         order: None,
                         prompt: Some("Why?".into()),
-                        required: Some(true)
+                        required: Some(true),
+                        ..Default::default()
                     })
                 ),
                 (
@@ -450,7 +455,8 @@ pub fn load_template_config_in_format(format: &str) {
                                 value: "b".into()
                             }),
                             TemplateVariableEnumValue::String("c".into())
-                        ]
+                        ],
+                        ..Default::default()
                     })
                 ),
                 (
@@ -460,7 +466,8 @@ pub fn load_template_config_in_format(format: &str) {
                         internal: false,
                         order: Some(1),
                         prompt: Some("Why?".into()),
-                        required: None
+                        required: None,
+                        ..Default::default()
                     })
                 ),
                 (
@@ -470,7 +477,8 @@ pub fn load_template_config_in_format(format: &str) {
                         internal: true,
                         order: None,
                         prompt: Some("Why?".into()),
-                        required: None
+                        required: None,
+                        ..Default::default()
                     })
                 ),
             ]),
@@ -3,8 +3,8 @@ mod utils;
 use httpmock::prelude::*;
 use moon_common::Id;
 use moon_config::{
-    FilePath, GlobPath, TemplateLocator, VcsProvider, WorkspaceConfig, WorkspaceProjectGlobFormat,
-    WorkspaceProjects,
+    FilePath, GlobPath, TemplateLocator, VcsClient, VcsProvider, WorkspaceConfig,
+    WorkspaceProjectGlobFormat, WorkspaceProjects,
 };
 use moon_config_loader::ConfigLoader;
 use rustc_hash::FxHashMap;
@@ -734,6 +734,7 @@ notifier:
             let config = test_load_config(FILENAME, "pipeline: {}", load_config_from_root);
 
             assert_eq!(config.pipeline.cache_lifetime, "7 days");
+            assert_eq!(config.pipeline.history_retention, "30 days");
             assert!(config.pipeline.inherit_colors_for_piped_tasks);
         }
 
@@ -752,6 +753,37 @@ pipeline:
             assert_eq!(config.pipeline.cache_lifetime, "10 hours");
             assert!(!config.pipeline.inherit_colors_for_piped_tasks);
         }
+
+        #[test]
+        fn can_set_resources() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+pipeline:
+  resources:
+    cpu: 8
+    memory: 16gib
+",
+                load_config_from_root,
+            );
+
+            assert_eq!(config.pipeline.resources.cpu, Some(8));
+            assert_eq!(config.pipeline.resources.memory.as_deref(), Some("16gib"));
+        }
+
+        #[test]
+        #[should_panic(expected = "invalid size")]
+        fn errors_for_invalid_resources_memory() {
+            test_load_config(
+                FILENAME,
+                r"
+pipeline:
+  resources:
+    memory: 16 bananas
+",
+                load_config_from_root,
+            );
+        }
     }
 
     mod vcs {
@@ -785,18 +817,38 @@ vcs:
         }
 
         #[test]
-        #[should_panic(expected = "unknown variant `mercurial`, expected `git`")]
+        #[should_panic(
+            expected = "unknown variant `svn`, expected one of `git`, `jujutsu`, `mercurial`, `sapling`"
+        )]
         fn errors_on_invalid_client() {
             test_load_config(
                 FILENAME,
                 r"
 vcs:
-  client: mercurial
+  client: svn
 ",
                 load_config_from_root,
             );
         }
 
+        #[test]
+        fn parses_clients() {
+            for (value, expected) in [
+                ("git", VcsClient::Git),
+                ("jujutsu", VcsClient::Jujutsu),
+                ("mercurial", VcsClient::Mercurial),
+                ("sapling", VcsClient::Sapling),
+            ] {
+                let config = test_load_config(
+                    FILENAME,
+                    &format!("vcs:\n  client: {value}"),
+                    load_config_from_root,
+                );
+
+                assert_eq!(config.vcs.client, expected);
+            }
+        }
+
         #[test]
         fn parses_providers() {
             for (value, expected) in [
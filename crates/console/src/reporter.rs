@@ -1,5 +1,6 @@
 use moon_action::{
     Action, ActionNode, ActionPipelineStatus, ActionStatus, Operation, OperationList,
+    TaskCheckResult, TaskTestCase, TaskTestResults,
 };
 use moon_common::{color, is_test_env};
 use moon_config::TaskOutputStyle;
@@ -32,6 +33,7 @@ impl Level {
 #[derive(Debug, Default)]
 pub struct PipelineReportItem {
     pub duration: Option<Duration>,
+    pub remote_cache: Vec<RemoteCacheReportItem>,
     // 1 - Summary
     // 2 - + Stats
     // 3 - + Review
@@ -39,6 +41,18 @@ pub struct PipelineReportItem {
     pub status: ActionPipelineStatus,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct RemoteCacheReportItem {
+    pub backend: String,
+    pub bytes_downloaded: u64,
+    pub bytes_uploaded: u64,
+    pub errors: u64,
+    pub hits: u64,
+    /// Average latency per request.
+    pub latency: Duration,
+    pub misses: u64,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct TaskReportItem {
     pub attempt_current: u8,
@@ -68,6 +82,24 @@ pub enum Checkpoint {
     Setup,
 }
 
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{size:.1} {}", UNITS[unit])
+}
+
 fn bold(message: &str) -> String {
     if no_color() {
         message.to_owned()
@@ -189,6 +221,68 @@ impl MoonReporter {
         color::muted(format!("({})", comments.join(", ")))
     }
 
+    pub fn format_check_result(&self, check: &TaskCheckResult) -> String {
+        let outcome = if check.outcome.is_failure() {
+            color::failure(check.outcome.label())
+        } else {
+            color::muted_light(check.outcome.label())
+        };
+
+        let mut comments = vec![];
+
+        if let Some(code) = check.exit_code {
+            comments.push(format!("exit {code}"));
+        }
+
+        if check.timed_out {
+            comments.push("timed out".into());
+        }
+
+        if let Some(fingerprint) = &check.fingerprint {
+            comments.push(self.get_short_hash(fingerprint));
+        }
+
+        format!(
+            "     {} {} {}",
+            outcome,
+            color::shell(&check.script),
+            self.format_comments(comments),
+        )
+        .trim_end()
+        .to_owned()
+    }
+
+    pub fn format_test_counts(&self, tests: &TaskTestResults) -> String {
+        let mut counts = vec![format!("{} passed", tests.passed)];
+
+        if tests.failed > 0 {
+            counts.push(format!("{} failed", tests.failed));
+        }
+
+        if tests.skipped > 0 {
+            counts.push(format!("{} skipped", tests.skipped));
+        }
+
+        format!("{} tests: {}", tests.get_total(), counts.join(", "))
+    }
+
+    pub fn format_failed_test(&self, test: &TaskTestCase) -> String {
+        let mut comments = vec![];
+
+        if let Some(message) = &test.message {
+            comments.push(message.to_owned());
+        }
+
+        format!(
+            "     {} {} {}",
+            color::failure("failed"),
+            color::label(test.get_label()),
+            self.format_comments(comments),
+        )
+        .trim_end()
+        .to_owned()
+    }
+
     pub fn format_entry_key<K: AsRef<str>>(&self, key: K) -> String {
         color::muted_light(format!("{}:", key.as_ref()))
     }
@@ -484,21 +578,65 @@ impl MoonReporter {
             elapsed_time = format!("{} {}", elapsed_time, label_to_the_moon());
         }
 
-        if item
+        let label_width = if item
             .summary
             .as_ref()
             .is_some_and(|level| level.is(Level::Two))
         {
             self.print_entry("Actions", counts_message)?;
             self.print_entry("   Time", elapsed_time)?;
+            7
         } else {
             self.print_entry("Tasks", counts_message)?;
             self.print_entry(" Time", elapsed_time)?;
+            5
+        };
+
+        for remote in &item.remote_cache {
+            self.print_entry(
+                format!("{:>label_width$}", "Cache"),
+                self.format_remote_cache_stats(remote, item.remote_cache.len() > 1),
+            )?;
         }
 
         Ok(())
     }
 
+    fn format_remote_cache_stats(
+        &self,
+        item: &RemoteCacheReportItem,
+        with_backend: bool,
+    ) -> String {
+        let mut parts = vec![
+            color::success(format!("{} hits", item.hits)),
+            color::muted_light(format!("{} misses", item.misses)),
+        ];
+
+        if item.errors > 0 {
+            parts.push(color::failure(format!("{} errors", item.errors)));
+        }
+
+        parts.push(color::muted_light(format!(
+            "{} down, {} up",
+            format_bytes(item.bytes_downloaded),
+            format_bytes(item.bytes_uploaded)
+        )));
+
+        let mut message = parts.join(&color::muted(", "));
+
+        message.push(' ');
+        message.push_str(&color::muted_light(format!(
+            "(avg {})",
+            time::elapsed(item.latency)
+        )));
+
+        if with_backend {
+            message = format!("{} {message}", color::id(&item.backend));
+        }
+
+        message
+    }
+
     fn print_pipeline_summary(&self, actions: &[Action]) -> miette::Result<()> {
         let mut items = vec![];
         let mut failed_items = vec![];
@@ -532,13 +670,29 @@ impl MoonReporter {
                 comments.push(self.get_short_hash(hash));
             }
 
-            let message = format!(
+            if let Some(tests) = &action.tests {
+                comments.push(self.format_test_counts(tests));
+            }
+
+            let mut message = format!(
                 "{} {} {}",
                 status,
                 action.label,
                 self.format_comments(comments),
             );
 
+            for check in &action.checks {
+                message.push('\n');
+                message.push_str(&self.format_check_result(check));
+            }
+
+            if let Some(tests) = &action.tests {
+                for test in tests.get_failed_tests() {
+                    message.push('\n');
+                    message.push_str(&self.format_failed_test(test));
+                }
+            }
+
             if action.has_failed() {
                 failed_items.push(message);
             } else {
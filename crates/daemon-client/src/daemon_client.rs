@@ -5,6 +5,7 @@ use moon_daemon_proto::{moon_daemon_client::MoonDaemonClient, *};
 use moon_daemon_utils::endpoint::*;
 use moon_hash::{Digest, InternalDigestExt};
 use moon_manifest::Manifest;
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::io::Error;
 use std::path::Path;
@@ -31,6 +32,15 @@ const CONTROL_DEADLINE: Duration = Duration::from_secs(5);
 /// run it inline before responding, so stay generous.
 const WORK_DEADLINE: Duration = Duration::from_secs(60);
 
+/// Deadline for query procedures (GetWorkspaceGraph, HashTaskInputs).
+/// When the daemon's cache is cold, these do the same work the client
+/// would otherwise do itself, so allow roughly as long as a local build.
+const QUERY_DEADLINE: Duration = Duration::from_secs(30);
+
+/// Maximum size of a response message. A serialized workspace graph for
+/// a large repository easily exceeds tonic's default of 4MB.
+const MAX_DECODING_MESSAGE_SIZE: usize = 256 * 1024 * 1024;
+
 /// Extra client-side grace on top of the `grpc-timeout` deadline, so the
 /// server's own deadline handling gets a chance to respond first with a
 /// more descriptive error.
@@ -133,7 +143,8 @@ impl DaemonClient {
         };
 
         Ok(Self {
-            inner: MoonDaemonClient::new(channel),
+            inner: MoonDaemonClient::new(channel)
+                .max_decoding_message_size(MAX_DECODING_MESSAGE_SIZE),
         })
     }
 
@@ -277,6 +288,63 @@ impl DaemonClient {
         Ok(response.into_inner())
     }
 
+    /// Fetch the daemon's cached workspace graph, serialized as the JSON
+    /// representation of the workspace builder. Returns `None` if the graph
+    /// has not been built yet, or if the daemon's file watcher has observed
+    /// a change since it was built, and it's being rebuilt.
+    #[instrument(skip(self))]
+    pub async fn get_workspace_graph(&mut self) -> miette::Result<Option<Vec<u8>>> {
+        let response = with_deadline(
+            "GetWorkspaceGraph",
+            QUERY_DEADLINE,
+            self.inner.get_workspace_graph(request_with_deadline(
+                GetWorkspaceGraphRequest {},
+                QUERY_DEADLINE,
+            )),
+        )
+        .await?
+        .into_inner();
+
+        Ok(
+            if response.ready && response.generation == response.watcher_generation {
+                Some(response.graph)
+            } else {
+                None
+            },
+        )
+    }
+
+    /// Fetch the resolved input files for a task, mapped to their content
+    /// hash. The digest must match the daemon's own copy of the task,
+    /// otherwise `None` is returned, as the daemon and client disagree on
+    /// what the task's inputs are.
+    #[instrument(skip(self))]
+    pub async fn hash_task_inputs(
+        &mut self,
+        task_target: String,
+        task_digest: String,
+    ) -> miette::Result<Option<BTreeMap<String, String>>> {
+        let response = with_deadline(
+            "HashTaskInputs",
+            QUERY_DEADLINE,
+            self.inner.hash_task_inputs(request_with_deadline(
+                HashTaskInputsRequest {
+                    task_target,
+                    task_digest,
+                },
+                QUERY_DEADLINE,
+            )),
+        )
+        .await?
+        .into_inner();
+
+        Ok(if response.hashed {
+            Some(response.files.into_iter().collect())
+        } else {
+            None
+        })
+    }
+
     #[instrument(skip(self))]
     pub async fn send_webhook(
         &mut self,
@@ -1,5 +1,6 @@
 use crate::daemon_server_error::DaemonServerError;
 use crate::daemon_watcher::{start_file_listener, start_file_watcher};
+use crate::task_inputs_cache::*;
 use moon_app_context::AppContext;
 use moon_cache_storage::{Manifest, ManifestSource, ManifestUnpacker, StorageOptions};
 use moon_common::path::WorkspaceRelativePathBuf;
@@ -14,8 +15,11 @@ use moon_file_watcher::{BoxedFileWatcher, FileEvent};
 use moon_hash::{Digest, InternalDigestExt};
 use moon_notifier::notify_webhook;
 use moon_process::ProcessRegistry;
+use moon_target::Target;
+use moon_task_hasher::TaskHasher;
 use moon_workspace_graph::WorkspaceGraph;
 use starbase_utils::fs;
+use std::collections::BTreeMap;
 use std::env;
 use std::future::Future;
 use std::path::PathBuf;
@@ -50,6 +54,21 @@ const TASK_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
 pub struct DaemonState {
     pub app_context: Arc<AppContext>,
     pub workspace_graph: Arc<WorkspaceGraph>,
+
+    /// The workspace builder that produced the graph, serialized for clients.
+    /// Is `None` until the graph has been built, and while it's being rebuilt.
+    pub workspace_graph_data: Option<Arc<Vec<u8>>>,
+
+    /// The watcher generation that the serialized graph was built at.
+    pub workspace_graph_generation: u64,
+
+    /// Incremented by the file watcher whenever a change invalidates the
+    /// workspace graph. Clients only load the serialized graph if it was
+    /// built at the current generation, instead of validating it themselves.
+    pub watcher_generation: u64,
+
+    /// Input file hashes for tasks, invalidated by file events.
+    pub task_inputs: TaskInputsCache,
 }
 
 pub type AtomicDaemonState = Arc<RwLock<DaemonState>>;
@@ -296,6 +315,147 @@ impl MoonDaemon for DaemonService {
         }))
     }
 
+    async fn get_workspace_graph(
+        &self,
+        _request: Request<GetWorkspaceGraphRequest>,
+    ) -> Result<Response<GetWorkspaceGraphResponse>, Status> {
+        self.track_activity("GetWorkspaceGraph");
+
+        let state = self.state.read().await;
+
+        Ok(Response::new(match &state.workspace_graph_data {
+            Some(graph) => GetWorkspaceGraphResponse {
+                ready: true,
+                graph: graph.to_vec(),
+                generation: state.workspace_graph_generation,
+                watcher_generation: state.watcher_generation,
+            },
+            None => GetWorkspaceGraphResponse {
+                watcher_generation: state.watcher_generation,
+                ..Default::default()
+            },
+        }))
+    }
+
+    async fn hash_task_inputs(
+        &self,
+        request: Request<HashTaskInputsRequest>,
+    ) -> Result<Response<HashTaskInputsResponse>, Status> {
+        self.track_activity("HashTaskInputs");
+
+        let request = request.into_inner();
+        let target = Target::parse_strict(&request.task_target)
+            .map_err(|error| Status::invalid_argument(error.to_string()))?;
+
+        let (app_context, workspace_graph, generation) = {
+            let state = self.state.read().await;
+
+            if let Some(entry) = state.task_inputs.get(&target, &request.task_digest) {
+                return Ok(Response::new(HashTaskInputsResponse {
+                    hashed: true,
+                    files: map_input_hashes(&entry.files),
+                }));
+            }
+
+            // The graph is not loaded, or is being rebuilt
+            if state.workspace_graph_data.is_none() {
+                return Ok(Response::new(HashTaskInputsResponse::default()));
+            }
+
+            (
+                Arc::clone(&state.app_context),
+                Arc::clone(&state.workspace_graph),
+                state.task_inputs.generation(),
+            )
+        };
+
+        // The client's task must match ours, otherwise the inputs may differ
+        let Ok(task) = workspace_graph.get_task(&target) else {
+            return Ok(Response::new(HashTaskInputsResponse::default()));
+        };
+
+        let task_digest =
+            Digest::from_data(task.as_ref()).map_err(|error| Status::unknown(error.to_string()))?;
+
+        if task_digest.hash.as_str() != request.task_digest {
+            debug!(
+                task_target = target.as_str(),
+                "Task differs from the client's, unable to hash inputs",
+            );
+
+            return Ok(Response::new(HashTaskInputsResponse::default()));
+        }
+
+        let project = workspace_graph
+            .get_project(
+                target
+                    .get_project_id()
+                    .map_err(|error| Status::invalid_argument(error.to_string()))?,
+            )
+            .map_err(|error| Status::not_found(error.to_string()))?;
+
+        let mut hasher = TaskHasher::new(
+            &app_context,
+            &project,
+            &task,
+            &app_context.workspace_config.hasher,
+        );
+
+        let input_files = hasher
+            .collect_input_files()
+            .await
+            .map_err(|error| Status::unknown(error.to_string()))?;
+
+        let mut hashes = hasher
+            .hash_files(input_files.clone())
+            .await
+            .map_err(|error| Status::unknown(error.to_string()))?;
+
+        // Include files that couldn't be hashed, so that the client
+        // receives the full list of resolved input files
+        let files = input_files
+            .into_iter()
+            .map(|file| {
+                let hash = hashes.remove(&file).unwrap_or_default();
+
+                (file, hash)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let response = HashTaskInputsResponse {
+            hashed: true,
+            files: map_input_hashes(&files),
+        };
+
+        let mut state = self.state.write().await;
+
+        // Don't cache if the graph was rebuilt while hashing (a file
+        // changing while hashing is handled by the generation)
+        if state.workspace_graph_data.is_some()
+            && Arc::ptr_eq(&state.workspace_graph, &workspace_graph)
+        {
+            state.task_inputs.insert(
+                target,
+                TaskInputsEntry {
+                    has_external_inputs: task
+                        .input_files
+                        .keys()
+                        .any(|file| !file.starts_with(&project.source))
+                        || task
+                            .input_globs
+                            .keys()
+                            .any(|glob| !glob.starts_with(&project.source)),
+                    files,
+                    generation,
+                    project_source: project.source.clone(),
+                    task_digest: task_digest.hash.to_string(),
+                },
+            );
+        }
+
+        Ok(Response::new(response))
+    }
+
     async fn send_webhook(
         &self,
         request: Request<SendWebhookRequest>,
@@ -385,7 +545,7 @@ impl MoonDaemon for DaemonService {
 /// is released.
 pub async fn start_daemon_server(
     state: DaemonState,
-    watchers: Vec<BoxedFileWatcher<AtomicDaemonState>>,
+    mut watchers: Vec<BoxedFileWatcher<AtomicDaemonState>>,
 ) -> miette::Result<()> {
     let daemon_dir = state.app_context.daemon_dir.clone();
     let workspace_root = state.app_context.workspace_root.clone();
@@ -441,6 +601,9 @@ pub async fn start_daemon_server(
     let (shutdown_tx, mut shutdown_rx) = broadcast::channel::<()>(1);
     let mut signal_rx = ProcessRegistry::instance().receive_signal();
 
+    // Invalidate cached task inputs before other watchers run
+    watchers.insert(0, Box::new(TaskInputsWatcher));
+
     // Spawn the file watcher and listener in the background
     let (event_tx, event_rx) = broadcast::channel::<FileEvent>(EVENT_CHANNEL_CAPACITY);
     let watcher_handle = tokio::spawn(start_file_watcher(
@@ -533,6 +696,15 @@ pub async fn start_daemon_server(
     serve_result
 }
 
+fn map_input_hashes<T: FromIterator<(String, String)>>(
+    files: &BTreeMap<WorkspaceRelativePathBuf, String>,
+) -> T {
+    files
+        .iter()
+        .map(|(path, hash)| (path.to_string(), hash.to_owned()))
+        .collect()
+}
+
 /// Retire the daemon on its own when its workspace is deleted or it goes unused
 /// for [`IDLE_TTL`], by triggering the shared shutdown. Runs until shutdown.
 async fn monitor_lifecycle(
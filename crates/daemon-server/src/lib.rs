@@ -1,7 +1,9 @@
 mod daemon_server;
 mod daemon_server_error;
 mod daemon_watcher;
+mod task_inputs_cache;
 
 pub use daemon_server::*;
 pub use daemon_server_error::*;
 pub use daemon_watcher::*;
+pub use task_inputs_cache::*;
@@ -0,0 +1,226 @@
+use crate::daemon_server::AtomicDaemonState;
+use async_trait::async_trait;
+use moon_common::path::{WorkspaceRelativePath, WorkspaceRelativePathBuf};
+use moon_file_watcher::{FileEvent, FileWatcher};
+use moon_target::Target;
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+use tracing::trace;
+
+pub struct TaskInputsEntry {
+    /// Digest of the task that the inputs were hashed for.
+    pub task_digest: String,
+
+    /// All resolved input files mapped to their content hash.
+    pub files: BTreeMap<WorkspaceRelativePathBuf, String>,
+
+    /// The cache generation the files were resolved and hashed at.
+    pub generation: u64,
+
+    /// Source of the task's project, relative from the workspace root.
+    pub project_source: WorkspaceRelativePathBuf,
+
+    /// Whether the task has inputs outside of its project, in which case
+    /// any change within the workspace must invalidate the entry.
+    pub has_external_inputs: bool,
+}
+
+impl TaskInputsEntry {
+    pub fn is_affected_by(&self, path: &WorkspaceRelativePath) -> bool {
+        self.has_external_inputs
+            || path.starts_with(&self.project_source)
+            || self.files.contains_key(path)
+    }
+}
+
+/// Input file hashes for tasks, keyed by target. Entries are invalidated
+/// when a file they may depend on changes, and cleared entirely whenever
+/// the workspace graph is rebuilt.
+///
+/// Every invalidation increments the generation, so that hashes
+/// computed while a file was changing are not cached, as the file event
+/// may have been handled before the entry was inserted.
+#[derive(Default)]
+pub struct TaskInputsCache {
+    entries: FxHashMap<Target, TaskInputsEntry>,
+    generation: u64,
+}
+
+impl TaskInputsCache {
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn get(&self, target: &Target, task_digest: &str) -> Option<&TaskInputsEntry> {
+        self.entries
+            .get(target)
+            .filter(|entry| entry.task_digest == task_digest)
+    }
+
+    pub fn insert(&mut self, target: Target, entry: TaskInputsEntry) {
+        if entry.generation == self.generation {
+            self.entries.insert(target, entry);
+        }
+    }
+
+    pub fn invalidate(&mut self, path: &WorkspaceRelativePath) {
+        self.generation += 1;
+
+        self.entries.retain(|target, entry| {
+            let affected = entry.is_affected_by(path);
+
+            if affected {
+                trace!(
+                    task_target = target.as_str(),
+                    path = path.as_str(),
+                    "Invalidating cached task input hashes",
+                );
+            }
+
+            !affected
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.generation += 1;
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Invalidates cached task input hashes when files change.
+pub struct TaskInputsWatcher;
+
+#[async_trait]
+impl FileWatcher<AtomicDaemonState> for TaskInputsWatcher {
+    async fn on_init(&mut self, _state: AtomicDaemonState) -> miette::Result<()> {
+        Ok(())
+    }
+
+    async fn on_file_event(
+        &mut self,
+        state: AtomicDaemonState,
+        event: &FileEvent,
+    ) -> miette::Result<()> {
+        if event.is_mutated() {
+            state.write().await.task_inputs.invalidate(&event.path);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_entry(source: &str, files: &[&str], has_external_inputs: bool) -> TaskInputsEntry {
+        TaskInputsEntry {
+            task_digest: "digest".into(),
+            files: files
+                .iter()
+                .map(|file| (WorkspaceRelativePathBuf::from(*file), "hash".into()))
+                .collect(),
+            generation: 0,
+            project_source: WorkspaceRelativePathBuf::from(source),
+            has_external_inputs,
+        }
+    }
+
+    fn create_cache() -> TaskInputsCache {
+        let mut cache = TaskInputsCache::default();
+
+        cache.insert(
+            Target::parse("app:build").unwrap(),
+            create_entry("apps/app", &["apps/app/src/index.ts"], false),
+        );
+        cache.insert(
+            Target::parse("lib:build").unwrap(),
+            create_entry("packages/lib", &["packages/lib/src/index.ts"], false),
+        );
+        cache.insert(
+            Target::parse("root:lint").unwrap(),
+            create_entry("scripts", &["scripts/lint.sh"], true),
+        );
+
+        cache
+    }
+
+    #[test]
+    fn returns_entry_with_matching_digest() {
+        let cache = create_cache();
+        let target = Target::parse("app:build").unwrap();
+
+        assert!(cache.get(&target, "digest").is_some());
+        assert!(cache.get(&target, "other").is_none());
+    }
+
+    #[test]
+    fn doesnt_insert_entries_hashed_at_a_previous_generation() {
+        let mut cache = TaskInputsCache::default();
+        let target = Target::parse("app:build").unwrap();
+
+        // A file changed while the entry was being hashed
+        cache.invalidate(&WorkspaceRelativePathBuf::from("apps/app/src/index.ts"));
+        cache.insert(
+            target.clone(),
+            create_entry("apps/app", &["apps/app/src/index.ts"], false),
+        );
+
+        assert!(cache.get(&target, "digest").is_none());
+
+        let mut entry = create_entry("apps/app", &["apps/app/src/index.ts"], false);
+        entry.generation = cache.generation();
+        cache.insert(target.clone(), entry);
+
+        assert!(cache.get(&target, "digest").is_some());
+    }
+
+    #[test]
+    fn invalidates_entries_within_project_source() {
+        let mut cache = create_cache();
+
+        cache.invalidate(&WorkspaceRelativePathBuf::from("apps/app/src/new.ts"));
+
+        assert!(
+            cache
+                .get(&Target::parse("app:build").unwrap(), "digest")
+                .is_none()
+        );
+        assert!(
+            cache
+                .get(&Target::parse("lib:build").unwrap(), "digest")
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn always_invalidates_entries_with_external_inputs() {
+        let mut cache = create_cache();
+
+        cache.invalidate(&WorkspaceRelativePathBuf::from("README.md"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(
+            cache
+                .get(&Target::parse("root:lint").unwrap(), "digest")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn clears_all_entries() {
+        let mut cache = create_cache();
+
+        cache.clear();
+
+        assert!(cache.is_empty());
+    }
+}
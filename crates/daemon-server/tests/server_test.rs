@@ -5,27 +5,38 @@ use moon_daemon_client::DaemonClient;
 use moon_daemon_server::*;
 use moon_daemon_utils::endpoint::*;
 use moon_daemon_utils::lock::DaemonLock;
+use moon_hash::Digest;
+use moon_target::Target;
 use moon_test_utils::{WorkspaceGraph, WorkspaceMocker};
-use starbase_sandbox::create_empty_sandbox;
-use starbase_utils::fs;
+use moon_workspace::WorkspaceBuilder;
+use starbase_sandbox::{Sandbox, create_empty_sandbox};
+use starbase_utils::{fs, json};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{RwLock, broadcast};
 
+pub fn create_daemon_state(workspace_root: PathBuf) -> DaemonState {
+    let mocker = WorkspaceMocker::new(workspace_root);
+
+    DaemonState {
+        app_context: Arc::new(mocker.mock_app_context()),
+        workspace_graph: Arc::new(WorkspaceGraph::default()),
+        workspace_graph_data: None,
+        workspace_graph_generation: 0,
+        watcher_generation: 0,
+        task_inputs: Default::default(),
+    }
+}
+
 pub fn build_daemon_service(
     workspace_root: PathBuf,
     endpoint: String,
     pid: u32,
     shutdown_tx: broadcast::Sender<()>,
 ) -> DaemonService {
-    let mocker = WorkspaceMocker::new(workspace_root);
-
     DaemonService::new(
-        Arc::new(RwLock::new(DaemonState {
-            app_context: Arc::new(mocker.mock_app_context()),
-            workspace_graph: Arc::new(WorkspaceGraph::default()),
-        })),
+        Arc::new(RwLock::new(create_daemon_state(workspace_root))),
         endpoint,
         pid,
         shutdown_tx,
@@ -56,6 +67,10 @@ async fn test_server_defers_when_ownership_lock_held() {
         DaemonState {
             app_context: Arc::new(app_context),
             workspace_graph: Arc::new(WorkspaceGraph::default()),
+            workspace_graph_data: None,
+            workspace_graph_generation: 0,
+            watcher_generation: 0,
+            task_inputs: Default::default(),
         },
         vec![],
     )
@@ -75,6 +90,16 @@ mod unix_rpc {
     /// Helper: start a gRPC server in the background on a temporary UDS,
     /// returning a shutdown sender so the test can stop it.
     async fn start_test_server(daemon_dir: &Path, workspace_root: &Path) -> broadcast::Sender<()> {
+        start_test_server_with_state(daemon_dir, create_daemon_state(workspace_root.to_owned()))
+            .await
+    }
+
+    /// Helper: like [`start_test_server`], but serves the provided state,
+    /// for example, one with a real workspace graph.
+    async fn start_test_server_with_state(
+        daemon_dir: &Path,
+        state: DaemonState,
+    ) -> broadcast::Sender<()> {
         let endpoint = get_endpoint(daemon_dir);
         let pid = std::process::id();
 
@@ -84,8 +109,8 @@ mod unix_rpc {
             let _ = shutdown_rx.recv().await;
         };
 
-        let service = build_daemon_service(
-            workspace_root.to_owned(),
+        let service = DaemonService::new(
+            Arc::new(RwLock::new(state)),
             endpoint.clone(),
             pid,
             shutdown_tx.clone(),
@@ -207,6 +232,167 @@ mod unix_rpc {
         let _ = shutdown_tx.send(());
     }
 
+    #[tokio::test]
+    async fn test_get_workspace_graph_rpc_not_ready_before_build() {
+        let sandbox = create_empty_sandbox();
+        let daemon_dir = sandbox.path().join("daemon");
+        let workspace_root = sandbox.path().to_path_buf();
+
+        fs::create_dir_all(&daemon_dir).unwrap();
+
+        let shutdown_tx = start_test_server(&daemon_dir, &workspace_root).await;
+        let mut client = DaemonClient::connect(&daemon_dir).await.unwrap();
+
+        assert!(client.get_workspace_graph().await.unwrap().is_none());
+
+        let _ = shutdown_tx.send(());
+    }
+
+    #[tokio::test]
+    async fn test_hash_task_inputs_rpc_not_hashed_before_build() {
+        let sandbox = create_empty_sandbox();
+        let daemon_dir = sandbox.path().join("daemon");
+        let workspace_root = sandbox.path().to_path_buf();
+
+        fs::create_dir_all(&daemon_dir).unwrap();
+
+        let shutdown_tx = start_test_server(&daemon_dir, &workspace_root).await;
+        let mut client = DaemonClient::connect(&daemon_dir).await.unwrap();
+
+        let files = client
+            .hash_task_inputs("app:build".into(), "digest".into())
+            .await
+            .unwrap();
+
+        assert!(files.is_none());
+
+        let _ = shutdown_tx.send(());
+    }
+
+    #[tokio::test]
+    async fn test_hash_task_inputs_rpc_rejects_invalid_target() {
+        let sandbox = create_empty_sandbox();
+        let daemon_dir = sandbox.path().join("daemon");
+        let workspace_root = sandbox.path().to_path_buf();
+
+        fs::create_dir_all(&daemon_dir).unwrap();
+
+        let shutdown_tx = start_test_server(&daemon_dir, &workspace_root).await;
+        let mut client = DaemonClient::connect(&daemon_dir).await.unwrap();
+
+        let result = client.hash_task_inputs(":".into(), "digest".into()).await;
+
+        assert!(result.is_err());
+
+        let _ = shutdown_tx.send(());
+    }
+
+    /// Create a git workspace with a single project and task, and a daemon
+    /// state that serves its graph, as if the daemon had built it.
+    async fn create_workspace_state(sandbox: &Sandbox) -> (WorkspaceMocker, DaemonState) {
+        sandbox.create_file(
+            "app/moon.yml",
+            "tasks:\n  build:\n    command: noop\n    inputs:\n      - 'src/**/*'\n",
+        );
+        sandbox.create_file("app/src/index.ts", "export {};");
+        sandbox.create_file(".moon/workspace.yml", "projects:\n  - 'app'\n");
+        sandbox.enable_git();
+
+        let mocker = WorkspaceMocker::new(sandbox.path()).load_default_configs();
+
+        let builder = WorkspaceBuilder::new_with_cache(mocker.mock_workspace_builder_context())
+            .await
+            .unwrap();
+
+        let state = DaemonState {
+            app_context: Arc::new(mocker.mock_app_context()),
+            workspace_graph: Arc::new(mocker.mock_workspace_graph().await),
+            workspace_graph_data: Some(Arc::new(
+                json::format(&builder, false).unwrap().into_bytes(),
+            )),
+            workspace_graph_generation: 0,
+            watcher_generation: 0,
+            task_inputs: Default::default(),
+        };
+
+        (mocker, state)
+    }
+
+    #[tokio::test]
+    async fn test_get_workspace_graph_rpc_serves_graph_built_at_watcher_generation() {
+        let sandbox = create_empty_sandbox();
+        let (mocker, state) = create_workspace_state(&sandbox).await;
+        let daemon_dir = state.app_context.daemon_dir.clone();
+
+        fs::create_dir_all(&daemon_dir).unwrap();
+
+        let shutdown_tx = start_test_server_with_state(&daemon_dir, state).await;
+        let mut client = DaemonClient::connect(&daemon_dir).await.unwrap();
+
+        let data = client.get_workspace_graph().await.unwrap().unwrap();
+
+        assert!(
+            WorkspaceBuilder::new_from_serialized(mocker.mock_workspace_builder_context(), &data)
+                .is_ok()
+        );
+
+        let _ = shutdown_tx.send(());
+    }
+
+    #[tokio::test]
+    async fn test_get_workspace_graph_rpc_doesnt_serve_graph_from_previous_watcher_generation() {
+        let sandbox = create_empty_sandbox();
+        let (_mocker, mut state) = create_workspace_state(&sandbox).await;
+        let daemon_dir = state.app_context.daemon_dir.clone();
+
+        // A file changed after the graph was built, and it's being rebuilt
+        state.watcher_generation += 1;
+
+        fs::create_dir_all(&daemon_dir).unwrap();
+
+        let shutdown_tx = start_test_server_with_state(&daemon_dir, state).await;
+        let mut client = DaemonClient::connect(&daemon_dir).await.unwrap();
+
+        assert!(client.get_workspace_graph().await.unwrap().is_none());
+
+        let _ = shutdown_tx.send(());
+    }
+
+    #[tokio::test]
+    async fn test_hash_task_inputs_rpc_returns_resolved_input_files() {
+        let sandbox = create_empty_sandbox();
+        let (_mocker, state) = create_workspace_state(&sandbox).await;
+        let daemon_dir = state.app_context.daemon_dir.clone();
+
+        let target = Target::parse("app:build").unwrap();
+        let task_digest =
+            Digest::from_data(state.workspace_graph.get_task(&target).unwrap().as_ref()).unwrap();
+
+        fs::create_dir_all(&daemon_dir).unwrap();
+
+        let shutdown_tx = start_test_server_with_state(&daemon_dir, state).await;
+        let mut client = DaemonClient::connect(&daemon_dir).await.unwrap();
+
+        let files = client
+            .hash_task_inputs(target.to_string(), task_digest.hash.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!files.get("app/src/index.ts").unwrap().is_empty());
+
+        // Served from the cache the second time around
+        let cached_files = client
+            .hash_task_inputs(target.to_string(), task_digest.hash.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(cached_files, files);
+
+        let _ = shutdown_tx.send(());
+    }
+
     #[tokio::test]
     async fn test_connect_to_nonexistent_socket_fails() {
         let sandbox = create_empty_sandbox();
@@ -236,6 +422,10 @@ mod unix_rpc {
             DaemonState {
                 app_context: Arc::new(app_context),
                 workspace_graph: Arc::new(WorkspaceGraph::default()),
+                workspace_graph_data: None,
+                workspace_graph_generation: 0,
+                watcher_generation: 0,
+                task_inputs: Default::default(),
             },
             vec![],
         )
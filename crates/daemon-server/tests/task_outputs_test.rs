@@ -46,6 +46,10 @@ impl TestDaemon {
             Arc::new(RwLock::new(DaemonState {
                 app_context: Arc::clone(&app_context),
                 workspace_graph: Arc::new(WorkspaceGraph::default()),
+                workspace_graph_data: None,
+                workspace_graph_generation: 0,
+                watcher_generation: 0,
+                task_inputs: Default::default(),
             })),
             endpoint.clone(),
             std::process::id(),
@@ -197,6 +197,10 @@ mod connect {
             Arc::new(RwLock::new(DaemonState {
                 app_context: Arc::new(mocker.mock_app_context()),
                 workspace_graph: Arc::new(WorkspaceGraph::default()),
+                workspace_graph_data: None,
+                workspace_graph_generation: 0,
+                watcher_generation: 0,
+                task_inputs: Default::default(),
             })),
             endpoint.clone(),
             std::process::id(),
@@ -245,6 +249,10 @@ mod connect {
             Arc::new(RwLock::new(DaemonState {
                 app_context: Arc::new(mocker.mock_app_context()),
                 workspace_graph: Arc::new(WorkspaceGraph::default()),
+                workspace_graph_data: None,
+                workspace_graph_generation: 0,
+                watcher_generation: 0,
+                task_inputs: Default::default(),
             })),
             endpoint.clone(),
             std::process::id(),
@@ -295,6 +303,10 @@ mod connect {
             Arc::new(RwLock::new(DaemonState {
                 app_context: Arc::new(mocker.mock_app_context()),
                 workspace_graph: Arc::new(WorkspaceGraph::default()),
+                workspace_graph_data: None,
+                workspace_graph_generation: 0,
+                watcher_generation: 0,
+                task_inputs: Default::default(),
             })),
             endpoint.clone(),
             std::process::id(),
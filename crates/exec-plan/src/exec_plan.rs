@@ -1,6 +1,7 @@
 use moon_affected::{DownstreamScope, UpstreamScope};
 use moon_common::{cacheable, cacheable_enum};
 use moon_target::TargetLocator;
+use std::fmt;
 
 cacheable!(
     #[derive(Default)]
@@ -24,6 +25,29 @@ cacheable!(
     }
 );
 
+cacheable_enum!(
+    // How targets are distributed across jobs
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub enum JobBalance {
+        #[default]
+        Count,
+        Duration,
+    }
+);
+
+impl fmt::Display for JobBalance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Count => "count",
+                Self::Duration => "duration",
+            }
+        )
+    }
+}
+
 cacheable_enum!(
     pub enum OnFailure {
         Bail,
@@ -42,6 +66,7 @@ cacheable!(
         pub on_failure: Option<OnFailure>,
         pub job: Option<usize>,
         pub job_total: Option<usize>,
+        pub balance: Option<JobBalance>,
     }
 );
 
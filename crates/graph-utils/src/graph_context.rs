@@ -1,4 +1,6 @@
+use moon_common::path::WorkspaceRelativePathBuf;
 use moon_config::{ExtensionsConfig, ToolchainsConfig, WorkspaceConfig};
+use rustc_hash::FxHashSet;
 use std::{path::PathBuf, sync::Arc};
 
 #[derive(Clone, Debug, Default)]
@@ -7,8 +9,10 @@ pub struct GraphExpanderContext {
     pub extensions_config: Arc<ExtensionsConfig>,
     pub toolchains_config: Arc<ToolchainsConfig>,
     pub vcs_branch: Arc<String>,
+    pub vcs_changed_files: Arc<FxHashSet<WorkspaceRelativePathBuf>>,
     pub vcs_repository: Arc<String>,
     pub vcs_revision: Arc<String>,
+    pub vcs_tag: Arc<String>,
     pub working_dir: PathBuf,
     pub workspace_config: Arc<WorkspaceConfig>,
     pub workspace_root: PathBuf,
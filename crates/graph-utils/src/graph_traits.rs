@@ -1,14 +1,147 @@
 use petgraph::Direction;
+use petgraph::algo::{all_simple_paths, tarjan_scc};
 use petgraph::graph::{DiGraph, NodeIndex};
-use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
+use serde::Serialize;
+use std::collections::VecDeque;
 use std::fmt::Display;
 use std::hash::Hash;
 
+/// A single hop within a path, from a node to one of its dependencies.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GraphPathHop<K, E> {
+    pub from: K,
+    pub to: K,
+    pub edge: E,
+}
+
+pub type GraphPath<K, E> = Vec<GraphPathHop<K, E>>;
+
 pub trait GraphData<N, E, K> {
     fn get_graph(&self) -> &DiGraph<NodeIndex, E>;
     fn get_nodes(&self) -> FxHashMap<NodeIndex, &N>;
     fn get_node_by_index(&self, index: &NodeIndex) -> &N;
     fn get_node_key(&self, node: &N) -> K;
+
+    /// Return every shortest path from the source node to the target node,
+    /// following dependency edges. Each hop includes the edge that connects them.
+    fn shortest_paths_between(&self, from: NodeIndex, to: NodeIndex) -> Vec<GraphPath<K, E>>
+    where
+        E: Clone,
+    {
+        if from == to {
+            return vec![];
+        }
+
+        let graph = self.get_graph();
+        let mut distances = FxHashMap::from_iter([(from, 0)]);
+        let mut predecessors = FxHashMap::<NodeIndex, Vec<NodeIndex>>::default();
+        let mut queue = VecDeque::from([from]);
+
+        // Breadth-first search, while tracking every predecessor
+        // that reaches a node at its shortest distance
+        while let Some(index) = queue.pop_front() {
+            if index == to {
+                continue;
+            }
+
+            let distance = distances[&index] + 1;
+
+            for dep_index in graph.neighbors_directed(index, Direction::Outgoing) {
+                match distances.get(&dep_index) {
+                    None => {
+                        distances.insert(dep_index, distance);
+                        queue.push_back(dep_index);
+                    }
+                    Some(dep_distance) if *dep_distance == distance => {}
+                    _ => continue,
+                };
+
+                let list = predecessors.entry(dep_index).or_default();
+
+                if !list.contains(&index) {
+                    list.push(index);
+                }
+            }
+        }
+
+        // Then walk backwards from the target to the source
+        let mut paths = vec![];
+        let mut stack = vec![vec![to]];
+
+        while let Some(mut indices) = stack.pop() {
+            let last = indices[indices.len() - 1];
+
+            if last == from {
+                indices.reverse();
+                paths.push(create_path(self, &indices));
+                continue;
+            }
+
+            for pred_index in predecessors.get(&last).into_iter().flatten() {
+                let mut next = indices.clone();
+                next.push(*pred_index);
+                stack.push(next);
+            }
+        }
+
+        paths
+    }
+
+    /// Return every simple path (no repeated nodes) from the source node to the
+    /// target node, following dependency edges, ordered from shortest to longest.
+    /// This may be expensive for large and densely connected graphs.
+    fn simple_paths_between(&self, from: NodeIndex, to: NodeIndex) -> Vec<GraphPath<K, E>>
+    where
+        E: Clone,
+    {
+        if from == to {
+            return vec![];
+        }
+
+        let mut paths =
+            all_simple_paths::<Vec<_>, _, FxBuildHasher>(self.get_graph(), from, to, 0, None)
+                .map(|indices| create_path(self, &indices))
+                .collect::<Vec<_>>();
+
+        paths.sort_by_key(|path| path.len());
+        paths
+    }
+
+    /// Return all strongly connected components that contain more than 1 node,
+    /// in other words, groups of nodes that form a cycle.
+    fn strongly_connected_components(&self) -> Vec<Vec<K>> {
+        tarjan_scc(self.get_graph())
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                component
+                    .into_iter()
+                    .map(|index| self.get_node_key(self.get_node_by_index(&index)))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn create_path<N, E: Clone, K, G: GraphData<N, E, K> + ?Sized>(
+    graph_impl: &G,
+    indices: &[NodeIndex],
+) -> GraphPath<K, E> {
+    let graph = graph_impl.get_graph();
+
+    indices
+        .windows(2)
+        .filter_map(|pair| {
+            let edge = graph.find_edge(pair[0], pair[1])?;
+
+            Some(GraphPathHop {
+                from: graph_impl.get_node_key(graph_impl.get_node_by_index(&pair[0])),
+                to: graph_impl.get_node_key(graph_impl.get_node_by_index(&pair[1])),
+                edge: graph[edge].clone(),
+            })
+        })
+        .collect()
 }
 
 fn traverse_deep<N, E, K: Hash + Eq, G: GraphConnections<N, E, K> + ?Sized>(
@@ -107,6 +240,43 @@ pub trait GraphConnections<N, E, K: Hash + Eq>: GraphData<N, E, K> {
         traverse_deep(self, node, true)
     }
 
+    /// Return the depth of the provided node, which is the length of the longest
+    /// chain of dependencies beneath it. Nodes without dependencies have a depth of 0.
+    fn depth_of(&self, node: &N) -> usize {
+        fn visit<E>(
+            graph: &DiGraph<NodeIndex, E>,
+            index: NodeIndex,
+            depths: &mut FxHashMap<NodeIndex, usize>,
+            visiting: &mut FxHashSet<NodeIndex>,
+        ) -> usize {
+            if let Some(depth) = depths.get(&index) {
+                return *depth;
+            }
+
+            // Break cycles by treating the revisited node as a leaf
+            if !visiting.insert(index) {
+                return 0;
+            }
+
+            let depth = graph
+                .neighbors_directed(index, Direction::Outgoing)
+                .map(|dep_index| visit(graph, dep_index, depths, visiting) + 1)
+                .max()
+                .unwrap_or_default();
+
+            visiting.remove(&index);
+            depths.insert(index, depth);
+            depth
+        }
+
+        visit(
+            self.get_graph(),
+            self.get_node_index(node),
+            &mut FxHashMap::default(),
+            &mut FxHashSet::default(),
+        )
+    }
+
     /// Return a list of keys for all nodes currently within the graph.
     fn get_node_keys(&self) -> Vec<K> {
         self.get_graph()
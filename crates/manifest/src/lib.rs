@@ -3,9 +3,11 @@ mod manifest;
 mod manifest_error;
 mod manifest_packer;
 mod manifest_unpacker;
+mod task_outputs_manifest;
 
 pub use helpers::*;
 pub use manifest::*;
 pub use manifest_error::*;
 pub use manifest_packer::*;
 pub use manifest_unpacker::*;
+pub use task_outputs_manifest::*;
@@ -0,0 +1,93 @@
+use crate::manifest::Manifest;
+use moon_common::path::WorkspaceRelativePathBuf;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskOutputsSource {
+    /// Outputs were created by running the task.
+    #[default]
+    Fresh,
+    /// Outputs were hydrated from (or reused within) the local cache.
+    Local,
+    /// Outputs were hydrated from a remote cache.
+    Remote,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TaskOutputsFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    pub executable: bool,
+    pub path: WorkspaceRelativePathBuf,
+    pub size: u64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TaskOutputsSymlink {
+    pub path: WorkspaceRelativePathBuf,
+    pub target: WorkspaceRelativePathBuf,
+}
+
+/// A user facing summary of the outputs a task produced, derived from a cache
+/// manifest. This is persisted as `outputs.json` in the task's state directory.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TaskOutputsManifest {
+    pub files: Vec<TaskOutputsFile>,
+    pub hash: String,
+    pub source: TaskOutputsSource,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub symlinks: Vec<TaskOutputsSymlink>,
+    pub target: String,
+}
+
+impl TaskOutputsManifest {
+    pub fn from_manifest(
+        target: impl AsRef<str>,
+        hash: impl AsRef<str>,
+        source: TaskOutputsSource,
+        manifest: &Manifest,
+    ) -> Self {
+        let mut files = manifest
+            .files
+            .iter()
+            .map(|file| TaskOutputsFile {
+                digest: file.digest.as_ref().map(|digest| digest.hash.to_string()),
+                executable: file.is_executable,
+                path: file.path.clone(),
+                size: file
+                    .digest
+                    .as_ref()
+                    .map(|digest| digest.size.max(0) as u64)
+                    .unwrap_or_default(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut symlinks = manifest
+            .symlinks
+            .iter()
+            .map(|link| TaskOutputsSymlink {
+                path: link.path.clone(),
+                target: link.target.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        symlinks.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Self {
+            files,
+            hash: hash.as_ref().to_owned(),
+            source,
+            symlinks,
+            target: target.as_ref().to_owned(),
+        }
+    }
+
+    pub fn get_total_size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+}
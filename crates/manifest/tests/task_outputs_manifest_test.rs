@@ -0,0 +1,95 @@
+use moon_hash::{ContentHash, Digest};
+use moon_manifest::{
+    Manifest, ManifestFile, ManifestSymlink, TaskOutputsManifest, TaskOutputsSource,
+};
+
+fn digest(seed: char, size: i64) -> Digest {
+    Digest {
+        hash: ContentHash::from_hex(std::iter::repeat_n(seed, 64).collect::<String>()).unwrap(),
+        size,
+    }
+}
+
+fn create_manifest() -> Manifest {
+    Manifest {
+        files: vec![
+            ManifestFile {
+                digest: Some(digest('b', 20)),
+                path: "out/b.txt".into(),
+                ..Default::default()
+            },
+            ManifestFile {
+                digest: Some(digest('a', 10)),
+                is_executable: true,
+                path: "out/a.sh".into(),
+                ..Default::default()
+            },
+            ManifestFile {
+                digest: None,
+                path: "out/empty".into(),
+                ..Default::default()
+            },
+        ],
+        symlinks: vec![ManifestSymlink {
+            path: "out/link".into(),
+            target: "out/a.sh".into(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn derives_files_sorted_by_path() {
+    let outputs = TaskOutputsManifest::from_manifest(
+        "app:build",
+        "hash123",
+        TaskOutputsSource::Fresh,
+        &create_manifest(),
+    );
+
+    assert_eq!(outputs.target, "app:build");
+    assert_eq!(outputs.hash, "hash123");
+    assert_eq!(
+        outputs
+            .files
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect::<Vec<_>>(),
+        ["out/a.sh", "out/b.txt", "out/empty"]
+    );
+}
+
+#[test]
+fn includes_digests_sizes_and_executable_bit() {
+    let outputs = TaskOutputsManifest::from_manifest(
+        "app:build",
+        "hash123",
+        TaskOutputsSource::Remote,
+        &create_manifest(),
+    );
+
+    let file = &outputs.files[0];
+
+    assert_eq!(file.digest.as_deref(), Some("a".repeat(64).as_str()));
+    assert_eq!(file.size, 10);
+    assert!(file.executable);
+
+    assert_eq!(outputs.files[2].digest, None);
+    assert_eq!(outputs.files[2].size, 0);
+    assert_eq!(outputs.get_total_size(), 30);
+    assert_eq!(outputs.source, TaskOutputsSource::Remote);
+}
+
+#[test]
+fn includes_symlinks() {
+    let outputs = TaskOutputsManifest::from_manifest(
+        "app:build",
+        "hash123",
+        TaskOutputsSource::Local,
+        &create_manifest(),
+    );
+
+    assert_eq!(outputs.symlinks.len(), 1);
+    assert_eq!(outputs.symlinks[0].target.as_str(), "out/a.sh");
+}
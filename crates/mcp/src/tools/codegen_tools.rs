@@ -49,6 +49,28 @@ impl Variables {
     }
 }
 
+impl Variables {
+    /// Validate the values against the type and constraints of the
+    /// template's variables, so that errors are reported before generating.
+    pub fn validate(&self, config: &FxHashMap<String, TemplateVariable>) -> Result<(), String> {
+        for (name, value) in self.iter() {
+            let Some(var) = config.get(name) else {
+                return Err(format!("Unknown variable {name}"));
+            };
+
+            if var.is_internal() {
+                return Err(format!("Variable {name} is internal and cannot be set"));
+            }
+
+            if let Some(message) = var.validate_value(value) {
+                return Err(format!("Invalid value for variable {name}: {message}"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl Deref for Variables {
     type Target = FxHashMap<String, serde_json::Value>;
 
@@ -60,7 +82,10 @@ impl Deref for Variables {
 #[mcp_tool(
     name = "generate",
     title = "Generate",
-    description = "Generate code or scaffold from a template."
+    description = "Generate code or scaffold from a template. Returns a plan of the files that were \
+                   created, replaced, merged, or skipped, with content hashes and diffs against \
+                   existing files. Use `dry_run` to preview the plan without writing files. \
+                   The template's post-generate hooks are only ran when `run_hooks` is enabled."
 )]
 #[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
 pub struct GenerateTool {
@@ -73,6 +98,9 @@ pub struct GenerateTool {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub force: Option<bool>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_hooks: Option<bool>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub variables: Option<Variables>,
 }
@@ -80,6 +108,8 @@ pub struct GenerateTool {
 #[derive(Serialize)]
 pub struct GenerateResponse {
     pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<Value>,
     pub success: bool,
 }
 
@@ -93,6 +123,7 @@ impl GenerateTool {
             self.template.clone(),
             "--to".into(),
             self.to.clone(),
+            "--json".into(),
         ];
 
         if self.dry_run.unwrap_or_default() {
@@ -101,16 +132,38 @@ impl GenerateTool {
             args.push("--force".into());
         }
 
+        if self.run_hooks.unwrap_or_default() {
+            args.push("--run-hooks".into());
+        }
+
         if let Some(vars) = &self.variables
             && !vars.is_empty()
         {
+            let generator = load_generator(app_context).await?;
+            let template = generator
+                .get_template(&self.template)
+                .map_err(map_miette_error)?;
+
+            if let Err(error) = vars.validate(&template.config.variables) {
+                return Ok(CallToolResult::text_content(vec![TextContent::new(
+                    serde_json::to_string_pretty(&GenerateResponse {
+                        error: Some(error),
+                        plan: None,
+                        success: false,
+                    })
+                    .map_err(CallToolError::new)?,
+                    None,
+                    None,
+                )]));
+            }
+
             args.push("--".into());
 
             for (key, value) in vars.iter() {
                 let opt = format!("--{key}");
 
                 match value {
-                    Value::Null | Value::Object(_) => {
+                    Value::Null => {
                         // Skip
                     }
                     Value::Bool(inner) => {
@@ -135,6 +188,11 @@ impl GenerateTool {
                             args.push(item.to_string());
                         }
                     }
+                    Value::Object(_) => {
+                        // Passed as a JSON string
+                        args.push(opt);
+                        args.push(value.to_string());
+                    }
                 }
             }
         }
@@ -148,10 +206,13 @@ impl GenerateTool {
 
         let mut response = GenerateResponse {
             error: None,
+            plan: None,
             success: true,
         };
 
-        if !output.success() {
+        if output.success() {
+            response.plan = serde_json::from_slice(&output.stdout).ok();
+        } else {
             response.success = false;
             response.error = Some(output_to_trimmed_string(&output.stderr));
         }
@@ -168,7 +229,8 @@ impl GenerateTool {
     name = "get_template",
     title = "Get template",
     description = "Describe a template's full variable schema, including types, defaults, \
-                   prompts, required flags, and enum values. The `extends` chain is resolved \
+                   prompts, required flags, enum values, constraints (`pattern`, `min`, `max`), \
+                   and `when` conditions. The `extends` chain is resolved \
                    so the returned schema matches what `generate` would actually prompt for. \
                    Internal variables are excluded."
 )]
@@ -1,3 +1,7 @@
+use moon_config::{
+    TemplateVariable, TemplateVariableBoolSetting, TemplateVariableNumberSetting,
+    TemplateVariableStringSetting, schematic,
+};
 use moon_mcp::tools::codegen_tools::{GenerateTool, GetTemplateTool, GetTemplatesTool, Variables};
 use rustc_hash::FxHashMap;
 use serde_json::Value;
@@ -228,5 +232,87 @@ mod codegen_tools {
             assert_eq!(deserialized.len(), 2);
             assert_eq!(deserialized.get("name").unwrap(), "test");
         }
+
+        fn create_config() -> FxHashMap<String, TemplateVariable> {
+            FxHashMap::from_iter([
+                (
+                    "name".to_string(),
+                    TemplateVariable::String(TemplateVariableStringSetting {
+                        pattern: Some(schematic::RegexSetting::new("^[a-z]+$").unwrap()),
+                        ..Default::default()
+                    }),
+                ),
+                (
+                    "count".to_string(),
+                    TemplateVariable::Number(TemplateVariableNumberSetting {
+                        max: Some(10),
+                        ..Default::default()
+                    }),
+                ),
+                (
+                    "internal".to_string(),
+                    TemplateVariable::Boolean(TemplateVariableBoolSetting {
+                        internal: true,
+                        ..Default::default()
+                    }),
+                ),
+            ])
+        }
+
+        #[test]
+        fn validates_values() {
+            let vars = Variables(FxHashMap::from_iter([
+                ("name".to_string(), Value::String("test".to_string())),
+                ("count".to_string(), Value::Number(5.into())),
+            ]));
+
+            assert_eq!(vars.validate(&create_config()), Ok(()));
+        }
+
+        #[test]
+        fn errors_for_invalid_values() {
+            let vars = Variables(FxHashMap::from_iter([(
+                "count".to_string(),
+                Value::Number(42.into()),
+            )]));
+
+            assert_eq!(
+                vars.validate(&create_config()),
+                Err("Invalid value for variable count: Must be less than or equal to 10".into())
+            );
+
+            let vars = Variables(FxHashMap::from_iter([(
+                "name".to_string(),
+                Value::String("Test".to_string()),
+            )]));
+
+            assert_eq!(
+                vars.validate(&create_config()),
+                Err("Invalid value for variable name: Must match the pattern ^[a-z]+$".into())
+            );
+        }
+
+        #[test]
+        fn errors_for_unknown_and_internal_variables() {
+            let vars = Variables(FxHashMap::from_iter([(
+                "unknown".to_string(),
+                Value::Bool(true),
+            )]));
+
+            assert_eq!(
+                vars.validate(&create_config()),
+                Err("Unknown variable unknown".into())
+            );
+
+            let vars = Variables(FxHashMap::from_iter([(
+                "internal".to_string(),
+                Value::Bool(true),
+            )]));
+
+            assert_eq!(
+                vars.validate(&create_config()),
+                Err("Variable internal is internal and cannot be set".into())
+            );
+        }
     }
 }
@@ -115,6 +115,10 @@ api_struct!(
         /// Map of inherited tasks keyed by a unique ID, typically extracted from a manifest.
         #[serde(skip_serializing_if = "BTreeMap::is_empty")]
         pub tasks: BTreeMap<Id, PartialTaskConfig>,
+
+        /// Current version of this project, typically extracted from a manifest.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub version: Option<String>,
     }
 );
 
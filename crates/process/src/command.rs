@@ -139,6 +139,10 @@ impl CommandExecutable {
 pub struct Command {
     pub args: VecDeque<CommandArg>,
 
+    /// Directories to bind mount (source, target), in order, within
+    /// a mount namespace private to the process (Linux only)
+    pub bind_mounts: Vec<(OsString, OsString)>,
+
     pub cache: bool,
 
     /// Continuously write to stdin and read from stdout
@@ -176,6 +180,7 @@ impl Command {
     pub fn new<T: AsRef<OsStr>>(bin: T) -> Self {
         Command {
             args: VecDeque::new(),
+            bind_mounts: vec![],
             cache: false,
             continuous_pipe: false,
             cwd: None,
@@ -233,6 +238,18 @@ impl Command {
         self
     }
 
+    pub fn bind_mount<S, T>(&mut self, source: S, target: T) -> &mut Self
+    where
+        S: AsRef<OsStr>,
+        T: AsRef<OsStr>,
+    {
+        self.bind_mounts.push((
+            source.as_ref().to_os_string(),
+            target.as_ref().to_os_string(),
+        ));
+        self
+    }
+
     pub fn contains_arg<A>(&self, arg: A) -> bool
     where
         A: AsRef<OsStr>,
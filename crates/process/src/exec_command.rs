@@ -506,6 +506,21 @@ impl Command {
             command.env("PWD", cwd);
         }
 
+        if !self.bind_mounts.is_empty() {
+            #[cfg(target_os = "linux")]
+            crate::mount_namespace::apply_bind_mounts(
+                &mut command,
+                &self.bind_mounts,
+                self.cwd.as_deref(),
+            )?;
+
+            #[cfg(not(target_os = "linux"))]
+            return Err(ProcessError::BindMountUnsupported {
+                bin: self.get_bin_name(),
+            }
+            .into());
+        }
+
         // And lastly inherit lookup paths
         let path_key = OsString::from("PATH");
 
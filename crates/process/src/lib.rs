@@ -1,17 +1,23 @@
 mod command;
 mod exec_command;
 mod helpers;
+#[cfg(target_os = "linux")]
+mod mount_namespace;
 mod output;
 mod process_error;
 mod process_registry;
+mod process_scope;
 mod shared_child;
 mod signal;
 
 pub use command::*;
 pub use helpers::*;
+#[cfg(target_os = "linux")]
+pub use mount_namespace::is_mount_namespace_supported;
 pub use output::*;
 pub use process_error::*;
 pub use process_registry::*;
+pub use process_scope::*;
 pub use shared_child::*;
 pub use signal::*;
 pub use starbase_shell::{BoxedShell, ShellType};
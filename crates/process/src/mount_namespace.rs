@@ -0,0 +1,132 @@
+// https://man7.org/linux/man-pages/man7/mount_namespaces.7.html
+// https://man7.org/linux/man-pages/man7/user_namespaces.7.html
+
+use miette::IntoDiagnostic;
+use std::ffi::{CStr, CString, OsStr, OsString};
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::process::CommandExt;
+use std::process::Command as StdCommand;
+use std::ptr;
+use std::sync::OnceLock;
+
+/// Whether the current process is able to create an unprivileged user
+/// and mount namespace, which may be disabled by the kernel, a container
+/// runtime, or a security module. The result is cached.
+pub fn is_mount_namespace_supported() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+
+    *SUPPORTED.get_or_init(|| unsafe {
+        // Probe within a forked child, as a namespace can't be left once entered,
+        // and only call async-signal-safe functions before exiting
+        match libc::fork() {
+            -1 => false,
+            0 => libc::_exit(
+                if libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNS) == 0 {
+                    0
+                } else {
+                    1
+                },
+            ),
+            pid => {
+                let mut status = 0;
+
+                libc::waitpid(pid, &mut status, 0) == pid
+                    && libc::WIFEXITED(status)
+                    && libc::WEXITSTATUS(status) == 0
+            }
+        }
+    })
+}
+
+/// Spawn the command within its own user and mount namespaces, and bind mount
+/// the provided directories (in order) before it executes. The mounts are only
+/// visible to the process tree, and the process retains its user and group.
+pub fn apply_bind_mounts(
+    command: &mut StdCommand,
+    mounts: &[(OsString, OsString)],
+    cwd: Option<&OsStr>,
+) -> miette::Result<()> {
+    let to_cstring = |value: &OsStr| CString::new(value.as_bytes()).into_diagnostic();
+
+    let mounts = mounts
+        .iter()
+        .map(|(source, target)| Ok((to_cstring(source)?, to_cstring(target)?)))
+        .collect::<miette::Result<Vec<_>>>()?;
+    let cwd = cwd.map(to_cstring).transpose()?;
+
+    // Map our own user and group into the namespace, so that files
+    // created by the process are owned by the current user
+    let uid_map = format!("{0} {0} 1", unsafe { libc::getuid() });
+    let gid_map = format!("{0} {0} 1", unsafe { libc::getgid() });
+
+    // SAFETY: Everything is allocated before forking, and the hook only
+    // calls async-signal-safe functions
+    unsafe {
+        command.pre_exec(move || {
+            check(libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNS))?;
+
+            write_proc_file(c"/proc/self/setgroups", b"deny")?;
+            write_proc_file(c"/proc/self/uid_map", uid_map.as_bytes())?;
+            write_proc_file(c"/proc/self/gid_map", gid_map.as_bytes())?;
+
+            // Don't propagate mounts back into the parent namespace
+            check(libc::mount(
+                c"none".as_ptr(),
+                c"/".as_ptr(),
+                ptr::null(),
+                libc::MS_REC | libc::MS_PRIVATE,
+                ptr::null(),
+            ))?;
+
+            for (source, target) in &mounts {
+                check(libc::mount(
+                    source.as_ptr(),
+                    target.as_ptr(),
+                    ptr::null(),
+                    libc::MS_BIND | libc::MS_REC,
+                    ptr::null(),
+                ))?;
+            }
+
+            // The working directory was entered before the mounts were
+            // created, so enter it again to resolve through them
+            if let Some(cwd) = &cwd {
+                check(libc::chdir(cwd.as_ptr()))?;
+            }
+
+            Ok(())
+        });
+    }
+
+    Ok(())
+}
+
+fn check(result: libc::c_int) -> io::Result<()> {
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+unsafe fn write_proc_file(path: &CStr, data: &[u8]) -> io::Result<()> {
+    unsafe {
+        let fd = libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let written = libc::write(fd, data.as_ptr().cast(), data.len());
+        let error = io::Error::last_os_error();
+
+        libc::close(fd);
+
+        if written < 0 {
+            return Err(error);
+        }
+    }
+
+    Ok(())
+}
@@ -15,6 +15,13 @@ impl ProcessError {
 
 #[derive(Error, Debug, Diagnostic)]
 pub enum ProcessError {
+    #[diagnostic(code(process::bind_mount::unsupported))]
+    #[error(
+        "Unable to execute {}, as bind mounts are only supported on Linux.",
+        .bin.style(Style::Shell),
+    )]
+    BindMountUnsupported { bin: String },
+
     #[diagnostic(code(process::capture::failed))]
     #[error(
         "Failed to execute {} and capture output.",
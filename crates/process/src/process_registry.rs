@@ -1,4 +1,5 @@
 use crate::output::Output;
+use crate::process_scope::ProcessScope;
 use crate::shared_child::*;
 use crate::signal::*;
 use core::time::Duration;
@@ -66,6 +67,10 @@ impl ProcessRegistry {
     pub async fn add_running(&self, child: Child) -> SharedChild {
         let shared = SharedChild::new(child);
 
+        if let Some(scope) = ProcessScope::current() {
+            scope.track(shared.id());
+        }
+
         self.running
             .write()
             .await
@@ -83,9 +88,23 @@ impl ProcessRegistry {
     }
 
     pub async fn remove_running_by_pid(&self, id: u32) {
+        if let Some(scope) = ProcessScope::current() {
+            scope.untrack(id);
+        }
+
         self.running.write().await.remove(&id);
     }
 
+    pub async fn get_running_in_scope(&self, scope: &ProcessScope) -> FxHashMap<u32, SharedChild> {
+        self.running
+            .read()
+            .await
+            .iter()
+            .filter(|(pid, _)| scope.contains(**pid))
+            .map(|(pid, child)| (*pid, child.clone()))
+            .collect()
+    }
+
     pub fn receive_signal(&self) -> Receiver<SignalType> {
         self.signal_sender.subscribe()
     }
@@ -94,6 +113,84 @@ impl ProcessRegistry {
         let _ = self.signal_sender.send(SignalType::Terminate);
     }
 
+    /// Terminate only the running processes that were spawned within the
+    /// provided scope. Unlike [`ProcessRegistry::terminate_running`], this
+    /// does not broadcast a signal, so other pipelines continue to run.
+    pub async fn terminate_scope(&self, scope: &ProcessScope) {
+        let children = self.get_running_in_scope(scope).await;
+
+        if children.is_empty() {
+            return;
+        }
+
+        debug!(
+            pids = ?children.keys().collect::<Vec<_>>(),
+            "Terminating {} scoped child processes",
+            children.len()
+        );
+
+        let mut set = JoinSet::new();
+
+        for (pid, child) in children {
+            let running = self.running.clone();
+            let scope = scope.clone();
+
+            set.spawn(async move {
+                if let Err(error) = child.kill_with_signal(SignalType::Terminate).await {
+                    warn!(
+                        pid,
+                        error = error.to_string(),
+                        "Failed to terminate child process"
+                    );
+                }
+
+                scope.untrack(pid);
+                running.write().await.remove(&pid);
+            });
+        }
+
+        // Don't block the caller on processes that ignore the signal,
+        // the shutdown wait will kill them after the threshold
+        set.detach_all();
+    }
+
+    /// Wait for the running processes within the provided scope to exit,
+    /// and kill any that are still running after the threshold.
+    pub async fn wait_for_scope_to_shutdown(&self, scope: &ProcessScope) {
+        let mut count = 0;
+
+        loop {
+            if self.get_running_in_scope(scope).await.is_empty() {
+                return;
+            }
+
+            if self.threshold > 0 && count >= self.threshold {
+                break;
+            }
+
+            sleep(Duration::from_millis(50)).await;
+            count += 50;
+        }
+
+        for (pid, child) in self.get_running_in_scope(scope).await {
+            debug!(
+                pid,
+                "Wait threshold exhausted, killing scoped child process"
+            );
+
+            if let Err(error) = child.kill_with_signal(SignalType::Kill).await {
+                warn!(
+                    pid,
+                    error = error.to_string(),
+                    "Failed to kill child process"
+                );
+            }
+
+            scope.untrack(pid);
+            self.running.write().await.remove(&pid);
+        }
+    }
+
     pub async fn wait_for_running_to_shutdown(&self) {
         let mut count = 0;
 
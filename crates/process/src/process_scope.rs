@@ -0,0 +1,61 @@
+use rustc_hash::FxHashSet;
+use std::sync::{Arc, Mutex};
+
+tokio::task_local! {
+    static CURRENT_SCOPE: ProcessScope;
+}
+
+/// Tracks the child processes that were spawned while running a future,
+/// so that a subset of running processes (for example, a single pipeline run)
+/// can be terminated without signaling every process in the registry.
+///
+/// The scope is bound to the task that runs the future, and is *not*
+/// inherited by tasks spawned from within it.
+#[derive(Clone, Debug, Default)]
+pub struct ProcessScope {
+    pids: Arc<Mutex<FxHashSet<u32>>>,
+}
+
+impl ProcessScope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the scope of the currently running task, if any.
+    pub fn current() -> Option<ProcessScope> {
+        CURRENT_SCOPE.try_with(|scope| scope.clone()).ok()
+    }
+
+    /// Run the future within this scope.
+    pub async fn run<F: Future>(&self, future: F) -> F::Output {
+        CURRENT_SCOPE.scope(self.clone(), future).await
+    }
+
+    pub fn contains(&self, pid: u32) -> bool {
+        self.pids.lock().unwrap().contains(&pid)
+    }
+
+    pub fn get_pids(&self) -> Vec<u32> {
+        let mut pids = self
+            .pids
+            .lock()
+            .unwrap()
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+        pids.sort();
+        pids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pids.lock().unwrap().is_empty()
+    }
+
+    pub(crate) fn track(&self, pid: u32) {
+        self.pids.lock().unwrap().insert(pid);
+    }
+
+    pub(crate) fn untrack(&self, pid: u32) {
+        self.pids.lock().unwrap().remove(&pid);
+    }
+}
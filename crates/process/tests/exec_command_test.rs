@@ -271,3 +271,68 @@ mod exec_stream_and_capture_output_bytes {
         assert_eq!(output.stdout.as_ref(), b"one\r\ntwo\r\n");
     }
 }
+
+#[cfg(target_os = "linux")]
+mod bind_mounts {
+    use super::*;
+    use moon_process::is_mount_namespace_supported;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn create_dirs(name: &str) -> (PathBuf, PathBuf) {
+        let root = std::env::temp_dir()
+            .join("moon-process-tests")
+            .join(format!("{name}-{}", std::process::id()));
+        let source = root.join("source");
+        let target = root.join("target");
+
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&source).unwrap();
+        fs::create_dir_all(&target).unwrap();
+        fs::write(source.join("file.txt"), "source").unwrap();
+        fs::write(target.join("file.txt"), "target").unwrap();
+
+        (source, target)
+    }
+
+    #[tokio::test]
+    async fn mounts_source_over_target() {
+        if !is_mount_namespace_supported() {
+            return;
+        }
+
+        let (source, target) = create_dirs("mount");
+
+        let mut command = create_command("cat file.txt; echo ' created' > created.txt");
+        command.cwd(&target);
+        command.bind_mount(&source, &target);
+
+        let output = command.exec_capture_output().await.unwrap();
+
+        assert_eq!(output.stdout.as_ref(), b"source");
+
+        // Writes go to the source, and the mount isn't visible to us
+        assert!(source.join("created.txt").exists());
+        assert!(!target.join("created.txt").exists());
+        assert_eq!(
+            fs::read_to_string(target.join("file.txt")).unwrap(),
+            "target"
+        );
+    }
+
+    #[tokio::test]
+    async fn hides_files_not_in_source() {
+        if !is_mount_namespace_supported() {
+            return;
+        }
+
+        let (source, target) = create_dirs("hide");
+        fs::write(target.join("secret.txt"), "secret").unwrap();
+
+        let mut command = create_command("cat secret.txt");
+        command.cwd(&target);
+        command.bind_mount(&source, &target);
+
+        assert!(command.exec_capture_output().await.is_err());
+    }
+}
@@ -1,6 +1,6 @@
 #![cfg(unix)]
 
-use moon_process::ProcessRegistry;
+use moon_process::{ProcessRegistry, ProcessScope};
 use std::sync::Arc;
 use tokio::process::{Child, Command};
 
@@ -52,4 +52,61 @@ mod process_registry {
             .wait_for_running_to_shutdown()
             .await;
     }
+
+    #[tokio::test]
+    async fn tracks_children_spawned_within_a_scope() {
+        let registry = ProcessRegistry::new(2000);
+        let scope = ProcessScope::new();
+
+        let scoped = scope.run(registry.add_running(spawn_sleep())).await;
+        let unscoped = registry.add_running(spawn_sleep()).await;
+
+        assert!(scope.contains(scoped.id()));
+        assert!(!scope.contains(unscoped.id()));
+        assert_eq!(
+            registry
+                .get_running_in_scope(&scope)
+                .await
+                .into_keys()
+                .collect::<Vec<_>>(),
+            vec![scoped.id()]
+        );
+
+        scope.run(registry.remove_running(scoped.clone())).await;
+
+        assert!(scope.is_empty());
+
+        registry.remove_running(unscoped.clone()).await;
+
+        let _ = scoped.kill().await;
+        let _ = unscoped.kill().await;
+    }
+
+    #[tokio::test]
+    async fn terminates_only_scoped_children() {
+        let registry = ProcessRegistry::new(2000);
+        let scope = ProcessScope::new();
+
+        let scoped = scope.run(registry.add_running(spawn_sleep())).await;
+        let unscoped = registry.add_running(spawn_sleep()).await;
+
+        registry.terminate_scope(&scope).await;
+        registry.wait_for_scope_to_shutdown(&scope).await;
+
+        assert!(registry.get_running_by_pid(scoped.id()).await.is_none());
+        assert!(registry.get_running_by_pid(unscoped.id()).await.is_some());
+
+        registry.remove_running(unscoped.clone()).await;
+
+        let _ = unscoped.kill().await;
+    }
+
+    #[tokio::test]
+    async fn scope_is_not_current_outside_of_run() {
+        assert!(ProcessScope::current().is_none());
+
+        let scope = ProcessScope::new();
+
+        assert!(scope.run(async { ProcessScope::current().is_some() }).await);
+    }
 }
@@ -40,6 +40,7 @@ pub struct ProjectBuilder<'app> {
     source: &'app WorkspaceRelativePath,
     aliases: Vec<ProjectAlias>,
     root: PathBuf,
+    version: Option<String>,
 
     pub language: LanguageType,
 
@@ -81,6 +82,7 @@ impl<'app> ProjectBuilder<'app> {
             language: LanguageType::Unknown,
             toolchains: vec![],
             toolchains_inheritance: vec![],
+            version: None,
         })
     }
 
@@ -291,6 +293,12 @@ impl<'app> ProjectBuilder<'app> {
         self
     }
 
+    /// Set the version of the project, if one has not already been set.
+    pub fn set_version(&mut self, version: String) -> &mut Self {
+        self.version.get_or_insert(version);
+        self
+    }
+
     #[instrument(name = "build_project", skip_all)]
     pub async fn build(mut self) -> miette::Result<Project> {
         // Build dependencies first since they're required for tasks
@@ -315,6 +323,7 @@ impl<'app> ProjectBuilder<'app> {
             tasks,
             task_targets,
             toolchains: self.toolchains,
+            version: self.version,
             ..Project::default()
         };
 
@@ -2342,6 +2342,176 @@ mod project_graph {
 
             assert_eq!(get_ids_from_projects(projects), vec!["b", "d"]);
         }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn with_negated_conditions() {
+            let (_sandbox, graph) = build_graph_from_fixture("query").await;
+
+            let projects = graph
+                .query_projects(
+                    build_query("projectLayer=library && !(taskType=build || projectTag=three)")
+                        .unwrap(),
+                )
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), Vec::<String>::new());
+
+            let projects = graph
+                .query_projects(build_query("NOT (language IN [javascript, go])").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["b", "c"]);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn by_task_persistent() {
+            let (_sandbox, graph) = build_graph_from_fixture("query").await;
+
+            let projects = graph
+                .query_projects(build_query("taskPersistent=true").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["a"]);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn by_depends_on() {
+            let (_sandbox, graph) = build_graph_from_fixture("dependencies").await;
+
+            let projects = graph
+                .query_projects(build_query("dependsOn=c").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["b", "d"]);
+
+            let projects = graph
+                .query_projects(build_query("dependsOn!=c").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["a", "c"]);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn by_dependents() {
+            let (_sandbox, graph) = build_graph_from_fixture("dependencies").await;
+
+            let projects = graph
+                .query_projects(build_query("dependents=d").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["a", "b", "c"]);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn by_depth() {
+            let (_sandbox, graph) = build_graph_from_fixture("dependencies").await;
+
+            let projects = graph
+                .query_projects(build_query("depth<2").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["b", "c"]);
+
+            let projects = graph
+                .query_projects(build_query("depth>=3").unwrap())
+                .unwrap();
+
+            assert_eq!(get_ids_from_projects(projects), vec!["d"]);
+        }
+    }
+
+    mod paths {
+        use super::*;
+
+        fn map_path(
+            path: GraphPath<Id, DependencyScope>,
+        ) -> Vec<(String, String, DependencyScope)> {
+            path.into_iter()
+                .map(|hop| (hop.from.to_string(), hop.to.to_string(), hop.edge))
+                .collect()
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn returns_shortest_paths() {
+            let (_sandbox, graph) = build_graph_from_fixture("dependencies").await;
+            let projects = &graph.projects;
+            let a = projects.get_node_index(&graph.get_project("a").unwrap());
+            let c = projects.get_node_index(&graph.get_project("c").unwrap());
+            let d = projects.get_node_index(&graph.get_project("d").unwrap());
+
+            assert_eq!(
+                projects
+                    .shortest_paths_between(d, c)
+                    .into_iter()
+                    .map(map_path)
+                    .collect::<Vec<_>>(),
+                [[("d".into(), "c".into(), DependencyScope::Production)]]
+            );
+
+            assert_eq!(
+                projects
+                    .shortest_paths_between(a, c)
+                    .into_iter()
+                    .map(map_path)
+                    .collect::<Vec<_>>(),
+                [[
+                    ("a".into(), "b".into(), DependencyScope::Development),
+                    ("b".into(), "c".into(), DependencyScope::Production)
+                ]]
+            );
+
+            // Wrong direction
+            assert!(projects.shortest_paths_between(c, d).is_empty());
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn returns_all_simple_paths() {
+            let (_sandbox, graph) = build_graph_from_fixture("dependencies").await;
+            let projects = &graph.projects;
+            let c = projects.get_node_index(&graph.get_project("c").unwrap());
+            let d = projects.get_node_index(&graph.get_project("d").unwrap());
+
+            assert_eq!(
+                projects
+                    .simple_paths_between(d, c)
+                    .into_iter()
+                    .map(map_path)
+                    .collect::<Vec<_>>(),
+                [
+                    vec![("d".into(), "c".into(), DependencyScope::Production)],
+                    vec![
+                        ("d".into(), "b".into(), DependencyScope::Build),
+                        ("b".into(), "c".into(), DependencyScope::Production)
+                    ],
+                    vec![
+                        ("d".into(), "a".into(), DependencyScope::Peer),
+                        ("a".into(), "b".into(), DependencyScope::Development),
+                        ("b".into(), "c".into(), DependencyScope::Production)
+                    ],
+                ]
+            );
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn returns_strongly_connected_components() {
+            let (_sandbox, graph) = build_graph_from_fixture("dependencies").await;
+
+            assert!(graph.projects.strongly_connected_components().is_empty());
+
+            let (_sandbox, graph) = build_graph_from_fixture("dev-prod-loop").await;
+            let mut components = graph
+                .projects
+                .strongly_connected_components()
+                .into_iter()
+                .map(|mut component| {
+                    component.sort();
+                    map_ids(component)
+                })
+                .collect::<Vec<_>>();
+            components.sort();
+
+            assert_eq!(components, [["a", "b"]]);
+        }
     }
 
     mod to_dot {
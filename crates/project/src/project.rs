@@ -81,6 +81,10 @@ cacheable!(
         /// Toolchains derived from the configured language.
         #[serde(skip_serializing_if = "Vec::is_empty")]
         pub toolchains: Vec<Id>,
+
+        /// Current version derived from plugins, typically toolchain manifests.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub version: Option<String>,
     }
 );
 
@@ -12,6 +12,9 @@ pub type FieldValues<'l> = Vec<FieldValue<'l>>;
 
 #[derive(Debug, PartialEq)]
 pub enum Field<'l> {
+    Dependents(FieldValues<'l>),
+    DependsOn(FieldValues<'l>),
+    Depth(Vec<usize>),
     Language(Vec<LanguageType>),
     Project(FieldValues<'l>),
     ProjectAlias(FieldValues<'l>),
@@ -21,6 +24,9 @@ pub enum Field<'l> {
     ProjectStack(Vec<StackType>),
     ProjectTag(FieldValues<'l>),
     Task(FieldValues<'l>),
+    TaskInteractive(Vec<bool>),
+    TaskInternal(Vec<bool>),
+    TaskPersistent(Vec<bool>),
     TaskTag(FieldValues<'l>),
     TaskToolchain(FieldValues<'l>),
     TaskType(Vec<TaskType>),
@@ -35,6 +41,9 @@ pub enum Condition<'l> {
     Criteria {
         criteria: Criteria<'l>,
     },
+    NegatedCriteria {
+        criteria: Criteria<'l>,
+    },
 }
 
 impl Condition<'_> {
@@ -51,8 +60,10 @@ impl Condition<'_> {
                 ComparisonOperator::NotEqual => !haystack.contains(&Cow::Borrowed(needle)),
                 ComparisonOperator::Like => GlobSet::new(haystack)?.is_included(needle),
                 ComparisonOperator::NotLike => !GlobSet::new(haystack)?.is_included(needle),
+                // Ordering operators are only supported for numbers
+                _ => false,
             },
-            Condition::Criteria { .. } => false,
+            _ => false,
         })
     }
 
@@ -70,6 +81,32 @@ impl Condition<'_> {
         Ok(false)
     }
 
+    /// Like [`Condition::matches_list`], but negated operators require that
+    /// none of the needles match, instead of at least one needle not matching.
+    /// This is used for relations, where `dependsOn!=foo` should exclude
+    /// any node that depends on `foo`, regardless of its other relations.
+    pub fn matches_relations<T: AsRef<str>>(
+        &self,
+        haystack: &FieldValues,
+        needles: &[T],
+    ) -> miette::Result<bool> {
+        if let Condition::Field {
+            op: ComparisonOperator::NotEqual | ComparisonOperator::NotLike,
+            ..
+        } = self
+        {
+            for needle in needles {
+                if !self.matches(haystack, needle)? {
+                    return Ok(false);
+                }
+            }
+
+            return Ok(true);
+        }
+
+        self.matches_list(haystack, needles)
+    }
+
     pub fn matches_enum<T: PartialEq>(&self, haystack: &[T], needle: &T) -> miette::Result<bool> {
         Ok(match self {
             Condition::Field { op, .. } => match op {
@@ -78,7 +115,27 @@ impl Condition<'_> {
                 // Like and NotLike are not supported for enums
                 _ => false,
             },
-            Condition::Criteria { .. } => false,
+            _ => false,
+        })
+    }
+
+    pub fn matches_number(&self, haystack: &[usize], needle: usize) -> miette::Result<bool> {
+        Ok(match self {
+            Condition::Field { op, .. } => match op {
+                ComparisonOperator::Equal => haystack.contains(&needle),
+                ComparisonOperator::NotEqual => !haystack.contains(&needle),
+                ComparisonOperator::LessThan => haystack.iter().any(|value| needle < *value),
+                ComparisonOperator::LessThanOrEqual => {
+                    haystack.iter().any(|value| needle <= *value)
+                }
+                ComparisonOperator::GreaterThan => haystack.iter().any(|value| needle > *value),
+                ComparisonOperator::GreaterThanOrEqual => {
+                    haystack.iter().any(|value| needle >= *value)
+                }
+                // Like and NotLike are not supported for numbers
+                _ => false,
+            },
+            _ => false,
         })
     }
 }
@@ -118,6 +175,28 @@ fn build_criteria_enum<T: FromStr>(
     Ok(result)
 }
 
+fn build_criteria_number(
+    field: &str,
+    op: &ComparisonOperator,
+    values: FieldValues<'_>,
+) -> miette::Result<Vec<usize>> {
+    if matches!(op, ComparisonOperator::Like | ComparisonOperator::NotLike) {
+        return Err(QueryError::UnsupportedLikeOperator(field.to_owned()).into());
+    }
+
+    let mut result = vec![];
+
+    for value in values {
+        result.push(
+            value
+                .parse()
+                .map_err(|_| QueryError::InvalidNumberValue(field.to_owned(), value.to_string()))?,
+        );
+    }
+
+    Ok(result)
+}
+
 fn build_criteria(ast: Vec<AstNode<'_>>) -> miette::Result<Criteria<'_>> {
     let mut op = None;
     let mut conditions = vec![];
@@ -125,7 +204,21 @@ fn build_criteria(ast: Vec<AstNode<'_>>) -> miette::Result<Criteria<'_>> {
     for node in ast {
         match node {
             AstNode::Comparison { field, op, value } => {
+                if matches!(
+                    op,
+                    ComparisonOperator::LessThan
+                        | ComparisonOperator::LessThanOrEqual
+                        | ComparisonOperator::GreaterThan
+                        | ComparisonOperator::GreaterThanOrEqual
+                ) && field != "depth"
+                {
+                    return Err(QueryError::UnsupportedOrderingOperator(field.to_string()).into());
+                }
+
                 let field = match field.as_ref() {
+                    "dependents" => Field::Dependents(value),
+                    "dependsOn" => Field::DependsOn(value),
+                    "depth" => Field::Depth(build_criteria_number(&field, &op, value)?),
                     "language" => {
                         Field::Language(build_criteria_enum::<LanguageType>(&field, &op, value)?)
                     }
@@ -141,6 +234,15 @@ fn build_criteria(ast: Vec<AstNode<'_>>) -> miette::Result<Criteria<'_>> {
                     }
                     "projectTag" | "projectTags" | "tag" => Field::ProjectTag(value),
                     "task" => Field::Task(value),
+                    "taskInteractive" => {
+                        Field::TaskInteractive(build_criteria_enum::<bool>(&field, &op, value)?)
+                    }
+                    "taskInternal" => {
+                        Field::TaskInternal(build_criteria_enum::<bool>(&field, &op, value)?)
+                    }
+                    "taskPersistent" => {
+                        Field::TaskPersistent(build_criteria_enum::<bool>(&field, &op, value)?)
+                    }
                     "taskTag" | "taskTags" => Field::TaskTag(value),
                     "taskToolchain" | "taskToolchains" => Field::TaskToolchain(value),
                     "taskType" => {
@@ -167,6 +269,11 @@ fn build_criteria(ast: Vec<AstNode<'_>>) -> miette::Result<Criteria<'_>> {
                     criteria: build_criteria(nodes)?,
                 });
             }
+            AstNode::NegatedGroup { nodes } => {
+                conditions.push(Condition::NegatedCriteria {
+                    criteria: build_criteria(nodes)?,
+                });
+            }
         }
     }
 
@@ -1,8 +1,4 @@
-use pest::{
-    Parser,
-    error::Error,
-    iterators::{Pair, Pairs},
-};
+use pest::{Parser, error::Error, iterators::Pair};
 use pest_derive::Parser;
 use std::borrow::Cow;
 use tracing::instrument;
@@ -22,9 +18,13 @@ pub enum LogicalOperator {
 pub enum ComparisonOperator {
     #[default]
     Equal, // =
-    NotEqual, // !=
-    Like,     // ~
-    NotLike,  // !~
+    NotEqual,           // !=
+    Like,               // ~
+    NotLike,            // !~
+    LessThan,           // <
+    LessThanOrEqual,    // <=
+    GreaterThan,        // >
+    GreaterThanOrEqual, // >=
 }
 
 #[derive(Debug, PartialEq)]
@@ -40,6 +40,9 @@ pub enum AstNode<'l> {
     Group {
         nodes: Vec<AstNode<'l>>,
     },
+    NegatedGroup {
+        nodes: Vec<AstNode<'l>>,
+    },
 }
 
 fn parse_ast_node(pair: Pair<Rule>) -> Result<Option<AstNode>, Box<Error<Rule>>> {
@@ -60,6 +63,13 @@ fn parse_ast_node(pair: Pair<Rule>) -> Result<Option<AstNode>, Box<Error<Rule>>>
                     Rule::neq => ComparisonOperator::NotEqual,
                     Rule::like => ComparisonOperator::Like,
                     Rule::nlike => ComparisonOperator::NotLike,
+                    // Sets are shorthand for a list of values
+                    Rule::in_set => ComparisonOperator::Equal,
+                    Rule::nin_set => ComparisonOperator::NotEqual,
+                    Rule::lt => ComparisonOperator::LessThan,
+                    Rule::lte => ComparisonOperator::LessThanOrEqual,
+                    Rule::gt => ComparisonOperator::GreaterThan,
+                    Rule::gte => ComparisonOperator::GreaterThanOrEqual,
                     _ => unreachable!(),
                 },
                 value: match value.as_rule() {
@@ -73,9 +83,22 @@ fn parse_ast_node(pair: Pair<Rule>) -> Result<Option<AstNode>, Box<Error<Rule>>>
                 },
             })
         }
-        Rule::expr_group => Some(AstNode::Group {
-            nodes: parse_ast(pair.into_inner())?,
-        }),
+        Rule::expr_group => {
+            let mut inner = pair.into_inner().peekable();
+
+            if inner
+                .next_if(|pair| pair.as_rule() == Rule::negate)
+                .is_some()
+            {
+                Some(AstNode::NegatedGroup {
+                    nodes: parse_ast(inner)?,
+                })
+            } else {
+                Some(AstNode::Group {
+                    nodes: parse_ast(inner)?,
+                })
+            }
+        }
         Rule::and => Some(AstNode::Op {
             op: LogicalOperator::And,
         }),
@@ -87,7 +110,9 @@ fn parse_ast_node(pair: Pair<Rule>) -> Result<Option<AstNode>, Box<Error<Rule>>>
     })
 }
 
-fn parse_ast(pairs: Pairs<'_, Rule>) -> Result<Vec<AstNode<'_>>, Box<Error<Rule>>> {
+fn parse_ast<'l>(
+    pairs: impl Iterator<Item = Pair<'l, Rule>>,
+) -> Result<Vec<AstNode<'l>>, Box<Error<Rule>>> {
     let mut ast = vec![];
 
     for pair in pairs {
@@ -20,10 +20,18 @@ pub enum QueryError {
     #[error("Unknown query value {} for field {}.", .1.style(Style::Symbol), .0.style(Style::Id))]
     UnknownFieldValue(String, String),
 
+    #[diagnostic(code(query::invalid_number_value))]
+    #[error("Invalid query value {} for field {}, expected a number.", .1.style(Style::Symbol), .0.style(Style::Id))]
+    InvalidNumberValue(String, String),
+
     #[diagnostic(code(query::operator::unsupported))]
     #[error("Like operators (~ and !~) are not supported for field {}.", .0.style(Style::Id))]
     UnsupportedLikeOperator(String),
 
+    #[diagnostic(code(query::operator::unsupported_ordering))]
+    #[error("Ordering operators (<, <=, >, and >=) are only supported for numeric fields, not {}.", .0.style(Style::Id))]
+    UnsupportedOrderingOperator(String),
+
     #[diagnostic(code(query::parse::failed))]
     #[error("Failed to parse query:\n\n{}", .0.style(Style::MutedLight))]
     ParseFailure(String),
@@ -739,4 +739,165 @@ mod mql_build {
             build_query("taskType!~node").unwrap();
         }
     }
+
+    mod negation {
+        use super::*;
+
+        #[test]
+        fn negates_group() {
+            assert_eq!(
+                build_query("language=javascript && !(taskType=build || taskType=run)").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![
+                        Condition::Field {
+                            field: Field::Language(vec![LanguageType::JavaScript]),
+                            op: ComparisonOperator::Equal,
+                        },
+                        Condition::NegatedCriteria {
+                            criteria: Criteria {
+                                op: LogicalOperator::Or,
+                                conditions: vec![
+                                    Condition::Field {
+                                        field: Field::TaskType(vec![TaskType::Build]),
+                                        op: ComparisonOperator::Equal,
+                                    },
+                                    Condition::Field {
+                                        field: Field::TaskType(vec![TaskType::Run]),
+                                        op: ComparisonOperator::Equal,
+                                    },
+                                ],
+                                input: None,
+                            }
+                        }
+                    ],
+                    input: Some("language=javascript && !(taskType=build || taskType=run)".into())
+                }
+            );
+        }
+    }
+
+    mod relations {
+        use super::*;
+
+        #[test]
+        fn depends_on() {
+            assert_eq!(
+                build_query("dependsOn IN [foo, bar]").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::DependsOn(value_list(["foo", "bar"])),
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("dependsOn IN [foo, bar]".into())
+                }
+            );
+        }
+
+        #[test]
+        fn dependents_like() {
+            assert_eq!(
+                build_query("dependents~app:*").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::Dependents(value_list(["app:*"])),
+                        op: ComparisonOperator::Like,
+                    }],
+                    input: Some("dependents~app:*".into())
+                }
+            );
+        }
+    }
+
+    mod depth {
+        use super::*;
+
+        #[test]
+        fn less_than() {
+            assert_eq!(
+                build_query("depth<2").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::Depth(vec![2]),
+                        op: ComparisonOperator::LessThan,
+                    }],
+                    input: Some("depth<2".into())
+                }
+            );
+        }
+
+        #[test]
+        fn matches_numbers() {
+            let criteria = build_query("depth>=1").unwrap();
+            let condition = &criteria.conditions[0];
+            let Condition::Field {
+                field: Field::Depth(depths),
+                ..
+            } = condition
+            else {
+                panic!();
+            };
+
+            assert!(!condition.matches_number(depths, 0).unwrap());
+            assert!(condition.matches_number(depths, 1).unwrap());
+            assert!(condition.matches_number(depths, 5).unwrap());
+        }
+
+        #[test]
+        #[should_panic(expected = "Invalid query value abc for field depth, expected a number.")]
+        fn errors_for_non_number() {
+            build_query("depth=abc").unwrap();
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Ordering operators (<, <=, >, and >=) are only supported for numeric fields, not project."
+        )]
+        fn errors_for_ordering_non_number() {
+            build_query("project>foo").unwrap();
+        }
+    }
+
+    mod task_options {
+        use super::*;
+
+        #[test]
+        fn persistent() {
+            assert_eq!(
+                build_query("taskPersistent=true").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::TaskPersistent(vec![true]),
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("taskPersistent=true".into())
+                }
+            );
+        }
+
+        #[test]
+        fn interactive() {
+            assert_eq!(
+                build_query("taskInteractive!=false").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::TaskInteractive(vec![false]),
+                        op: ComparisonOperator::NotEqual,
+                    }],
+                    input: Some("taskInteractive!=false".into())
+                }
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Unknown query value yes for field taskInternal.")]
+        fn errors_for_non_bool() {
+            build_query("taskInternal=yes").unwrap();
+        }
+    }
 }
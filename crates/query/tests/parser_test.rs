@@ -385,4 +385,115 @@ mod mql_parse {
             }],
         );
     }
+
+    #[test]
+    fn comp_in_set() {
+        assert_eq!(
+            parse_query("key IN [v1, v2]").unwrap(),
+            vec![AstNode::Comparison {
+                field: "key".into(),
+                op: ComparisonOperator::Equal,
+                value: vec!["v1".into(), "v2".into()],
+            }],
+        );
+    }
+
+    #[test]
+    fn comp_not_in_set() {
+        assert_eq!(
+            parse_query("key NOT IN [v1,v2]").unwrap(),
+            vec![AstNode::Comparison {
+                field: "key".into(),
+                op: ComparisonOperator::NotEqual,
+                value: vec!["v1".into(), "v2".into()],
+            }],
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_in_set_without_list() {
+        parse_query("key IN value").unwrap();
+    }
+
+    #[test]
+    fn comp_ordering() {
+        for (input, op) in [
+            ("key<2", ComparisonOperator::LessThan),
+            ("key <= 2", ComparisonOperator::LessThanOrEqual),
+            ("key>2", ComparisonOperator::GreaterThan),
+            ("key >= 2", ComparisonOperator::GreaterThanOrEqual),
+        ] {
+            assert_eq!(
+                parse_query(input).unwrap(),
+                vec![AstNode::Comparison {
+                    field: "key".into(),
+                    op,
+                    value: vec!["2".into()],
+                }],
+            );
+        }
+    }
+
+    #[test]
+    fn negated_groups() {
+        for input in ["!(k1=v1 || k2=v2)", "NOT (k1=v1 || k2=v2)"] {
+            assert_eq!(
+                parse_query(input).unwrap(),
+                vec![AstNode::NegatedGroup {
+                    nodes: vec![
+                        AstNode::Comparison {
+                            field: "k1".into(),
+                            op: ComparisonOperator::Equal,
+                            value: vec!["v1".into()],
+                        },
+                        AstNode::Op {
+                            op: LogicalOperator::Or,
+                        },
+                        AstNode::Comparison {
+                            field: "k2".into(),
+                            op: ComparisonOperator::Equal,
+                            value: vec!["v2".into()],
+                        },
+                    ]
+                }],
+            );
+        }
+    }
+
+    #[test]
+    fn negated_group_after_op() {
+        assert_eq!(
+            parse_query("k1=v1 AND NOT (k2=v2)").unwrap(),
+            vec![
+                AstNode::Comparison {
+                    field: "k1".into(),
+                    op: ComparisonOperator::Equal,
+                    value: vec!["v1".into()],
+                },
+                AstNode::Op {
+                    op: LogicalOperator::And,
+                },
+                AstNode::NegatedGroup {
+                    nodes: vec![AstNode::Comparison {
+                        field: "k2".into(),
+                        op: ComparisonOperator::Equal,
+                        value: vec!["v2".into()],
+                    }]
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn target_values() {
+        assert_eq!(
+            parse_query("key=app:build").unwrap(),
+            vec![AstNode::Comparison {
+                field: "key".into(),
+                op: ComparisonOperator::Equal,
+                value: vec!["app:build".into()],
+            }],
+        );
+    }
 }
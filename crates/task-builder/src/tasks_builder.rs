@@ -3,22 +3,22 @@
 use crate::tasks_builder_error::TasksBuilderError;
 use indexmap::{IndexMap, IndexSet};
 use moon_common::{
-    Id, color,
+    Id, color, parse_byte_size,
     path::{WorkspaceRelativePath, encode_component, is_root_level_source},
 };
 use moon_config::{
-    EnvMap, InheritedTasksConfig, Input, MergeStrategy, ProjectConfig, ProjectDependencyConfig,
-    ProjectInput, ProjectWorkspaceInheritedTasksConfig, TaskArgs, TaskConfig, TaskDependency,
-    TaskDependencyConfig, TaskOptionAffectedFilesEntry, TaskOptionCache, TaskOptionRunInCI,
-    TaskOptionsConfig, TaskOutputStyle, TaskPreset, TaskPriority, TaskType, ToolchainsConfig,
-    is_glob_like, merge_index_map, merge_vec,
+    EnvMap, InheritedTasksConfig, Input, MergeStrategy, Output, ProjectConfig,
+    ProjectDependencyConfig, ProjectInput, ProjectWorkspaceInheritedTasksConfig, TaskArgs,
+    TaskConfig, TaskDependency, TaskDependencyConfig, TaskOptionAffectedFilesEntry,
+    TaskOptionCache, TaskOptionRunInCI, TaskOptionsConfig, TaskOutputStyle, TaskPreset,
+    TaskPriority, TaskType, ToolchainsConfig, is_glob_like, merge_index_map, merge_vec,
 };
 use moon_config_loader::ConfigLoader;
 use moon_env_var::contains_env_var;
 use moon_target::{Target, TargetProjectScope};
 use moon_task::{
-    Task, TaskArg, TaskOptionAffectedFiles, TaskOptionEnvFile, TaskOptions, TaskState,
-    TaskUnixShell, TaskWindowsShell,
+    Task, TaskArg, TaskOptionAffectedFiles, TaskOptionEnvFile, TaskOptionResources,
+    TaskOptionSemaphore, TaskOptions, TaskState, TaskUnixShell, TaskWindowsShell,
 };
 use moon_toolchain::filter_and_resolve_toolchain_ids;
 use moon_toolchain_plugin::{ToolchainRegistry, api::DefineRequirementsInput};
@@ -550,6 +550,33 @@ impl<'proj> TasksBuilder<'proj> {
             };
         }
 
+        // Test reports are cached with the outputs, so that cached runs can still
+        // report their results. Add them *after* the type has been inferred,
+        // otherwise test tasks would be inferred as build tasks!
+        if let Some(test_reports) = &task.options.test_reports {
+            for report in test_reports {
+                if task
+                    .outputs
+                    .iter()
+                    .any(|output| output.as_str() == report.as_str())
+                {
+                    continue;
+                }
+
+                task.outputs.push(match report.to_owned() {
+                    Output::File(mut file) => {
+                        file.optional = Some(true);
+                        Output::File(file)
+                    }
+                    Output::Glob(mut glob) => {
+                        glob.optional = Some(true);
+                        Output::Glob(glob)
+                    }
+                    other => other,
+                });
+            }
+        }
+
         if !state.set_run_in_ci {
             task.options.run_in_ci = TaskOptionRunInCI::Enabled(matches!(
                 task.type_of,
@@ -750,6 +777,13 @@ impl<'proj> TasksBuilder<'proj> {
                 options.priority = *priority;
             }
 
+            if let Some(resources) = &config.resources {
+                options.resources = Some(TaskOptionResources {
+                    cpu: resources.cpu,
+                    memory: resources.memory.as_deref().and_then(parse_byte_size),
+                });
+            }
+
             if let Some(retry_count) = &config.retry_count {
                 options.retry_count = *retry_count;
             }
@@ -771,11 +805,27 @@ impl<'proj> TasksBuilder<'proj> {
                 options.run_from_workspace_root = *run_from_workspace_root;
             }
 
+            if let Some(sandbox) = &config.sandbox {
+                options.sandbox = *sandbox;
+            }
+
+            if let Some(semaphore) = &config.semaphore {
+                options.semaphore = Some(TaskOptionSemaphore {
+                    global: semaphore.global,
+                    name: semaphore.name.clone(),
+                    permits: semaphore.permits,
+                });
+            }
+
             if let Some(shell) = &config.shell {
                 options.shell = Some(*shell);
                 state.shell_disabled = !shell;
             }
 
+            if let Some(test_reports) = &config.test_reports {
+                options.test_reports = Some(test_reports.to_owned());
+            }
+
             if let Some(timeout) = &config.timeout {
                 options.timeout = Some(*timeout);
             }
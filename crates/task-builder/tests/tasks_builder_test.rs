@@ -1160,6 +1160,34 @@ tasks:
 
             assert_eq!(task.options.os, Some(vec![]));
         }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn adds_test_reports_as_optional_outputs() {
+            let sandbox = create_sandbox("builder");
+            let container = TasksBuilderContainer::new(sandbox.path());
+
+            let tasks = container.build_tasks("options").await;
+
+            let task = tasks.get("test-reports").unwrap();
+
+            let mut file = stub_file_output("reports/junit.xml");
+            file.optional = Some(true);
+
+            let mut glob = stub_glob_output("reports/*.tap");
+            glob.optional = Some(true);
+
+            assert_eq!(task.outputs, vec![Output::File(file), Output::Glob(glob)]);
+
+            // Reports shouldn't change the inferred type
+            assert_eq!(task.type_of, TaskType::Test);
+
+            let task = tasks.get("test-reports-with-outputs").unwrap();
+
+            assert_eq!(
+                task.outputs,
+                vec![Output::File(stub_file_output("reports/junit.xml"))]
+            );
+        }
     }
 
     mod default_options {
@@ -10,6 +10,8 @@ use moon_time::{now_millis, now_timestamp};
 use pathdiff::diff_paths;
 use regex::Regex;
 use rustc_hash::{FxHashMap, FxHashSet};
+use starbase_utils::json::{self, JsonValue};
+use starbase_utils::yaml;
 use std::borrow::Cow;
 use std::env;
 use std::mem;
@@ -108,6 +110,8 @@ impl<'graph> TokenExpander<'graph> {
         if self.has_token_function(&command) {
             let result = self.replace_function(task, &command)?;
 
+            self.infer_inputs_from_result(task, &result);
+
             if let (Some(token), Some(value)) = (result.token, result.value) {
                 command = Cow::Owned(command.replace(&token, &value));
             }
@@ -436,6 +440,21 @@ impl<'graph> TokenExpander<'graph> {
                     &mut result,
                 )?;
             }
+            "changed" => {
+                self.check_scope(
+                    task,
+                    token,
+                    &[TokenScope::Script, TokenScope::Args, TokenScope::Env],
+                )?;
+
+                result.files.extend(
+                    self.project
+                        .get_file_group(arg)?
+                        .files(&self.context.workspace_root, false)?
+                        .into_iter()
+                        .filter(|file| self.context.vcs_changed_files.contains(file)),
+                );
+            }
             // Inputs, outputs
             "in" => {
                 self.check_scope(task, token, &[TokenScope::Script, TokenScope::Args])?;
@@ -528,11 +547,56 @@ impl<'graph> TokenExpander<'graph> {
             "envs" => {
                 self.check_scope(task, token, &[TokenScope::Inputs])?;
 
-                self.update_result_for_file_group(
-                    self.project.get_file_group(arg)?,
-                    func,
-                    &mut result,
+                // A file group takes precedence, otherwise the argument
+                // is a prefix of environment variables to match
+                if let Ok(group) = self.project.get_file_group(arg) {
+                    self.update_result_for_file_group(group, func, &mut result)?;
+                } else {
+                    let mut vars = vec![];
+
+                    GlobalEnvBag::instance().list(|var_name, _| {
+                        if let Some(var_name) = var_name.to_str()
+                            && var_name.starts_with(arg)
+                        {
+                            vars.push(var_name.to_owned());
+                        }
+                    });
+
+                    vars.sort();
+
+                    result.env.extend(vars);
+                }
+            }
+            "json" => {
+                self.check_scope(
+                    task,
+                    token,
+                    &[
+                        TokenScope::Command,
+                        TokenScope::Script,
+                        TokenScope::Args,
+                        TokenScope::Env,
+                    ],
                 )?;
+
+                let (file, key_path) = self.read_json_args(task, token, arg)?;
+                let file = match file.strip_prefix('/') {
+                    Some(inner) => {
+                        path::expand_to_workspace_relative(RelativeFrom::Workspace, inner)
+                    }
+                    None => path::expand_to_workspace_relative(
+                        RelativeFrom::Project(self.project.source.as_str()),
+                        file,
+                    ),
+                };
+
+                result.value = Some(self.read_json_value(task, token, &file, key_path)?);
+
+                // The file's content affects the expanded value,
+                // so it must always be considered for hashing
+                result
+                    .files_for_input
+                    .insert(file, TaskFileInput::default());
             }
             "meta" => {
                 self.check_scope(
@@ -638,6 +702,17 @@ impl<'graph> TokenExpander<'graph> {
             "projectRoot" => Cow::Owned(self.stringify_path(&project.root, Some(task))?),
             "projectSource" => Cow::Borrowed(project.source.as_str()),
             "projectStack" => Cow::Owned(project.stack.to_string()),
+            "projectVersion" => match &project.version {
+                Some(version) => Cow::Borrowed(version.as_str()),
+                None => {
+                    return Err(TokenExpanderError::MissingProjectVersion {
+                        project: project.id.to_string(),
+                        target: task.target.to_string(),
+                        token: token_match.as_str().to_owned(),
+                    }
+                    .into());
+                }
+            },
             // Task
             "target" => Cow::Borrowed(task.target.as_str()),
             "task" | "taskId" => Cow::Borrowed(task.id.as_str()),
@@ -656,6 +731,12 @@ impl<'graph> TokenExpander<'graph> {
             "vcsBranch" => Cow::Borrowed(self.context.vcs_branch.as_ref().as_str()),
             "vcsRepository" => Cow::Borrowed(self.context.vcs_repository.as_ref().as_str()),
             "vcsRevision" => Cow::Borrowed(self.context.vcs_revision.as_ref().as_str()),
+            "vcsShortRevision" => {
+                let revision = self.context.vcs_revision.as_ref().as_str();
+
+                Cow::Borrowed(revision.get(0..7).unwrap_or(revision))
+            }
+            "vcsTag" => Cow::Borrowed(self.context.vcs_tag.as_ref().as_str()),
             _ => {
                 return Ok(value);
             }
@@ -690,6 +771,66 @@ impl<'graph> TokenExpander<'graph> {
             })?)
     }
 
+    fn read_json_args<'arg>(
+        &self,
+        task: &Task,
+        token: &str,
+        arg: &'arg str,
+    ) -> miette::Result<(&'arg str, &'arg str)> {
+        match arg.split_once(',') {
+            Some((file, key_path)) if !file.trim().is_empty() && !key_path.trim().is_empty() => {
+                Ok((file.trim(), key_path.trim()))
+            }
+            _ => Err(TokenExpanderError::InvalidJsonArgs {
+                target: task.target.to_string(),
+                token: token.to_owned(),
+            }
+            .into()),
+        }
+    }
+
+    fn read_json_value(
+        &self,
+        task: &Task,
+        token: &str,
+        file: &WorkspaceRelativePathBuf,
+        key_path: &str,
+    ) -> miette::Result<String> {
+        let abs_file = file.to_logical_path(&self.context.workspace_root);
+
+        let data: JsonValue = if abs_file
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        {
+            yaml::read_file(&abs_file)?
+        } else {
+            json::read_file(&abs_file)?
+        };
+
+        let mut current = &data;
+
+        for key in key_path.split('.') {
+            let next = match current {
+                JsonValue::Array(list) => key.parse::<usize>().ok().and_then(|i| list.get(i)),
+                JsonValue::Object(map) => map.get(key),
+                _ => None,
+            };
+
+            current = next.ok_or_else(|| TokenExpanderError::MissingJsonValue {
+                file: file.to_string(),
+                path: key_path.to_owned(),
+                target: task.target.to_string(),
+                token: token.to_owned(),
+            })?;
+        }
+
+        Ok(match current {
+            JsonValue::Null => String::new(),
+            JsonValue::String(value) => value.to_owned(),
+            value => value.to_string(),
+        })
+    }
+
     fn update_result_for_file_group(
         &self,
         group: &FileGroup,
@@ -849,8 +990,10 @@ impl<'graph> TokenExpander<'graph> {
     }
 
     fn infer_inputs_from_result(&self, task: &mut Task, result: &ExpandedResult) {
+        // Files read while expanding (`@json`) are always inputs
+        task.input_files.extend(result.files_for_input.clone());
+
         if task.options.infer_inputs {
-            task.input_files.extend(result.files_for_input.clone());
             task.input_files.extend(
                 result
                     .files
@@ -26,6 +26,14 @@ pub enum TokenExpanderError {
     )]
     InvalidTokenIndexReference { target: String, token: String },
 
+    #[diagnostic(code(token_expander::invalid_json_args))]
+    #[error(
+        "Token {} in task {} requires a file path and a property path, separated by a comma.",
+        .token.style(Style::Symbol),
+        .target.style(Style::Label),
+    )]
+    InvalidJsonArgs { target: String, token: String },
+
     #[diagnostic(code(token_expander::invalid_scope))]
     #[error(
         "Token {} in task {} cannot be used within task {scope}.",
@@ -50,6 +58,36 @@ pub enum TokenExpanderError {
         token: String,
     },
 
+    #[diagnostic(code(token_expander::missing_json_value))]
+    #[error(
+        "Property path \"{path}\" does not exist in {} for token {} in task {}.",
+        .file.style(Style::File),
+        .token.style(Style::Symbol),
+        .target.style(Style::Label),
+    )]
+    MissingJsonValue {
+        file: String,
+        path: String,
+        target: String,
+        token: String,
+    },
+
+    #[diagnostic(
+        code(token_expander::missing_project_version),
+        help = "The project's toolchain plugins must provide a version, typically from a manifest."
+    )]
+    #[error(
+        "Token {} in task {} requires a version, but project {} does not have one.",
+        .token.style(Style::Symbol),
+        .target.style(Style::Label),
+        .project.style(Style::Id),
+    )]
+    MissingProjectVersion {
+        project: String,
+        target: String,
+        token: String,
+    },
+
     #[diagnostic(code(token_expander::missing_out_index))]
     #[error(
         "Output index {index} does not exist for token {} in task {}.",
@@ -13,6 +13,7 @@ use rustc_hash::{FxHashMap, FxHashSet};
 use starbase_sandbox::{create_empty_sandbox, create_sandbox, predicates::prelude::*};
 use std::borrow::Cow;
 use std::env;
+use std::sync::Arc;
 use utils::*;
 
 mod token_expander {
@@ -255,6 +256,135 @@ mod token_expander {
             assert_eq!(get_value("@meta(string)"), "\"custom\"");
             assert_eq!(get_value("@meta(list)"), "[\"item\",true]");
         }
+
+        #[test]
+        fn changed_filters_group_to_changed_files() {
+            let sandbox = create_sandbox("file-group");
+            let project_graph = create_project_graph([]);
+            let project = create_project(sandbox.path());
+            let task = create_task();
+
+            let mut context = create_context(sandbox.path());
+            context.vcs_changed_files = Arc::new(FxHashSet::from_iter([
+                "project/source/docs.md".into(),
+                "project/source/other/file.json".into(),
+                "project/source/dir/subdir/not-used.md".into(),
+            ]));
+
+            let expander = TokenExpander::new(&project_graph, &project, &context);
+            let mut result = expander.replace_function(&task, "@changed(all)").unwrap();
+            result.files.sort();
+
+            assert_eq!(
+                result.files,
+                ["project/source/docs.md", "project/source/other/file.json"]
+            );
+        }
+
+        #[test]
+        fn changed_returns_nothing_when_no_changes() {
+            let sandbox = create_sandbox("file-group");
+            let project_graph = create_project_graph([]);
+            let project = create_project(sandbox.path());
+            let task = create_task();
+            let context = create_context(sandbox.path());
+            let expander = TokenExpander::new(&project_graph, &project, &context);
+
+            let result = expander.replace_function(&task, "@changed(all)").unwrap();
+
+            assert!(result.files.is_empty());
+        }
+
+        #[test]
+        fn json_reads_values_from_files() {
+            let sandbox = create_empty_sandbox();
+            sandbox.create_file(
+                "project/source/package.json",
+                r#"{ "name": "pkg", "version": "1.2.3", "private": true, "files": ["lib", "src"], "engines": null }"#,
+            );
+            sandbox.create_file("project/source/config.yml", "build:\n  target: es2022\n");
+            sandbox.create_file("root.json", r#"{ "nested": { "value": 10 } }"#);
+
+            let project_graph = create_project_graph([]);
+            let project = create_project(sandbox.path());
+            let task = create_task();
+            let context = create_context(sandbox.path());
+            let expander = TokenExpander::new(&project_graph, &project, &context);
+
+            let get_value = |token: &str| {
+                expander
+                    .replace_function(&task, token)
+                    .unwrap()
+                    .value
+                    .unwrap()
+            };
+
+            assert_eq!(get_value("@json(package.json, version)"), "1.2.3");
+            assert_eq!(get_value("@json(package.json,private)"), "true");
+            assert_eq!(get_value("@json(package.json, files.1)"), "src");
+            assert_eq!(get_value("@json(package.json, files)"), "[\"lib\",\"src\"]");
+            assert_eq!(get_value("@json(package.json, engines)"), "");
+            assert_eq!(get_value("@json(config.yml, build.target)"), "es2022");
+            assert_eq!(get_value("@json(/root.json, nested.value)"), "10");
+        }
+
+        #[test]
+        fn json_marks_file_as_input() {
+            let sandbox = create_empty_sandbox();
+            sandbox.create_file("project/source/package.json", r#"{ "version": "1.2.3" }"#);
+
+            let project_graph = create_project_graph([]);
+            let project = create_project(sandbox.path());
+            let task = create_task();
+            let context = create_context(sandbox.path());
+            let expander = TokenExpander::new(&project_graph, &project, &context);
+
+            let result = expander
+                .replace_function(&task, "@json(package.json, version)")
+                .unwrap();
+
+            assert_eq!(
+                result.files_for_input,
+                create_file_input_map(vec!["project/source/package.json"])
+            );
+            assert!(result.files.is_empty());
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "does not exist in project/source/package.json for token @json(package.json, version) in task project:task."
+        )]
+        fn errors_if_json_value_missing() {
+            let sandbox = create_empty_sandbox();
+            sandbox.create_file("project/source/package.json", r#"{ "name": "pkg" }"#);
+
+            let project_graph = create_project_graph([]);
+            let project = create_project(sandbox.path());
+            let task = create_task();
+            let context = create_context(sandbox.path());
+            let expander = TokenExpander::new(&project_graph, &project, &context);
+
+            expander
+                .replace_function(&task, "@json(package.json, version)")
+                .unwrap();
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Token @json(package.json) in task project:task requires a file path and a property path, separated by a comma."
+        )]
+        fn errors_if_json_missing_path() {
+            let sandbox = create_empty_sandbox();
+            let project_graph = create_project_graph([]);
+            let project = create_project(sandbox.path());
+            let task = create_task();
+            let context = create_context(sandbox.path());
+            let expander = TokenExpander::new(&project_graph, &project, &context);
+
+            expander
+                .replace_function(&task, "@json(package.json)")
+                .unwrap();
+        }
     }
 
     mod vars {
@@ -422,6 +552,52 @@ mod token_expander {
                     .unwrap(),
                 "abcd1234"
             );
+            assert_eq!(
+                expander
+                    .replace_variable(&task, Cow::Borrowed("$vcsShortRevision"))
+                    .unwrap(),
+                "abcd123"
+            );
+            assert_eq!(
+                expander
+                    .replace_variable(&task, Cow::Borrowed("$vcsTag"))
+                    .unwrap(),
+                "v1.2.3"
+            );
+        }
+
+        #[test]
+        fn replaces_project_version() {
+            let sandbox = create_empty_sandbox();
+            let project_graph = create_project_graph([]);
+            let mut project = create_project(sandbox.path());
+            let task = create_task();
+            let context = create_context(sandbox.path());
+
+            project.version = Some("1.2.3".into());
+
+            assert_eq!(
+                TokenExpander::new(&project_graph, &project, &context)
+                    .replace_variable(&task, Cow::Borrowed("v$projectVersion"))
+                    .unwrap(),
+                "v1.2.3"
+            );
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Token $projectVersion in task project:task requires a version, but project project does not have one."
+        )]
+        fn errors_for_missing_project_version() {
+            let sandbox = create_empty_sandbox();
+            let project_graph = create_project_graph([]);
+            let project = create_project(sandbox.path());
+            let task = create_task();
+            let context = create_context(sandbox.path());
+
+            TokenExpander::new(&project_graph, &project, &context)
+                .replace_variable(&task, Cow::Borrowed("$projectVersion"))
+                .unwrap();
         }
 
         #[test]
@@ -762,6 +938,52 @@ mod token_expander {
     mod args {
         use super::*;
 
+        #[test]
+        fn supports_json_func() {
+            let sandbox = create_empty_sandbox();
+            sandbox.create_file("project/source/package.json", r#"{ "version": "1.2.3" }"#);
+
+            let project_graph = create_project_graph([]);
+            let project = create_project(sandbox.path());
+            let mut task = create_task();
+
+            task.args.push("--version".into());
+            task.args.push("@json(package.json, version)".into());
+
+            let context = create_context(sandbox.path());
+            let mut expander = TokenExpander::new(&project_graph, &project, &context);
+
+            assert_eq!(
+                expander.expand_args(&mut task).unwrap(),
+                vec!["--version", "1.2.3"]
+            );
+
+            // Always an input, regardless of `inferInputs`
+            assert!(!task.options.infer_inputs);
+            assert_eq!(
+                task.input_files,
+                create_file_input_map(vec!["project/source/package.json"])
+            );
+        }
+
+        #[test]
+        fn supports_changed_func() {
+            let sandbox = create_sandbox("file-group");
+            let project_graph = create_project_graph([]);
+            let project = create_project(sandbox.path());
+            let mut task = create_task();
+
+            task.args.push("@changed(all)".into());
+
+            let mut context = create_context(sandbox.path());
+            context.vcs_changed_files =
+                Arc::new(FxHashSet::from_iter(["project/source/docs.md".into()]));
+
+            let mut expander = TokenExpander::new(&project_graph, &project, &context);
+
+            assert_eq!(expander.expand_args(&mut task).unwrap(), vec!["docs.md"]);
+        }
+
         #[test]
         fn supports_meta_func() {
             let sandbox = create_empty_sandbox();
@@ -1541,6 +1763,36 @@ mod token_expander {
             );
         }
 
+        #[test]
+        fn supports_envs_func_with_prefix() {
+            let sandbox = create_empty_sandbox();
+            let project_graph = create_project_graph([]);
+            let project = create_project(sandbox.path());
+            let mut task = create_task();
+
+            task.inputs = vec![Input::TokenFunc("@envs(PREFIX_)".into())];
+
+            let bag = GlobalEnvBag::instance();
+            bag.set("PREFIX_TWO", "2");
+            bag.set("PREFIX_ONE", "1");
+            bag.set("OTHER_PREFIX_ONE", "1");
+
+            let context = create_context(sandbox.path());
+            let mut expander = TokenExpander::new(&project_graph, &project, &context);
+
+            assert_eq!(
+                expander.expand_inputs(&task).unwrap(),
+                ExpandedResult {
+                    env: vec!["PREFIX_ONE".into(), "PREFIX_TWO".into()],
+                    ..ExpandedResult::default()
+                }
+            );
+
+            bag.remove("PREFIX_TWO");
+            bag.remove("PREFIX_ONE");
+            bag.remove("OTHER_PREFIX_ONE");
+        }
+
         #[test]
         fn supports_envs_func() {
             let sandbox = create_sandbox("file-group");
@@ -68,6 +68,7 @@ pub fn create_context(workspace_root: &Path) -> GraphExpanderContext {
         vcs_branch: Arc::new(String::from("master")),
         vcs_repository: Arc::new(String::from("moonrepo/moon")),
         vcs_revision: Arc::new(String::from("abcd1234")),
+        vcs_tag: Arc::new(String::from("v1.2.3")),
         working_dir: workspace_root.to_path_buf(),
         workspace_root: workspace_root.to_path_buf(),
         ..Default::default()
@@ -14,4 +14,24 @@ pub enum TaskGraphError {
         source_target: String,
         target_target: String,
     },
+
+    #[diagnostic(
+        code(task_graph::mismatched_semaphore_permits),
+        help = "Tasks that share a semaphore must configure the same number of permits."
+    )]
+    #[error(
+        "Task {} configures {} permits for semaphore {}, but task {} configures {} permits.",
+        .target.style(Style::Id),
+        .permits,
+        .name.style(Style::Id),
+        .other_target.style(Style::Id),
+        .other_permits,
+    )]
+    MismatchedSemaphorePermits {
+        name: String,
+        target: String,
+        permits: u32,
+        other_target: String,
+        other_permits: u32,
+    },
 }
@@ -10,6 +10,7 @@ use moon_project::Project;
 use moon_task::{Target, Task};
 use rustc_hash::{FxHashMap, FxHashSet};
 use starbase_utils::glob::GlobSet;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use tracing::{debug, warn};
 
@@ -68,14 +69,17 @@ impl<'task> TaskHasher<'task> {
     }
 
     pub async fn hash_inputs(&mut self) -> miette::Result<()> {
-        let absolute_inputs = self.aggregate_inputs().await?;
-        let processed_inputs = self.process_inputs(absolute_inputs)?;
+        let files = self.collect_input_hashes().await?;
 
-        if !processed_inputs.is_empty() && self.app_context.vcs.is_enabled() {
-            let files = processed_inputs.into_iter().collect::<Vec<_>>();
+        self.hash_input_files(files);
 
-            self.fingerprint.inputs = self.app_context.hash_files(&files).await?;
-        }
+        Ok(())
+    }
+
+    /// Hash the input files using precomputed hashes (for example, from
+    /// the daemon), instead of locating and hashing them from scratch.
+    pub fn hash_input_files(&mut self, files: BTreeMap<WorkspaceRelativePathBuf, String>) {
+        self.fingerprint.inputs = files;
 
         if !self.task.input_env.is_empty() {
             let bag = GlobalEnvBag::instance();
@@ -86,8 +90,38 @@ impl<'task> TaskHasher<'task> {
                     .insert(input, bag.get(input).unwrap_or_default());
             }
         }
+    }
 
-        Ok(())
+    /// Locate all input files for the task, without hashing them.
+    pub async fn collect_input_files(
+        &mut self,
+    ) -> miette::Result<FxHashSet<WorkspaceRelativePathBuf>> {
+        let absolute_inputs = self.aggregate_inputs().await?;
+
+        self.process_inputs(absolute_inputs)
+    }
+
+    /// Locate all input files for the task and hash their contents.
+    pub async fn collect_input_hashes(
+        &mut self,
+    ) -> miette::Result<BTreeMap<WorkspaceRelativePathBuf, String>> {
+        let processed_inputs = self.collect_input_files().await?;
+
+        self.hash_files(processed_inputs).await
+    }
+
+    /// Hash the contents of input files that were previously located.
+    pub async fn hash_files(
+        &self,
+        files: FxHashSet<WorkspaceRelativePathBuf>,
+    ) -> miette::Result<BTreeMap<WorkspaceRelativePathBuf, String>> {
+        if files.is_empty() || !self.app_context.vcs.is_enabled() {
+            return Ok(BTreeMap::new());
+        }
+
+        let files = files.into_iter().collect::<Vec<_>>();
+
+        self.app_context.hash_files(&files).await
     }
 
     async fn aggregate_inputs(&mut self) -> miette::Result<FxHashSet<PathBuf>> {
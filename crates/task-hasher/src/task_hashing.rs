@@ -3,6 +3,7 @@ use miette::IntoDiagnostic;
 use moon_action::ActionNode;
 use moon_action_context::{ActionContext, TargetState};
 use moon_app_context::AppContext;
+use moon_common::path::WorkspaceRelativePathBuf;
 use moon_config::{
     DependencyScope, HasherOptimization, ProjectConfig, TaskDependencyCacheStrategy,
     UnresolvedVersionSpec, VersionSpec,
@@ -27,6 +28,7 @@ pub async fn hash_common_task_contents(
     project: &Project,
     task: &Task,
     node: &ActionNode,
+    input_hashes: Option<BTreeMap<WorkspaceRelativePathBuf, String>>,
     hasher: &mut ContentHasher,
 ) -> miette::Result<()> {
     let mut task_hasher = TaskHasher::new(
@@ -59,7 +61,11 @@ pub async fn hash_common_task_contents(
         deps
     });
 
-    task_hasher.hash_inputs().await?;
+    // Input hashes may have been precomputed by the daemon
+    match input_hashes {
+        Some(files) => task_hasher.hash_input_files(files),
+        None => task_hasher.hash_inputs().await?,
+    };
 
     if let ActionNode::RunTask(inner) = node {
         task_hasher.hash_args(&inner.args);
@@ -1,4 +1,4 @@
-use moon_action::{ActionStatus, Operation};
+use moon_action::{ActionStatus, Operation, TaskCheckOutcome, TaskCheckResult};
 use moon_app_context::AppContext;
 use moon_process::{Command, Output};
 use moon_task::{Task, TaskCheck};
@@ -17,6 +17,19 @@ pub struct CheckExecuteResult {
     pub output: Option<Output>,
 }
 
+impl CheckExecuteResult {
+    pub fn is_success(&self) -> bool {
+        self.output.as_ref().is_some_and(|output| output.success())
+    }
+
+    pub fn to_result(&self, outcome: TaskCheckOutcome) -> TaskCheckResult {
+        let mut result = TaskCheckResult::new(self.check.get_script(), outcome);
+        result.exit_code = self.output.as_ref().and_then(|output| output.code());
+        result.timed_out = self.output.is_none() && self.error.is_none();
+        result
+    }
+}
+
 pub struct CheckExecutor {
     task: Arc<Task>,
     command: Command,
@@ -53,7 +53,7 @@ impl<'task> ChecksRunner<'task> {
         // Execute the checks in parallel
         let mut set = JoinSet::new();
 
-        for check in checks {
+        for (index, check) in checks.into_iter().enumerate() {
             let command = CommandBuilder::new(self.app_context, self.project, self.task)
                 .build_check(check)
                 .await?;
@@ -62,7 +62,7 @@ impl<'task> ChecksRunner<'task> {
 
             let check = check.to_owned();
 
-            set.spawn(async move { executor.execute(check).await });
+            set.spawn(async move { executor.execute(check).await.map(|result| (index, result)) });
         }
 
         let mut results = vec![];
@@ -71,6 +71,9 @@ impl<'task> ChecksRunner<'task> {
             results.push(result.into_diagnostic()??);
         }
 
-        Ok(results)
+        // Return in the configured order, not the completion order
+        results.sort_by_key(|(index, _)| *index);
+
+        Ok(results.into_iter().map(|(_, result)| result).collect())
     }
 }
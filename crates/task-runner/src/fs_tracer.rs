@@ -0,0 +1,418 @@
+use miette::IntoDiagnostic;
+use moon_app_context::AppContext;
+use moon_common::color;
+use moon_common::path::{RelativePathBuf, WorkspaceRelativePathBuf};
+use moon_process::{Command, CommandArg, CommandExecutable, find_command_on_path};
+use moon_project::Project;
+use moon_task::Task;
+use starbase_utils::fs;
+use starbase_utils::glob::GlobSet;
+use std::collections::BTreeSet;
+use std::mem;
+use std::path::{Path, PathBuf};
+use tracing::{debug, instrument, trace};
+
+const TRACED_SYSCALLS: &str = "trace=open,openat,openat2,creat,rename,renameat,renameat2,chdir";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FsAccess {
+    Read,
+    Write,
+}
+
+/// Files that were accessed by a task's process tree, relative to the workspace root.
+#[derive(Debug, Default, PartialEq)]
+pub struct FsTraceAccess {
+    pub reads: BTreeSet<WorkspaceRelativePathBuf>,
+    pub writes: BTreeSet<WorkspaceRelativePathBuf>,
+}
+
+/// Files that were accessed by a task, but are not covered by its
+/// declared (and expanded) `inputs` or `outputs`.
+#[derive(Debug, Default, PartialEq)]
+pub struct FsTraceReport {
+    pub undeclared_inputs: Vec<WorkspaceRelativePathBuf>,
+    pub undeclared_outputs: Vec<WorkspaceRelativePathBuf>,
+}
+
+impl FsTraceReport {
+    pub fn is_empty(&self) -> bool {
+        self.undeclared_inputs.is_empty() && self.undeclared_outputs.is_empty()
+    }
+
+    /// Format the report as a suggested patch for the task's `moon.*` config.
+    /// Paths within the project are project relative, while all other paths
+    /// are workspace relative (prefixed with `/`).
+    pub fn format_suggestion(&self, project: &Project, task: &Task) -> String {
+        let format_path = |path: &WorkspaceRelativePathBuf| match path.strip_prefix(&project.source)
+        {
+            Ok(rel_path) if project.source.as_str() != "." => format!("'{rel_path}'"),
+            _ => format!("'/{path}'"),
+        };
+
+        let mut lines = vec!["tasks:".to_owned(), format!("  {}:", task.id)];
+
+        if !self.undeclared_inputs.is_empty() {
+            lines.push("    inputs:".into());
+
+            for input in &self.undeclared_inputs {
+                lines.push(format!("      - {}", format_path(input)));
+            }
+        }
+
+        if !self.undeclared_outputs.is_empty() {
+            lines.push("    outputs:".into());
+
+            for output in &self.undeclared_outputs {
+                lines.push(format!("      - {}", format_path(output)));
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    /// Format the report for display in the terminal.
+    pub fn format(&self, project: &Project, task: &Task) -> String {
+        let mut lines = vec![format!(
+            "Undeclared file access detected for {}",
+            color::label(task.target.as_str())
+        )];
+
+        if !self.undeclared_inputs.is_empty() {
+            lines.push("Read but not an input:".into());
+
+            for input in &self.undeclared_inputs {
+                lines.push(format!("  - {}", color::file(input.as_str())));
+            }
+        }
+
+        if !self.undeclared_outputs.is_empty() {
+            lines.push("Written but not an output:".into());
+
+            for output in &self.undeclared_outputs {
+                lines.push(format!("  - {}", color::file(output.as_str())));
+            }
+        }
+
+        lines.push(format!(
+            "Suggested changes for {}:",
+            color::file(format!("{}/moon.*", project.source))
+        ));
+
+        for line in self.format_suggestion(project, task).lines() {
+            lines.push(format!("  {line}"));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Observe the file reads and writes of a task's process tree with `strace`,
+/// to detect files that are not covered by the task's `inputs` and `outputs`.
+pub struct FsTracer<'task> {
+    app: &'task AppContext,
+    task: &'task Task,
+
+    /// Directory in which `strace` writes a log file per process.
+    trace_dir: PathBuf,
+
+    /// Absolute working directory of the task.
+    working_dir: PathBuf,
+}
+
+impl<'task> FsTracer<'task> {
+    pub fn is_supported() -> bool {
+        cfg!(target_os = "linux") && find_command_on_path("strace").is_some()
+    }
+
+    pub fn new(
+        app: &'task AppContext,
+        project: &'task Project,
+        task: &'task Task,
+        hash: &str,
+    ) -> FsTracer<'task> {
+        let name = if hash.is_empty() {
+            task.target
+                .as_str()
+                .replace([':', '/', '\\', '#', '~'], "-")
+        } else {
+            hash.to_owned()
+        };
+
+        FsTracer {
+            app,
+            task,
+            trace_dir: app.cache_engine.temp_dir.join(format!("trace-fs-{name}")),
+            working_dir: if task.options.run_from_workspace_root {
+                app.workspace_root.clone()
+            } else {
+                project.root.clone()
+            },
+        }
+    }
+
+    /// Wrap the command so that it's executed through `strace`, following forks.
+    pub fn wrap_command(&self, command: &mut Command) -> miette::Result<()> {
+        fs::remove_dir_all(&self.trace_dir)?;
+        fs::create_dir_all(&self.trace_dir)?;
+
+        let log_prefix = self.trace_dir.join("trace");
+        let strace_args = [
+            "-ff".into(),
+            "-qq".into(),
+            "-y".into(),
+            "-s".into(),
+            "4096".into(),
+            "-e".into(),
+            TRACED_SYSCALLS.into(),
+            "-o".into(),
+            log_prefix.to_string_lossy().to_string(),
+            "--".into(),
+        ];
+
+        debug!(
+            task_target = self.task.target.as_str(),
+            trace_dir = ?self.trace_dir,
+            "Tracing file system access of task with strace",
+        );
+
+        match mem::replace(
+            &mut command.exe,
+            CommandExecutable::Binary(CommandArg::from("strace")),
+        ) {
+            CommandExecutable::Binary(bin) => {
+                command.args.push_front(bin);
+
+                for arg in strace_args.into_iter().rev() {
+                    command.args.push_front(CommandArg::from(arg));
+                }
+            }
+            CommandExecutable::Script(script) => {
+                let shell = command.shell.unwrap_or_default().to_string();
+
+                command.exe = CommandExecutable::Script(
+                    format!(
+                        "strace {} {shell} -c {}",
+                        strace_args.map(|arg| quote(&arg)).join(" "),
+                        quote(&script.to_string_lossy()),
+                    )
+                    .into(),
+                );
+            }
+        };
+
+        Ok(())
+    }
+
+    /// Parse the trace logs of every process, and compare the accessed files
+    /// against the task's declared inputs and outputs.
+    #[instrument(skip(self))]
+    pub async fn analyze(&self) -> miette::Result<FsTraceReport> {
+        let access = self.load_access()?;
+        let ignored_dirs = self.get_ignored_dirs().await;
+
+        let is_ignored = |path: &WorkspaceRelativePathBuf| {
+            ignored_dirs
+                .iter()
+                .any(|dir| path.components().any(|comp| comp.as_str() == dir))
+                || !self.app.workspace_root.join(path.as_str()).is_file()
+        };
+
+        let input_globset = self.task.create_globset()?;
+        let output_globset = GlobSet::new_owned(self.task.output_globs.keys())?;
+        let mut report = FsTraceReport::default();
+
+        for path in &access.writes {
+            if is_ignored(path) {
+                continue;
+            }
+
+            let is_output = output_globset.matches(path.as_str())
+                || self
+                    .task
+                    .output_files
+                    .keys()
+                    .any(|output| path == output || path.starts_with(output));
+
+            if !is_output {
+                report.undeclared_outputs.push(path.to_owned());
+            }
+        }
+
+        for path in &access.reads {
+            // Files created by the task itself are not inputs
+            if access.writes.contains(path) || is_ignored(path) {
+                continue;
+            }
+
+            let is_input = input_globset.matches(path.as_str())
+                || self
+                    .task
+                    .input_files
+                    .keys()
+                    .any(|input| path == input || path.starts_with(input));
+
+            if !is_input {
+                report.undeclared_inputs.push(path.to_owned());
+            }
+        }
+
+        debug!(
+            task_target = self.task.target.as_str(),
+            reads = access.reads.len(),
+            writes = access.writes.len(),
+            undeclared_inputs = report.undeclared_inputs.len(),
+            undeclared_outputs = report.undeclared_outputs.len(),
+            "Analyzed file system access of task",
+        );
+
+        Ok(report)
+    }
+
+    pub fn cleanup(self) -> miette::Result<()> {
+        fs::remove_dir_all(&self.trace_dir)?;
+
+        Ok(())
+    }
+
+    /// Load all files accessed within the workspace from the trace logs.
+    pub fn load_access(&self) -> miette::Result<FsTraceAccess> {
+        let mut access = FsTraceAccess::default();
+
+        for entry in fs::read_dir(&self.trace_dir)? {
+            let log = fs::read_file(entry.path())?;
+            let mut cwd = self.working_dir.clone();
+
+            for line in log.lines() {
+                let Some((kind, path)) = parse_trace_line(line, &mut cwd) else {
+                    continue;
+                };
+
+                let Ok(rel_path) = path.strip_prefix(&self.app.workspace_root) else {
+                    continue;
+                };
+
+                let rel_path = RelativePathBuf::from_path(rel_path).into_diagnostic()?;
+
+                trace!(
+                    task_target = self.task.target.as_str(),
+                    path = rel_path.as_str(),
+                    "Traced {kind:?} access",
+                );
+
+                match kind {
+                    FsAccess::Read => access.reads.insert(rel_path),
+                    FsAccess::Write => access.writes.insert(rel_path),
+                };
+            }
+        }
+
+        Ok(access)
+    }
+
+    async fn get_ignored_dirs(&self) -> Vec<String> {
+        let mut dirs = vec![".git".to_owned(), ".moon".to_owned()];
+        let registry = &self.app.toolchain_registry;
+
+        for toolchain_id in &self.task.toolchains {
+            if let Ok(toolchain) = registry.load(toolchain_id).await
+                && let Some(vendor_dir_name) = &toolchain.metadata.vendor_dir_name
+            {
+                dirs.push(vendor_dir_name.to_owned());
+            }
+        }
+
+        dirs
+    }
+}
+
+/// Parse a single `strace -y` log line into the kind of access and its absolute path.
+/// Failed syscalls are ignored, and `chdir` calls update the current directory.
+pub fn parse_trace_line(line: &str, cwd: &mut PathBuf) -> Option<(FsAccess, PathBuf)> {
+    let (call, rest) = line.split_once('(')?;
+    let call = call.trim();
+    let (args, result) = rest.rsplit_once(") = ")?;
+    let result = result.trim();
+
+    if result.starts_with('-') || result.starts_with('?') {
+        return None;
+    }
+
+    let strings = extract_quoted_strings(args);
+    let resolve = |path: &str| {
+        let path = Path::new(path);
+
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            cwd.join(path)
+        }
+    };
+
+    match call {
+        "chdir" => {
+            *cwd = resolve(strings.first()?);
+
+            None
+        }
+        "open" | "openat" | "openat2" | "creat" => {
+            // With `-y`, the returned descriptor is decoded to its absolute path
+            let path = match (result.find('<'), result.rfind('>')) {
+                (Some(start), Some(end)) if start < end => PathBuf::from(&result[start + 1..end]),
+                _ => resolve(strings.first()?),
+            };
+
+            let is_write = call == "creat"
+                || ["O_WRONLY", "O_RDWR", "O_CREAT", "O_TRUNC"]
+                    .iter()
+                    .any(|flag| args.contains(flag));
+
+            if args.contains("O_DIRECTORY") {
+                return None;
+            }
+
+            Some((
+                if is_write {
+                    FsAccess::Write
+                } else {
+                    FsAccess::Read
+                },
+                path,
+            ))
+        }
+        "rename" | "renameat" | "renameat2" => Some((FsAccess::Write, resolve(strings.last()?))),
+        _ => None,
+    }
+}
+
+fn extract_quoted_strings(args: &str) -> Vec<String> {
+    let mut strings = vec![];
+    let mut current: Option<String> = None;
+    let mut chars = args.chars();
+
+    while let Some(ch) = chars.next() {
+        if let Some(value) = &mut current {
+            match ch {
+                '\\' => {
+                    if let Some(next) = chars.next() {
+                        value.push(next);
+                    }
+                }
+                '"' => {
+                    strings.extend(current.take());
+                }
+                _ => {
+                    value.push(ch);
+                }
+            }
+        } else if ch == '"' {
+            current = Some(String::new());
+        }
+    }
+
+    strings
+}
+
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
@@ -2,12 +2,17 @@
 mod check_executor;
 mod checks_runner;
 pub mod command_builder;
+pub mod fs_tracer;
 pub mod output_archiver;
+pub mod output_extractor;
 pub mod output_hydrater;
 mod run_state;
 pub mod task_executor;
 mod task_runner;
 mod task_runner_error;
+pub mod task_sandbox;
+pub mod task_semaphore;
+pub mod test_report_parser;
 
 pub use run_state::*;
 pub use task_runner::*;
@@ -3,18 +3,64 @@ use crate::task_runner_error::TaskRunnerError;
 use miette::IntoDiagnostic;
 use moon_app_context::AppContext;
 use moon_cache::{Manifest, StorageOptions};
-use moon_common::color;
+use moon_common::{Id, color, is_ci};
+use moon_config::RemoteCacheWriteRule;
 use moon_daemon_client::DaemonClient;
 use moon_manifest::ManifestPacker;
 use moon_task::Task;
 use starbase_archive::Archiver;
+use starbase_utils::glob::GlobSet;
 use std::sync::Arc;
 use tokio::task::spawn_blocking;
 use tracing::{debug, instrument, warn};
 
 pub enum ArchiveOutcome {
     Skipped,
-    Queued,
+    /// Includes the manifest of the archived outputs.
+    Queued(Box<Manifest>),
+}
+
+/// The environment that remote cache write rules are matched against.
+pub struct RemoteWriteContext<'a> {
+    pub branch: &'a str,
+    pub ci: bool,
+    pub default_branch: bool,
+    pub pull_request: bool,
+    pub tags: &'a [Id],
+}
+
+/// Determine whether uploads are allowed by the first rule that matches,
+/// denying when no rules match. When there are no rules, always allow.
+pub fn is_remote_write_allowed(
+    rules: &[RemoteCacheWriteRule],
+    context: &RemoteWriteContext,
+) -> miette::Result<bool> {
+    if rules.is_empty() {
+        return Ok(true);
+    }
+
+    // Pull requests run untrusted code, even when the source
+    // branch has the same name as the default branch
+    let default_branch = context.default_branch && !context.pull_request;
+
+    for rule in rules {
+        if rule.ci.is_some_and(|ci| ci != context.ci)
+            || rule
+                .default_branch
+                .is_some_and(|value| value != default_branch)
+            || (!rule.tags.is_empty() && !rule.tags.iter().any(|tag| context.tags.contains(tag)))
+        {
+            continue;
+        }
+
+        if !rule.branches.is_empty() && !GlobSet::new(&rule.branches)?.matches(context.branch) {
+            continue;
+        }
+
+        return Ok(rule.allow);
+    }
+
+    Ok(false)
 }
 
 /// Cache outputs to the `.moon/cache` folder and to cloud storage,
@@ -54,15 +100,17 @@ impl OutputArchiver<'_> {
         }
 
         let task_target = self.task.target.as_str();
+        let remote_cache_writable =
+            state.remote_cache_writable && self.is_remote_write_allowed().await?;
 
-        if state.local_cache_writable && state.remote_cache_writable {
+        if state.local_cache_writable && remote_cache_writable {
             debug!(
                 task_target,
                 hash, "Storing task outputs in local and remote caches"
             );
         } else if state.local_cache_writable {
             debug!(task_target, hash, "Storing task outputs in local cache");
-        } else if state.remote_cache_writable {
+        } else if remote_cache_writable {
             debug!(task_target, hash, "Storing task outputs in remote cache");
         } else {
             debug!(
@@ -74,18 +122,20 @@ impl OutputArchiver<'_> {
         }
 
         let use_local = state.local_cas_enabled && state.local_cache_writable;
-        let use_remote = state.remote_cache_writable;
+        let use_remote = remote_cache_writable;
+
+        // Hash the outputs once, and return the manifest so that
+        // the caller doesn't have to hash them again
+        let manifest = self.create_cache_manifest(state).await?;
 
         // Store the manifest in the local/remote caches
         if use_local || use_remote {
-            let manifest = self.create_cache_manifest(state).await?;
-
             if let Some(mut daemon) = self.daemon_client.clone() {
                 daemon
                     .archive_task_outputs(
                         self.task.target.to_string(),
                         state.digest.clone(),
-                        manifest,
+                        manifest.clone(),
                         use_local,
                         use_remote,
                     )
@@ -99,7 +149,7 @@ impl OutputArchiver<'_> {
                         include_remote: use_remote,
                         ..Default::default()
                     })
-                    .archive_manifest(&state.digest, manifest)
+                    .archive_manifest(&state.digest, manifest.clone())
                     .await?;
             }
         }
@@ -109,7 +159,57 @@ impl OutputArchiver<'_> {
             self.pack_local_archive(hash, state).await?;
         }
 
-        Ok(ArchiveOutcome::Queued)
+        Ok(ArchiveOutcome::Queued(Box::new(manifest)))
+    }
+
+    /// Apply the remote cache write policy, so that untrusted branches
+    /// or environments can't poison the remote cache.
+    async fn is_remote_write_allowed(&self) -> miette::Result<bool> {
+        let rules = &self.app_context.workspace_config.remote.cache.write_policy;
+
+        if rules.is_empty() {
+            return Ok(true);
+        }
+
+        let vcs = &self.app_context.vcs;
+        let ci_environment = ci_env::get_environment();
+
+        // A pull request is detected by its ID or URL, or by the target branch
+        let pull_request = ci_environment.as_ref().is_some_and(|env| {
+            [&env.request_id, &env.request_url, &env.base_branch]
+                .into_iter()
+                .any(|value| value.as_ref().is_some_and(|value| !value.is_empty()))
+        });
+
+        // Detached checkouts are common in CI, so prefer the provider's branch
+        let branch = match ci_environment
+            .map(|env| env.branch)
+            .filter(|branch| !branch.is_empty())
+        {
+            Some(branch) => branch,
+            None if vcs.is_enabled() => vcs.get_local_branch().await.unwrap_or_default(),
+            None => String::new(),
+        };
+
+        let allowed = is_remote_write_allowed(
+            rules,
+            &RemoteWriteContext {
+                ci: is_ci(),
+                default_branch: !branch.is_empty() && vcs.is_default_branch(&branch),
+                branch: &branch,
+                pull_request,
+                tags: &self.task.tags,
+            },
+        )?;
+
+        if !allowed {
+            debug!(
+                task_target = self.task.target.as_str(),
+                branch, pull_request, "Remote cache write policy denied uploading task outputs"
+            );
+        }
+
+        Ok(allowed)
     }
 
     #[instrument(skip(self))]
@@ -156,14 +256,20 @@ impl OutputArchiver<'_> {
         Ok(true)
     }
 
-    #[instrument(skip(self, state))]
-    async fn create_cache_manifest(&self, state: &TaskRunState) -> miette::Result<Manifest> {
+    /// Create a manifest of the task's outputs that currently exist on the file system,
+    /// without any process or source information.
+    #[instrument(skip(self))]
+    pub async fn create_outputs_manifest(&self) -> miette::Result<Manifest> {
+        Ok(self.create_outputs_packer().await?.pack())
+    }
+
+    async fn create_outputs_packer(&self) -> miette::Result<ManifestPacker> {
         let task = Arc::clone(self.task);
         let workspace_root = self.app_context.workspace_root.clone();
 
         // Building the manifest incurs a lot of file system calls,
         // so we run it in a blocking thread to avoid blocking the async runtime
-        let mut packer = spawn_blocking(move || {
+        spawn_blocking(move || {
             let outputs = task.get_output_files(&workspace_root, true)?;
             let mut packer = ManifestPacker::new(workspace_root);
 
@@ -174,7 +280,12 @@ impl OutputArchiver<'_> {
             Ok::<_, miette::Report>(packer)
         })
         .await
-        .into_diagnostic()??;
+        .into_diagnostic()?
+    }
+
+    #[instrument(skip(self, state))]
+    async fn create_cache_manifest(&self, state: &TaskRunState) -> miette::Result<Manifest> {
+        let mut packer = self.create_outputs_packer().await?;
 
         // Then inherit the execution operation metadata
         packer.inherit_operation(&state.operation)?;
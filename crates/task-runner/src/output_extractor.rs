@@ -0,0 +1,198 @@
+use crate::run_state::TaskRunCacheState;
+use miette::IntoDiagnostic;
+use moon_app_context::AppContext;
+use moon_cache::{
+    ContentHash, Digest, Manifest, ManifestPacker, ManifestSource, ManifestUnpacker,
+    TaskOutputsManifest, TaskOutputsSource,
+};
+use moon_task::Task;
+use starbase_archive::Archiver;
+use starbase_utils::{fs, json};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::task::spawn_blocking;
+use tracing::{debug, instrument};
+
+/// Inspect and extract the cached outputs of a task, without running it.
+pub struct OutputExtractor<'task> {
+    app_context: &'task Arc<AppContext>,
+    task: &'task Arc<Task>,
+}
+
+impl OutputExtractor<'_> {
+    pub fn new<'task>(
+        app_context: &'task Arc<AppContext>,
+        task: &'task Arc<Task>,
+    ) -> miette::Result<OutputExtractor<'task>> {
+        Ok(OutputExtractor { app_context, task })
+    }
+
+    /// Return the hash of the last successful run of the task, if any.
+    pub fn get_last_hash(&self) -> miette::Result<Option<String>> {
+        let state = self
+            .app_context
+            .cache_engine
+            .state
+            .load_target_state::<TaskRunCacheState>(&self.task.target)?;
+
+        if state.data.hash.is_empty() || state.data.exit_code != 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(state.data.hash))
+    }
+
+    /// Load a summary of the outputs cached for the provided hash. Will use the
+    /// `outputs.json` of the last run if the hash matches, otherwise the cache.
+    #[instrument(skip(self))]
+    pub async fn load_manifest(&self, hash: &str) -> miette::Result<Option<TaskOutputsManifest>> {
+        let recorded_path = self
+            .app_context
+            .cache_engine
+            .state
+            .get_target_dir(&self.task.target)
+            .join("outputs.json");
+
+        if recorded_path.exists() {
+            let outputs: TaskOutputsManifest = json::read_file(&recorded_path)?;
+
+            if outputs.hash == hash {
+                return Ok(Some(outputs));
+            }
+        }
+
+        if let Some((temp_dir, manifest)) = self.unpack_local_archive(hash).await? {
+            fs::remove_dir_all(temp_dir)?;
+
+            return Ok(Some(TaskOutputsManifest::from_manifest(
+                &self.task.target,
+                hash,
+                TaskOutputsSource::Local,
+                &manifest,
+            )));
+        }
+
+        if let Some((_, source)) = self.load_storage_manifest(hash).await? {
+            return Ok(Some(TaskOutputsManifest::from_manifest(
+                &self.task.target,
+                hash,
+                if source.remote {
+                    TaskOutputsSource::Remote
+                } else {
+                    TaskOutputsSource::Local
+                },
+                &source.manifest,
+            )));
+        }
+
+        Ok(None)
+    }
+
+    /// Extract the outputs cached for the provided hash into the destination
+    /// directory, maintaining their workspace relative paths. Returns false
+    /// if nothing has been cached for the hash.
+    #[instrument(skip(self))]
+    pub async fn extract(&self, hash: &str, dest_dir: &Path) -> miette::Result<bool> {
+        if let Some((temp_dir, manifest)) = self.unpack_local_archive(hash).await? {
+            ManifestUnpacker::new(&manifest, dest_dir.to_path_buf()).unpack()?;
+
+            fs::remove_dir_all(temp_dir)?;
+
+            return Ok(true);
+        }
+
+        if let Some((digest, source)) = self.load_storage_manifest(hash).await?
+            && let Some(manifest) = self
+                .app_context
+                .cache_engine
+                .storage
+                .hydrate_manifest(&digest, source)
+                .await?
+        {
+            ManifestUnpacker::new(&manifest, dest_dir.to_path_buf()).unpack()?;
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    async fn load_storage_manifest(
+        &self,
+        hash: &str,
+    ) -> miette::Result<Option<(Digest, ManifestSource)>> {
+        // The storage backends key manifests by the digest of the hash manifest,
+        // which is only available if the task was hashed on this machine
+        let manifest_path = self.app_context.cache_engine.hash.get_manifest_path(hash);
+
+        if !manifest_path.exists() {
+            debug!(
+                task_target = self.task.target.as_str(),
+                hash, "No hash manifest found, unable to query cache storage"
+            );
+
+            return Ok(None);
+        }
+
+        let digest = Digest {
+            hash: ContentHash::from_hex(hash)?,
+            size: fs::metadata(&manifest_path)?.len() as i64,
+        };
+
+        Ok(self
+            .app_context
+            .cache_engine
+            .storage
+            .load_manifest(&digest)
+            .await?
+            .map(|source| (digest, source)))
+    }
+
+    /// Unpack the legacy archive (if it exists) into a temporary directory,
+    /// and create a manifest for the task's outputs within it.
+    async fn unpack_local_archive(
+        &self,
+        hash: &str,
+    ) -> miette::Result<Option<(PathBuf, Manifest)>> {
+        let archive_file = self.app_context.cache_engine.hash.get_archive_path(hash);
+
+        if !archive_file.exists() {
+            return Ok(None);
+        }
+
+        debug!(
+            task_target = self.task.target.as_str(),
+            hash,
+            archive_file = ?archive_file,
+            "Unpacking task outputs from local cache archive"
+        );
+
+        let temp_dir = self
+            .app_context
+            .cache_engine
+            .temp_dir
+            .join(format!("outputs-{hash}"));
+        let task = Arc::clone(self.task);
+
+        let manifest = spawn_blocking(move || {
+            // Remove leftovers from a previous extraction
+            fs::remove_dir_all(&temp_dir)?;
+
+            Archiver::new(&temp_dir, &archive_file)
+                .unpack_from_ext()
+                .into_diagnostic()?;
+
+            let mut packer = ManifestPacker::new(temp_dir.clone());
+
+            for output in task.get_output_files(&temp_dir, true)? {
+                packer.inherit_output(output)?;
+            }
+
+            Ok::<_, miette::Report>((temp_dir, packer.pack()))
+        })
+        .await
+        .into_diagnostic()??;
+
+        Ok(Some(manifest))
+    }
+}
@@ -1,12 +1,14 @@
-use moon_action::Operation;
+use moon_action::{Operation, TaskCheckResult};
 use moon_action_context::TargetState;
 use moon_app_context::AppContext;
+use moon_cache::Manifest;
 use moon_cache_item::cache_item;
 use moon_hash::Digest;
 use moon_task::Task;
 
 cache_item!(
     pub struct TaskRunCacheState {
+        pub checks: Vec<TaskCheckResult>,
         pub exit_code: i32,
         pub hash: String,
         pub last_run_time: u128,
@@ -26,6 +28,10 @@ pub struct TaskRunState {
     /// The final state of the target, for use within the action context.
     pub target: Option<TargetState>,
 
+    /// The manifest of outputs that were archived, so that they're
+    /// not hashed again when recording them.
+    pub archived_manifest: Option<Box<Manifest>>,
+
     /// Read and write states for the local/remote caches.
     pub local_cas_enabled: bool,
     pub local_cache_readable: bool,
@@ -1,31 +1,46 @@
 use crate::checks_runner::ChecksRunner;
 use crate::command_builder::CommandBuilder;
+use crate::fs_tracer::FsTracer;
 use crate::output_archiver::{ArchiveOutcome, OutputArchiver};
 use crate::output_hydrater::{HydrateFrom, HydrateOutcome, OutputHydrater};
 use crate::run_state::*;
 use crate::task_executor::TaskExecutor;
 use crate::task_runner_error::TaskRunnerError;
-use moon_action::{ActionNode, ActionStatus, Operation, OperationList, OperationMeta};
+use crate::task_sandbox::TaskSandbox;
+use crate::task_semaphore::TaskSemaphorePermit;
+use crate::test_report_parser::TestReportParser;
+use miette::IntoDiagnostic;
+use moon_action::{
+    ActionNode, ActionStatus, Operation, OperationList, OperationMeta, TaskCheckOutcome,
+    TaskCheckResult, TaskTestResults,
+};
 use moon_action_context::{ActionContext, TargetState};
 use moon_app_context::AppContext;
-use moon_cache::{CacheItem, StorageOptions};
+use moon_cache::{
+    CacheItem, CacheLease, Manifest, StorageOptions, TaskOutputsManifest, TaskOutputsSource,
+};
+use moon_common::format_error_chain;
+use moon_common::path::WorkspaceRelativePathBuf;
 use moon_console::TaskReportItem;
 use moon_daemon_client::DaemonClient;
-use moon_hash::{ContentHash, ContentHasher};
+use moon_hash::{ContentHash, ContentHasher, Digest};
 use moon_process::ProcessError;
 use moon_project::Project;
 use moon_task::{Task, TaskCheck, TaskCheckFingerprint, TaskCheckType};
 use moon_task_hasher::*;
 use moon_time::{is_stale, now_millis};
-use starbase_utils::fs;
+use starbase_utils::{fs, json};
+use std::collections::BTreeMap;
 use std::sync::Arc;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 #[derive(Debug)]
 pub struct TaskRunResult {
+    pub checks: Vec<TaskCheckResult>,
     pub hash: Option<ContentHash>,
     pub error: Option<miette::Report>,
     pub operations: OperationList,
+    pub tests: Option<TaskTestResults>,
 }
 
 pub struct TaskRunner<'task> {
@@ -34,13 +49,17 @@ pub struct TaskRunner<'task> {
     pub task: &'task Arc<Task>,
 
     archiver: OutputArchiver<'task>,
+    daemon_client: Option<DaemonClient>,
     hydrater: OutputHydrater<'task>,
+    lease: Option<CacheLease>,
 
     // Public for testing
     pub cache: CacheItem<TaskRunCacheState>,
+    pub checks: Vec<TaskCheckResult>,
     pub operations: OperationList,
     pub report: TaskReportItem,
     pub state: TaskRunState,
+    pub tests: Option<TaskTestResults>,
 }
 
 impl<'task> TaskRunner<'task> {
@@ -67,8 +86,11 @@ impl<'task> TaskRunner<'task> {
         Ok(Self {
             state: TaskRunState::new(app_context, task),
             cache,
+            checks: vec![],
             archiver: OutputArchiver::new(app_context, task, daemon_client.clone())?,
-            hydrater: OutputHydrater::new(app_context, task, daemon_client)?,
+            hydrater: OutputHydrater::new(app_context, task, daemon_client.clone())?,
+            daemon_client,
+            lease: None,
             project,
             report: TaskReportItem {
                 output_style: task.options.output_style,
@@ -77,6 +99,7 @@ impl<'task> TaskRunner<'task> {
             task,
             app_context,
             operations: OperationList::default(),
+            tests: None,
         })
     }
 
@@ -101,6 +124,10 @@ impl<'task> TaskRunner<'task> {
                 "Caching is enabled for task, will attempt to hydrate and archive outputs"
             );
 
+            // Wait for other processes running the same task to complete,
+            // so that we can hydrate from their result instead of re-executing
+            self.acquire_lease(&hash).await?;
+
             // Exit early if this build has already been cached/hashed
             if self.hydrate(&hash).await? {
                 return Ok(Some(hash));
@@ -121,6 +148,13 @@ impl<'task> TaskRunner<'task> {
             self.execute(context, node).await?;
         }
 
+        self.save_outputs_manifest(
+            &hash,
+            TaskOutputsSource::Fresh,
+            self.state.archived_manifest.as_deref(),
+        )
+        .await?;
+
         Ok(Some(hash))
     }
 
@@ -137,9 +171,16 @@ impl<'task> TaskRunner<'task> {
 
         let result = self.internal_run(context, node).await;
 
+        self.parse_test_reports();
+
+        self.cache.data.checks = self.checks.clone();
         self.cache.data.last_run_time = now_millis();
         self.cache.save()?;
 
+        // Release *after* the state has been persisted, so that waiting
+        // processes can reuse the outputs of this run
+        self.lease = None;
+
         match result {
             Ok(maybe_hash) => {
                 context.set_target_state(
@@ -157,9 +198,11 @@ impl<'task> TaskRunner<'task> {
                 )?;
 
                 Ok(TaskRunResult {
+                    checks: std::mem::take(&mut self.checks),
                     error: None,
                     hash: maybe_hash,
                     operations: self.operations.take(),
+                    tests: self.tests.take(),
                 })
             }
             Err(error) => {
@@ -178,9 +221,11 @@ impl<'task> TaskRunner<'task> {
                 )?;
 
                 Ok(TaskRunResult {
+                    checks: std::mem::take(&mut self.checks),
                     error: Some(error),
                     hash: None,
                     operations: self.operations.take(),
+                    tests: self.tests.take(),
                 })
             }
         }
@@ -201,6 +246,78 @@ impl<'task> TaskRunner<'task> {
         Ok(result)
     }
 
+    /// Parse the test reports of the task, but only if they were written
+    /// by this run, or restored from the cache.
+    #[instrument(skip(self))]
+    pub fn parse_test_reports(&mut self) {
+        if self.task.options.test_reports.is_none()
+            || !self.operations.iter().any(|op| {
+                op.meta.is_task_execution() || (op.meta.is_output_hydration() && op.is_cached())
+            })
+        {
+            return;
+        }
+
+        match TestReportParser::new(self.app_context, self.project, self.task).parse() {
+            Ok(tests) => {
+                self.tests = tests;
+            }
+            Err(error) => {
+                warn!(
+                    task_target = self.task.target.as_str(),
+                    error = format_error_chain(&error),
+                    "Failed to parse test reports"
+                );
+            }
+        };
+    }
+
+    #[instrument(skip(self))]
+    pub async fn acquire_lease(&mut self, hash: &str) -> miette::Result<()> {
+        let cache_engine = Arc::clone(&self.app_context.cache_engine);
+        let name = format!("task-{hash}");
+
+        // Try to acquire the lease without waiting, so that checking
+        // whether it's held and acquiring it is a single operation
+        if let Some(lease) = cache_engine.try_create_lease(&name)? {
+            self.lease = Some(lease);
+
+            return Ok(());
+        }
+
+        debug!(
+            task_target = self.task.target.as_str(),
+            hash,
+            "Task with the same hash is currently running in another process, waiting for it to complete"
+        );
+
+        // Locking blocks until the lease has been released
+        let lease = tokio::task::spawn_blocking(move || cache_engine.create_lease(name))
+            .await
+            .into_diagnostic()??;
+
+        // The other process has completed, so reload the state it persisted,
+        // which will be used when checking the cache
+        debug!(
+            task_target = self.task.target.as_str(),
+            hash, "Acquired task lease, checking for a cached result"
+        );
+
+        self.cache = self
+            .app_context
+            .cache_engine
+            .state
+            .load_target_state::<TaskRunCacheState>(&self.task.target)?;
+
+        if self.cache.data.target.is_empty() {
+            self.cache.data.target = self.task.target.to_string();
+        }
+
+        self.lease = Some(lease);
+
+        Ok(())
+    }
+
     #[instrument(skip(self))]
     pub async fn is_cached(&mut self, hash: &str) -> miette::Result<Option<HydrateFrom>> {
         let cache_engine = &self.app_context.cache_engine;
@@ -386,12 +503,15 @@ impl<'task> TaskRunner<'task> {
         let mut operation = Operation::hash_generation();
 
         // Hash common fields
+        let input_hashes = self.load_input_hashes_from_daemon().await;
+
         hash_common_task_contents(
             self.app_context,
             context,
             self.project,
             self.task,
             node,
+            input_hashes,
             &mut hasher,
         )
         .await?;
@@ -422,6 +542,45 @@ impl<'task> TaskRunner<'task> {
         Ok(digest.hash)
     }
 
+    async fn load_input_hashes_from_daemon(
+        &self,
+    ) -> Option<BTreeMap<WorkspaceRelativePathBuf, String>> {
+        let mut client = self.daemon_client.clone()?;
+
+        // The daemon only hashes the task if its copy is identical to ours
+        let task_digest = Digest::from_data(self.task.as_ref()).ok()?;
+
+        let files = match client
+            .hash_task_inputs(self.task.target.to_string(), task_digest.hash.to_string())
+            .await
+        {
+            Ok(Some(files)) => files,
+            Ok(None) => return None,
+            Err(error) => {
+                debug!(
+                    task_target = self.task.target.as_str(),
+                    error = format_error_chain(&error),
+                    "Failed to load input hashes from daemon, hashing locally instead"
+                );
+
+                return None;
+            }
+        };
+
+        debug!(
+            task_target = self.task.target.as_str(),
+            "Received input hashes from daemon"
+        );
+
+        Some(
+            files
+                .into_iter()
+                .filter(|(_, hash)| !hash.is_empty())
+                .map(|(path, hash)| (WorkspaceRelativePathBuf::from(path), hash))
+                .collect(),
+        )
+    }
+
     #[instrument(skip(self, hasher))]
     pub async fn hash_checks(&mut self, hasher: &mut ContentHasher) -> miette::Result<()> {
         let checks = ChecksRunner::new(self.app_context, self.project, self.task)?
@@ -434,17 +593,20 @@ impl<'task> TaskRunner<'task> {
 
         let mut fingerprint = TaskChecksFingerprint::default();
 
-        for check_result in checks {
+        for mut check_result in checks {
             // If a hard failure, we should abort the runner
-            if let Some(error) = check_result.error {
+            if let Some(error) = check_result.error.take() {
                 return Err(error);
             }
 
-            self.operations.push(check_result.attempt);
+            self.operations.push(check_result.attempt.clone());
 
-            if let Some(output) = check_result.output {
+            if let Some(output) = &check_result.output {
                 // If the check failed, we should fail with a descriptive error
                 if !output.success() {
+                    self.checks
+                        .push(check_result.to_result(TaskCheckOutcome::FingerprintFailed));
+
                     return Err(TaskRunnerError::FingerprintCheckFailed {
                         target: self.task.target.clone(),
                         script: check_result.check.get_script().into(),
@@ -461,6 +623,10 @@ impl<'task> TaskRunner<'task> {
                         TaskCheckFingerprint::Enabled(state) => {
                             // Don't hash anything if disabled!
                             if !state {
+                                self.checks.push(
+                                    check_result.to_result(TaskCheckOutcome::FingerprintIgnored),
+                                );
+
                                 continue;
                             }
                         }
@@ -481,6 +647,11 @@ impl<'task> TaskRunner<'task> {
                     unreachable!();
                 }
 
+                let mut result = check_result.to_result(TaskCheckOutcome::Fingerprinted);
+                result.fingerprint = Some(Digest::from_data(&info)?.hash.to_string());
+
+                self.checks.push(result);
+
                 fingerprint
                     .checks
                     .insert(check_result.check.get_script().into(), info);
@@ -520,18 +691,60 @@ impl<'task> TaskRunner<'task> {
         );
 
         // Build the command from the current task
-        let command = CommandBuilder::new(self.app_context, self.project, self.task)
-            .build(
-                context,
-                node,
-                self.report.hash.as_deref().unwrap_or_default(),
-            )
+        let hash = self.report.hash.clone().unwrap_or_default();
+        let mut command = CommandBuilder::new(self.app_context, self.project, self.task)
+            .build(context, node, &hash)
             .await?;
 
+        // Materialize the declared inputs into a sandbox, and run the command within it.
+        // Never fall back to the workspace, as the results would not be hermetic
+        let sandbox = if self.task.options.sandbox {
+            if !TaskSandbox::is_supported() {
+                return Err(TaskRunnerError::SandboxUnsupported {
+                    target: self.task.target.clone(),
+                }
+                .into());
+            }
+
+            let sandbox =
+                TaskSandbox::create(self.app_context, self.project, self.task, &hash).await?;
+            sandbox.prepare_command(&mut command);
+
+            Some(sandbox)
+        } else {
+            None
+        };
+
+        // Trace file system access of the process tree, to detect undeclared inputs and outputs
+        let tracer = if context.trace_fs && sandbox.is_none() {
+            if FsTracer::is_supported() {
+                let tracer = FsTracer::new(self.app_context, self.project, self.task, &hash);
+                tracer.wrap_command(&mut command)?;
+
+                Some(tracer)
+            } else {
+                warn!(
+                    task_target = self.task.target.as_str(),
+                    "Tracing file system access requires Linux and strace to be installed, skipping"
+                );
+
+                None
+            }
+        } else {
+            None
+        };
+
+        // Remove stale test reports, so they aren't parsed if not written again
+        if self.task.options.test_reports.is_some() {
+            TestReportParser::new(self.app_context, self.project, self.task).clean()?;
+        }
+
         // Execute the command and gather all attempts made
         let executor = TaskExecutor::new(self.app_context, self.project, self.task, node, command);
 
-        let result = if let Some(mutex_name) = &self.task.options.mutex {
+        // Acquire the mutex lock and semaphore permit (if defined) before
+        // executing, and hold them until the execution has completed
+        let mutex_guard = if let Some(mutex_name) = &self.task.options.mutex {
             let mut operation = Operation::mutex_acquisition();
 
             debug!(
@@ -540,8 +753,11 @@ impl<'task> TaskRunner<'task> {
                 "Waiting to acquire task mutex lock"
             );
 
-            let mutex = context.get_or_create_mutex(mutex_name).await;
-            let _guard = mutex.lock().await;
+            let guard = context
+                .get_or_create_mutex(mutex_name)
+                .await
+                .lock_owned()
+                .await;
 
             debug!(
                 task_target = self.task.target.as_str(),
@@ -553,18 +769,74 @@ impl<'task> TaskRunner<'task> {
 
             self.operations.push(operation);
 
-            // This execution is required within this block so that the
-            // guard above isn't immediately dropped!
-            executor.execute(context, &mut self.report).await?
+            Some(guard)
         } else {
-            executor.execute(context, &mut self.report).await?
+            None
         };
 
+        let semaphore_permit = if let Some(semaphore) = &self.task.options.semaphore {
+            let mut operation = Operation::semaphore_acquisition();
+
+            let permit =
+                TaskSemaphorePermit::acquire(self.app_context, context, self.task, semaphore)
+                    .await?;
+
+            operation.finish(ActionStatus::Passed);
+
+            self.operations.push(operation);
+
+            Some(permit)
+        } else {
+            None
+        };
+
+        let result = executor.execute(context, &mut self.report).await?;
+
+        drop(semaphore_permit);
+        drop(mutex_guard);
+
         // Persist the state locally and for the remote service
         if let Some(last_attempt) = result.attempts.get_last_execution() {
             self.persist_state(last_attempt)?;
         }
 
+        // Restore outputs from the sandbox, or hint at why the task failed within it
+        if let Some(sandbox) = sandbox {
+            if let Some(last_attempt) = result.attempts.get_last_execution() {
+                if last_attempt.has_passed() {
+                    sandbox.restore_outputs()?;
+                } else if let Some(input) = last_attempt.get_exec_output().and_then(|output| {
+                    output
+                        .stderr
+                        .iter()
+                        .chain(output.stdout.iter())
+                        .find_map(|out| sandbox.find_undeclared_input(out))
+                }) {
+                    warn!(
+                        task_target = self.task.target.as_str(),
+                        input = ?input,
+                        "Task failed within its sandbox, and may have attempted to read {}, which is not a declared input",
+                        input.display(),
+                    );
+                }
+            }
+
+            sandbox.cleanup()?;
+        }
+
+        if let Some(tracer) = tracer {
+            let report = tracer.analyze().await?;
+
+            if !report.is_empty() {
+                self.app_context
+                    .console
+                    .out
+                    .write_line(report.format(self.project, self.task))?;
+            }
+
+            tracer.cleanup()?;
+        }
+
         // Extract the attempts from the result
         self.operations.merge(result.attempts);
 
@@ -611,22 +883,19 @@ impl<'task> TaskRunner<'task> {
         let mut has_conditions = false;
         let mut all_conditions_met = true;
 
-        for check_result in checks {
+        for mut check_result in checks {
             // If a hard failure, we should abort the runner
-            if let Some(error) = check_result.error {
+            if let Some(error) = check_result.error.take() {
                 return Err(error);
             }
 
-            self.operations.push(check_result.attempt);
+            self.operations.push(check_result.attempt.clone());
 
-            match check_result.check {
+            match &check_result.check {
                 // Success: Track the skipped state. If all conditions pass, skip running the task.
                 // Failure: Do not return an error, as the task itself will run instead.
                 TaskCheck::Condition(condition) => {
-                    let passed = check_result
-                        .output
-                        .as_ref()
-                        .is_some_and(|output| output.success());
+                    let passed = check_result.is_success();
 
                     debug!(
                         task_target = self.task.target.as_str(),
@@ -641,7 +910,13 @@ impl<'task> TaskRunner<'task> {
 
                     has_conditions = true;
 
-                    if !passed {
+                    if passed {
+                        self.checks
+                            .push(check_result.to_result(TaskCheckOutcome::ConditionMet));
+                    } else {
+                        self.checks
+                            .push(check_result.to_result(TaskCheckOutcome::ConditionUnmet));
+
                         all_conditions_met = false;
                     }
                 }
@@ -658,16 +933,22 @@ impl<'task> TaskRunner<'task> {
                         "Checking requirement"
                     );
 
-                    if let Some(output) = check_result.output
+                    if let Some(output) = &check_result.output
                         && !output.success()
                     {
+                        self.checks
+                            .push(check_result.to_result(TaskCheckOutcome::RequirementFailed));
+
                         return Err(TaskRunnerError::RequirementCheckFailed {
                             target: self.task.target.clone(),
-                            script: requirement.script,
+                            script: requirement.script.clone(),
                             error: Box::new(output.to_error("<requirement>", true)),
                         }
                         .into());
                     }
+
+                    self.checks
+                        .push(check_result.to_result(TaskCheckOutcome::RequirementMet));
                 }
                 TaskCheck::Fingerprint(_) => {
                     unreachable!();
@@ -764,11 +1045,13 @@ impl<'task> TaskRunner<'task> {
 
                 false
             }
-            ArchiveOutcome::Queued => {
+            ArchiveOutcome::Queued(manifest) => {
                 debug!(task_target, "Enqueued cache archiving operation");
 
                 operation.finish(ActionStatus::Passed);
 
+                self.state.archived_manifest = Some(manifest);
+
                 true
             }
         };
@@ -844,6 +1127,9 @@ impl<'task> TaskRunner<'task> {
 
                 operation.finish(ActionStatus::Cached);
 
+                self.save_outputs_manifest(hash, TaskOutputsSource::Local, None)
+                    .await?;
+
                 true
             }
             HydrateOutcome::HitFromStorage(manifest, is_remote) => {
@@ -875,6 +1161,17 @@ impl<'task> TaskRunner<'task> {
 
                 self.persist_state(&operation)?;
 
+                self.save_outputs_manifest(
+                    hash,
+                    if is_remote {
+                        TaskOutputsSource::Remote
+                    } else {
+                        TaskOutputsSource::Local
+                    },
+                    Some(manifest.as_ref()),
+                )
+                .await?;
+
                 true
             }
         };
@@ -888,6 +1185,67 @@ impl<'task> TaskRunner<'task> {
         Ok(hydrated)
     }
 
+    /// Record the outputs the task produced (or hydrated) in an `outputs.json`
+    /// file within the task's state directory.
+    #[instrument(skip(self, manifest))]
+    pub async fn save_outputs_manifest(
+        &self,
+        hash: &str,
+        source: TaskOutputsSource,
+        manifest: Option<&Manifest>,
+    ) -> miette::Result<()> {
+        if !self.task.has_outputs()
+            || self
+                .state
+                .target
+                .as_ref()
+                .is_some_and(|state| state.is_skipped())
+        {
+            return Ok(());
+        }
+
+        let outputs_path = self
+            .app_context
+            .cache_engine
+            .state
+            .get_target_dir(&self.task.target)
+            .join("outputs.json");
+
+        let outputs = match manifest {
+            Some(manifest) => {
+                TaskOutputsManifest::from_manifest(&self.task.target, hash, source, manifest)
+            }
+            None => {
+                // On a local hit, the outputs were recorded by the run that
+                // produced them, so reuse that instead of hashing them again
+                let stored = if source == TaskOutputsSource::Local && outputs_path.exists() {
+                    json::read_file::<TaskOutputsManifest>(&outputs_path)
+                        .ok()
+                        .filter(|stored| stored.hash == hash)
+                } else {
+                    None
+                };
+
+                match stored {
+                    Some(mut stored) => {
+                        stored.source = source;
+                        stored
+                    }
+                    None => TaskOutputsManifest::from_manifest(
+                        &self.task.target,
+                        hash,
+                        source,
+                        &self.archiver.create_outputs_manifest().await?,
+                    ),
+                }
+            }
+        };
+
+        json::write_file(outputs_path, &outputs, true)?;
+
+        Ok(())
+    }
+
     // If a task fails *before* the command is actually executed, say during the command
     // build process, or the toolchain plugin layer, that error is not bubbled up as a
     // failure, and the last operation is used instead (which is typically skipped).
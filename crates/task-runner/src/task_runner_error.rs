@@ -81,4 +81,27 @@ pub enum TaskRunnerError {
         .output.style(Style::Path),
     )]
     OutputFileNotDeclared { target: Target, output: PathBuf },
+
+    #[diagnostic(
+        code(task_runner::sandbox_unsupported),
+        help = "Sandboxing requires Linux with unprivileged user namespaces enabled. Disable the task's sandbox option to run it in the workspace instead."
+    )]
+    #[error(
+        "Task {} requires a sandbox, but sandboxing is not supported on this machine.",
+        .target.style(Style::Label),
+    )]
+    SandboxUnsupported { target: Target },
+
+    #[diagnostic(code(task_runner::semaphore_lock_failed))]
+    #[error(
+        "Task {} failed to acquire a permit from its global semaphore, as the lock file {} could not be locked.",
+        .target.style(Style::Label),
+        .path.style(Style::Path),
+    )]
+    SemaphoreLockFailed {
+        target: Target,
+        path: PathBuf,
+        #[source]
+        error: Box<std::io::Error>,
+    },
 }
@@ -0,0 +1,264 @@
+use moon_app_context::AppContext;
+use moon_process::Command;
+use moon_project::Project;
+use moon_task::Task;
+use starbase_utils::fs;
+use std::env;
+use std::path::{Component, Path, PathBuf};
+use tracing::{debug, instrument, trace};
+
+/// A hermetic directory tree that only contains the declared inputs of a task
+/// (and the vendor directories of its toolchains), in which the task is executed.
+/// The tree is bind mounted over the workspace root, within a mount namespace
+/// private to the task's process, so that the task observes its inputs at their
+/// original paths, while undeclared files do not exist at all.
+pub struct TaskSandbox<'task> {
+    app: &'task AppContext,
+    task: &'task Task,
+
+    /// Root of the sandbox, which mirrors the workspace root.
+    pub root: PathBuf,
+
+    /// Toolchain vendor directories, relative from the workspace root,
+    /// that are mounted into the sandbox as-is.
+    vendor_dirs: Vec<PathBuf>,
+
+    /// Working directory relative from the workspace root.
+    working_dir: PathBuf,
+}
+
+impl<'task> TaskSandbox<'task> {
+    /// Requires Linux, and the ability to create user and mount namespaces.
+    pub fn is_supported() -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            moon_process::is_mount_namespace_supported()
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            false
+        }
+    }
+
+    #[instrument(name = "create_sandbox", skip_all)]
+    pub async fn create(
+        app: &'task AppContext,
+        project: &'task Project,
+        task: &'task Task,
+        hash: &str,
+    ) -> miette::Result<TaskSandbox<'task>> {
+        let name = if hash.is_empty() {
+            task.target
+                .as_str()
+                .replace([':', '/', '\\', '#', '~'], "-")
+        } else {
+            hash.to_owned()
+        };
+
+        let root = env::temp_dir()
+            .join("moon-sandbox")
+            .join(format!("{name}-{}", std::process::id()));
+
+        let working_dir = if task.options.run_from_workspace_root {
+            PathBuf::new()
+        } else {
+            project
+                .root
+                .strip_prefix(&app.workspace_root)
+                .map(|dir| dir.to_path_buf())
+                .unwrap_or_default()
+        };
+
+        debug!(
+            task_target = task.target.as_str(),
+            sandbox = ?root,
+            "Creating sandbox for task",
+        );
+
+        // Remove leftovers from a previous run that was aborted
+        fs::remove_dir_all(&root)?;
+        fs::create_dir_all(root.join(&working_dir))?;
+
+        let mut sandbox = Self {
+            app,
+            task,
+            root,
+            vendor_dirs: vec![],
+            working_dir,
+        };
+
+        sandbox.materialize_inputs()?;
+        sandbox.vendor_dirs = sandbox.materialize_toolchains(project).await?;
+
+        Ok(sandbox)
+    }
+
+    /// Run the command within the sandbox, by mounting the toolchain vendor directories
+    /// into the sandbox, and then the sandbox over the workspace root. Paths are left
+    /// as-is, as the sandbox is observed at the workspace root.
+    pub fn prepare_command(&self, command: &mut Command) {
+        for rel_path in &self.vendor_dirs {
+            command.bind_mount(
+                self.app.workspace_root.join(rel_path),
+                self.root.join(rel_path),
+            );
+        }
+
+        command.bind_mount(&self.root, &self.app.workspace_root);
+        command.env("MOON_SANDBOX_ROOT", &self.root);
+    }
+
+    /// Copy the declared outputs created within the sandbox back into the workspace,
+    /// replacing any stale outputs from a previous run.
+    #[instrument(skip(self))]
+    pub fn restore_outputs(&self) -> miette::Result<()> {
+        if !self.task.has_outputs() {
+            return Ok(());
+        }
+
+        for output in self.task.get_output_files(&self.root, true)? {
+            if !output.exists() {
+                continue;
+            }
+
+            let Ok(rel_path) = output.strip_prefix(&self.root) else {
+                continue;
+            };
+
+            let dest = self.app.workspace_root.join(rel_path);
+
+            trace!(
+                task_target = self.task.target.as_str(),
+                output = ?rel_path,
+                "Restoring output from sandbox",
+            );
+
+            fs::remove(&dest)?;
+
+            if output.is_dir() {
+                fs::copy_dir_all(&output, &dest)?;
+            } else {
+                fs::copy_file(&output, &dest)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Attempt to find a workspace file that the task tried to read, by scanning the
+    /// output of a failed run for paths that exist in the workspace, but were not
+    /// materialized into the sandbox (because they are not a declared input).
+    /// This is only a hint for why the task failed, as the read itself already failed.
+    pub fn find_undeclared_input(&self, output: &str) -> Option<PathBuf> {
+        let tokens = output.split(|ch: char| {
+            ch.is_whitespace() || matches!(ch, '\'' | '"' | '`' | ':' | ',' | '(' | ')')
+        });
+
+        for token in tokens {
+            if token.is_empty() {
+                continue;
+            }
+
+            let path = Path::new(token);
+
+            let rel_path = if path.is_absolute() {
+                match path.strip_prefix(&self.app.workspace_root) {
+                    Ok(rel) => rel.to_path_buf(),
+                    Err(_) => continue,
+                }
+            } else {
+                self.working_dir.join(path)
+            };
+
+            // Don't allow paths to escape the workspace
+            if rel_path
+                .components()
+                .any(|comp| !matches!(comp, Component::Normal(_) | Component::CurDir))
+            {
+                continue;
+            }
+
+            if self.app.workspace_root.join(&rel_path).is_file()
+                && !self.root.join(&rel_path).exists()
+            {
+                return Some(rel_path);
+            }
+        }
+
+        None
+    }
+
+    pub fn cleanup(self) -> miette::Result<()> {
+        fs::remove_dir_all(&self.root)?;
+
+        Ok(())
+    }
+
+    fn materialize_inputs(&self) -> miette::Result<()> {
+        let workspace_root = &self.app.workspace_root;
+        let inputs = self.task.get_input_files(workspace_root)?;
+
+        debug!(
+            task_target = self.task.target.as_str(),
+            "Materializing {} inputs into sandbox",
+            inputs.len(),
+        );
+
+        for input in inputs {
+            let Ok(rel_path) = input.strip_prefix(workspace_root) else {
+                continue;
+            };
+
+            let dest = self.root.join(rel_path);
+
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            // Never hard link, as writes to the link would modify the
+            // workspace file, so reflink (or copy) instead
+            fs::reflink_file(&input, &dest)?;
+        }
+
+        Ok(())
+    }
+
+    async fn materialize_toolchains(&self, project: &Project) -> miette::Result<Vec<PathBuf>> {
+        let registry = &self.app.toolchain_registry;
+        let mut vendor_dirs = vec![];
+
+        for toolchain_id in project.get_enabled_toolchains_for_task(self.task) {
+            let Ok(toolchain) = registry.load(toolchain_id).await else {
+                continue;
+            };
+
+            let Some(vendor_dir_name) = &toolchain.metadata.vendor_dir_name else {
+                continue;
+            };
+
+            for dir in [PathBuf::new(), self.working_dir.clone()] {
+                let rel_path = dir.join(vendor_dir_name);
+                let vendor_dir = self.app.workspace_root.join(&rel_path);
+
+                if !vendor_dir.is_dir() || vendor_dirs.contains(&rel_path) {
+                    continue;
+                }
+
+                trace!(
+                    task_target = self.task.target.as_str(),
+                    toolchain_id = toolchain_id.as_str(),
+                    vendor_dir = ?rel_path,
+                    "Mounting toolchain vendor directory into sandbox",
+                );
+
+                // Create the mount point
+                fs::create_dir_all(self.root.join(&rel_path))?;
+
+                vendor_dirs.push(rel_path);
+            }
+        }
+
+        Ok(vendor_dirs)
+    }
+}
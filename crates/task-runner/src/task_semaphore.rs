@@ -0,0 +1,125 @@
+use crate::task_runner_error::TaskRunnerError;
+use moon_action_context::ActionContext;
+use moon_app_context::AppContext;
+use moon_common::path::encode_component;
+use moon_daemon_utils::lock::DaemonLock;
+use moon_task::{Task, TaskOptionSemaphore};
+use starbase_utils::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::time::sleep;
+use tracing::{debug, trace};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A permit acquired from a named semaphore, that's held while the task
+/// runs, and released when dropped.
+pub struct TaskSemaphorePermit {
+    _permit: OwnedSemaphorePermit,
+
+    // Held to keep the slot locked for other processes
+    _slot: Option<DaemonLock>,
+}
+
+impl TaskSemaphorePermit {
+    /// Acquire a permit from the semaphore configured for the task, waiting
+    /// until one is available. Permits are always acquired from the semaphore
+    /// shared within the pipeline, and for global semaphores, additionally
+    /// from a file-lock backed slot shared with other processes.
+    pub async fn acquire(
+        app: &AppContext,
+        context: &ActionContext,
+        task: &Task,
+        semaphore: &TaskOptionSemaphore,
+    ) -> miette::Result<Self> {
+        let permits = semaphore.permits.max(1);
+
+        debug!(
+            task_target = task.target.as_str(),
+            semaphore = &semaphore.name,
+            permits,
+            global = semaphore.global,
+            "Waiting to acquire task semaphore permit"
+        );
+
+        let permit = context
+            .get_or_create_semaphore(&semaphore.name, permits as usize)
+            .await
+            .acquire_owned()
+            .await
+            .expect("Task semaphore has been closed!");
+
+        let slot = if semaphore.global {
+            Some(acquire_global_slot(app, task, semaphore, permits).await?)
+        } else {
+            None
+        };
+
+        debug!(
+            task_target = task.target.as_str(),
+            semaphore = &semaphore.name,
+            "Acquired task semaphore permit"
+        );
+
+        Ok(Self {
+            _permit: permit,
+            _slot: slot,
+        })
+    }
+}
+
+/// Each permit of a global semaphore is a lock file, so a slot is owned by
+/// the process holding its lock, and is released by the kernel if the process
+/// crashes, avoiding stale permits. Slots are machine-wide (not scoped to the
+/// workspace), so that machine resources can be shared across workspaces.
+async fn acquire_global_slot(
+    app: &AppContext,
+    task: &Task,
+    semaphore: &TaskOptionSemaphore,
+    permits: u32,
+) -> miette::Result<DaemonLock> {
+    let slots_dir = app.moon_env.temp_dir.join("semaphores");
+    let name = encode_component(&semaphore.name);
+
+    fs::create_dir_all(&slots_dir)?;
+
+    let slots = (0..permits)
+        .map(|index| slots_dir.join(format!("{name}-{index}.lock")))
+        .collect::<Vec<PathBuf>>();
+    let mut logged = false;
+
+    loop {
+        for slot in &slots {
+            let lock = DaemonLock::try_acquire(slot).map_err(|error| {
+                TaskRunnerError::SemaphoreLockFailed {
+                    target: task.target.clone(),
+                    path: slot.to_owned(),
+                    error: Box::new(error),
+                }
+            })?;
+
+            if let Some(lock) = lock {
+                trace!(
+                    task_target = task.target.as_str(),
+                    slot = ?slot,
+                    "Acquired global semaphore slot",
+                );
+
+                return Ok(lock);
+            }
+        }
+
+        if !logged {
+            debug!(
+                task_target = task.target.as_str(),
+                semaphore = &semaphore.name,
+                "All permits of the global semaphore are held by other processes, waiting"
+            );
+
+            logged = true;
+        }
+
+        sleep(POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,424 @@
+use moon_action::{TaskTestCase, TaskTestResults, TaskTestStatus};
+use moon_app_context::AppContext;
+use moon_common::path::WorkspaceRelativePathBuf;
+use moon_config::Output;
+use moon_project::Project;
+use moon_task::Task;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::reader::Reader;
+use rustc_hash::FxHashMap;
+use starbase_utils::{fs, glob};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, instrument, warn};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TestReportFormat {
+    JUnit,
+    Tap,
+}
+
+impl TestReportFormat {
+    /// Detect the format from the file extension, and fallback to
+    /// sniffing the content, as JUnit XML always starts with a tag.
+    pub fn detect(path: &Path, content: &str) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("xml") => Self::JUnit,
+            Some("tap") => Self::Tap,
+            _ => {
+                if content.trim_start().starts_with('<') {
+                    Self::JUnit
+                } else {
+                    Self::Tap
+                }
+            }
+        }
+    }
+}
+
+/// Locates the test report files that a task writes (configured with
+/// `options.testReports`), and parses them into per-test results.
+pub struct TestReportParser<'task> {
+    app_context: &'task AppContext,
+    project: &'task Project,
+    task: &'task Task,
+}
+
+impl<'task> TestReportParser<'task> {
+    pub fn new(app_context: &'task AppContext, project: &'task Project, task: &'task Task) -> Self {
+        Self {
+            app_context,
+            project,
+            task,
+        }
+    }
+
+    /// Remove reports from a previous run, so that a run that crashes
+    /// before writing its reports doesn't inherit stale results.
+    #[instrument(skip(self))]
+    pub fn clean(&self) -> miette::Result<()> {
+        for report in self.locate_reports()? {
+            fs::remove_file(report.to_logical_path(&self.app_context.workspace_root))?;
+        }
+
+        Ok(())
+    }
+
+    /// Parse all existing report files. Reports that fail to parse are
+    /// logged and ignored, as they should never fail the task itself.
+    #[instrument(skip(self))]
+    pub fn parse(&self) -> miette::Result<Option<TaskTestResults>> {
+        let reports = self.locate_reports()?;
+
+        if reports.is_empty() {
+            debug!(
+                task_target = self.task.target.as_str(),
+                "No test reports were found for task"
+            );
+
+            return Ok(None);
+        }
+
+        let mut results = TaskTestResults::default();
+
+        for report in reports {
+            let report_path = report.to_logical_path(&self.app_context.workspace_root);
+            let content = fs::read_file(&report_path)?;
+
+            let tests = match TestReportFormat::detect(&report_path, &content) {
+                TestReportFormat::JUnit => parse_junit_xml(&content),
+                TestReportFormat::Tap => Ok(parse_tap(&content)),
+            };
+
+            match tests {
+                Ok(tests) => {
+                    debug!(
+                        task_target = self.task.target.as_str(),
+                        report = report.as_str(),
+                        tests = tests.len(),
+                        "Parsed test report"
+                    );
+
+                    for test in tests {
+                        results.add_test(test);
+                    }
+
+                    results.reports.push(report.to_string());
+                }
+                Err(error) => {
+                    warn!(
+                        task_target = self.task.target.as_str(),
+                        report = report.as_str(),
+                        "Failed to parse test report: {error}"
+                    );
+                }
+            };
+        }
+
+        Ok(Some(results))
+    }
+
+    fn locate_reports(&self) -> miette::Result<Vec<WorkspaceRelativePathBuf>> {
+        let Some(test_reports) = &self.task.options.test_reports else {
+            return Ok(vec![]);
+        };
+
+        let workspace_root = &self.app_context.workspace_root;
+        let mut files = vec![];
+        let mut globs = vec![];
+
+        for report in test_reports {
+            match report {
+                Output::File(file) => {
+                    let path = file.to_workspace_relative(self.project.source.as_str());
+
+                    if path.to_logical_path(workspace_root).is_file() {
+                        files.push(path);
+                    }
+                }
+                Output::Glob(glob) => {
+                    globs.push(
+                        glob.to_workspace_relative(self.project.source.as_str())
+                            .to_string(),
+                    );
+                }
+                // Tokens are only supported by outputs
+                _ => {}
+            };
+        }
+
+        if !globs.is_empty() {
+            for path in glob::walk_files(workspace_root, &globs)? {
+                if let Ok(rel_path) = path.strip_prefix(workspace_root)
+                    && let Ok(rel_path) = WorkspaceRelativePathBuf::from_path(rel_path)
+                    && !files.contains(&rel_path)
+                {
+                    files.push(rel_path);
+                }
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+// JUNIT XML
+
+fn parse_seconds(value: Option<&String>) -> Option<Duration> {
+    value
+        .and_then(|value| value.replace(',', "").parse::<f64>().ok())
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+}
+
+fn parse_xml_attrs(
+    reader: &Reader<&[u8]>,
+    element: &BytesStart,
+) -> Result<FxHashMap<String, String>, String> {
+    let mut attrs = FxHashMap::default();
+
+    for attr in element.attributes() {
+        let attr = attr.map_err(|error| error.to_string())?;
+
+        attrs.insert(
+            String::from_utf8_lossy(attr.key.as_ref()).into_owned(),
+            attr.decode_and_unescape_value(reader.decoder())
+                .map_err(|error| error.to_string())?
+                .into_owned(),
+        );
+    }
+
+    Ok(attrs)
+}
+
+fn get_first_line(value: &str) -> Option<String> {
+    value
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .map(|line| line.to_owned())
+}
+
+/// Parse a JUnit XML report, as written by most test runners (Jest, Vitest,
+/// pytest, cargo-nextest, Surefire, etc). Both `<testsuites>` and a single
+/// root `<testsuite>` are supported.
+pub fn parse_junit_xml(content: &str) -> Result<Vec<TaskTestCase>, String> {
+    let mut reader = Reader::from_str(content);
+    let mut tests = vec![];
+    let mut suites: Vec<Option<String>> = vec![];
+    let mut current: Option<TaskTestCase> = None;
+    let mut message: Option<String> = None;
+    let mut found_root = false;
+
+    loop {
+        let (element, closed) = match reader.read_event().map_err(|error| error.to_string())? {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::End(element) => {
+                match element.name().as_ref() {
+                    b"testsuite" => {
+                        suites.pop();
+                    }
+                    b"testcase" => {
+                        if let Some(test) = current.take() {
+                            tests.push(test);
+                        }
+                    }
+                    b"failure" | b"error" | b"skipped" => {
+                        if let Some(text) = message.take()
+                            && let Some(test) = &mut current
+                            && test.message.is_none()
+                        {
+                            test.message = get_first_line(&text);
+                        }
+                    }
+                    _ => {}
+                };
+
+                continue;
+            }
+            Event::Text(text) => {
+                if let Some(message) = &mut message {
+                    message.push_str(&text.decode().map_err(|error| error.to_string())?);
+                }
+
+                continue;
+            }
+            Event::CData(data) => {
+                if let Some(message) = &mut message {
+                    message.push_str(&data.decode().map_err(|error| error.to_string())?);
+                }
+
+                continue;
+            }
+            Event::GeneralRef(entity) => {
+                if let Some(message) = &mut message {
+                    if let Some(ch) = entity
+                        .resolve_char_ref()
+                        .map_err(|error| error.to_string())?
+                    {
+                        message.push(ch);
+                    } else {
+                        let name = entity.decode().map_err(|error| error.to_string())?;
+
+                        message.push_str(resolve_predefined_entity(&name).unwrap_or_default());
+                    }
+                }
+
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        match element.name().as_ref() {
+            b"testsuites" => {
+                found_root = true;
+            }
+            b"testsuite" => {
+                found_root = true;
+
+                if !closed {
+                    suites.push(parse_xml_attrs(&reader, &element)?.remove("name"));
+                }
+            }
+            b"testcase" => {
+                let attrs = parse_xml_attrs(&reader, &element)?;
+                let mut test = TaskTestCase::new(
+                    attrs
+                        .get("name")
+                        .map(|name| name.as_str())
+                        .unwrap_or_default(),
+                    TaskTestStatus::Passed,
+                );
+
+                test.suite = attrs
+                    .get("classname")
+                    .cloned()
+                    .or_else(|| suites.last().cloned().flatten());
+                test.duration = parse_seconds(attrs.get("time"));
+
+                if closed {
+                    tests.push(test);
+                } else {
+                    current = Some(test);
+                }
+            }
+            name @ (b"failure" | b"error" | b"skipped") => {
+                if let Some(test) = &mut current {
+                    let attrs = parse_xml_attrs(&reader, &element)?;
+
+                    test.status = if name == b"skipped" {
+                        TaskTestStatus::Skipped
+                    } else {
+                        TaskTestStatus::Failed
+                    };
+
+                    if test.message.is_none() {
+                        test.message = attrs.get("message").and_then(|msg| get_first_line(msg));
+                    }
+
+                    // Collect the text content, as a fallback for the message attribute
+                    if !closed {
+                        message = Some(String::new());
+                    }
+                }
+            }
+            _ => {}
+        };
+    }
+
+    if !found_root {
+        return Err("missing a <testsuites> or <testsuite> root element".into());
+    }
+
+    Ok(tests)
+}
+
+// TAP
+
+/// Parse a TAP (Test Anything Protocol) report. Only top-level test points
+/// are parsed, as indented subtests are summarized by their parent.
+pub fn parse_tap(content: &str) -> Vec<TaskTestCase> {
+    let mut tests: Vec<TaskTestCase> = vec![];
+    let mut in_diagnostic = false;
+
+    for line in content.lines() {
+        // YAML diagnostic blocks follow a failed test point
+        if in_diagnostic {
+            let trimmed = line.trim();
+
+            if trimmed == "..." {
+                in_diagnostic = false;
+            } else if let Some(message) = trimmed.strip_prefix("message:")
+                && let Some(test) = tests.last_mut()
+                && test.message.is_none()
+            {
+                test.message =
+                    get_first_line(message.trim().trim_matches(|ch| ch == '\'' || ch == '"'));
+            }
+
+            continue;
+        }
+
+        if line.starts_with(char::is_whitespace) {
+            if line.trim() == "---" && !tests.is_empty() {
+                in_diagnostic = true;
+            }
+
+            continue;
+        }
+
+        let (passed, rest) = if let Some(rest) = line.strip_prefix("not ok") {
+            (false, rest)
+        } else if let Some(rest) = line.strip_prefix("ok") {
+            (true, rest)
+        } else {
+            continue;
+        };
+
+        if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+            continue;
+        }
+
+        // Strip the test number and separator
+        let rest = rest
+            .trim_start()
+            .trim_start_matches(|ch: char| ch.is_ascii_digit())
+            .trim_start();
+        let rest = rest.strip_prefix('-').unwrap_or(rest).trim();
+
+        let (description, directive) = match rest.split_once(" # ") {
+            Some((description, directive)) => (description.trim(), Some(directive.trim())),
+            None => match rest.strip_prefix('#') {
+                Some(directive) => ("", Some(directive.trim())),
+                None => (rest, None),
+            },
+        };
+
+        let directive_upper = directive.map(|d| d.to_uppercase()).unwrap_or_default();
+        let is_skip = directive_upper.starts_with("SKIP");
+        let is_todo = directive_upper.starts_with("TODO");
+
+        // Failing todo tests are expected to fail, so they don't count as failures
+        let status = if is_skip || (is_todo && !passed) {
+            TaskTestStatus::Skipped
+        } else if passed {
+            TaskTestStatus::Passed
+        } else {
+            TaskTestStatus::Failed
+        };
+
+        let mut test = TaskTestCase::new(description, status);
+
+        if status == TaskTestStatus::Skipped
+            && let Some(directive) = directive
+        {
+            test.message = get_first_line(directive[4..].trim_start_matches(':'));
+        }
+
+        tests.push(test);
+    }
+
+    tests
+}
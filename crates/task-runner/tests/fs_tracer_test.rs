@@ -0,0 +1,214 @@
+mod utils;
+
+use moon_common::path::RelativePathBuf;
+use moon_process::{Command, CommandExecutable};
+use moon_task_runner::fs_tracer::{FsAccess, FsTracer, parse_trace_line};
+use std::fs;
+use std::path::PathBuf;
+use utils::*;
+
+mod parse_trace_line {
+    use super::*;
+
+    #[test]
+    fn parses_reads_from_returned_descriptor() {
+        let mut cwd = PathBuf::from("/workspace/project");
+
+        assert_eq!(
+            parse_trace_line(
+                r#"openat(AT_FDCWD, "src/index.js", O_RDONLY|O_CLOEXEC) = 3</workspace/project/src/index.js>"#,
+                &mut cwd
+            ),
+            Some((
+                FsAccess::Read,
+                PathBuf::from("/workspace/project/src/index.js")
+            ))
+        );
+    }
+
+    #[test]
+    fn parses_writes_from_flags() {
+        let mut cwd = PathBuf::from("/workspace/project");
+
+        assert_eq!(
+            parse_trace_line(
+                r#"openat(AT_FDCWD, "dist/index.js", O_WRONLY|O_CREAT|O_TRUNC|O_CLOEXEC, 0666) = 4</workspace/project/dist/index.js>"#,
+                &mut cwd
+            ),
+            Some((
+                FsAccess::Write,
+                PathBuf::from("/workspace/project/dist/index.js")
+            ))
+        );
+        assert_eq!(
+            parse_trace_line(r#"rename("dist/.tmp", "dist/out.js") = 0"#, &mut cwd),
+            Some((
+                FsAccess::Write,
+                PathBuf::from("/workspace/project/dist/out.js")
+            ))
+        );
+    }
+
+    #[test]
+    fn ignores_failed_calls_and_directories() {
+        let mut cwd = PathBuf::from("/workspace/project");
+
+        assert_eq!(
+            parse_trace_line(
+                r#"openat(AT_FDCWD, "missing.txt", O_RDONLY) = -1 ENOENT (No such file or directory)"#,
+                &mut cwd
+            ),
+            None
+        );
+        assert_eq!(
+            parse_trace_line(
+                r#"openat(AT_FDCWD, "src", O_RDONLY|O_NONBLOCK|O_CLOEXEC|O_DIRECTORY) = 3</workspace/project/src>"#,
+                &mut cwd
+            ),
+            None
+        );
+        assert_eq!(parse_trace_line("+++ exited with 0 +++", &mut cwd), None);
+    }
+
+    #[test]
+    fn tracks_chdir_for_relative_paths() {
+        let mut cwd = PathBuf::from("/workspace/project");
+
+        assert_eq!(parse_trace_line(r#"chdir("../other") = 0"#, &mut cwd), None);
+        assert_eq!(
+            parse_trace_line(r#"creat("file.txt", 0644) = 3"#, &mut cwd),
+            Some((
+                FsAccess::Write,
+                PathBuf::from("/workspace/project/../other/file.txt")
+            ))
+        );
+    }
+}
+
+mod fs_tracer {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn wraps_binary_commands() {
+        let container = TaskRunnerContainer::new("trace-fs", "build").await;
+        let tracer = FsTracer::new(
+            &container.app_context,
+            &container.project,
+            &container.task,
+            "hash123",
+        );
+
+        let mut command = Command::new("node");
+        command.arg("build.js");
+
+        tracer.wrap_command(&mut command).unwrap();
+
+        assert_eq!(command.get_bin_name(), "strace");
+
+        let args = command.get_args_list();
+
+        assert_eq!(args[0], "-ff");
+        assert_eq!(args[args.len() - 3], "--");
+        assert_eq!(args[args.len() - 2], "node");
+        assert_eq!(args[args.len() - 1], "build.js");
+
+        tracer.cleanup().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn wraps_scripts_in_a_shell() {
+        let container = TaskRunnerContainer::new("trace-fs", "build").await;
+        let tracer = FsTracer::new(
+            &container.app_context,
+            &container.project,
+            &container.task,
+            "hash123",
+        );
+
+        let mut command = Command::new_script("echo 'a' && echo b");
+
+        tracer.wrap_command(&mut command).unwrap();
+
+        let CommandExecutable::Script(script) = &command.exe else {
+            panic!("expected a script");
+        };
+        let script = script.to_string_lossy();
+
+        assert!(script.starts_with("strace '-ff'"));
+        assert!(script.ends_with(r#"-c 'echo '\''a'\'' && echo b'"#));
+
+        tracer.cleanup().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reports_undeclared_inputs_and_outputs() {
+        let container = TaskRunnerContainer::new("trace-fs", "build").await;
+        let tracer = FsTracer::new(
+            &container.app_context,
+            &container.project,
+            &container.task,
+            "hash123",
+        );
+        let root = &container.app_context.workspace_root;
+
+        tracer.wrap_command(&mut Command::new("noop")).unwrap();
+
+        container.sandbox.create_file("project/dist/index.js", "");
+        container.sandbox.create_file("project/types.d.ts", "");
+
+        let log = [
+            format!(
+                r#"openat(AT_FDCWD, "src/index.js", O_RDONLY) = 3<{}>"#,
+                root.join("project/src/index.js").display()
+            ),
+            format!(
+                r#"openat(AT_FDCWD, "tsconfig.json", O_RDONLY) = 3<{}>"#,
+                root.join("project/tsconfig.json").display()
+            ),
+            format!(
+                r#"openat(AT_FDCWD, "../shared.txt", O_RDONLY) = 3<{}>"#,
+                root.join("shared.txt").display()
+            ),
+            format!(
+                r#"openat(AT_FDCWD, "dist/index.js", O_WRONLY|O_CREAT) = 4<{}>"#,
+                root.join("project/dist/index.js").display()
+            ),
+            format!(
+                r#"openat(AT_FDCWD, "types.d.ts", O_WRONLY|O_CREAT) = 4<{}>"#,
+                root.join("project/types.d.ts").display()
+            ),
+            r#"openat(AT_FDCWD, "/usr/lib/libc.so.6", O_RDONLY) = 3</usr/lib/libc.so.6>"#
+                .to_owned(),
+        ];
+
+        fs::write(
+            container
+                .app_context
+                .cache_engine
+                .temp_dir
+                .join("trace-fs-hash123/trace.1234"),
+            log.join("\n"),
+        )
+        .unwrap();
+
+        let report = tracer.analyze().await.unwrap();
+
+        assert_eq!(
+            report.undeclared_inputs,
+            [
+                RelativePathBuf::from("project/tsconfig.json"),
+                RelativePathBuf::from("shared.txt")
+            ]
+        );
+        assert_eq!(
+            report.undeclared_outputs,
+            [RelativePathBuf::from("project/types.d.ts")]
+        );
+        assert_eq!(
+            report.format_suggestion(&container.project, &container.task),
+            "tasks:\n  build:\n    inputs:\n      - 'tsconfig.json'\n      - '/shared.txt'\n    outputs:\n      - 'types.d.ts'"
+        );
+
+        tracer.cleanup().unwrap();
+    }
+}
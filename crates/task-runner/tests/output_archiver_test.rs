@@ -2,9 +2,13 @@ mod utils;
 
 use moon_blob::Blob;
 use moon_cache::CacheMode;
+use moon_common::Id;
+use moon_config::RemoteCacheWriteRule;
 use moon_env_var::GlobalEnvBag;
 use moon_hash::Digest;
-use moon_task_runner::output_archiver::ArchiveOutcome;
+use moon_task_runner::output_archiver::{
+    ArchiveOutcome, RemoteWriteContext, is_remote_write_allowed,
+};
 use starbase_archive::Archiver;
 use std::fs;
 use utils::*;
@@ -53,7 +57,7 @@ mod output_archiver {
 
             assert!(matches!(
                 archiver.archive("hash123", &state).await.unwrap(),
-                ArchiveOutcome::Queued
+                ArchiveOutcome::Queued(_)
             ));
             assert!(
                 container
@@ -77,7 +81,7 @@ mod output_archiver {
 
             assert!(matches!(
                 archiver.archive("hash123", &state).await.unwrap(),
-                ArchiveOutcome::Queued
+                ArchiveOutcome::Queued(_)
             ));
 
             let file = container
@@ -620,7 +624,7 @@ mod output_archiver {
 
             assert!(matches!(
                 archiver.archive("hash123", &state).await.unwrap(),
-                ArchiveOutcome::Queued
+                ArchiveOutcome::Queued(_)
             ));
             container.flush_storage().await;
 
@@ -645,7 +649,7 @@ mod output_archiver {
 
             assert!(matches!(
                 archiver.archive("hash123", &state).await.unwrap(),
-                ArchiveOutcome::Queued
+                ArchiveOutcome::Queued(_)
             ));
             container.flush_storage().await;
 
@@ -704,4 +708,102 @@ mod output_archiver {
             assert!(archiver.has_outputs_been_created(false).unwrap());
         }
     }
+
+    mod write_policy {
+        use super::*;
+
+        fn create_context<'a>(branch: &'a str, ci: bool, tags: &'a [Id]) -> RemoteWriteContext<'a> {
+            RemoteWriteContext {
+                branch,
+                ci,
+                default_branch: branch == "master",
+                pull_request: false,
+                tags,
+            }
+        }
+
+        fn only_ci_on_default_branch() -> Vec<RemoteCacheWriteRule> {
+            vec![RemoteCacheWriteRule {
+                ci: Some(true),
+                default_branch: Some(true),
+                ..Default::default()
+            }]
+        }
+
+        #[test]
+        fn allows_without_rules() {
+            assert!(is_remote_write_allowed(&[], &create_context("feature", false, &[])).unwrap());
+        }
+
+        #[test]
+        fn denies_when_no_rules_match() {
+            let rules = only_ci_on_default_branch();
+
+            assert!(
+                !is_remote_write_allowed(&rules, &create_context("master", false, &[])).unwrap()
+            );
+            assert!(
+                !is_remote_write_allowed(&rules, &create_context("feature", true, &[])).unwrap()
+            );
+            assert!(is_remote_write_allowed(&rules, &create_context("master", true, &[])).unwrap());
+        }
+
+        #[test]
+        fn treats_pull_requests_as_non_default_branch() {
+            let rules = only_ci_on_default_branch();
+            let mut context = create_context("master", true, &[]);
+
+            assert!(is_remote_write_allowed(&rules, &context).unwrap());
+
+            context.pull_request = true;
+
+            assert!(!is_remote_write_allowed(&rules, &context).unwrap());
+        }
+
+        #[test]
+        fn applies_first_matching_rule() {
+            let rules = vec![
+                RemoteCacheWriteRule {
+                    allow: false,
+                    tags: vec![Id::raw("untrusted")],
+                    ..Default::default()
+                },
+                RemoteCacheWriteRule {
+                    ci: Some(true),
+                    ..Default::default()
+                },
+            ];
+
+            assert!(
+                !is_remote_write_allowed(
+                    &rules,
+                    &create_context("master", true, &[Id::raw("untrusted")])
+                )
+                .unwrap()
+            );
+            assert!(
+                is_remote_write_allowed(&rules, &create_context("master", true, &[Id::raw("app")]))
+                    .unwrap()
+            );
+        }
+
+        #[test]
+        fn matches_branch_globs() {
+            let rules = vec![RemoteCacheWriteRule {
+                branches: vec!["release/*".into(), "!release/legacy".into()],
+                ..Default::default()
+            }];
+
+            assert!(
+                is_remote_write_allowed(&rules, &create_context("release/v2", true, &[])).unwrap()
+            );
+            assert!(
+                !is_remote_write_allowed(&rules, &create_context("release/legacy", true, &[]))
+                    .unwrap()
+            );
+            assert!(
+                !is_remote_write_allowed(&rules, &create_context("feature/a", true, &[])).unwrap()
+            );
+        }
+    }
 }
@@ -1,8 +1,8 @@
 mod utils;
 
-use moon_action::ActionStatus;
+use moon_action::{ActionStatus, TaskCheckOutcome};
 use moon_action_context::*;
-use moon_cache::{CacheMode, Manifest};
+use moon_cache::{CacheMode, Manifest, TaskOutputsManifest, TaskOutputsSource};
 use moon_config::{
     TaskCheck, TaskCheckConditionConfig, TaskCheckFingerprint, TaskCheckFingerprintConfig,
     TaskCheckRequirementConfig,
@@ -14,6 +14,7 @@ use moon_task_runner::TaskRunner;
 use moon_task_runner::output_hydrater::HydrateFrom;
 use moon_time::now_millis;
 use rustc_hash::FxHashSet;
+use starbase_utils::json;
 use utils::*;
 
 mod task_runner {
@@ -457,6 +458,55 @@ mod task_runner {
         }
     }
 
+    mod acquire_lease {
+        use super::*;
+        use std::time::Duration;
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn locks_the_hash() {
+            let container = TaskRunnerContainer::new("runner", "base").await;
+            let mut runner = container.create_runner();
+
+            runner.acquire_lease("hash123").await.unwrap();
+
+            assert!(container.app_context.cache_engine.is_locked("task-hash123"));
+            assert!(!container.app_context.cache_engine.is_locked("task-hash456"));
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn waits_for_other_process_and_reloads_state() {
+            let container = TaskRunnerContainer::new("runner", "base").await;
+            let mut runner = container.create_runner();
+
+            // Simulate another process running the same task
+            let lock = container
+                .app_context
+                .cache_engine
+                .create_lease("task-hash123")
+                .unwrap();
+
+            let mut other = container.create_runner();
+            other.cache.data.exit_code = 0;
+            other.cache.data.hash = "hash123".into();
+            other.cache.save().unwrap();
+
+            let release = async {
+                tokio::time::sleep(Duration::from_millis(250)).await;
+                drop(lock);
+            };
+
+            let (result, _) = tokio::join!(runner.acquire_lease("hash123"), release);
+
+            result.unwrap();
+
+            assert_eq!(runner.cache.data.hash, "hash123");
+            assert!(matches!(
+                runner.is_cached("hash123").await.unwrap(),
+                Some(HydrateFrom::PreviousOutput)
+            ));
+        }
+    }
+
     mod is_cached {
         use super::*;
 
@@ -1069,6 +1119,29 @@ mod task_runner {
             assert_eq!(operation.status, ActionStatus::Passed);
         }
 
+        #[tokio::test(flavor = "multi_thread")]
+        async fn creates_operation_for_semaphore_acquire() {
+            let container = TaskRunnerContainer::new_os("runner", "with-semaphore").await;
+            container.sandbox.enable_git();
+
+            let mut runner = container.create_runner();
+            let node = container.create_action_node();
+            let context = ActionContext::default();
+
+            setup_exec_state(&mut runner);
+
+            // Swallow panic so we can check operations
+            let _ = runner.execute(&context, &node).await;
+
+            let operation = runner
+                .operations
+                .iter()
+                .find(|op| op.meta.is_semaphore_acquisition())
+                .unwrap();
+
+            assert_eq!(operation.status, ActionStatus::Passed);
+        }
+
         #[tokio::test(flavor = "multi_thread")]
         #[should_panic(expected = "failed to run")]
         async fn errors_when_task_exec_fails() {
@@ -1242,6 +1315,132 @@ mod task_runner {
         }
     }
 
+    mod save_outputs_manifest {
+        use super::*;
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn writes_outputs_json() {
+            let container = TaskRunnerContainer::new("runner", "outputs").await;
+            container.sandbox.enable_git();
+            container.sandbox.create_file("project/file.txt", "content");
+
+            let runner = container.create_runner();
+            runner
+                .save_outputs_manifest("hash123", TaskOutputsSource::Fresh, None)
+                .await
+                .unwrap();
+
+            let outputs: TaskOutputsManifest = json::read_file(
+                container
+                    .app_context
+                    .cache_engine
+                    .state
+                    .get_target_dir(&container.task.target)
+                    .join("outputs.json"),
+            )
+            .unwrap();
+
+            assert_eq!(outputs.hash, "hash123");
+            assert_eq!(outputs.source, TaskOutputsSource::Fresh);
+            assert_eq!(outputs.files.len(), 1);
+            assert_eq!(outputs.files[0].path.as_str(), "project/file.txt");
+            assert_eq!(outputs.files[0].size, 7);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn reuses_stored_outputs_for_local_hits() {
+            let container = TaskRunnerContainer::new("runner", "outputs").await;
+            container.sandbox.enable_git();
+            container.sandbox.create_file("project/file.txt", "content");
+
+            let runner = container.create_runner();
+            runner
+                .save_outputs_manifest("hash123", TaskOutputsSource::Fresh, None)
+                .await
+                .unwrap();
+
+            // Not hashed again, so the change isn't reflected
+            container
+                .sandbox
+                .create_file("project/file.txt", "changed content");
+
+            runner
+                .save_outputs_manifest("hash123", TaskOutputsSource::Local, None)
+                .await
+                .unwrap();
+
+            let outputs: TaskOutputsManifest = json::read_file(
+                container
+                    .app_context
+                    .cache_engine
+                    .state
+                    .get_target_dir(&container.task.target)
+                    .join("outputs.json"),
+            )
+            .unwrap();
+
+            assert_eq!(outputs.source, TaskOutputsSource::Local);
+            assert_eq!(outputs.files[0].size, 7);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn rehashes_outputs_for_local_hits_with_a_different_hash() {
+            let container = TaskRunnerContainer::new("runner", "outputs").await;
+            container.sandbox.enable_git();
+            container.sandbox.create_file("project/file.txt", "content");
+
+            let runner = container.create_runner();
+            runner
+                .save_outputs_manifest("hash123", TaskOutputsSource::Fresh, None)
+                .await
+                .unwrap();
+
+            container
+                .sandbox
+                .create_file("project/file.txt", "changed content");
+
+            runner
+                .save_outputs_manifest("hash456", TaskOutputsSource::Local, None)
+                .await
+                .unwrap();
+
+            let outputs: TaskOutputsManifest = json::read_file(
+                container
+                    .app_context
+                    .cache_engine
+                    .state
+                    .get_target_dir(&container.task.target)
+                    .join("outputs.json"),
+            )
+            .unwrap();
+
+            assert_eq!(outputs.hash, "hash456");
+            assert_eq!(outputs.files[0].size, 15);
+        }
+
+        #[tokio::test(flavor = "multi_thread")]
+        async fn doesnt_write_for_tasks_without_outputs() {
+            let container = TaskRunnerContainer::new("runner", "base").await;
+            container.sandbox.enable_git();
+
+            let runner = container.create_runner();
+            runner
+                .save_outputs_manifest("hash123", TaskOutputsSource::Fresh, None)
+                .await
+                .unwrap();
+
+            assert!(
+                !container
+                    .app_context
+                    .cache_engine
+                    .state
+                    .get_target_dir(&container.task.target)
+                    .join("outputs.json")
+                    .exists()
+            );
+        }
+    }
+
     mod hydrate {
         use super::*;
 
@@ -1459,6 +1658,35 @@ mod task_runner {
             assert!(serialized.contains("\"exit_code\":0"));
         }
 
+        #[tokio::test(flavor = "multi_thread")]
+        async fn fingerprint_records_check_results() {
+            let container = TaskRunnerContainer::new("runner", "base").await;
+            let mut task = container.task.as_ref().to_owned();
+            task.checks.push(make_fingerprint_check(
+                "echo hello",
+                TaskCheckFingerprint::default(),
+            ));
+            task.checks.push(make_fingerprint_check(
+                "echo ignored",
+                TaskCheckFingerprint::Enabled(false),
+            ));
+            let task = std::sync::Arc::new(task);
+            let mut runner =
+                TaskRunner::new(&container.app_context, &container.project, &task, None).unwrap();
+
+            let mut hasher = ContentHasher::new("test");
+            runner.hash_checks(&mut hasher).await.unwrap();
+
+            assert_eq!(runner.checks.len(), 2);
+            assert_eq!(runner.checks[0].outcome, TaskCheckOutcome::Fingerprinted);
+            assert!(runner.checks[0].fingerprint.is_some());
+            assert_eq!(
+                runner.checks[1].outcome,
+                TaskCheckOutcome::FingerprintIgnored
+            );
+            assert!(runner.checks[1].fingerprint.is_none());
+        }
+
         #[cfg(unix)]
         #[tokio::test(flavor = "multi_thread")]
         async fn fingerprint_hash_stdout_only() {
@@ -1813,6 +2041,34 @@ mod task_runner {
                 assert!(!runner.operations.is_empty());
                 assert!(runner.operations[0].meta.is_process_execution());
             }
+
+            #[tokio::test(flavor = "multi_thread")]
+            async fn records_check_results() {
+                let container = TaskRunnerContainer::new("runner", "base").await;
+                let mut task = container.task.as_ref().to_owned();
+                task.checks.push(make_requirement("exit 0"));
+                task.checks.push(make_requirement("exit 3"));
+                let task = std::sync::Arc::new(task);
+                let mut runner =
+                    TaskRunner::new(&container.app_context, &container.project, &task, None)
+                        .unwrap();
+
+                assert!(runner.execute_checks().await.is_err());
+
+                let results = runner
+                    .checks
+                    .iter()
+                    .map(|check| (check.script.as_str(), check.outcome, check.exit_code))
+                    .collect::<Vec<_>>();
+
+                assert_eq!(
+                    results,
+                    [
+                        ("exit 0", TaskCheckOutcome::RequirementMet, Some(0)),
+                        ("exit 3", TaskCheckOutcome::RequirementFailed, Some(3)),
+                    ]
+                );
+            }
         }
 
         mod conditions {
@@ -1904,6 +2160,34 @@ mod task_runner {
                 assert!(!runner.operations.is_empty());
                 assert!(runner.operations[0].meta.is_process_execution());
             }
+
+            #[tokio::test(flavor = "multi_thread")]
+            async fn records_check_results_in_configured_order() {
+                let container = TaskRunnerContainer::new("runner", "base").await;
+                let mut task = container.task.as_ref().to_owned();
+                task.checks.push(make_condition("sleep 1 && exit 0"));
+                task.checks.push(make_condition("exit 1"));
+                let task = std::sync::Arc::new(task);
+                let mut runner =
+                    TaskRunner::new(&container.app_context, &container.project, &task, None)
+                        .unwrap();
+
+                runner.execute_checks().await.unwrap();
+
+                let results = runner
+                    .checks
+                    .iter()
+                    .map(|check| (check.script.as_str(), check.outcome))
+                    .collect::<Vec<_>>();
+
+                assert_eq!(
+                    results,
+                    [
+                        ("sleep 1 && exit 0", TaskCheckOutcome::ConditionMet),
+                        ("exit 1", TaskCheckOutcome::ConditionUnmet),
+                    ]
+                );
+            }
         }
 
         mod mixed {
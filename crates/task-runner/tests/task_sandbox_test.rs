@@ -0,0 +1,169 @@
+mod utils;
+
+use moon_action_context::ActionContext;
+use moon_process::Command;
+use moon_task_runner::task_sandbox::TaskSandbox;
+use std::path::PathBuf;
+use utils::*;
+
+async fn create_sandbox<'a>(container: &'a TaskRunnerContainer, hash: &str) -> TaskSandbox<'a> {
+    TaskSandbox::create(
+        &container.app_context,
+        &container.project,
+        &container.task,
+        hash,
+    )
+    .await
+    .unwrap()
+}
+
+mod task_sandbox {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn only_materializes_declared_inputs() {
+        let container = TaskRunnerContainer::new("sandbox", "build").await;
+        let sandbox = create_sandbox(&container, "inputs").await;
+
+        assert!(!sandbox.root.starts_with(container.sandbox.path()));
+        assert!(sandbox.root.join("project/src/index.js").exists());
+        assert!(!sandbox.root.join("project/undeclared.txt").exists());
+        assert!(!sandbox.root.join("project/moon.yml").exists());
+
+        sandbox.cleanup().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn mounts_sandbox_over_workspace() {
+        let container = TaskRunnerContainer::new("sandbox", "build").await;
+        let sandbox = create_sandbox(&container, "command").await;
+        let workspace_root = &container.app_context.workspace_root;
+
+        let mut command = Command::new("cat");
+        command.cwd(workspace_root.join("project"));
+        command.arg(workspace_root.join("project/src/index.js"));
+
+        sandbox.prepare_command(&mut command);
+
+        // Paths are not remapped
+        assert_eq!(
+            command.cwd.as_ref().map(PathBuf::from).unwrap(),
+            workspace_root.join("project")
+        );
+        assert_eq!(
+            command.bind_mounts.last().unwrap(),
+            &(
+                sandbox.root.clone().into_os_string(),
+                workspace_root.clone().into_os_string()
+            )
+        );
+
+        sandbox.cleanup().unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn fails_to_read_undeclared_inputs() {
+        if !TaskSandbox::is_supported() {
+            return;
+        }
+
+        let container = TaskRunnerContainer::new("sandbox", "build").await;
+        let sandbox = create_sandbox(&container, "read").await;
+        let workspace_root = &container.app_context.workspace_root;
+
+        let mut declared = Command::new("cat");
+        declared.cwd(workspace_root.join("project"));
+        declared.arg("src/index.js");
+        sandbox.prepare_command(&mut declared);
+
+        assert!(declared.exec_capture_output().await.is_ok());
+
+        let mut undeclared = Command::new("cat");
+        undeclared.cwd(workspace_root.join("project"));
+        undeclared.arg("undeclared.txt");
+        sandbox.prepare_command(&mut undeclared);
+
+        assert!(undeclared.exec_capture_output().await.is_err());
+
+        sandbox.cleanup().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn restores_outputs_into_workspace() {
+        let container = TaskRunnerContainer::new("sandbox", "build").await;
+        let sandbox = create_sandbox(&container, "outputs").await;
+
+        std::fs::create_dir_all(sandbox.root.join("project/dist")).unwrap();
+        std::fs::write(sandbox.root.join("project/dist/index.js"), "built").unwrap();
+
+        sandbox.restore_outputs().unwrap();
+
+        let output = container.sandbox.path().join("project/dist/index.js");
+
+        assert!(output.exists());
+        assert_eq!(std::fs::read_to_string(output).unwrap(), "built");
+
+        sandbox.cleanup().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn detects_undeclared_inputs_from_output() {
+        let container = TaskRunnerContainer::new("sandbox", "build").await;
+        let sandbox = create_sandbox(&container, "undeclared").await;
+
+        assert_eq!(
+            sandbox.find_undeclared_input("cat: undeclared.txt: No such file or directory"),
+            Some(PathBuf::from("project/undeclared.txt"))
+        );
+        assert_eq!(
+            sandbox.find_undeclared_input(&format!(
+                "Error: ENOENT: no such file or directory, open '{}'",
+                container
+                    .app_context
+                    .workspace_root
+                    .join("project/undeclared.txt")
+                    .display()
+            )),
+            Some(PathBuf::from("project/undeclared.txt"))
+        );
+        assert_eq!(sandbox.find_undeclared_input("src/index.js failed"), None);
+        assert_eq!(sandbox.find_undeclared_input("missing.txt"), None);
+
+        sandbox.cleanup().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn removes_sandbox_on_cleanup() {
+        let container = TaskRunnerContainer::new("sandbox", "build").await;
+        let sandbox = create_sandbox(&container, "cleanup").await;
+        let root = sandbox.root.clone();
+
+        assert!(root.exists());
+
+        sandbox.cleanup().unwrap();
+
+        assert!(!root.exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn errors_when_unsupported_instead_of_running_in_workspace() {
+        if TaskSandbox::is_supported() {
+            return;
+        }
+
+        let container = TaskRunnerContainer::new("sandbox", "build").await;
+        let mut runner = container.create_runner();
+        let node = container.create_action_node();
+
+        runner.report.hash = Some("hash123".into());
+
+        let error = runner
+            .execute(&ActionContext::default(), &node)
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("requires a sandbox"));
+        assert!(runner.state.target.is_none());
+    }
+}
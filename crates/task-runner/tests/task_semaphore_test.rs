@@ -0,0 +1,109 @@
+mod utils;
+
+use moon_action_context::ActionContext;
+use moon_daemon_utils::lock::DaemonLock;
+use moon_task_runner::task_semaphore::TaskSemaphorePermit;
+use std::time::Duration;
+use tokio::time::timeout;
+use utils::*;
+
+async fn acquire(container: &TaskRunnerContainer, context: &ActionContext) -> TaskSemaphorePermit {
+    TaskSemaphorePermit::acquire(
+        &container.app_context,
+        context,
+        &container.task,
+        container.task.options.semaphore.as_ref().unwrap(),
+    )
+    .await
+    .unwrap()
+}
+
+async fn try_acquire(
+    container: &TaskRunnerContainer,
+    context: &ActionContext,
+) -> Option<TaskSemaphorePermit> {
+    timeout(Duration::from_millis(500), acquire(container, context))
+        .await
+        .ok()
+}
+
+mod task_semaphore {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn waits_when_all_permits_are_held() {
+        let container = TaskRunnerContainer::new("semaphore", "local").await;
+        let context = ActionContext::default();
+
+        let first = acquire(&container, &context).await;
+        let _second = acquire(&container, &context).await;
+
+        assert!(try_acquire(&container, &context).await.is_none());
+
+        drop(first);
+
+        assert!(try_acquire(&container, &context).await.is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn doesnt_share_local_permits_between_contexts() {
+        let container = TaskRunnerContainer::new("semaphore", "local").await;
+        let context = ActionContext::default();
+
+        let _first = acquire(&container, &context).await;
+        let _second = acquire(&container, &context).await;
+
+        assert!(
+            try_acquire(&container, &ActionContext::default())
+                .await
+                .is_some()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn shares_global_permits_between_contexts() {
+        let container = TaskRunnerContainer::new("semaphore", "global").await;
+
+        let permit = acquire(&container, &ActionContext::default()).await;
+
+        assert!(
+            try_acquire(&container, &ActionContext::default())
+                .await
+                .is_none()
+        );
+
+        drop(permit);
+
+        assert!(
+            try_acquire(&container, &ActionContext::default())
+                .await
+                .is_some()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn waits_for_global_slots_held_by_other_processes() {
+        let container = TaskRunnerContainer::new("semaphore", "global").await;
+        let slots_dir = container.app_context.moon_env.temp_dir.join("semaphores");
+
+        std::fs::create_dir_all(&slots_dir).unwrap();
+
+        let lock = DaemonLock::try_acquire(&slots_dir.join("global-pool-0.lock"))
+            .unwrap()
+            .unwrap();
+
+        assert!(
+            try_acquire(&container, &ActionContext::default())
+                .await
+                .is_none()
+        );
+
+        drop(lock);
+
+        assert!(
+            try_acquire(&container, &ActionContext::default())
+                .await
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,247 @@
+mod utils;
+
+use moon_action::{ActionStatus, Operation, TaskTestCase, TaskTestStatus};
+use moon_task_runner::test_report_parser::*;
+use std::time::Duration;
+use utils::*;
+
+mod junit {
+    use super::*;
+
+    #[test]
+    fn parses_testcases() {
+        let tests = parse_junit_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="suite" tests="2">
+  <testcase name="a" time="1.5" />
+  <testcase classname="pkg.Class" name="b"></testcase>
+</testsuite>"#,
+        )
+        .unwrap();
+
+        let mut a = TaskTestCase::new("a", TaskTestStatus::Passed);
+        a.suite = Some("suite".into());
+        a.duration = Some(Duration::from_millis(1500));
+
+        let mut b = TaskTestCase::new("b", TaskTestStatus::Passed);
+        b.suite = Some("pkg.Class".into());
+
+        assert_eq!(tests, vec![a, b]);
+    }
+
+    #[test]
+    fn parses_failures_errors_and_skips() {
+        let tests = parse_junit_xml(
+            r#"<testsuites>
+  <testsuite name="suite">
+    <testcase name="failure"><failure message="expected &lt;1&gt;" type="assert">stack</failure></testcase>
+    <testcase name="error"><error><![CDATA[
+TypeError: x is undefined
+    at line 1
+]]></error></testcase>
+    <testcase name="skip"><skipped message="not ready" /></testcase>
+  </testsuite>
+</testsuites>"#,
+        )
+        .unwrap();
+
+        assert_eq!(tests.len(), 3);
+        assert_eq!(tests[0].status, TaskTestStatus::Failed);
+        assert_eq!(tests[0].message.as_deref(), Some("expected <1>"));
+        assert_eq!(tests[1].status, TaskTestStatus::Failed);
+        assert_eq!(
+            tests[1].message.as_deref(),
+            Some("TypeError: x is undefined")
+        );
+        assert_eq!(tests[2].status, TaskTestStatus::Skipped);
+        assert_eq!(tests[2].message.as_deref(), Some("not ready"));
+    }
+
+    #[test]
+    fn supports_gt_in_attribute_values() {
+        let tests = parse_junit_xml(r#"<testsuite><testcase name="a > b" /></testsuite>"#).unwrap();
+
+        assert_eq!(tests[0].name, "a > b");
+    }
+
+    #[test]
+    fn uses_nested_suite_names() {
+        let tests = parse_junit_xml(
+            r#"<testsuites><testsuite name="outer"><testsuite name="inner"><testcase name="a" /></testsuite><testcase name="b" /></testsuite></testsuites>"#,
+        )
+        .unwrap();
+
+        assert_eq!(tests[0].suite.as_deref(), Some("inner"));
+        assert_eq!(tests[1].suite.as_deref(), Some("outer"));
+    }
+
+    #[test]
+    fn decodes_entities_in_messages() {
+        let tests = parse_junit_xml(
+            r#"<testsuite><testcase name="a"><failure>
+  a &amp; b &#x41;
+</failure></testcase></testsuite>"#,
+        )
+        .unwrap();
+
+        assert_eq!(tests[0].message.as_deref(), Some("a & b A"));
+    }
+
+    #[test]
+    fn ignores_invalid_durations() {
+        let tests = parse_junit_xml(
+            r#"<testsuite><testcase name="a" time="-1" /><testcase name="b" time="1e400" /><testcase name="c" time="1,000.5" /></testsuite>"#,
+        )
+        .unwrap();
+
+        assert_eq!(tests[0].duration, None);
+        assert_eq!(tests[1].duration, None);
+        assert_eq!(tests[2].duration, Some(Duration::from_millis(1_000_500)));
+    }
+
+    #[test]
+    fn errors_for_invalid_xml() {
+        assert!(parse_junit_xml("<testsuite><testcase name=a /></testsuite>").is_err());
+        assert!(parse_junit_xml("<testsuite><testcase").is_err());
+        assert!(parse_junit_xml("not xml").is_err());
+    }
+}
+
+mod tap {
+    use super::*;
+
+    #[test]
+    fn parses_test_points() {
+        let tests = parse_tap(
+            "TAP version 14
+1..5
+ok 1 - first
+not ok 2 - second
+  ---
+  message: 'expected true'
+  ...
+ok 3 third # SKIP not supported
+not ok 4 - fourth # TODO implement
+ok
+",
+        );
+
+        assert_eq!(
+            tests
+                .iter()
+                .map(|test| (test.name.as_str(), test.status))
+                .collect::<Vec<_>>(),
+            vec![
+                ("first", TaskTestStatus::Passed),
+                ("second", TaskTestStatus::Failed),
+                ("third", TaskTestStatus::Skipped),
+                ("fourth", TaskTestStatus::Skipped),
+                ("", TaskTestStatus::Passed),
+            ]
+        );
+        assert_eq!(tests[1].message.as_deref(), Some("expected true"));
+        assert_eq!(tests[2].message.as_deref(), Some("not supported"));
+    }
+
+    #[test]
+    fn ignores_subtests_and_other_lines() {
+        let tests = parse_tap(
+            "# Subtest: group
+    ok 1 - nested
+    1..1
+ok 1 - group
+okay this is not a test
+Bail out!
+",
+        );
+
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].name, "group");
+    }
+}
+
+mod test_report_parser {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn parses_files_and_globs() {
+        let container = TaskRunnerContainer::new("test-reports", "test").await;
+        let parser =
+            TestReportParser::new(&container.app_context, &container.project, &container.task);
+
+        let results = parser.parse().unwrap().unwrap();
+
+        assert_eq!(
+            results.reports,
+            vec!["project/reports/junit.xml", "project/reports/unit.tap"]
+        );
+        assert_eq!(results.passed, 2);
+        assert_eq!(results.failed, 2);
+        assert_eq!(results.skipped, 1);
+        assert_eq!(
+            results
+                .get_failed_tests()
+                .map(|test| test.get_label())
+                .collect::<Vec<_>>(),
+            vec!["math › subtracts", "formats"]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn returns_none_if_no_reports() {
+        let container = TaskRunnerContainer::new("test-reports", "missing").await;
+        let parser =
+            TestReportParser::new(&container.app_context, &container.project, &container.task);
+
+        assert!(parser.parse().unwrap().is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn cleans_existing_reports() {
+        let container = TaskRunnerContainer::new("test-reports", "test").await;
+        let parser =
+            TestReportParser::new(&container.app_context, &container.project, &container.task);
+
+        parser.clean().unwrap();
+
+        assert!(
+            !container
+                .sandbox
+                .path()
+                .join("project/reports/junit.xml")
+                .exists()
+        );
+        assert!(
+            !container
+                .sandbox
+                .path()
+                .join("project/reports/unit.tap")
+                .exists()
+        );
+        assert!(parser.parse().unwrap().is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn only_parses_in_runner_when_executed_or_hydrated() {
+        let container = TaskRunnerContainer::new("test-reports", "test").await;
+        let mut runner = container.create_runner();
+
+        runner.parse_test_reports();
+
+        assert!(runner.tests.is_none());
+
+        let mut operation = Operation::output_hydration();
+        operation.finish(ActionStatus::Skipped);
+        runner.operations.push(operation);
+        runner.parse_test_reports();
+
+        assert!(runner.tests.is_none());
+
+        let mut operation = Operation::task_execution("noop");
+        operation.finish(ActionStatus::Failed);
+        runner.operations.push(operation);
+        runner.parse_test_reports();
+
+        assert_eq!(runner.tests.unwrap().failed, 2);
+    }
+}
@@ -264,6 +264,29 @@ impl Task {
         Ok(list.into_iter().collect())
     }
 
+    /// Return true if the provided token appears anywhere in the task's
+    /// command, args, env, inputs, outputs, or script, before expansion.
+    pub fn has_token(&self, token: &str) -> bool {
+        self.command.value.contains(token)
+            || self.args.iter().any(|arg| arg.value.contains(token))
+            || self
+                .env
+                .values()
+                .any(|value| value.as_ref().is_some_and(|value| value.contains(token)))
+            || self
+                .inputs
+                .iter()
+                .any(|input| input.as_str().contains(token))
+            || self
+                .outputs
+                .iter()
+                .any(|output| output.as_str().contains(token))
+            || self
+                .script
+                .as_ref()
+                .is_some_and(|script| script.contains(token))
+    }
+
     pub fn has_outputs(&self) -> bool {
         !self.outputs.is_empty() || !self.output_files.is_empty() || !self.output_globs.is_empty()
     }
@@ -1,7 +1,8 @@
 use moon_common::cacheable;
 use moon_config::{
-    Input, MergeStrategy, TaskOperatingSystem, TaskOptionAffectedFilesPattern, TaskOptionCache,
-    TaskOptionRunInCI, TaskOutputStyle, TaskPriority, TaskUnixShell, TaskWindowsShell,
+    Input, MergeStrategy, Output, TaskOperatingSystem, TaskOptionAffectedFilesPattern,
+    TaskOptionCache, TaskOptionRunInCI, TaskOutputStyle, TaskPriority, TaskUnixShell,
+    TaskWindowsShell,
 };
 
 cacheable!(
@@ -16,6 +17,29 @@ cacheable!(
     }
 );
 
+cacheable!(
+    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    #[serde(default)]
+    pub struct TaskOptionResources {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub cpu: Option<u32>,
+
+        // In bytes
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub memory: Option<u64>,
+    }
+);
+
+cacheable!(
+    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    #[serde(default)]
+    pub struct TaskOptionSemaphore {
+        pub global: bool,
+        pub name: String,
+        pub permits: u32,
+    }
+);
+
 cacheable!(
     #[derive(Clone, Debug, Eq, PartialEq)]
     #[serde(default)]
@@ -71,6 +95,9 @@ cacheable!(
 
         pub priority: TaskPriority,
 
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub resources: Option<TaskOptionResources>,
+
         pub retry_count: u8,
 
         pub run_deps_in_parallel: bool,
@@ -82,9 +109,17 @@ cacheable!(
 
         pub run_from_workspace_root: bool,
 
+        pub sandbox: bool,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub semaphore: Option<TaskOptionSemaphore>,
+
         #[serde(skip_serializing_if = "Option::is_none")]
         pub shell: Option<bool>,
 
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub test_reports: Option<Vec<Output>>,
+
         #[serde(skip_serializing_if = "Option::is_none")]
         pub timeout: Option<u64>,
 
@@ -119,12 +154,16 @@ impl Default for TaskOptions {
             output_style: None,
             persistent: false,
             priority: TaskPriority::Normal,
+            resources: None,
             retry_count: 0,
             run_deps_in_parallel: true,
             run_in_sync_phase: false,
             run_from_workspace_root: false,
             run_in_ci: TaskOptionRunInCI::Enabled(true),
+            sandbox: false,
+            semaphore: None,
             shell: Some(true),
+            test_reports: None,
             timeout: None,
             unix_shell: TaskUnixShell::Bash,
             windows_shell: TaskWindowsShell::Pwsh,
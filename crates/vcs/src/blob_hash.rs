@@ -0,0 +1,34 @@
+use std::path::Path;
+
+/// Compute a hash for the file contents that is identical to `git hash-object`,
+/// so that hashes are interchangeable between VCS clients, and with a colocated
+/// git repository. Used by clients that cannot hash working copy files natively.
+pub fn hash_blob_contents(path: &Path) -> std::io::Result<String> {
+    let data = std::fs::read(path)?;
+
+    let hash = gix::objs::compute_hash(gix::hash::Kind::Sha1, gix::objs::Kind::Blob, &data)
+        .map_err(std::io::Error::other)?;
+
+    Ok(hash.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_like_git() {
+        let dir = std::env::temp_dir().join("moon-vcs-blob-hash");
+        let file = dir.join("file.txt");
+
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(&file, "hello\n").unwrap();
+
+        assert_eq!(
+            hash_blob_contents(&file).unwrap(),
+            "ce013625030ba8dba906f756967f9e9ca394464a"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -338,6 +338,21 @@ impl Vcs for Git {
         Ok(output_to_trimmed_string(&output.stdout))
     }
 
+    async fn get_local_tag(&self) -> miette::Result<Option<String>> {
+        // Fails when no tags exist, which is not an error for us
+        let Ok(output) = self
+            .create_command(["describe", "--tags", "--abbrev=0"])
+            .exec_capture_output()
+            .await
+        else {
+            return Ok(None);
+        };
+
+        let tag = output_to_trimmed_string(&output.stdout);
+
+        Ok(if tag.is_empty() { None } else { Some(tag) })
+    }
+
     async fn get_default_branch(&self) -> miette::Result<String> {
         Ok(self.default_branch.clone())
     }
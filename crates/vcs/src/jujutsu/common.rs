@@ -0,0 +1,248 @@
+use super::jj_error::JujutsuError;
+use crate::changed_files::*;
+use crate::git::common::normalize_branch_ref;
+use moon_process::{Command, CommandArg};
+use regex::Regex;
+use rustc_hash::FxHashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+pub static SUMMARY_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(A|C|D|M|R) (.+)$").unwrap());
+
+pub static SYMBOL_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z0-9_][A-Za-z0-9_./\-]*$").unwrap());
+
+pub fn create_command<I, A>(args: I) -> Command
+where
+    I: IntoIterator<Item = A>,
+    A: Into<CommandArg>,
+{
+    let mut command = Command::new("jj");
+
+    // We run non-interactively and parse the output,
+    // so disable anything that would decorate it
+    command.args(["--no-pager", "--color", "never"]);
+    command.args(args);
+
+    // The VCS binary should be available on the system,
+    // so avoid the shell overhead
+    command.no_shell();
+
+    // Always cache the output of jj commands, as they are expensive to run
+    // and we often run the same commands multiple times (e.g. `jj diff`).
+    command.set_cache(true);
+    command
+}
+
+/// Validate that a revision (typically user provided) doesn't look like a
+/// command line option, otherwise it can be abused for argument injection.
+/// Note that `@-` is a valid revset, so only reject leading dashes.
+pub fn validate_revision(revision: &str) -> Result<(), JujutsuError> {
+    if revision.starts_with('-') {
+        return Err(JujutsuError::InvalidRevision {
+            revision: revision.to_owned(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Normalize a revision that may have been provided in a git format. Consumers
+/// default to `HEAD` for the head revision, which is the parent of the working
+/// copy commit in jj, and CI providers may pass fully-qualified branch refs.
+pub fn normalize_revision(revision: &str) -> &str {
+    if revision == "HEAD" {
+        "@-"
+    } else {
+        normalize_branch_ref(revision)
+    }
+}
+
+/// Return true if the revision is a plain symbol (bookmark, tag, or commit/change ID),
+/// and not a revset expression, so that it can be combined with a remote (`main@origin`).
+pub fn is_revision_symbol(revision: &str) -> bool {
+    SYMBOL_PATTERN.is_match(revision)
+}
+
+pub fn clean_jj_version(version: String) -> String {
+    let version = version.trim().to_lowercase();
+    let version = version.strip_prefix("jj").unwrap_or(&version).trim();
+
+    // Development builds append the commit hash: 0.25.0-abcdef
+    let version = version.split(['-', '+', ' ']).next().unwrap_or_default();
+
+    let mut parts = version.split('.');
+
+    format!(
+        "{}.{}.{}",
+        parts.next().unwrap_or("0"),
+        parts.next().unwrap_or("0"),
+        parts.next().unwrap_or("0")
+    )
+}
+
+// Renames and copies are formatted with braces around the differing
+// segments, for example: `dir/{old => new}/file.txt`.
+fn expand_rename(path: &str) -> Option<(String, String)> {
+    let start = path.find('{')?;
+    let end = path[start..].find('}')? + start;
+    let (before, after) = path[start + 1..end].split_once(" => ")?;
+    let prefix = &path[..start];
+    let suffix = &path[end + 1..];
+
+    let join = |middle: &str| {
+        format!("{prefix}{middle}{suffix}")
+            .replace("//", "/")
+            .trim_start_matches('/')
+            .to_owned()
+    };
+
+    Some((join(before), join(after)))
+}
+
+/// Parse the output of `jj diff --summary`, where each line is formatted as
+/// `X path`. Jujutsu has no staging area, so the provided status is used to denote
+/// whether the change exists in the working copy, or between revisions.
+pub fn parse_diff_summary(
+    output: &str,
+    root: &Path,
+    state: ChangedStatus,
+) -> ChangedFiles<PathBuf> {
+    let mut files = FxHashMap::default();
+
+    for line in output.lines() {
+        let Some(caps) = SUMMARY_PATTERN.captures(line) else {
+            continue;
+        };
+
+        let x = caps.get(1).unwrap().as_str();
+        let path = caps.get(2).unwrap().as_str();
+
+        match x {
+            "A" => {
+                files.insert(root.join(path), vec![ChangedStatus::Added, state]);
+            }
+            "D" => {
+                files.insert(root.join(path), vec![ChangedStatus::Deleted, state]);
+            }
+            "M" => {
+                files.insert(root.join(path), vec![ChangedStatus::Modified, state]);
+            }
+            "C" | "R" => {
+                let Some((from, to)) = expand_rename(path) else {
+                    continue;
+                };
+
+                // For renames the original path no longer exists,
+                // so also mark it as deleted
+                if x == "R" {
+                    files.insert(root.join(from), vec![ChangedStatus::Deleted, state]);
+                }
+
+                files.insert(root.join(to), vec![ChangedStatus::Added, state]);
+            }
+            _ => {}
+        };
+    }
+
+    ChangedFiles { files }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions() {
+        assert_eq!(clean_jj_version("jj 0.25.0".into()), "0.25.0");
+        assert_eq!(clean_jj_version(" jj 0.25.0 \n".into()), "0.25.0");
+        assert_eq!(clean_jj_version("jj 0.30.0-a1b2c3d4e5".into()), "0.30.0");
+        assert_eq!(clean_jj_version("jj 0.9".into()), "0.9.0");
+    }
+
+    #[test]
+    fn revisions() {
+        assert!(validate_revision("main").is_ok());
+        assert!(validate_revision("@-").is_ok());
+        assert!(validate_revision("trunk()").is_ok());
+        assert!(validate_revision("").is_ok());
+
+        assert!(validate_revision("-r").is_err());
+        assert!(validate_revision("--config=foo").is_err());
+    }
+
+    #[test]
+    fn normalizes_revisions() {
+        assert_eq!(normalize_revision("HEAD"), "@-");
+        assert_eq!(normalize_revision("refs/heads/main"), "main");
+        assert_eq!(normalize_revision("main"), "main");
+        assert_eq!(normalize_revision("@"), "@");
+    }
+
+    #[test]
+    fn symbols() {
+        assert!(is_revision_symbol("main"));
+        assert!(is_revision_symbol("feature/foo-bar"));
+        assert!(is_revision_symbol("v1.2.3"));
+        assert!(is_revision_symbol("a1b2c3d"));
+
+        assert!(!is_revision_symbol("@-"));
+        assert!(!is_revision_symbol("main@origin"));
+        assert!(!is_revision_symbol("trunk()"));
+        assert!(!is_revision_symbol("main | dev"));
+    }
+
+    #[test]
+    fn renames() {
+        assert_eq!(
+            expand_rename("{old.txt => new.txt}"),
+            Some(("old.txt".into(), "new.txt".into()))
+        );
+        assert_eq!(
+            expand_rename("dir/{a => b}/file.txt"),
+            Some(("dir/a/file.txt".into(), "dir/b/file.txt".into()))
+        );
+        assert_eq!(
+            expand_rename("dir/{ => sub}/file.txt"),
+            Some(("dir/file.txt".into(), "dir/sub/file.txt".into()))
+        );
+        assert_eq!(expand_rename("file.txt"), None);
+    }
+
+    #[test]
+    fn diff_summary() {
+        let root = PathBuf::from("/repo");
+        let changed = parse_diff_summary(
+            "A added.txt\nD deleted.txt\nM dir/modified.txt\nR {old => new}.txt\nunknown\n",
+            &root,
+            ChangedStatus::Unstaged,
+        );
+
+        assert_eq!(
+            changed.files,
+            FxHashMap::from_iter([
+                (
+                    root.join("added.txt"),
+                    vec![ChangedStatus::Added, ChangedStatus::Unstaged]
+                ),
+                (
+                    root.join("deleted.txt"),
+                    vec![ChangedStatus::Deleted, ChangedStatus::Unstaged]
+                ),
+                (
+                    root.join("dir/modified.txt"),
+                    vec![ChangedStatus::Modified, ChangedStatus::Unstaged]
+                ),
+                (
+                    root.join("old.txt"),
+                    vec![ChangedStatus::Deleted, ChangedStatus::Unstaged]
+                ),
+                (
+                    root.join("new.txt"),
+                    vec![ChangedStatus::Added, ChangedStatus::Unstaged]
+                ),
+            ])
+        );
+    }
+}
@@ -0,0 +1,612 @@
+use super::common::*;
+use super::jj_error::JujutsuError;
+use crate::blob_hash::hash_blob_contents;
+use crate::changed_files::*;
+use crate::git::Git;
+use crate::vcs::{Vcs, VcsHookEnvironment};
+use async_trait::async_trait;
+use git_url_parse::types::provider::GenericProvider;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use miette::IntoDiagnostic;
+use moon_common::path::{
+    PathExt, WorkspaceRelativePath, WorkspaceRelativePathBuf, clean_components,
+};
+use moon_process::{Command, CommandArg, find_command_on_path, output_to_trimmed_string};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, warn};
+use version_spec::Version;
+
+#[derive(Debug)]
+pub struct Jujutsu {
+    /// Default branch (bookmark) name.
+    pub default_branch: String,
+
+    /// Ignore rules derived from a `.gitignore` file.
+    pub ignore: Option<Arc<Gitignore>>,
+
+    /// Absolute path to the `.jj` directory of the current workspace.
+    pub jj_dir: PathBuf,
+
+    /// List of remotes to use as merge candidates.
+    pub remote_candidates: Vec<String>,
+
+    /// Root of the repository that contains the `.jj/repo` store. When using
+    /// multiple workspaces, this is the root of the initial workspace.
+    pub repository_root: PathBuf,
+
+    /// Root of the moon workspace. This may be nested within
+    /// the repository root, or workspace root.
+    pub workspace_root: PathBuf,
+
+    /// The current working copy. Either a secondary workspace checkout,
+    /// or the root of the repository itself.
+    pub work_dir: PathBuf,
+}
+
+impl Jujutsu {
+    pub fn load<R: AsRef<Path>, B: AsRef<str>>(
+        workspace_root: R,
+        default_branch: B,
+        remote_candidates: &[String],
+    ) -> miette::Result<Jujutsu> {
+        debug!("Using jj as a version control system");
+
+        let workspace_root = workspace_root.as_ref();
+
+        debug!(
+            starting_dir = ?workspace_root,
+            "Attempting to find a .jj directory"
+        );
+
+        let mut work_dir = workspace_root.to_path_buf();
+        let mut repository_root = workspace_root.to_path_buf();
+
+        match workspace_root
+            .ancestors()
+            .find(|dir| dir.join(".jj").is_dir())
+        {
+            Some(dir) => {
+                work_dir = dir.to_path_buf();
+                repository_root = work_dir.clone();
+
+                // Secondary workspaces contain a `repo` file that
+                // points to the `.jj/repo` directory of the initial workspace
+                let repo_file = work_dir.join(".jj").join("repo");
+
+                if repo_file.is_file() {
+                    let repo_dir =
+                        PathBuf::from(fs::read_to_string(&repo_file).into_diagnostic()?.trim());
+                    let repo_dir = if repo_dir.is_absolute() {
+                        repo_dir
+                    } else {
+                        work_dir.join(".jj").join(repo_dir)
+                    };
+
+                    if let Some(root) = repo_dir.parent().and_then(|dir| dir.parent()) {
+                        repository_root = clean_components(root);
+                    }
+
+                    debug!(
+                        jj = ?work_dir,
+                        "Found a .jj directory (workspace root)"
+                    );
+                } else {
+                    debug!(
+                        jj = ?work_dir,
+                        "Found a .jj directory (repository root)"
+                    );
+                }
+            }
+            None => {
+                debug!("Unable to find .jj, falling back to workspace root");
+            }
+        };
+
+        let mut jj = Jujutsu {
+            default_branch: default_branch.as_ref().to_owned(),
+            ignore: None,
+            jj_dir: work_dir.join(".jj"),
+            remote_candidates: remote_candidates.to_owned(),
+            repository_root,
+            workspace_root: workspace_root.to_path_buf(),
+            work_dir,
+        };
+
+        jj.load_ignore()?;
+
+        Ok(jj)
+    }
+
+    pub fn create_command<I, A>(&self, args: I) -> Command
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<CommandArg>,
+    {
+        let mut command = create_command(args);
+
+        // Run from the working copy root, so that all file paths
+        // in the output are relative from the same location
+        command.cwd(&self.work_dir);
+
+        command
+    }
+
+    /// Return true if a git repository is colocated with jj,
+    /// in which case `git` commands also operate on the repository.
+    pub fn is_colocated(&self) -> bool {
+        self.repository_root.join(".git").exists()
+    }
+
+    fn load_ignore(&mut self) -> miette::Result<()> {
+        // jj uses `.gitignore` files for ignore rules, even when
+        // not using a colocated git repository
+        let ignore_path = self.work_dir.join(".gitignore");
+
+        if ignore_path.exists() {
+            debug!(
+                ignore_file = ?ignore_path,
+                "Loading ignore rules from .gitignore",
+            );
+
+            let mut builder = GitignoreBuilder::new(&self.work_dir);
+
+            if let Some(error) = builder.add(&ignore_path) {
+                return Err(JujutsuError::IgnoreLoadFailed {
+                    path: ignore_path,
+                    error: Box::new(error),
+                }
+                .into());
+            }
+
+            self.ignore = Some(Arc::new(builder.build().map_err(|error| {
+                JujutsuError::IgnoreLoadFailed {
+                    path: ignore_path,
+                    error: Box::new(error),
+                }
+            })?));
+        }
+
+        Ok(())
+    }
+
+    fn get_git_dir(&self) -> Option<PathBuf> {
+        let git_dir = self.repository_root.join(".git");
+
+        if git_dir.is_dir() {
+            return Some(git_dir);
+        }
+
+        // Non-colocated repositories store the git backend within
+        // the store, and the `git_target` file points to it
+        let store_dir = self.repository_root.join(".jj").join("repo").join("store");
+        let target = fs::read_to_string(store_dir.join("git_target")).ok()?;
+        let target = PathBuf::from(target.trim());
+
+        Some(clean_components(if target.is_absolute() {
+            target
+        } else {
+            store_dir.join(target)
+        }))
+    }
+
+    async fn resolve_revision(&self, revset: &str) -> miette::Result<Option<String>> {
+        let output = self
+            .create_command([
+                "log",
+                "--ignore-working-copy",
+                "--no-graph",
+                "-r",
+                revset,
+                "-T",
+                "commit_id ++ \"\\n\"",
+            ])
+            .exec_capture_output()
+            .await?;
+
+        let revision = output_to_trimmed_string(&output.stdout);
+
+        Ok(revision.lines().next().map(|line| line.to_owned()))
+    }
+
+    async fn exec_diff(
+        &self,
+        args: Vec<String>,
+        state: ChangedStatus,
+    ) -> miette::Result<ChangedFiles> {
+        let mut command = self.create_command(["diff", "--summary"]);
+        command.args(args);
+
+        let output = command.exec_capture_output().await?;
+
+        if output.stdout.is_empty() {
+            return Ok(ChangedFiles::default());
+        }
+
+        // Paths are relative from the cwd
+        parse_diff_summary(
+            &output_to_trimmed_string(&output.stdout),
+            &self.work_dir,
+            state,
+        )
+        .into_workspace_relative(&self.workspace_root)
+    }
+}
+
+#[async_trait]
+impl Vcs for Jujutsu {
+    async fn get_local_branch(&self) -> miette::Result<String> {
+        // jj does not have a "current branch", so use the
+        // closest bookmark in the ancestry of the working copy
+        let output = self
+            .create_command([
+                "log",
+                "--ignore-working-copy",
+                "--no-graph",
+                "-r",
+                "latest(heads(::@ & bookmarks()))",
+                "-T",
+                "local_bookmarks.map(|b| b.name()).join(\"\\n\")",
+            ])
+            .exec_capture_output()
+            .await?;
+
+        let branches = output_to_trimmed_string(&output.stdout);
+
+        Ok(branches.lines().next().unwrap_or_default().to_owned())
+    }
+
+    async fn get_local_branch_revision(&self) -> miette::Result<String> {
+        // The working copy commit is rewritten on every snapshot, so use its
+        // parent, which is equivalent to `HEAD` in a colocated git repository
+        Ok(self.resolve_revision("@-").await?.unwrap_or_default())
+    }
+
+    async fn get_local_tag(&self) -> miette::Result<Option<String>> {
+        // Like `git describe`, use the closest tag in the ancestry of the working copy
+        let Ok(output) = self
+            .create_command([
+                "log",
+                "--ignore-working-copy",
+                "--no-graph",
+                "-r",
+                "latest(::@ & tags())",
+                "-T",
+                "tags.map(|t| t.name()).join(\"\\n\")",
+            ])
+            .exec_capture_output()
+            .await
+        else {
+            return Ok(None);
+        };
+
+        let tags = output_to_trimmed_string(&output.stdout);
+
+        Ok(tags.lines().next().map(|tag| tag.to_owned()))
+    }
+
+    async fn get_default_branch(&self) -> miette::Result<String> {
+        Ok(self.default_branch.clone())
+    }
+
+    async fn get_default_branch_revision(&self) -> miette::Result<String> {
+        let branch = normalize_revision(&self.default_branch);
+
+        validate_revision(branch)?;
+
+        let mut candidates = vec![format!("present({branch})")];
+
+        if is_revision_symbol(branch) {
+            for remote in &self.remote_candidates {
+                candidates.push(format!("present({branch}@{remote})"));
+            }
+        }
+
+        Ok(self
+            .resolve_revision(&format!("latest({})", candidates.join(" | ")))
+            .await?
+            .unwrap_or_default())
+    }
+
+    async fn get_file_hashes(
+        &self,
+        files: &[WorkspaceRelativePathBuf],
+        allow_ignored: bool,
+    ) -> miette::Result<BTreeMap<WorkspaceRelativePathBuf, String>> {
+        let mut objects = vec![];
+
+        for file in files {
+            let abs_file = file.to_logical_path(&self.workspace_root);
+
+            // File must exist and must not be a directory
+            if abs_file.exists()
+                && abs_file.is_file()
+                && (allow_ignored || !self.is_ignored(&abs_file))
+            {
+                objects.push((file.to_owned(), abs_file));
+            }
+        }
+
+        if objects.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        // jj does not provide a command for hashing arbitrary working copy
+        // files, so compute git compatible blob hashes ourselves
+        tokio::task::spawn_blocking(move || {
+            let mut map = BTreeMap::new();
+
+            for (file, abs_file) in objects {
+                let hash = hash_blob_contents(&abs_file).map_err(|error| {
+                    JujutsuError::HashFileFailed {
+                        path: abs_file.clone(),
+                        error: Box::new(error),
+                    }
+                })?;
+
+                map.insert(file, hash);
+            }
+
+            Ok(map)
+        })
+        .await
+        .into_diagnostic()?
+    }
+
+    async fn get_file_tree(
+        &self,
+        dir: &WorkspaceRelativePath,
+    ) -> miette::Result<Vec<WorkspaceRelativePathBuf>> {
+        let mut args = vec!["file".to_owned(), "list".to_owned()];
+
+        // Use an absolute path to avoid issues where moon is nested
+        // within the repository and not at the root
+        let abs_dir = dir.to_logical_path(&self.workspace_root);
+        let target_dir = abs_dir.relative_to(&self.work_dir).into_diagnostic()?;
+
+        if !target_dir.as_str().is_empty() && target_dir.as_str() != "." {
+            // Use a fileset so that special characters are not interpreted
+            args.push(format!(
+                "root:\"{}\"",
+                target_dir
+                    .as_str()
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+            ));
+        }
+
+        // This snapshots the working copy, so that
+        // new (untracked) files are also included
+        let output = self.create_command(args).exec_capture_output().await?;
+        let files_output = output_to_trimmed_string(&output.stdout);
+        let mut paths = vec![];
+
+        for file in files_output.lines() {
+            if file.is_empty() {
+                continue;
+            }
+
+            // Paths are relative from the cwd
+            let path = self.work_dir.join(file);
+
+            if path.is_file() {
+                paths.push(path.relative_to(&self.workspace_root).into_diagnostic()?);
+            }
+        }
+
+        Ok(paths)
+    }
+
+    fn get_repository_root(&self) -> miette::Result<PathBuf> {
+        Ok(self.repository_root.clone())
+    }
+
+    async fn get_repository_slug(&self) -> miette::Result<String> {
+        use git_url_parse::GitUrl;
+
+        let output = self
+            .create_command(["git", "remote", "list", "--ignore-working-copy"])
+            .exec_capture_output()
+            .await?;
+        let remotes = output_to_trimmed_string(&output.stdout);
+
+        for candidate in &self.remote_candidates {
+            // Lines are formatted as: <name> <url>
+            if let Some(remote_url) = remotes.lines().find_map(|line| {
+                line.split_once(' ')
+                    .filter(|(name, _)| name == candidate)
+                    .map(|(_, url)| url.trim())
+            }) {
+                return Ok(
+                    if let Ok(url) = GitUrl::parse(remote_url)
+                        .and_then(|url| url.provider_info::<GenericProvider>())
+                    {
+                        url.fullname()
+                    } else {
+                        remote_url.to_owned()
+                    },
+                );
+            }
+        }
+
+        Err(JujutsuError::ExtractRepoSlugFailed.into())
+    }
+
+    async fn get_changed_files(&self) -> miette::Result<ChangedFiles> {
+        // The working copy is a commit itself, so changes
+        // are between it and its parent(s)
+        self.exec_diff(vec!["-r".into(), "@".into()], ChangedStatus::Unstaged)
+            .await
+    }
+
+    async fn get_changed_files_against_previous_revision(
+        &self,
+        revision: &str,
+    ) -> miette::Result<ChangedFiles> {
+        validate_revision(revision)?;
+
+        let revision = if self.is_default_branch(revision) {
+            "@-"
+        } else {
+            normalize_revision(revision)
+        };
+
+        // Diffs against the parent(s), and against the
+        // empty tree when the revision is a root commit
+        self.exec_diff(vec!["-r".into(), revision.into()], ChangedStatus::Staged)
+            .await
+    }
+
+    async fn get_changed_files_between_revisions(
+        &self,
+        base_revision: &str,
+        head_revision: &str, // Can be empty
+    ) -> miette::Result<ChangedFiles> {
+        let base_revision = normalize_revision(base_revision);
+
+        validate_revision(base_revision)?;
+        validate_revision(head_revision)?;
+
+        // An empty head implies the current working copy
+        let head_revision = if head_revision.is_empty() {
+            "@"
+        } else {
+            normalize_revision(head_revision)
+        };
+
+        let mut candidates = vec![format!("present({base_revision})")];
+
+        if is_revision_symbol(base_revision) {
+            for remote in &self.remote_candidates {
+                candidates.push(format!("present({base_revision}@{remote})"));
+            }
+        }
+
+        // Determine the merge base revision based on the base/head. When multiple
+        // candidates resolve, the most recent common ancestor is used, which is
+        // necessary to support stale local bookmarks and forks!
+        let merge_base = self
+            .resolve_revision(&format!(
+                "latest(heads(::({head_revision}) & ::({})))",
+                candidates.join(" | ")
+            ))
+            .await
+            .ok()
+            .flatten();
+
+        let merge_base_revision = match &merge_base {
+            Some(rev) => rev.as_str(),
+            None => {
+                warn!(
+                    base = base_revision,
+                    head = head_revision,
+                    "Unable to resolve a merge base between the base and head revisions, so diffing directly against the base, which may include changes outside of this range. This can happen with shallow clones, or when the base hasn't been fetched.",
+                );
+
+                base_revision
+            }
+        };
+
+        self.exec_diff(
+            vec![
+                "--from".into(),
+                merge_base_revision.into(),
+                "--to".into(),
+                head_revision.into(),
+            ],
+            ChangedStatus::Staged,
+        )
+        .await
+    }
+
+    async fn get_version(&self) -> miette::Result<Version> {
+        let output = self
+            .create_command(["--version"])
+            .exec_capture_output()
+            .await?;
+
+        let version = clean_jj_version(output_to_trimmed_string(&output.stdout));
+
+        Ok(
+            Version::parse(version).map_err(|error| JujutsuError::InvalidVersion {
+                error: Box::new(error),
+            })?,
+        )
+    }
+
+    fn get_working_root(&self) -> miette::Result<PathBuf> {
+        Ok(self.work_dir.clone())
+    }
+
+    fn is_default_branch(&self, branch: &str) -> bool {
+        let default_branch = &self.default_branch;
+
+        if default_branch.as_str() == branch {
+            return true;
+        }
+
+        if default_branch.contains('/') {
+            return default_branch.ends_with(&format!("/{branch}"));
+        }
+
+        false
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.jj_dir.exists() && find_command_on_path("jj").is_some()
+    }
+
+    fn is_ignored(&self, file: &Path) -> bool {
+        if let Some(ignore) = &self.ignore {
+            ignore.matched(file, file.is_dir()).is_ignore()
+        } else {
+            false
+        }
+    }
+
+    async fn is_shallow_checkout(&self) -> miette::Result<bool> {
+        // jj uses git as its storage backend, and a checkout is shallow
+        // when a `shallow` file exists in the git directory
+        Ok(self
+            .get_git_dir()
+            .is_some_and(|git_dir| git_dir.join("shallow").exists()))
+    }
+
+    fn is_worktree(&self) -> bool {
+        self.work_dir != self.repository_root
+    }
+
+    async fn setup_hooks(&self) -> miette::Result<Option<VcsHookEnvironment>> {
+        // jj does not support hooks, but when colocated, git hooks are
+        // still triggered for git operations, so configure those instead
+        if !self.is_colocated() {
+            debug!("jj does not support hooks, and the repository is not colocated with git");
+
+            return Ok(None);
+        }
+
+        Git::load(
+            &self.workspace_root,
+            &self.default_branch,
+            &self.remote_candidates,
+        )?
+        .setup_hooks()
+        .await
+    }
+
+    async fn teardown_hooks(&self) -> miette::Result<()> {
+        if !self.is_colocated() {
+            return Ok(());
+        }
+
+        Git::load(
+            &self.workspace_root,
+            &self.default_branch,
+            &self.remote_candidates,
+        )?
+        .teardown_hooks()
+        .await
+    }
+}
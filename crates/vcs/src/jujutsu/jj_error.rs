@@ -0,0 +1,41 @@
+use miette::Diagnostic;
+use moon_common::{Style, Stylize};
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum JujutsuError {
+    #[diagnostic(code(jj::invalid_version))]
+    #[error("Invalid or unsupported jj version.")]
+    InvalidVersion {
+        #[source]
+        error: Box<version_spec::SpecError>,
+    },
+
+    #[diagnostic(code(jj::ignore::load_failed))]
+    #[error("Failed to load and parse {}.", .path.style(Style::Path))]
+    IgnoreLoadFailed {
+        path: PathBuf,
+        #[source]
+        error: Box<ignore::Error>,
+    },
+
+    #[diagnostic(code(jj::file::hash_failed))]
+    #[error("Failed to hash file {}.", .path.style(Style::Path))]
+    HashFileFailed {
+        path: PathBuf,
+        #[source]
+        error: Box<std::io::Error>,
+    },
+
+    #[diagnostic(code(jj::repository::extract_slug))]
+    #[error("Failed to extract a repository slug from jj remote candidates.")]
+    ExtractRepoSlugFailed,
+
+    #[diagnostic(code(jj::revision::invalid))]
+    #[error(
+        "Invalid jj revision {}, must not start with a dash.",
+        .revision.style(Style::Hash),
+    )]
+    InvalidRevision { revision: String },
+}
@@ -0,0 +1,6 @@
+pub mod common;
+mod jj_client;
+mod jj_error;
+
+pub use jj_client::Jujutsu;
+pub use jj_error::*;
@@ -1,5 +1,8 @@
 pub mod git;
+pub mod jujutsu;
+pub mod mercurial;
 
+mod blob_hash;
 mod changed_files;
 mod vcs;
 
@@ -0,0 +1,487 @@
+use super::hg_error::MercurialError;
+use crate::changed_files::*;
+use crate::git::common::normalize_branch_ref;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use moon_process::{Command, CommandArg};
+use regex::Regex;
+use rustc_hash::FxHashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tracing::debug;
+
+pub static SYMBOL_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z0-9_][A-Za-z0-9_./\-]*$").unwrap());
+
+pub static VERSION_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+)\.(\d+)(?:\.(\d+))?").unwrap());
+
+/// The flavor of Mercurial being used. Sapling is a fork of Mercurial
+/// with a compatible command line interface, but a different binary,
+/// metadata directory, and ignore file format.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum HgFlavor {
+    #[default]
+    Mercurial,
+    Sapling,
+}
+
+impl HgFlavor {
+    pub fn get_bin_name(&self) -> &'static str {
+        match self {
+            Self::Mercurial => "hg",
+            Self::Sapling => "sl",
+        }
+    }
+
+    /// Metadata directories in order of precedence. Older Sapling
+    /// checkouts use the `.hg` directory name.
+    pub fn get_dir_names(&self) -> &'static [&'static str] {
+        match self {
+            Self::Mercurial => &[".hg"],
+            Self::Sapling => &[".sl", ".hg"],
+        }
+    }
+}
+
+impl fmt::Display for HgFlavor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_bin_name())
+    }
+}
+
+pub fn create_command<I, A>(flavor: HgFlavor, args: I) -> Command
+where
+    I: IntoIterator<Item = A>,
+    A: Into<CommandArg>,
+{
+    let mut command = Command::new(flavor.get_bin_name());
+    command.args(args);
+    command.envs([
+        // Ignore user configuration (aliases, defaults, colors, pagers, etc)
+        // that would otherwise alter the output of the commands we parse
+        ("HGPLAIN", "1"),
+        ("SLPLAIN", "1"),
+        // We run non-interactively, so never prompt
+        ("HGINTERACTIVE", "0"),
+    ]);
+
+    // The VCS binary should be available on the system,
+    // so avoid the shell overhead
+    command.no_shell();
+
+    // Always cache the output of hg commands, as they are expensive to run
+    // and we often run the same commands multiple times (e.g. `hg status`).
+    command.set_cache(true);
+    command
+}
+
+/// Validate that a revision (typically user provided) doesn't look like a
+/// command line option, otherwise it can be abused for argument injection.
+pub fn validate_revision(flavor: HgFlavor, revision: &str) -> Result<(), MercurialError> {
+    if revision.starts_with('-') {
+        return Err(MercurialError::InvalidRevision {
+            bin: flavor.to_string(),
+            revision: revision.to_owned(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Normalize a revision that may have been provided in a git format. Consumers
+/// default to `HEAD` for the head revision, which is the working directory
+/// parent (`.`), and CI providers may pass fully-qualified branch refs.
+pub fn normalize_revision(revision: &str) -> &str {
+    if revision == "HEAD" {
+        "."
+    } else {
+        normalize_branch_ref(revision)
+    }
+}
+
+/// Format a revision for use within a revset. Plain symbols (bookmarks, branches,
+/// hashes) are quoted, so that characters like `/` and `-` are not interpreted
+/// as operators, while revset expressions are wrapped in parentheses.
+pub fn format_revset_symbol(revision: &str) -> String {
+    if SYMBOL_PATTERN.is_match(revision) {
+        format!("\"{revision}\"")
+    } else {
+        format!("({revision})")
+    }
+}
+
+pub fn clean_hg_version(version: String) -> String {
+    // Mercurial Distributed SCM (version 6.5.2)
+    // Sapling 0.2.20240718-145624+f4e9df48
+    match VERSION_PATTERN.captures(&version) {
+        Some(caps) => format!(
+            "{}.{}.{}",
+            caps.get(1).map(|m| m.as_str()).unwrap_or("0"),
+            caps.get(2).map(|m| m.as_str()).unwrap_or("0"),
+            caps.get(3).map(|m| m.as_str()).unwrap_or("0"),
+        ),
+        None => "0.0.0".into(),
+    }
+}
+
+/// Parse the output of `hg status --print0`, where each entry is formatted as
+/// `X path\0`. Mercurial has no staging area, but explicitly added and removed
+/// files are scheduled for the next commit, so we treat them as staged.
+/// When comparing revisions (`committed`), all changes are considered staged.
+pub fn parse_status(output: &str, root: &Path, committed: bool) -> ChangedFiles<PathBuf> {
+    let mut files = FxHashMap::default();
+    let state = if committed {
+        ChangedStatus::Staged
+    } else {
+        ChangedStatus::Unstaged
+    };
+
+    for token in output.split('\0') {
+        // X path
+        if token.len() < 3 || token.as_bytes()[1] != b' ' {
+            continue;
+        }
+
+        let x = token.chars().next().unwrap_or_default();
+        let file = root.join(&token[2..]);
+
+        match x {
+            'A' => {
+                files.insert(file, vec![ChangedStatus::Added, ChangedStatus::Staged]);
+            }
+            'R' => {
+                files.insert(file, vec![ChangedStatus::Deleted, ChangedStatus::Staged]);
+            }
+            'M' => {
+                files.insert(file, vec![ChangedStatus::Modified, state]);
+            }
+            // Missing (deleted without `hg remove`)
+            '!' => {
+                files.insert(file, vec![ChangedStatus::Deleted, ChangedStatus::Unstaged]);
+            }
+            // Unknown
+            '?' => {
+                files.insert(file, vec![ChangedStatus::Untracked]);
+            }
+            _ => {}
+        };
+    }
+
+    ChangedFiles { files }
+}
+
+/// Ignore rules derived from a `.hgignore` (Mercurial) or `.gitignore` (Sapling) file.
+/// Mercurial supports multiple syntaxes, of which regular expressions are the default,
+/// so they can't be parsed with gitignore rules alone.
+#[derive(Debug, Default)]
+pub struct HgIgnore {
+    globs: Option<Gitignore>,
+    patterns: Vec<Regex>,
+    root: PathBuf,
+}
+
+impl HgIgnore {
+    pub fn load(flavor: HgFlavor, root: &Path) -> Result<Self, MercurialError> {
+        let mut ignore = HgIgnore {
+            root: root.to_path_buf(),
+            ..Default::default()
+        };
+
+        let ignore_path = root.join(match flavor {
+            HgFlavor::Mercurial => ".hgignore",
+            HgFlavor::Sapling => ".gitignore",
+        });
+
+        if !ignore_path.exists() {
+            return Ok(ignore);
+        }
+
+        debug!(
+            ignore_file = ?ignore_path,
+            "Loading ignore rules from {}",
+            ignore_path.file_name().unwrap().to_string_lossy(),
+        );
+
+        let mut builder = GitignoreBuilder::new(root);
+
+        if flavor == HgFlavor::Sapling {
+            if let Some(error) = builder.add(&ignore_path) {
+                return Err(MercurialError::IgnoreLoadFailed {
+                    path: ignore_path,
+                    error: Box::new(error),
+                });
+            }
+        } else {
+            let content = std::fs::read_to_string(&ignore_path).map_err(|error| {
+                MercurialError::IgnoreLoadFailed {
+                    path: ignore_path.clone(),
+                    error: Box::new(error.into()),
+                }
+            })?;
+
+            ignore.parse_hgignore(&mut builder, &ignore_path, &content)?;
+        }
+
+        ignore.globs = Some(
+            builder
+                .build()
+                .map_err(|error| MercurialError::IgnoreLoadFailed {
+                    path: ignore_path,
+                    error: Box::new(error),
+                })?,
+        );
+
+        Ok(ignore)
+    }
+
+    // https://www.mercurial-scm.org/doc/hgignore.5.html
+    fn parse_hgignore(
+        &mut self,
+        builder: &mut GitignoreBuilder,
+        path: &Path,
+        content: &str,
+    ) -> Result<(), MercurialError> {
+        let mut syntax = "regexp";
+
+        for line in content.lines() {
+            // Comments start with `#`, unless escaped
+            let line = match line.find('#') {
+                Some(index) if index == 0 || line.as_bytes()[index - 1] != b'\\' => &line[..index],
+                _ => line,
+            };
+            let line = line.replace("\\#", "#");
+            let line = line.trim_end();
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if let Some(value) = line.strip_prefix("syntax:") {
+                syntax = match value.trim() {
+                    "re" | "regexp" => "regexp",
+                    "glob" | "relglob" => "glob",
+                    "rootglob" => "rootglob",
+                    _ => syntax,
+                };
+
+                continue;
+            }
+
+            let (kind, pattern) = if let Some(value) = line
+                .strip_prefix("re:")
+                .or_else(|| line.strip_prefix("regexp:"))
+            {
+                ("regexp", value)
+            } else if let Some(value) = line
+                .strip_prefix("glob:")
+                .or_else(|| line.strip_prefix("relglob:"))
+            {
+                ("glob", value)
+            } else if let Some(value) = line
+                .strip_prefix("rootglob:")
+                .or_else(|| line.strip_prefix("path:"))
+            {
+                ("rootglob", value)
+            } else {
+                (syntax, line)
+            };
+
+            if kind == "regexp" {
+                self.patterns.push(Regex::new(pattern).map_err(|error| {
+                    MercurialError::IgnoreInvalidPattern {
+                        path: path.to_path_buf(),
+                        pattern: pattern.to_owned(),
+                        error: Box::new(error),
+                    }
+                })?);
+
+                continue;
+            }
+
+            let result = if kind == "glob" {
+                // Globs are not rooted, and match at any directory depth
+                if pattern.contains('/') && !pattern.starts_with("**/") {
+                    builder.add_line(None, &format!("**/{pattern}"))
+                } else {
+                    builder.add_line(None, pattern)
+                }
+            } else {
+                builder.add_line(None, &format!("/{}", pattern.trim_start_matches('/')))
+            };
+
+            result.map_err(|error| MercurialError::IgnoreLoadFailed {
+                path: path.to_path_buf(),
+                error: Box::new(error),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    pub fn is_ignored(&self, file: &Path) -> bool {
+        let Ok(rel_file) = file.strip_prefix(&self.root) else {
+            return false;
+        };
+
+        if let Some(globs) = &self.globs
+            && globs
+                .matched_path_or_any_parents(rel_file, file.is_dir())
+                .is_ignore()
+        {
+            return true;
+        }
+
+        if self.patterns.is_empty() {
+            return false;
+        }
+
+        // Patterns are matched against the file and all of its
+        // parent directories, relative from the repository root
+        let rel_file = rel_file.to_string_lossy().replace('\\', "/");
+        let mut path = rel_file.as_str();
+
+        loop {
+            if self.patterns.iter().any(|pattern| pattern.is_match(path)) {
+                return true;
+            }
+
+            match path.rfind('/') {
+                Some(index) => path = &path[..index],
+                None => return false,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions() {
+        assert_eq!(
+            clean_hg_version("Mercurial Distributed SCM (version 6.5.2)".into()),
+            "6.5.2"
+        );
+        assert_eq!(
+            clean_hg_version("Mercurial Distributed SCM (version 6.7)\n(see https://mercurial-scm.org for more information)".into()),
+            "6.7.0"
+        );
+        assert_eq!(
+            clean_hg_version("Sapling 0.2.20240718-145624+f4e9df48".into()),
+            "0.2.20240718"
+        );
+        assert_eq!(clean_hg_version("unknown".into()), "0.0.0");
+    }
+
+    #[test]
+    fn revisions() {
+        assert!(validate_revision(HgFlavor::Mercurial, "default").is_ok());
+        assert!(validate_revision(HgFlavor::Mercurial, ".^").is_ok());
+        assert!(validate_revision(HgFlavor::Sapling, "remote/main").is_ok());
+
+        assert!(validate_revision(HgFlavor::Mercurial, "-r").is_err());
+        assert!(validate_revision(HgFlavor::Sapling, "--config=foo").is_err());
+    }
+
+    #[test]
+    fn normalizes_revisions() {
+        assert_eq!(normalize_revision("HEAD"), ".");
+        assert_eq!(normalize_revision("refs/heads/main"), "main");
+        assert_eq!(normalize_revision("main"), "main");
+    }
+
+    #[test]
+    fn formats_revset_symbols() {
+        assert_eq!(format_revset_symbol("main"), "\"main\"");
+        assert_eq!(format_revset_symbol("remote/main"), "\"remote/main\"");
+        assert_eq!(format_revset_symbol("."), "(.)");
+        assert_eq!(format_revset_symbol("p1(main)"), "(p1(main))");
+    }
+
+    #[test]
+    fn status() {
+        let root = PathBuf::from("/repo");
+        let changed = parse_status(
+            "M modified.txt\0A added.txt\0R removed.txt\0! missing.txt\0? unknown.txt\0C clean.txt\0",
+            &root,
+            false,
+        );
+
+        assert_eq!(
+            changed.files,
+            FxHashMap::from_iter([
+                (
+                    root.join("modified.txt"),
+                    vec![ChangedStatus::Modified, ChangedStatus::Unstaged]
+                ),
+                (
+                    root.join("added.txt"),
+                    vec![ChangedStatus::Added, ChangedStatus::Staged]
+                ),
+                (
+                    root.join("removed.txt"),
+                    vec![ChangedStatus::Deleted, ChangedStatus::Staged]
+                ),
+                (
+                    root.join("missing.txt"),
+                    vec![ChangedStatus::Deleted, ChangedStatus::Unstaged]
+                ),
+                (root.join("unknown.txt"), vec![ChangedStatus::Untracked]),
+            ])
+        );
+
+        let changed = parse_status("M modified.txt\0", &root, true);
+
+        assert_eq!(
+            changed.files,
+            FxHashMap::from_iter([(
+                root.join("modified.txt"),
+                vec![ChangedStatus::Modified, ChangedStatus::Staged]
+            )])
+        );
+    }
+
+    #[test]
+    fn hgignore() {
+        let root = PathBuf::from("/repo");
+        let mut ignore = HgIgnore {
+            root: root.clone(),
+            ..Default::default()
+        };
+        let mut builder = GitignoreBuilder::new(&root);
+
+        ignore
+            .parse_hgignore(
+                &mut builder,
+                &root.join(".hgignore"),
+                r"
+# Comment
+\.pyc$
+^build$
+syntax: glob
+*.log
+dist/out
+rootglob:tmp
+re:^cache/
+",
+            )
+            .unwrap();
+
+        ignore.globs = Some(builder.build().unwrap());
+
+        assert!(ignore.is_ignored(&root.join("src/module.pyc")));
+        assert!(ignore.is_ignored(&root.join("build/index.js")));
+        assert!(ignore.is_ignored(&root.join("logs/debug.log")));
+        assert!(ignore.is_ignored(&root.join("packages/a/dist/out")));
+        assert!(ignore.is_ignored(&root.join("tmp/file.txt")));
+        assert!(ignore.is_ignored(&root.join("cache/file.txt")));
+
+        assert!(!ignore.is_ignored(&root.join("src/module.py")));
+        assert!(!ignore.is_ignored(&root.join("packages/build/index.js")));
+        assert!(!ignore.is_ignored(&root.join("packages/tmp/file.txt")));
+        assert!(!ignore.is_ignored(&root.join("packages/cache/file.txt")));
+        assert!(!ignore.is_ignored(Path::new("/other/file.pyc")));
+    }
+}
@@ -0,0 +1,537 @@
+use super::common::*;
+use super::hg_error::MercurialError;
+use crate::blob_hash::hash_blob_contents;
+use crate::changed_files::*;
+use crate::vcs::Vcs;
+use async_trait::async_trait;
+use git_url_parse::types::provider::GenericProvider;
+use miette::IntoDiagnostic;
+use moon_common::path::{
+    PathExt, WorkspaceRelativePath, WorkspaceRelativePathBuf, clean_components,
+};
+use moon_process::{
+    Command, CommandArg, find_command_on_path, output_to_string, output_to_trimmed_string,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, warn};
+use version_spec::Version;
+
+#[derive(Debug)]
+pub struct Mercurial {
+    /// Default branch (or bookmark) name.
+    pub default_branch: String,
+
+    /// The flavor of Mercurial, which determines the binary to execute.
+    pub flavor: HgFlavor,
+
+    /// Absolute path to the `.hg` (or `.sl`) directory of the current checkout.
+    pub hg_dir: PathBuf,
+
+    /// Ignore rules derived from a `.hgignore` or `.gitignore` file.
+    pub ignore: Arc<HgIgnore>,
+
+    /// List of remote paths to use as merge candidates.
+    pub remote_candidates: Vec<String>,
+
+    /// Root of the repository that contains the store. When using
+    /// the share extension, this is the root of the source repository.
+    pub repository_root: PathBuf,
+
+    /// Root of the moon workspace. This may be nested within
+    /// the repository root.
+    pub workspace_root: PathBuf,
+
+    /// The current working directory checkout. Either a shared
+    /// checkout, or the root of the repository itself.
+    pub work_dir: PathBuf,
+}
+
+impl Mercurial {
+    pub fn load<R: AsRef<Path>, B: AsRef<str>>(
+        flavor: HgFlavor,
+        workspace_root: R,
+        default_branch: B,
+        remote_candidates: &[String],
+    ) -> miette::Result<Mercurial> {
+        debug!("Using {flavor} as a version control system");
+
+        let workspace_root = workspace_root.as_ref();
+        let dir_names = flavor.get_dir_names();
+
+        debug!(
+            starting_dir = ?workspace_root,
+            "Attempting to find a {} directory",
+            dir_names.join(" or "),
+        );
+
+        let mut work_dir = workspace_root.to_path_buf();
+        let mut hg_dir = workspace_root.join(dir_names[0]);
+        let mut repository_root = workspace_root.to_path_buf();
+
+        match workspace_root.ancestors().find_map(|dir| {
+            dir_names
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|hg_dir| hg_dir.is_dir())
+        }) {
+            Some(dir) => {
+                work_dir = dir.parent().unwrap().to_path_buf();
+                repository_root = work_dir.clone();
+                hg_dir = dir;
+
+                // Shared checkouts contain a `sharedpath` file that
+                // points to the metadata directory of the source repository
+                if let Ok(shared_path) = fs::read_to_string(hg_dir.join("sharedpath")) {
+                    let shared_dir = PathBuf::from(shared_path.trim());
+                    let shared_dir = if shared_dir.is_absolute() {
+                        shared_dir
+                    } else {
+                        hg_dir.join(shared_dir)
+                    };
+
+                    if let Some(root) = shared_dir.parent() {
+                        repository_root = clean_components(root);
+                    }
+
+                    debug!(
+                        hg = ?hg_dir,
+                        "Found a {} directory (shared checkout)",
+                        dir_names[0],
+                    );
+                } else {
+                    debug!(
+                        hg = ?hg_dir,
+                        "Found a {} directory (repository root)",
+                        dir_names[0],
+                    );
+                }
+            }
+            None => {
+                debug!(
+                    "Unable to find {}, falling back to workspace root",
+                    dir_names[0]
+                );
+            }
+        };
+
+        Ok(Mercurial {
+            default_branch: default_branch.as_ref().to_owned(),
+            flavor,
+            ignore: Arc::new(HgIgnore::load(flavor, &work_dir)?),
+            hg_dir,
+            remote_candidates: remote_candidates.to_owned(),
+            repository_root,
+            workspace_root: workspace_root.to_path_buf(),
+            work_dir,
+        })
+    }
+
+    pub fn create_command<I, A>(&self, args: I) -> Command
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<CommandArg>,
+    {
+        let mut command = create_command(self.flavor, args);
+
+        // Run from the checkout root, as status paths are relative
+        // from the cwd when patterns are provided
+        command.cwd(&self.work_dir);
+
+        command
+    }
+
+    fn get_merge_candidates(&self, revision: &str) -> Vec<String> {
+        let mut candidates = vec![format!("present({})", format_revset_symbol(revision))];
+
+        // Remote bookmarks are formatted as `<remote>/<bookmark>`
+        if SYMBOL_PATTERN.is_match(revision) {
+            for remote in &self.remote_candidates {
+                candidates.push(format!(
+                    "present({})",
+                    format_revset_symbol(&format!("{remote}/{revision}"))
+                ));
+            }
+        }
+
+        candidates
+    }
+
+    async fn resolve_revision(&self, revset: &str) -> miette::Result<Option<String>> {
+        let output = self
+            .create_command(["log", "--rev", revset, "--template", "{node}\\n"])
+            .exec_capture_output()
+            .await?;
+
+        let revision = output_to_trimmed_string(&output.stdout);
+
+        Ok(revision.lines().next().map(|line| line.to_owned()))
+    }
+
+    async fn exec_status(
+        &self,
+        args: Vec<String>,
+        committed: bool,
+    ) -> miette::Result<ChangedFiles> {
+        let mut command = self.create_command(["status", "--print0"]);
+        command.args(args);
+
+        let output = command.exec_capture_output().await?;
+
+        if output.stdout.is_empty() {
+            return Ok(ChangedFiles::default());
+        }
+
+        // Paths are relative from the checkout root
+        parse_status(&output_to_string(&output.stdout), &self.work_dir, committed)
+            .into_workspace_relative(&self.workspace_root)
+    }
+}
+
+#[async_trait]
+impl Vcs for Mercurial {
+    async fn get_local_branch(&self) -> miette::Result<String> {
+        // Bookmarks are the typical branching model,
+        // so prefer the active bookmark when available
+        let output = self
+            .create_command(["log", "--rev", ".", "--template", "{activebookmark}"])
+            .exec_capture_output()
+            .await?;
+
+        let bookmark = output_to_trimmed_string(&output.stdout);
+
+        if !bookmark.is_empty() || self.flavor == HgFlavor::Sapling {
+            return Ok(bookmark);
+        }
+
+        // Otherwise fallback to the named branch (Mercurial only)
+        let output = self
+            .create_command(["branch"])
+            .exec_capture_output()
+            .await?;
+
+        Ok(output_to_trimmed_string(&output.stdout))
+    }
+
+    async fn get_local_branch_revision(&self) -> miette::Result<String> {
+        Ok(self.resolve_revision(".").await?.unwrap_or_default())
+    }
+
+    async fn get_local_tag(&self) -> miette::Result<Option<String>> {
+        // Fails in an empty repository, which is not an error for us
+        let Ok(output) = self
+            .create_command(["log", "--rev", ".", "--template", "{latesttag}"])
+            .exec_capture_output()
+            .await
+        else {
+            return Ok(None);
+        };
+
+        let tag = output_to_trimmed_string(&output.stdout);
+
+        // Mercurial reports "null" when no tags are reachable
+        Ok(if tag.is_empty() || tag == "null" {
+            None
+        } else {
+            Some(tag)
+        })
+    }
+
+    async fn get_default_branch(&self) -> miette::Result<String> {
+        Ok(self.default_branch.clone())
+    }
+
+    async fn get_default_branch_revision(&self) -> miette::Result<String> {
+        let branch = normalize_revision(&self.default_branch);
+
+        validate_revision(self.flavor, branch)?;
+
+        Ok(self
+            .resolve_revision(&format!(
+                "max({})",
+                self.get_merge_candidates(branch).join(" + ")
+            ))
+            .await?
+            .unwrap_or_default())
+    }
+
+    async fn get_file_hashes(
+        &self,
+        files: &[WorkspaceRelativePathBuf],
+        allow_ignored: bool,
+    ) -> miette::Result<BTreeMap<WorkspaceRelativePathBuf, String>> {
+        let mut objects = vec![];
+
+        for file in files {
+            let abs_file = file.to_logical_path(&self.workspace_root);
+
+            // File must exist and must not be a directory
+            if abs_file.exists()
+                && abs_file.is_file()
+                && (allow_ignored || !self.is_ignored(&abs_file))
+            {
+                objects.push((file.to_owned(), abs_file));
+            }
+        }
+
+        if objects.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        // Mercurial's manifest stores filelog node IDs, which are not content
+        // hashes, so compute git compatible blob hashes for every file. This keeps
+        // hashes stable regardless of whether a file is clean or modified.
+        tokio::task::spawn_blocking(move || {
+            let mut map = BTreeMap::new();
+
+            for (file, abs_file) in objects {
+                let hash = hash_blob_contents(&abs_file).map_err(|error| {
+                    MercurialError::HashFileFailed {
+                        path: abs_file.clone(),
+                        error: Box::new(error),
+                    }
+                })?;
+
+                map.insert(file, hash);
+            }
+
+            Ok(map)
+        })
+        .await
+        .into_diagnostic()?
+    }
+
+    async fn get_file_tree(
+        &self,
+        dir: &WorkspaceRelativePath,
+    ) -> miette::Result<Vec<WorkspaceRelativePathBuf>> {
+        let mut args = vec![
+            "status".to_owned(),
+            // Includes tracked and untracked files, but not
+            // ignored, removed, or missing files
+            "--modified".to_owned(),
+            "--added".to_owned(),
+            "--clean".to_owned(),
+            "--unknown".to_owned(),
+            "--no-status".to_owned(),
+            // We use this option so that file names with special characters
+            // are displayed as-is and are not quoted/escaped
+            "--print0".to_owned(),
+        ];
+
+        // Use an absolute path to avoid issues where moon is nested
+        // within the repository and not at the root
+        let abs_dir = dir.to_logical_path(&self.workspace_root);
+        let target_dir = abs_dir.relative_to(&self.work_dir).into_diagnostic()?;
+
+        if !target_dir.as_str().is_empty() && target_dir.as_str() != "." {
+            args.push(format!("path:{target_dir}"));
+        }
+
+        let output = self.create_command(args).exec_capture_output().await?;
+        let files_output = output_to_string(&output.stdout);
+        let mut paths = vec![];
+
+        for file in files_output.split('\0') {
+            if file.is_empty() {
+                continue;
+            }
+
+            // Paths are relative from the cwd
+            let path = self.work_dir.join(file);
+
+            if path.is_file() {
+                paths.push(path.relative_to(&self.workspace_root).into_diagnostic()?);
+            }
+        }
+
+        Ok(paths)
+    }
+
+    fn get_repository_root(&self) -> miette::Result<PathBuf> {
+        Ok(self.repository_root.clone())
+    }
+
+    async fn get_repository_slug(&self) -> miette::Result<String> {
+        use git_url_parse::GitUrl;
+
+        // Mercurial names the primary remote path "default"
+        let mut candidates = self.remote_candidates.clone();
+        candidates.push("default".into());
+
+        for candidate in candidates {
+            if let Ok(output) = self
+                .create_command(["paths", &candidate])
+                .exec_capture_output()
+                .await
+            {
+                let remote_url = output_to_trimmed_string(&output.stdout);
+
+                if remote_url.is_empty() {
+                    continue;
+                }
+
+                return Ok(
+                    if let Ok(url) = GitUrl::parse(&remote_url)
+                        .and_then(|url| url.provider_info::<GenericProvider>())
+                    {
+                        url.fullname()
+                    } else {
+                        remote_url
+                    },
+                );
+            }
+        }
+
+        Err(MercurialError::ExtractRepoSlugFailed {
+            bin: self.flavor.to_string(),
+        }
+        .into())
+    }
+
+    async fn get_changed_files(&self) -> miette::Result<ChangedFiles> {
+        self.exec_status(vec![], false).await
+    }
+
+    async fn get_changed_files_against_previous_revision(
+        &self,
+        revision: &str,
+    ) -> miette::Result<ChangedFiles> {
+        validate_revision(self.flavor, revision)?;
+
+        let revision = if self.is_default_branch(revision) {
+            "."
+        } else {
+            normalize_revision(revision)
+        };
+
+        // Compares against the first parent, and against the
+        // empty revision when the revision is a root commit
+        self.exec_status(vec!["--change".into(), revision.into()], true)
+            .await
+    }
+
+    async fn get_changed_files_between_revisions(
+        &self,
+        base_revision: &str,
+        head_revision: &str, // Can be empty
+    ) -> miette::Result<ChangedFiles> {
+        let base_revision = normalize_revision(base_revision);
+
+        validate_revision(self.flavor, base_revision)?;
+        validate_revision(self.flavor, head_revision)?;
+
+        // An empty head implies the current working directory, but the
+        // merge base requires a real revision, so use its parent
+        let resolved_head_revision = if head_revision.is_empty() {
+            "."
+        } else {
+            normalize_revision(head_revision)
+        };
+
+        // Determine the merge base revision based on the base/head. When multiple
+        // candidates resolve, the most recent common ancestor is used, which is
+        // necessary to support stale local bookmarks and forks!
+        let merge_base = self
+            .resolve_revision(&format!(
+                "last(heads(::{} and ::({})))",
+                format_revset_symbol(resolved_head_revision),
+                self.get_merge_candidates(base_revision).join(" + ")
+            ))
+            .await
+            .ok()
+            .flatten();
+
+        let merge_base_revision = match &merge_base {
+            Some(rev) => rev.as_str(),
+            None => {
+                warn!(
+                    base = base_revision,
+                    head = resolved_head_revision,
+                    "Unable to resolve a merge base between the base and head revisions, so diffing directly against the base, which may include changes outside of this range. This can happen with shallow clones, or when the base hasn't been pulled.",
+                );
+
+                base_revision
+            }
+        };
+
+        let mut args = vec!["--rev".to_owned(), merge_base_revision.to_owned()];
+
+        // Pass the head as-is, as an empty value implies a
+        // comparison against the current working directory
+        if !head_revision.is_empty() {
+            args.push("--rev".into());
+            args.push(resolved_head_revision.into());
+        }
+
+        self.exec_status(args, true).await
+    }
+
+    async fn get_version(&self) -> miette::Result<Version> {
+        let output = self
+            .create_command(["--version"])
+            .exec_capture_output()
+            .await?;
+
+        let version = clean_hg_version(output_to_trimmed_string(&output.stdout));
+
+        Ok(
+            Version::parse(version).map_err(|error| MercurialError::InvalidVersion {
+                bin: self.flavor.to_string(),
+                error: Box::new(error),
+            })?,
+        )
+    }
+
+    fn get_working_root(&self) -> miette::Result<PathBuf> {
+        Ok(self.work_dir.clone())
+    }
+
+    fn is_default_branch(&self, branch: &str) -> bool {
+        let default_branch = &self.default_branch;
+
+        if default_branch.as_str() == branch {
+            return true;
+        }
+
+        if default_branch.contains('/') {
+            return default_branch.ends_with(&format!("/{branch}"));
+        }
+
+        false
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.hg_dir.exists() && find_command_on_path(self.flavor.get_bin_name()).is_some()
+    }
+
+    fn is_ignored(&self, file: &Path) -> bool {
+        self.ignore.is_ignored(file)
+    }
+
+    async fn is_shallow_checkout(&self) -> miette::Result<bool> {
+        // Mercurial has no shallow clones, but the remotefilelog extension (and
+        // Sapling's lazy repositories) only fetch file history on demand, which
+        // is equivalent, and is denoted by a requirement of the repository
+        for requires_file in [
+            self.hg_dir.join("requires"),
+            self.hg_dir.join("store").join("requires"),
+        ] {
+            if let Ok(requires) = fs::read_to_string(requires_file)
+                && requires
+                    .lines()
+                    .any(|line| matches!(line.trim(), "remotefilelog" | "shallowrepo"))
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_worktree(&self) -> bool {
+        self.work_dir != self.repository_root
+    }
+}
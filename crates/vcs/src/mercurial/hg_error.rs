@@ -0,0 +1,55 @@
+use miette::Diagnostic;
+use moon_common::{Style, Stylize};
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum MercurialError {
+    #[diagnostic(code(hg::invalid_version))]
+    #[error("Invalid or unsupported {bin} version.")]
+    InvalidVersion {
+        bin: String,
+        #[source]
+        error: Box<version_spec::SpecError>,
+    },
+
+    #[diagnostic(code(hg::ignore::load_failed))]
+    #[error("Failed to load and parse {}.", .path.style(Style::Path))]
+    IgnoreLoadFailed {
+        path: PathBuf,
+        #[source]
+        error: Box<ignore::Error>,
+    },
+
+    #[diagnostic(code(hg::ignore::invalid_pattern))]
+    #[error(
+        "Invalid pattern {} in {}.",
+        .pattern.style(Style::Symbol),
+        .path.style(Style::Path),
+    )]
+    IgnoreInvalidPattern {
+        path: PathBuf,
+        pattern: String,
+        #[source]
+        error: Box<regex::Error>,
+    },
+
+    #[diagnostic(code(hg::file::hash_failed))]
+    #[error("Failed to hash file {}.", .path.style(Style::Path))]
+    HashFileFailed {
+        path: PathBuf,
+        #[source]
+        error: Box<std::io::Error>,
+    },
+
+    #[diagnostic(code(hg::repository::extract_slug))]
+    #[error("Failed to extract a repository slug from {bin} remote paths.")]
+    ExtractRepoSlugFailed { bin: String },
+
+    #[diagnostic(code(hg::revision::invalid))]
+    #[error(
+        "Invalid {bin} revision {}, must not start with a dash.",
+        .revision.style(Style::Hash),
+    )]
+    InvalidRevision { bin: String, revision: String },
+}
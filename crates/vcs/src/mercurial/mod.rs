@@ -0,0 +1,7 @@
+pub mod common;
+mod hg_client;
+mod hg_error;
+
+pub use common::HgFlavor;
+pub use hg_client::Mercurial;
+pub use hg_error::*;
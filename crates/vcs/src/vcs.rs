@@ -21,6 +21,11 @@ pub trait Vcs: Debug {
     /// Get the revision hash/number of the local branch's HEAD.
     async fn get_local_branch_revision(&self) -> miette::Result<String>;
 
+    /// Get the latest tag reachable from the local branch's HEAD, if any.
+    async fn get_local_tag(&self) -> miette::Result<Option<String>> {
+        Ok(None)
+    }
+
     /// Get the remote checkout default name. Typically master/main on git, and trunk on svn.
     async fn get_default_branch(&self) -> miette::Result<String>;
 
@@ -127,6 +127,21 @@ mod git {
         assert_eq!(git.get_version().await.unwrap().major, 2);
     }
 
+    #[tokio::test]
+    async fn returns_latest_tag() {
+        let (sandbox, git) = create_git_sandbox("vcs");
+
+        assert_eq!(git.get_local_tag().await.unwrap(), None);
+
+        sandbox.run_git(|cmd| {
+            cmd.args(["tag", "v1.0.0"]);
+        });
+
+        let git = Git::load(sandbox.path(), "master", &["origin".into()]).unwrap();
+
+        assert_eq!(git.get_local_tag().await.unwrap().unwrap(), "v1.0.0");
+    }
+
     mod root {
         use super::*;
 
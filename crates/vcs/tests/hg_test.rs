@@ -0,0 +1,420 @@
+use moon_common::path::{RelativePath, RelativePathBuf, WorkspaceRelativePathBuf};
+use moon_vcs::{ChangedFiles, ChangedStatus, Vcs, mercurial::*};
+use rustc_hash::FxHashMap;
+use starbase_sandbox::{Sandbox, create_sandbox};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn run_hg<I, A>(dir: &Path, args: I) -> String
+where
+    I: IntoIterator<Item = A>,
+    A: AsRef<std::ffi::OsStr>,
+{
+    let output = Command::new("hg")
+        .args(args)
+        .env("HGPLAIN", "1")
+        .env("HGUSER", "moon <fakeemail@moonrepo.dev>")
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8_lossy(&output.stdout).trim().to_owned()
+}
+
+// Commit all fixture files on the `default` branch
+fn enable_hg(sandbox: &Sandbox) {
+    run_hg(sandbox.path(), ["init"]);
+    run_hg(
+        sandbox.path(),
+        ["commit", "--addremove", "-m", "Initial commit"],
+    );
+}
+
+fn load_hg(root: &Path) -> Mercurial {
+    Mercurial::load(HgFlavor::Mercurial, root, "default", &["default".into()]).unwrap()
+}
+
+fn create_hg_sandbox(fixture: &str) -> (Sandbox, Mercurial) {
+    let sandbox = create_sandbox(fixture);
+    enable_hg(&sandbox);
+
+    let hg = load_hg(sandbox.path());
+
+    (sandbox, hg)
+}
+
+fn create_hg_sandbox_with_ignored(fixture: &str) -> (Sandbox, Mercurial) {
+    let sandbox = create_sandbox(fixture);
+    sandbox.create_file(".hgignore", "syntax: glob\nfoo/*.txt\n");
+    enable_hg(&sandbox);
+
+    let hg = load_hg(sandbox.path());
+
+    (sandbox, hg)
+}
+
+fn create_nested_hg_sandbox() -> (Sandbox, Mercurial) {
+    let sandbox = create_sandbox("nested");
+    enable_hg(&sandbox);
+
+    let hg = load_hg(&sandbox.path().join("frontend"));
+
+    (sandbox, hg)
+}
+
+fn create_changed_map<
+    S: IntoIterator<Item = ChangedStatus>,
+    I: IntoIterator<Item = V>,
+    V: AsRef<str>,
+>(
+    statuses: S,
+    files: I,
+) -> FxHashMap<WorkspaceRelativePathBuf, Vec<ChangedStatus>> {
+    let statuses = statuses.into_iter().collect::<Vec<_>>();
+
+    FxHashMap::from_iter(
+        files
+            .into_iter()
+            .map(|v| (WorkspaceRelativePathBuf::from(v.as_ref()), statuses.clone())),
+    )
+}
+
+mod hg {
+    use super::*;
+
+    #[test]
+    fn loads_repository() {
+        let (sandbox, hg) = create_hg_sandbox("vcs");
+
+        assert_eq!(hg.hg_dir, sandbox.path().join(".hg"));
+        assert_eq!(hg.repository_root, sandbox.path());
+        assert_eq!(hg.workspace_root, sandbox.path());
+        assert_eq!(hg.work_dir, sandbox.path());
+        assert!(hg.is_enabled());
+        assert!(!hg.is_worktree());
+    }
+
+    #[tokio::test]
+    async fn returns_correct_values() {
+        let (sandbox, hg) = create_hg_sandbox("vcs");
+
+        let revision = run_hg(
+            sandbox.path(),
+            ["log", "--rev", ".", "--template", "{node}"],
+        );
+
+        assert_eq!(revision.len(), 40);
+        assert_eq!(hg.get_local_branch().await.unwrap().as_str(), "default");
+        assert_eq!(hg.get_local_branch_revision().await.unwrap(), revision);
+        assert_eq!(hg.get_default_branch().await.unwrap().as_str(), "default");
+        assert_eq!(hg.get_default_branch_revision().await.unwrap(), revision);
+        assert_eq!(hg.get_repository_root().unwrap(), sandbox.path());
+        assert_eq!(hg.get_working_root().unwrap(), sandbox.path());
+
+        // Activate a bookmark on a new commit
+        run_hg(sandbox.path(), ["bookmark", "feature"]);
+        sandbox.create_file("foo/file7.txt", "");
+        run_hg(sandbox.path(), ["commit", "--addremove", "-m", "Feature"]);
+
+        let hg = load_hg(sandbox.path());
+        let feature_revision = run_hg(
+            sandbox.path(),
+            ["log", "--rev", ".", "--template", "{node}"],
+        );
+
+        assert_ne!(feature_revision, revision);
+        assert_eq!(hg.get_local_branch().await.unwrap().as_str(), "feature");
+        assert_eq!(
+            hg.get_local_branch_revision().await.unwrap(),
+            feature_revision
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_latest_tag() {
+        let (sandbox, hg) = create_hg_sandbox("vcs");
+
+        assert_eq!(hg.get_local_tag().await.unwrap(), None);
+
+        run_hg(sandbox.path(), ["tag", "v1.0.0"]);
+
+        let hg = load_hg(sandbox.path());
+
+        assert_eq!(hg.get_local_tag().await.unwrap(), Some("v1.0.0".into()));
+    }
+
+    mod file_hashing {
+        use super::*;
+
+        #[tokio::test]
+        async fn hashes_a_list_of_files() {
+            let (_sandbox, hg) = create_hg_sandbox("vcs");
+
+            assert_eq!(
+                hg.get_file_hashes(&["foo/file2.txt".into(), "baz/file5.txt".into()], false)
+                    .await
+                    .unwrap(),
+                BTreeMap::from([
+                    (
+                        WorkspaceRelativePathBuf::from("baz/file5.txt"),
+                        "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391".to_owned()
+                    ),
+                    (
+                        WorkspaceRelativePathBuf::from("foo/file2.txt"),
+                        "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391".to_owned()
+                    )
+                ])
+            );
+        }
+
+        #[tokio::test]
+        async fn hashes_clean_and_dirty_files_the_same_way() {
+            let (sandbox, _) = create_hg_sandbox("vcs");
+
+            // Commit a file, and then create an identical untracked file
+            sandbox.create_file("foo/file1.txt", "modified");
+            run_hg(sandbox.path(), ["commit", "-m", "Modify"]);
+            sandbox.create_file("foo/file7.txt", "modified");
+
+            let hg = load_hg(sandbox.path());
+            let map = hg
+                .get_file_hashes(&["foo/file1.txt".into(), "foo/file7.txt".into()], false)
+                .await
+                .unwrap();
+
+            // Matches `git hash-object` for the same contents
+            assert_eq!(
+                map,
+                BTreeMap::from([
+                    (
+                        WorkspaceRelativePathBuf::from("foo/file1.txt"),
+                        "d84012fbd8415354de6b29158b6e5e17c4fda70b".to_owned()
+                    ),
+                    (
+                        WorkspaceRelativePathBuf::from("foo/file7.txt"),
+                        "d84012fbd8415354de6b29158b6e5e17c4fda70b".to_owned()
+                    )
+                ])
+            );
+        }
+
+        #[tokio::test]
+        async fn ignores_files_when_hashing() {
+            let (_sandbox, hg) = create_hg_sandbox_with_ignored("vcs");
+
+            assert_eq!(
+                hg.get_file_hashes(
+                    &[
+                        "foo/file1.txt".into(),
+                        "foo/file2.txt".into(),
+                        "baz/file5.txt".into()
+                    ],
+                    false,
+                )
+                .await
+                .unwrap(),
+                BTreeMap::from([(
+                    WorkspaceRelativePathBuf::from("baz/file5.txt"),
+                    "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391".to_owned()
+                )])
+            );
+        }
+
+        #[tokio::test]
+        async fn cannot_hash_dirs() {
+            let (_sandbox, hg) = create_hg_sandbox("vcs");
+
+            assert_eq!(
+                hg.get_file_hashes(&["foo".into(), "baz".into()], false)
+                    .await
+                    .unwrap(),
+                BTreeMap::new()
+            );
+        }
+    }
+
+    mod file_tree {
+        use super::*;
+
+        #[tokio::test]
+        async fn returns_from_dir() {
+            let (_sandbox, hg) = create_hg_sandbox("vcs");
+
+            let mut list = hg.get_file_tree(RelativePath::new("foo")).await.unwrap();
+            list.sort();
+
+            assert_eq!(
+                list,
+                vec![
+                    RelativePathBuf::from("foo/file1.txt"),
+                    RelativePathBuf::from("foo/file2.txt"),
+                    RelativePathBuf::from("foo/file3.txt"),
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn includes_untracked() {
+            let (sandbox, hg) = create_hg_sandbox("vcs");
+
+            sandbox.create_file("baz/file7.txt", "");
+
+            let list = hg.get_file_tree(RelativePath::new("baz")).await.unwrap();
+
+            assert!(list.contains(&RelativePathBuf::from("baz/file7.txt")));
+        }
+
+        #[tokio::test]
+        async fn removes_nested_workspace_prefix() {
+            let (_sandbox, hg) = create_nested_hg_sandbox();
+
+            let list = hg.get_file_tree(RelativePath::new(".")).await.unwrap();
+
+            assert_eq!(list, vec![RelativePathBuf::from("file.js")]);
+        }
+    }
+
+    mod changed_files {
+        use super::*;
+
+        #[tokio::test]
+        async fn returns_defaults_when_nothing() {
+            let (_sandbox, hg) = create_hg_sandbox("changed");
+
+            assert_eq!(
+                hg.get_changed_files().await.unwrap(),
+                ChangedFiles::default()
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_untracked() {
+            let (sandbox, hg) = create_hg_sandbox("changed");
+
+            sandbox.create_file("added.txt", "");
+
+            assert_eq!(
+                hg.get_changed_files().await.unwrap(),
+                ChangedFiles {
+                    files: create_changed_map([ChangedStatus::Untracked], ["added.txt"]),
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_added() {
+            let (sandbox, hg) = create_hg_sandbox("changed");
+
+            sandbox.create_file("added.txt", "");
+            run_hg(sandbox.path(), ["add", "added.txt"]);
+
+            assert_eq!(
+                hg.get_changed_files().await.unwrap(),
+                ChangedFiles {
+                    files: create_changed_map(
+                        [ChangedStatus::Added, ChangedStatus::Staged],
+                        ["added.txt"]
+                    ),
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_deleted() {
+            let (sandbox, hg) = create_hg_sandbox("changed");
+
+            fs::remove_file(sandbox.path().join("delete-me.txt")).unwrap();
+
+            assert_eq!(
+                hg.get_changed_files().await.unwrap(),
+                ChangedFiles {
+                    files: create_changed_map(
+                        [ChangedStatus::Deleted, ChangedStatus::Unstaged],
+                        ["delete-me.txt"]
+                    ),
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_removed() {
+            let (sandbox, hg) = create_hg_sandbox("changed");
+
+            run_hg(sandbox.path(), ["remove", "delete-me.txt"]);
+
+            assert_eq!(
+                hg.get_changed_files().await.unwrap(),
+                ChangedFiles {
+                    files: create_changed_map(
+                        [ChangedStatus::Deleted, ChangedStatus::Staged],
+                        ["delete-me.txt"]
+                    ),
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_modified() {
+            let (sandbox, hg) = create_hg_sandbox("changed");
+
+            sandbox.create_file("existing.txt", "modified");
+
+            assert_eq!(
+                hg.get_changed_files().await.unwrap(),
+                ChangedFiles {
+                    files: create_changed_map(
+                        [ChangedStatus::Modified, ChangedStatus::Unstaged],
+                        ["existing.txt"]
+                    ),
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_previous_revision() {
+            let (sandbox, _) = create_hg_sandbox("changed");
+
+            sandbox.create_file("existing.txt", "modified");
+            run_hg(sandbox.path(), ["commit", "-m", "Modify"]);
+
+            let hg = load_hg(sandbox.path());
+
+            assert_eq!(
+                hg.get_changed_files_against_previous_revision(".")
+                    .await
+                    .unwrap(),
+                ChangedFiles {
+                    files: create_changed_map(
+                        [ChangedStatus::Modified, ChangedStatus::Staged],
+                        ["existing.txt"]
+                    ),
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn removes_nested_workspace_prefix() {
+            let (sandbox, hg) = create_nested_hg_sandbox();
+
+            sandbox.create_file("frontend/file.js", "modified");
+
+            assert_eq!(
+                hg.get_changed_files().await.unwrap(),
+                ChangedFiles {
+                    files: create_changed_map(
+                        [ChangedStatus::Modified, ChangedStatus::Unstaged],
+                        ["file.js"]
+                    ),
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,440 @@
+use moon_common::path::{RelativePath, RelativePathBuf, WorkspaceRelativePathBuf};
+use moon_vcs::{ChangedFiles, ChangedStatus, Vcs, jujutsu::*};
+use rustc_hash::FxHashMap;
+use starbase_sandbox::{Sandbox, create_sandbox};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn run_jj<I, A>(dir: &Path, args: I) -> String
+where
+    I: IntoIterator<Item = A>,
+    A: AsRef<std::ffi::OsStr>,
+{
+    let output = Command::new("jj")
+        .args(["--no-pager", "--color", "never"])
+        .args(args)
+        .env("JJ_USER", "moon")
+        .env("JJ_EMAIL", "fakeemail@moonrepo.dev")
+        .current_dir(dir)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8_lossy(&output.stdout).trim().to_owned()
+}
+
+// Commit the fixture files and point the `master` bookmark at them,
+// leaving an empty working copy commit on top
+fn enable_jj(sandbox: &Sandbox) {
+    run_jj(sandbox.path(), ["git", "init"]);
+    run_jj(sandbox.path(), ["commit", "-m", "Initial commit"]);
+    run_jj(sandbox.path(), ["bookmark", "create", "master", "-r", "@-"]);
+}
+
+fn load_jj(root: &Path) -> Jujutsu {
+    Jujutsu::load(root, "master", &["origin".into()]).unwrap()
+}
+
+fn create_jj_sandbox(fixture: &str) -> (Sandbox, Jujutsu) {
+    let sandbox = create_sandbox(fixture);
+    enable_jj(&sandbox);
+
+    let jj = load_jj(sandbox.path());
+
+    (sandbox, jj)
+}
+
+fn create_jj_sandbox_with_ignored(fixture: &str) -> (Sandbox, Jujutsu) {
+    let sandbox = create_sandbox(fixture);
+    sandbox.create_file(".gitignore", "foo/*.txt");
+    enable_jj(&sandbox);
+
+    let jj = load_jj(sandbox.path());
+
+    (sandbox, jj)
+}
+
+fn create_nested_jj_sandbox() -> (Sandbox, Jujutsu) {
+    let sandbox = create_sandbox("nested");
+    enable_jj(&sandbox);
+
+    let jj = load_jj(&sandbox.path().join("frontend"));
+
+    (sandbox, jj)
+}
+
+fn create_changed_map<
+    S: IntoIterator<Item = ChangedStatus>,
+    I: IntoIterator<Item = V>,
+    V: AsRef<str>,
+>(
+    statuses: S,
+    files: I,
+) -> FxHashMap<WorkspaceRelativePathBuf, Vec<ChangedStatus>> {
+    let statuses = statuses.into_iter().collect::<Vec<_>>();
+
+    FxHashMap::from_iter(
+        files
+            .into_iter()
+            .map(|v| (WorkspaceRelativePathBuf::from(v.as_ref()), statuses.clone())),
+    )
+}
+
+mod jj {
+    use super::*;
+
+    #[test]
+    fn loads_repository() {
+        let (sandbox, jj) = create_jj_sandbox("vcs");
+
+        assert_eq!(jj.jj_dir, sandbox.path().join(".jj"));
+        assert_eq!(jj.repository_root, sandbox.path());
+        assert_eq!(jj.workspace_root, sandbox.path());
+        assert_eq!(jj.work_dir, sandbox.path());
+        assert!(jj.is_enabled());
+        assert!(!jj.is_worktree());
+    }
+
+    #[tokio::test]
+    async fn returns_correct_values() {
+        let (sandbox, jj) = create_jj_sandbox("vcs");
+
+        let revision = run_jj(
+            sandbox.path(),
+            ["log", "--no-graph", "-r", "@-", "-T", "commit_id"],
+        );
+
+        assert_eq!(revision.len(), 40);
+        assert_eq!(jj.get_local_branch().await.unwrap().as_str(), "master");
+        assert_eq!(jj.get_local_branch_revision().await.unwrap(), revision);
+        assert_eq!(jj.get_default_branch().await.unwrap().as_str(), "master");
+        assert_eq!(jj.get_default_branch_revision().await.unwrap(), revision);
+        assert_eq!(jj.get_repository_root().unwrap(), sandbox.path());
+        assert_eq!(jj.get_working_root().unwrap(), sandbox.path());
+
+        // Create a new bookmark on top of master
+        sandbox.create_file("foo/file7.txt", "");
+        run_jj(sandbox.path(), ["commit", "-m", "Feature"]);
+        run_jj(
+            sandbox.path(),
+            ["bookmark", "create", "feature", "-r", "@-"],
+        );
+
+        let jj = load_jj(sandbox.path());
+        let feature_revision = run_jj(
+            sandbox.path(),
+            ["log", "--no-graph", "-r", "@-", "-T", "commit_id"],
+        );
+
+        assert_ne!(feature_revision, revision);
+        assert_eq!(jj.get_local_branch().await.unwrap().as_str(), "feature");
+        assert_eq!(
+            jj.get_local_branch_revision().await.unwrap(),
+            feature_revision
+        );
+        assert_eq!(jj.get_default_branch_revision().await.unwrap(), revision);
+    }
+
+    #[tokio::test]
+    async fn returns_latest_bookmark_in_ancestry() {
+        let (sandbox, _) = create_jj_sandbox("vcs");
+
+        // Commits without a bookmark inherit the closest bookmark
+        sandbox.create_file("foo/file7.txt", "");
+        run_jj(sandbox.path(), ["commit", "-m", "Unnamed"]);
+
+        let jj = load_jj(sandbox.path());
+
+        assert_eq!(jj.get_local_branch().await.unwrap().as_str(), "master");
+    }
+
+    #[tokio::test]
+    async fn returns_latest_tag_in_ancestry() {
+        let sandbox = create_sandbox("vcs");
+
+        run_jj(sandbox.path(), ["git", "init", "--colocate"]);
+        run_jj(sandbox.path(), ["commit", "-m", "Initial commit"]);
+
+        let jj = load_jj(sandbox.path());
+
+        assert_eq!(jj.get_local_tag().await.unwrap(), None);
+
+        // Tag the initial commit (`HEAD` in git), and import it into jj
+        let output = Command::new("git")
+            .args(["tag", "v1.0.0"])
+            .current_dir(sandbox.path())
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+
+        run_jj(sandbox.path(), ["git", "import"]);
+
+        // Commits without a tag inherit the closest tag
+        sandbox.create_file("foo/file7.txt", "");
+        run_jj(sandbox.path(), ["commit", "-m", "Untagged"]);
+
+        let jj = load_jj(sandbox.path());
+
+        assert_eq!(jj.get_local_tag().await.unwrap(), Some("v1.0.0".into()));
+    }
+
+    mod file_hashing {
+        use super::*;
+
+        #[tokio::test]
+        async fn hashes_a_list_of_files() {
+            let (_sandbox, jj) = create_jj_sandbox("vcs");
+
+            assert_eq!(
+                jj.get_file_hashes(&["foo/file2.txt".into(), "baz/file5.txt".into()], false)
+                    .await
+                    .unwrap(),
+                BTreeMap::from([
+                    (
+                        WorkspaceRelativePathBuf::from("baz/file5.txt"),
+                        "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391".to_owned()
+                    ),
+                    (
+                        WorkspaceRelativePathBuf::from("foo/file2.txt"),
+                        "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391".to_owned()
+                    )
+                ])
+            );
+        }
+
+        #[tokio::test]
+        async fn hashes_committed_and_modified_files_the_same_way() {
+            let (sandbox, jj) = create_jj_sandbox("vcs");
+
+            sandbox.create_file("foo/file1.txt", "modified");
+            sandbox.create_file("foo/file7.txt", "modified");
+
+            let map = jj
+                .get_file_hashes(&["foo/file1.txt".into(), "foo/file7.txt".into()], false)
+                .await
+                .unwrap();
+
+            // Matches `git hash-object` for the same contents
+            assert_eq!(
+                map,
+                BTreeMap::from([
+                    (
+                        WorkspaceRelativePathBuf::from("foo/file1.txt"),
+                        "d84012fbd8415354de6b29158b6e5e17c4fda70b".to_owned()
+                    ),
+                    (
+                        WorkspaceRelativePathBuf::from("foo/file7.txt"),
+                        "d84012fbd8415354de6b29158b6e5e17c4fda70b".to_owned()
+                    )
+                ])
+            );
+        }
+
+        #[tokio::test]
+        async fn ignores_files_when_hashing() {
+            let (_sandbox, jj) = create_jj_sandbox_with_ignored("vcs");
+
+            assert_eq!(
+                jj.get_file_hashes(
+                    &[
+                        "foo/file1.txt".into(),
+                        "foo/file2.txt".into(),
+                        "baz/file5.txt".into()
+                    ],
+                    false,
+                )
+                .await
+                .unwrap(),
+                BTreeMap::from([(
+                    WorkspaceRelativePathBuf::from("baz/file5.txt"),
+                    "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391".to_owned()
+                )])
+            );
+        }
+
+        #[tokio::test]
+        async fn cannot_hash_dirs() {
+            let (_sandbox, jj) = create_jj_sandbox("vcs");
+
+            assert_eq!(
+                jj.get_file_hashes(&["foo".into(), "baz".into()], false)
+                    .await
+                    .unwrap(),
+                BTreeMap::new()
+            );
+        }
+    }
+
+    mod file_tree {
+        use super::*;
+
+        #[tokio::test]
+        async fn returns_from_dir() {
+            let (_sandbox, jj) = create_jj_sandbox("vcs");
+
+            let mut list = jj.get_file_tree(RelativePath::new("foo")).await.unwrap();
+            list.sort();
+
+            assert_eq!(
+                list,
+                vec![
+                    RelativePathBuf::from("foo/file1.txt"),
+                    RelativePathBuf::from("foo/file2.txt"),
+                    RelativePathBuf::from("foo/file3.txt"),
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn removes_nested_workspace_prefix() {
+            let (_sandbox, jj) = create_nested_jj_sandbox();
+
+            let list = jj.get_file_tree(RelativePath::new(".")).await.unwrap();
+
+            assert!(list.contains(&RelativePathBuf::from("file.js")));
+        }
+    }
+
+    mod changed_files {
+        use super::*;
+
+        #[tokio::test]
+        async fn returns_defaults_when_nothing() {
+            let (_sandbox, jj) = create_jj_sandbox("changed");
+
+            assert_eq!(
+                jj.get_changed_files().await.unwrap(),
+                ChangedFiles::default()
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_added() {
+            let (sandbox, jj) = create_jj_sandbox("changed");
+
+            sandbox.create_file("added.txt", "");
+
+            assert_eq!(
+                jj.get_changed_files().await.unwrap(),
+                ChangedFiles {
+                    files: create_changed_map(
+                        [ChangedStatus::Added, ChangedStatus::Unstaged],
+                        ["added.txt"]
+                    ),
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_deleted() {
+            let (sandbox, jj) = create_jj_sandbox("changed");
+
+            fs::remove_file(sandbox.path().join("delete-me.txt")).unwrap();
+
+            assert_eq!(
+                jj.get_changed_files().await.unwrap(),
+                ChangedFiles {
+                    files: create_changed_map(
+                        [ChangedStatus::Deleted, ChangedStatus::Unstaged],
+                        ["delete-me.txt"]
+                    ),
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_modified() {
+            let (sandbox, jj) = create_jj_sandbox("changed");
+
+            sandbox.create_file("existing.txt", "modified");
+
+            assert_eq!(
+                jj.get_changed_files().await.unwrap(),
+                ChangedFiles {
+                    files: create_changed_map(
+                        [ChangedStatus::Modified, ChangedStatus::Unstaged],
+                        ["existing.txt"]
+                    ),
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_previous_revision() {
+            let (sandbox, _) = create_jj_sandbox("changed");
+
+            sandbox.create_file("existing.txt", "modified");
+            run_jj(sandbox.path(), ["commit", "-m", "Modify"]);
+
+            let jj = load_jj(sandbox.path());
+
+            assert_eq!(
+                jj.get_changed_files_against_previous_revision("@-")
+                    .await
+                    .unwrap(),
+                ChangedFiles {
+                    files: create_changed_map(
+                        [ChangedStatus::Modified, ChangedStatus::Staged],
+                        ["existing.txt"]
+                    ),
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_between_revisions() {
+            let (sandbox, _) = create_jj_sandbox("changed");
+
+            sandbox.create_file("added.txt", "");
+            run_jj(sandbox.path(), ["commit", "-m", "Add"]);
+            fs::remove_file(sandbox.path().join("delete-me.txt")).unwrap();
+            run_jj(sandbox.path(), ["commit", "-m", "Delete"]);
+
+            let jj = load_jj(sandbox.path());
+
+            assert_eq!(
+                jj.get_changed_files_between_revisions("master", "@-")
+                    .await
+                    .unwrap(),
+                {
+                    let mut map = ChangedFiles {
+                        files: create_changed_map(
+                            [ChangedStatus::Added, ChangedStatus::Staged],
+                            ["added.txt"],
+                        ),
+                    };
+                    map.files.insert(
+                        "delete-me.txt".into(),
+                        vec![ChangedStatus::Deleted, ChangedStatus::Staged],
+                    );
+                    map
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn removes_nested_workspace_prefix() {
+            let (sandbox, jj) = create_nested_jj_sandbox();
+
+            sandbox.create_file("frontend/file.js", "modified");
+
+            assert_eq!(
+                jj.get_changed_files().await.unwrap(),
+                ChangedFiles {
+                    files: create_changed_map(
+                        [ChangedStatus::Modified, ChangedStatus::Unstaged],
+                        ["file.js"]
+                    ),
+                }
+            );
+        }
+    }
+}
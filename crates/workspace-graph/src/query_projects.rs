@@ -1,5 +1,6 @@
 use crate::WorkspaceGraph;
 use moon_common::{Id, IdExt, color};
+use moon_graph_utils::GraphConnections;
 use moon_project_graph::Project;
 use moon_query::*;
 use std::{fmt::Debug, sync::Arc};
@@ -83,6 +84,14 @@ impl WorkspaceGraph {
             let matches = match condition {
                 Condition::Field { field, .. } => {
                     let result = match field {
+                        Field::Dependents(ids) => {
+                            condition.matches_relations(ids, &self.projects.dependents_of(project))
+                        }
+                        Field::DependsOn(ids) => condition
+                            .matches_relations(ids, &self.projects.dependencies_of(project)),
+                        Field::Depth(depths) => {
+                            condition.matches_number(depths, self.projects.depth_of(project))
+                        }
                         Field::Language(langs) => condition.matches_enum(langs, &project.language),
                         Field::Project(ids) => {
                             if condition.matches(ids, &project.id)? {
@@ -115,6 +124,33 @@ impl WorkspaceGraph {
                                 .and_then(|task_id| condition.matches(ids, task_id))
                                 .unwrap_or_default()
                         })),
+                        Field::TaskInteractive(values) => Ok(self
+                            .tasks
+                            .get_many(&project.task_targets)?
+                            .iter()
+                            .any(|task| {
+                                condition
+                                    .matches_enum(values, &task.is_interactive())
+                                    .unwrap_or_default()
+                            })),
+                        Field::TaskInternal(values) => Ok(self
+                            .tasks
+                            .get_many(&project.task_targets)?
+                            .iter()
+                            .any(|task| {
+                                condition
+                                    .matches_enum(values, &task.is_internal())
+                                    .unwrap_or_default()
+                            })),
+                        Field::TaskPersistent(values) => Ok(self
+                            .tasks
+                            .get_many(&project.task_targets)?
+                            .iter()
+                            .any(|task| {
+                                condition
+                                    .matches_enum(values, &task.is_persistent())
+                                    .unwrap_or_default()
+                            })),
                         Field::TaskTag(tags) => Ok(self
                             .tasks
                             .get_many(&project.task_targets)?
@@ -154,6 +190,9 @@ impl WorkspaceGraph {
                 Condition::Criteria { criteria } => {
                     self.does_project_match_criteria(project, criteria)?
                 }
+                Condition::NegatedCriteria { criteria } => {
+                    !self.does_project_match_criteria(project, criteria)?
+                }
             };
 
             if matches {
@@ -1,5 +1,6 @@
 use crate::WorkspaceGraph;
 use moon_common::color;
+use moon_graph_utils::GraphConnections;
 use moon_project_graph::Project;
 use moon_query::*;
 use moon_task_graph::{Target, Task};
@@ -89,6 +90,15 @@ impl WorkspaceGraph {
             let matches = match condition {
                 Condition::Field { field, .. } => {
                     let result = match field {
+                        Field::Dependents(targets) => {
+                            condition.matches_relations(targets, &self.tasks.dependents_of(task))
+                        }
+                        Field::DependsOn(targets) => {
+                            condition.matches_relations(targets, &self.tasks.dependencies_of(task))
+                        }
+                        Field::Depth(depths) => {
+                            condition.matches_number(depths, self.tasks.depth_of(task))
+                        }
                         Field::Project(ids) => {
                             if let Ok(project_id) = task.target.get_project_id() {
                                 if condition.matches(ids, project_id)? {
@@ -105,6 +115,15 @@ impl WorkspaceGraph {
                             }
                         }
                         Field::Task(ids) => condition.matches(ids, &task.id),
+                        Field::TaskInteractive(values) => {
+                            condition.matches_enum(values, &task.is_interactive())
+                        }
+                        Field::TaskInternal(values) => {
+                            condition.matches_enum(values, &task.is_internal())
+                        }
+                        Field::TaskPersistent(values) => {
+                            condition.matches_enum(values, &task.is_persistent())
+                        }
                         Field::TaskTag(tags) => condition.matches_list(tags, &task.tags),
                         Field::TaskToolchain(ids) => condition.matches_list(ids, &task.toolchains),
                         Field::TaskType(types) => condition.matches_enum(types, &task.type_of),
@@ -117,6 +136,9 @@ impl WorkspaceGraph {
                 Condition::Criteria { criteria } => {
                     self.does_task_match_criteria(task, criteria)?
                 }
+                Condition::NegatedCriteria { criteria } => {
+                    !self.does_task_match_criteria(task, criteria)?
+                }
             };
 
             if matches {
@@ -194,6 +194,10 @@ pub async fn build_project(
         for (task_id, task_config) in extended_data.tasks {
             builder.extend_with_task(task_id, finalize_config(task_config)?);
         }
+
+        if let Some(version) = extended_data.version {
+            builder.set_version(version);
+        }
     }
 
     // Inherit aliases before building in case the project
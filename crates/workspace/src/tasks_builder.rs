@@ -159,3 +159,49 @@ impl WorkspaceTasksBuilder {
         task_graph
     }
 }
+
+/// Verify that all loaded tasks sharing a semaphore configure the same
+/// number of permits, as the first task to acquire from the semaphore
+/// would otherwise determine the count.
+pub(crate) fn validate_task_semaphores<'task>(
+    nodes: impl Iterator<Item = &'task NodeState<Task>>,
+) -> Result<(), TaskGraphError> {
+    let mut semaphores: FxHashMap<&str, (&Target, u32)> = FxHashMap::default();
+
+    for node in nodes {
+        let NodeState::Loaded(task) = node else {
+            continue;
+        };
+
+        let Some(semaphore) = &task.options.semaphore else {
+            continue;
+        };
+
+        let (other_target, other_permits) = *semaphores
+            .entry(&semaphore.name)
+            .or_insert((&task.target, semaphore.permits));
+
+        if other_permits != semaphore.permits {
+            return Err(TaskGraphError::MismatchedSemaphorePermits {
+                name: semaphore.name.clone(),
+                target: task.target.to_string(),
+                permits: semaphore.permits,
+                other_target: other_target.to_string(),
+                other_permits,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Return true if any loaded task references the provided token.
+pub(crate) fn has_task_with_token<'task>(
+    mut nodes: impl Iterator<Item = &'task NodeState<Task>>,
+    token: &str,
+) -> bool {
+    nodes.any(|node| match node {
+        NodeState::Loading => false,
+        NodeState::Loaded(task) => task.has_token(token),
+    })
+}
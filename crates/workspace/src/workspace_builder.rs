@@ -47,6 +47,7 @@ pub const LOCK_FILE_NAME: &str = "workspaceGraph.lock";
 pub const STATE_GRAPH_FILE_NAME: &str = "workspaceGraph.json";
 pub const STATE_CACHE_FILE_NAME: &str = "workspaceGraphStateV1.json";
 
+#[derive(Clone)]
 pub struct WorkspaceBuilderContext {
     pub cache_engine: Arc<CacheEngine>,
     pub config_loader: ConfigLoader,
@@ -156,6 +157,26 @@ impl WorkspaceBuilder {
         Ok(graph)
     }
 
+    /// Create a builder from one that was serialized by another process
+    /// (the daemon), which skips locating and loading projects and tasks.
+    #[instrument(skip_all)]
+    pub fn new_from_serialized(
+        context: WorkspaceBuilderContext,
+        data: &[u8],
+    ) -> miette::Result<WorkspaceBuilder> {
+        let mut graph: WorkspaceBuilder =
+            json::parse(std::str::from_utf8(data).into_diagnostic()?)?;
+
+        debug!(
+            "Loading workspace graph with {} projects from serialized data",
+            graph.project_data.len(),
+        );
+
+        graph.context = Some(Arc::new(context));
+
+        Ok(graph)
+    }
+
     #[instrument(skip_all)]
     pub async fn new_with_cache(
         context: WorkspaceBuilderContext,
@@ -262,6 +283,8 @@ impl WorkspaceBuilder {
     pub async fn build(mut self) -> miette::Result<WorkspaceGraph> {
         self.enforce_constraints()?;
 
+        validate_task_semaphores(self.task_graph.graph().node_weights())?;
+
         let context = self.context.take().unwrap();
 
         let mut graph_context = GraphExpanderContext {
@@ -284,6 +307,21 @@ impl WorkspaceBuilder {
                 if let Ok(repo) = vcs.get_repository_slug().await {
                     graph_context.vcs_repository = Arc::new(repo);
                 }
+
+                // These are expensive to compute (especially in large repos),
+                // so only query them when a task actually references them
+                if has_task_with_token(self.task_graph.graph().node_weights(), "$vcsTag")
+                    && let Ok(Some(tag)) = vcs.get_local_tag().await
+                {
+                    graph_context.vcs_tag = Arc::new(tag);
+                }
+
+                if has_task_with_token(self.task_graph.graph().node_weights(), "@changed(")
+                    && let Ok(changed_files) = vcs.get_changed_files().await
+                {
+                    graph_context.vcs_changed_files =
+                        Arc::new(changed_files.files.into_keys().collect());
+                }
             } else {
                 graph_context.vcs_branch = Arc::new(vcs.get_default_branch().await?);
             }
@@ -575,6 +613,10 @@ impl WorkspaceBuilder {
             for (task_id, task_config) in &extended_data.tasks {
                 builder.extend_with_task(task_id.to_owned(), finalize_config(task_config.clone())?);
             }
+
+            if let Some(version) = &extended_data.version {
+                builder.set_version(version.to_owned());
+            }
         }
 
         // Inherit aliases before building in case the project
@@ -1197,6 +1239,7 @@ mod tests {
     use moon_cache::CacheContext;
     use moon_extension_plugin::ExtensionRegistry;
     use moon_graph_utils::GraphConnections;
+    use moon_task::{TaskOptionSemaphore, TaskOptions};
     use moon_test_utils::create_empty_moon_sandbox;
     use moon_toolchain_plugin::ToolchainRegistry;
 
@@ -1351,4 +1394,53 @@ mod tests {
             vec![lint_target]
         );
     }
+
+    #[test]
+    fn errors_for_mismatched_semaphore_permits() {
+        let create_task = |target: &str, name: &str, permits: u32| {
+            NodeState::Loaded(Task {
+                target: Target::parse(target).unwrap(),
+                options: TaskOptions {
+                    semaphore: Some(TaskOptionSemaphore {
+                        name: name.into(),
+                        permits,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                ..Task::default()
+            })
+        };
+
+        assert!(
+            validate_task_semaphores(
+                [
+                    create_task("a:test", "db", 2),
+                    create_task("b:test", "db", 2),
+                    create_task("c:test", "sim", 1),
+                ]
+                .iter()
+            )
+            .is_ok()
+        );
+
+        let error = validate_task_semaphores(
+            [
+                create_task("a:test", "db", 2),
+                NodeState::Loading,
+                create_task("b:test", "db", 3),
+            ]
+            .iter(),
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            TaskGraphError::MismatchedSemaphorePermits {
+                permits: 3,
+                other_permits: 2,
+                ..
+            }
+        ));
+    }
 }
@@ -2,6 +2,7 @@ use crate::projects_builder::*;
 use crate::tasks_builder::*;
 use crate::workspace_builder::*;
 use crate::workspace_cache::*;
+use miette::IntoDiagnostic;
 use moon_common::Id;
 use moon_common::path::WorkspaceRelativePathBuf;
 use moon_graph_utils::GraphExpanderContext;
@@ -39,6 +40,29 @@ impl WorkspaceBuilderAsync {
         })
     }
 
+    /// Create a builder from one that was serialized by another process
+    /// (the daemon), which skips locating and loading projects and tasks.
+    #[instrument(skip_all)]
+    pub fn new_from_serialized(
+        context: WorkspaceBuilderContext,
+        data: &[u8],
+    ) -> miette::Result<Self> {
+        let mut graph: WorkspaceBuilderAsync =
+            json::parse(std::str::from_utf8(data).into_diagnostic()?)?;
+
+        debug!(
+            "Loading workspace graph with {} projects from serialized data",
+            graph.projects.ids_to_indexes.len(),
+        );
+
+        let context = Arc::new(context);
+
+        graph.projects.context = Some(Arc::clone(&context));
+        graph.context = Some(context);
+
+        Ok(graph)
+    }
+
     #[instrument(skip_all)]
     pub async fn new_with_cache(context: WorkspaceBuilderContext) -> miette::Result<Self> {
         let is_vcs_enabled = context
@@ -202,6 +226,8 @@ impl WorkspaceBuilderAsync {
         // apply to graphs that were loaded from the cache
         self.projects.enforce_constraints()?;
 
+        validate_task_semaphores(self.tasks.graph.graph().node_weights())?;
+
         let mut graph_context = GraphExpanderContext {
             config_dir: context.config_loader.dir.clone(),
             extensions_config: context.extensions_config.clone(),
@@ -222,6 +248,21 @@ impl WorkspaceBuilderAsync {
                 if let Ok(repo) = vcs.get_repository_slug().await {
                     graph_context.vcs_repository = Arc::new(repo);
                 }
+
+                // These are expensive to compute (especially in large repos),
+                // so only query them when a task actually references them
+                if has_task_with_token(self.tasks.graph.graph().node_weights(), "$vcsTag")
+                    && let Ok(Some(tag)) = vcs.get_local_tag().await
+                {
+                    graph_context.vcs_tag = Arc::new(tag);
+                }
+
+                if has_task_with_token(self.tasks.graph.graph().node_weights(), "@changed(")
+                    && let Ok(changed_files) = vcs.get_changed_files().await
+                {
+                    graph_context.vcs_changed_files =
+                        Arc::new(changed_files.files.into_keys().collect());
+                }
             } else {
                 graph_context.vcs_branch = Arc::new(vcs.get_default_branch().await?);
             }
@@ -57,6 +57,10 @@ export interface OperationMetaProcessExecution extends OperationBaseProcessOutpu
 	type: 'process-execution';
 }
 
+export interface OperationMetaSemaphoreAcquisition {
+	type: 'semaphore-acquisition';
+}
+
 export interface OperationMetaSetupOperation extends OperationBaseFileChange {
 	type: 'setup-operation';
 }
@@ -76,6 +80,7 @@ export type OperationMeta =
 	| OperationMetaNoOperation
 	| OperationMetaOutputHydration
 	| OperationMetaProcessExecution
+	| OperationMetaSemaphoreAcquisition
 	| OperationMetaSetupOperation
 	| OperationMetaSyncOperation
 	| OperationMetaTaskExecution;
@@ -106,6 +111,25 @@ export interface Action {
 	operations: Operation[];
 	startedAt?: string | null;
 	status: ActionStatus;
+	tests?: TaskTestResults | null;
+}
+
+export type TaskTestStatus = 'failed' | 'passed' | 'skipped';
+
+export interface TaskTestCase {
+	name: string;
+	suite?: string;
+	status: TaskTestStatus;
+	duration?: Duration;
+	message?: string;
+}
+
+export interface TaskTestResults {
+	failed: number;
+	passed: number;
+	reports: string[];
+	skipped: number;
+	tests: TaskTestCase[];
 }
 
 export interface TargetState {
@@ -144,6 +168,7 @@ export interface ActionContext {
 	primaryTargets: string[];
 	profile: 'cpu' | 'heap' | null;
 	targetStates: Record<string, TargetState>;
+	traceFs: boolean;
 }
 
 export interface RunReport {
@@ -36,6 +36,7 @@ export interface Project {
 	tasks?: Record<Id, Task>;
 	taskTargets?: string[];
 	toolchains?: Id[];
+	version?: string | null;
 }
 
 export interface ProjectFragment {
@@ -21,6 +21,17 @@ export interface InheritedTasks {
 	layers: Record<string, string[]>;
 }
 
+export interface TaskOptionResources {
+	cpu?: number | null;
+	memory?: number | null;
+}
+
+export interface TaskOptionSemaphore {
+	global: boolean;
+	name: string;
+	permits: number;
+}
+
 export interface TaskOptions {
 	affectedFiles?: TaskOptionAffectedFilesConfig | boolean | 'args' | 'env' | null;
 	allowFailure: boolean;
@@ -43,12 +54,16 @@ export interface TaskOptions {
 	outputStyle?: TaskOutputStyle | null;
 	persistent: boolean;
 	priority: TaskPriority;
+	resources?: TaskOptionResources | null;
 	retryCount: number;
 	runDepsInParallel: boolean;
 	runInCI: boolean;
 	runInSyncPhase: boolean;
 	runFromWorkspaceRoot: boolean;
+	sandbox: boolean;
+	semaphore?: TaskOptionSemaphore | null;
 	shell?: boolean | null;
+	testReports?: Output[] | null;
 	timeout?: number | null;
 	unixShell?: TaskUnixShell | null;
 	windowsShell?: TaskWindowsShell | null;
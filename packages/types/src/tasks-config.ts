@@ -249,6 +249,37 @@ export type TaskOutputStyle = 'buffer' | 'buffer-only-failure' | 'hash' | 'none'
  */
 export type TaskPriority = 'critical' | 'high' | 'normal' | 'low';
 
+/**
+ * Resources that a task requires while running, which the action pipeline
+ * reserves from the workspace capacity before running the task.
+ */
+export interface TaskOptionResourcesConfig {
+	/** The number of CPU cores the task utilizes. */
+	cpu?: number | null;
+	/** The amount of memory the task utilizes, for example "8gb" or "512mib". */
+	memory?: string | null;
+}
+
+/**
+ * A named counting semaphore, that limits how many tasks using the
+ * same semaphore can run concurrently.
+ */
+export interface TaskOptionSemaphoreConfig {
+	/**
+	 * Share the permits with other moon processes running on the same
+	 * machine, instead of only within the current action pipeline.
+	 */
+	global: boolean;
+	/** The name of the semaphore. Tasks with the same name share permits. */
+	name: string;
+	/**
+	 * The number of tasks that can hold a permit at the same time.
+	 *
+	 * @default 1
+	 */
+	permits: number;
+}
+
 /** A list of available shells on Unix. */
 export type TaskUnixShell =
 	| 'bash'
@@ -408,6 +439,12 @@ export interface TaskOptionsConfig {
 	 * @default 'normal'
 	 */
 	priority?: TaskPriority | null;
+	/**
+	 * The CPU and memory resources that the task requires while running.
+	 * The task will not be ran until these resources are available.
+	 * @since 2.6.0
+	 */
+	resources?: TaskOptionResourcesConfig | null;
 	/**
 	 * The number of times a failing task will be retried to succeed.
 	 *
@@ -429,11 +466,31 @@ export interface TaskOptionsConfig {
 	runInCI?: boolean | 'always' | 'affected' | 'only' | 'skip' | null;
 	/** Runs the task automatically when executing `moon sync`. */
 	runInSyncPhase?: boolean | null;
+	/**
+	 * Runs the task in a hermetic sandbox, that only contains the task's
+	 * declared inputs and toolchain dependencies. Declared outputs are
+	 * copied back into the workspace after a successful run.
+	 * @since 2.6.0
+	 */
+	sandbox?: boolean | null;
+	/**
+	 * Acquires a permit from a named counting semaphore before running,
+	 * limiting how many tasks using the same semaphore run concurrently.
+	 * @since 2.6.0
+	 */
+	semaphore?: TaskOptionSemaphoreConfig | null;
 	/**
 	 * Runs the task within a shell. When not defined, runs the task
 	 * directly while relying on native `PATH` resolution.
 	 */
 	shell?: boolean | null;
+	/**
+	 * A list of JUnit XML or TAP files (relative from the project root)
+	 * that the task writes test results to. After running, the files
+	 * are parsed into per-test results, and are cached as outputs.
+	 * @since 2.6.0
+	 */
+	testReports?: Output[] | null;
 	/** The maximum time in seconds that a task can run before being cancelled. */
 	timeout?: number | null;
 	/**
@@ -804,6 +861,37 @@ export interface PartialTaskOptionAffectedFilesConfig {
 	passInputsWhenNoMatch?: boolean | null;
 }
 
+/**
+ * Resources that a task requires while running, which the action pipeline
+ * reserves from the workspace capacity before running the task.
+ */
+export interface PartialTaskOptionResourcesConfig {
+	/** The number of CPU cores the task utilizes. */
+	cpu?: number | null;
+	/** The amount of memory the task utilizes, for example "8gb" or "512mib". */
+	memory?: string | null;
+}
+
+/**
+ * A named counting semaphore, that limits how many tasks using the
+ * same semaphore can run concurrently.
+ */
+export interface PartialTaskOptionSemaphoreConfig {
+	/**
+	 * Share the permits with other moon processes running on the same
+	 * machine, instead of only within the current action pipeline.
+	 */
+	global?: boolean | null;
+	/** The name of the semaphore. Tasks with the same name share permits. */
+	name?: string | null;
+	/**
+	 * The number of tasks that can hold a permit at the same time.
+	 *
+	 * @default 1
+	 */
+	permits?: number | null;
+}
+
 /** Options to control task inheritance, execution, and more. */
 export interface PartialTaskOptionsConfig {
 	/** The pattern in which affected files will be passed to the task. */
@@ -939,6 +1027,12 @@ export interface PartialTaskOptionsConfig {
 	 * @default 'normal'
 	 */
 	priority?: TaskPriority | null;
+	/**
+	 * The CPU and memory resources that the task requires while running.
+	 * The task will not be ran until these resources are available.
+	 * @since 2.6.0
+	 */
+	resources?: PartialTaskOptionResourcesConfig | null;
 	/**
 	 * The number of times a failing task will be retried to succeed.
 	 *
@@ -960,11 +1054,31 @@ export interface PartialTaskOptionsConfig {
 	runInCI?: boolean | 'always' | 'affected' | 'only' | 'skip' | null;
 	/** Runs the task automatically when executing `moon sync`. */
 	runInSyncPhase?: boolean | null;
+	/**
+	 * Runs the task in a hermetic sandbox, that only contains the task's
+	 * declared inputs and toolchain dependencies. Declared outputs are
+	 * copied back into the workspace after a successful run.
+	 * @since 2.6.0
+	 */
+	sandbox?: boolean | null;
+	/**
+	 * Acquires a permit from a named counting semaphore before running,
+	 * limiting how many tasks using the same semaphore run concurrently.
+	 * @since 2.6.0
+	 */
+	semaphore?: PartialTaskOptionSemaphoreConfig | null;
 	/**
 	 * Runs the task within a shell. When not defined, runs the task
 	 * directly while relying on native `PATH` resolution.
 	 */
 	shell?: boolean | null;
+	/**
+	 * A list of JUnit XML or TAP files (relative from the project root)
+	 * that the task writes test results to. After running, the files
+	 * are parsed into per-test results, and are cached as outputs.
+	 * @since 2.6.0
+	 */
+	testReports?: Output[] | null;
 	/** The maximum time in seconds that a task can run before being cancelled. */
 	timeout?: number | null;
 	/**
@@ -12,6 +12,11 @@ export interface TemplateFrontmatterConfig {
 	$schema: string;
 	/** Force overwrite a file at the destination if there is a conflict. */
 	force?: boolean;
+	/**
+	 * Only include this file when the expression, evaluated against
+	 * the template variables, is truthy.
+	 */
+	if?: string | null;
 	/** Skip writing this file to the destination. */
 	skip?: boolean;
 	/** Override the destination using a relative file path. */
@@ -26,6 +31,11 @@ export interface PartialTemplateFrontmatterConfig {
 	$schema?: string | null;
 	/** Force overwrite a file at the destination if there is a conflict. */
 	force?: boolean | null;
+	/**
+	 * Only include this file when the expression, evaluated against
+	 * the template variables, is truthy.
+	 */
+	if?: string | null;
 	/** Skip writing this file to the destination. */
 	skip?: boolean | null;
 	/** Override the destination using a relative file path. */
@@ -47,6 +57,11 @@ export interface TemplateVariableArraySetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required: boolean | null;
 	type: 'array';
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when: Record<string, unknown>;
 }
 
 /** Configuration for a template variable. */
@@ -64,6 +79,11 @@ export interface TemplateVariableBoolSetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required: boolean | null;
 	type: 'boolean';
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when: Record<string, unknown>;
 }
 
 export type TemplateVariableEnumDefault = string | string[];
@@ -93,6 +113,11 @@ export interface TemplateVariableEnumSetting {
 	type: 'enum';
 	/** List of acceptable values for this variable. */
 	values: TemplateVariableEnumValue[];
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when?: Record<string, unknown>;
 }
 
 /** Configuration for a template variable. */
@@ -103,6 +128,10 @@ export interface TemplateVariableNumberSetting {
 	default: number;
 	/** Marks the variable as internal, and won't be overwritten via CLI arguments. */
 	internal: boolean;
+	/** The maximum value allowed (inclusive). */
+	max: number | null;
+	/** The minimum value allowed (inclusive). */
+	min: number | null;
 	/** The order in which variables should be prompted for. */
 	order: number | null;
 	/** Prompt the user for a value when the generator is running. */
@@ -110,6 +139,11 @@ export interface TemplateVariableNumberSetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required: boolean | null;
 	type: 'number';
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when: Record<string, unknown>;
 }
 
 /** Configuration for a template variable. */
@@ -127,6 +161,11 @@ export interface TemplateVariableObjectSetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required: boolean | null;
 	type: 'object';
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when: Record<string, unknown>;
 }
 
 /** Configuration for a template variable. */
@@ -137,13 +176,24 @@ export interface TemplateVariableStringSetting {
 	default: string;
 	/** Marks the variable as internal, and won't be overwritten via CLI arguments. */
 	internal: boolean;
+	/** The maximum number of characters allowed. */
+	max: number | null;
+	/** The minimum number of characters allowed. */
+	min: number | null;
 	/** The order in which variables should be prompted for. */
 	order: number | null;
+	/** A regex pattern that the value must match. */
+	pattern: string | null;
 	/** Prompt the user for a value when the generator is running. */
 	prompt: string | null;
 	/** Marks the variable as required, and will not accept an empty value. */
 	required: boolean | null;
 	type: 'string';
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when: Record<string, unknown>;
 }
 
 export type TemplateVariable =
@@ -154,6 +204,15 @@ export type TemplateVariable =
 	| TemplateVariableObjectSetting
 	| TemplateVariableStringSetting;
 
+/** Configures commands to run during the generation lifecycle. */
+export interface TemplateHooksConfig {
+	/**
+	 * A list of commands to run in the destination, after all files
+	 * have been generated. Commands support variable interpolation.
+	 */
+	postGenerate?: string[];
+}
+
 /**
  * Configures a template and its files to be scaffolded.
  * Docs: https://moonrepo.dev/docs/config/template
@@ -169,6 +228,8 @@ export interface TemplateConfig {
 	destination?: string | null;
 	/** Extends one or many other templates. */
 	extends?: Id | Id[];
+	/** Commands to run during the generation lifecycle. */
+	hooks?: TemplateHooksConfig;
 	/** Overrides the identifier of the template, instead of using the folder name. */
 	id?: Id | null;
 	/** A human-readable title for the template. */
@@ -195,6 +256,11 @@ export interface PartialTemplateVariableArraySetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required?: boolean | null;
 	type?: 'array' | null;
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when?: Record<string, unknown> | null;
 }
 
 /** Configuration for a template variable. */
@@ -212,6 +278,11 @@ export interface PartialTemplateVariableBoolSetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required?: boolean | null;
 	type?: 'boolean' | null;
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when?: Record<string, unknown> | null;
 }
 
 export type PartialTemplateVariableEnumDefault = string | string[];
@@ -241,6 +312,11 @@ export interface PartialTemplateVariableEnumSetting {
 	type?: 'enum' | null;
 	/** List of acceptable values for this variable. */
 	values?: PartialTemplateVariableEnumValue[] | null;
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when?: Record<string, unknown> | null;
 }
 
 /** Configuration for a template variable. */
@@ -251,6 +327,10 @@ export interface PartialTemplateVariableNumberSetting {
 	default?: number | null;
 	/** Marks the variable as internal, and won't be overwritten via CLI arguments. */
 	internal?: boolean | null;
+	/** The maximum value allowed (inclusive). */
+	max?: number | null;
+	/** The minimum value allowed (inclusive). */
+	min?: number | null;
 	/** The order in which variables should be prompted for. */
 	order?: number | null;
 	/** Prompt the user for a value when the generator is running. */
@@ -258,6 +338,11 @@ export interface PartialTemplateVariableNumberSetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required?: boolean | null;
 	type?: 'number' | null;
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when?: Record<string, unknown> | null;
 }
 
 /** Configuration for a template variable. */
@@ -275,6 +360,11 @@ export interface PartialTemplateVariableObjectSetting {
 	/** Marks the variable as required, and will not accept an empty value. */
 	required?: boolean | null;
 	type?: 'object' | null;
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when?: Record<string, unknown> | null;
 }
 
 /** Configuration for a template variable. */
@@ -285,13 +375,24 @@ export interface PartialTemplateVariableStringSetting {
 	default?: string | null;
 	/** Marks the variable as internal, and won't be overwritten via CLI arguments. */
 	internal?: boolean | null;
+	/** The maximum number of characters allowed. */
+	max?: number | null;
+	/** The minimum number of characters allowed. */
+	min?: number | null;
 	/** The order in which variables should be prompted for. */
 	order?: number | null;
+	/** A regex pattern that the value must match. */
+	pattern?: string | null;
 	/** Prompt the user for a value when the generator is running. */
 	prompt?: string | null;
 	/** Marks the variable as required, and will not accept an empty value. */
 	required?: boolean | null;
 	type?: 'string' | null;
+	/**
+	 * Only prompt for the variable when other variables have the
+	 * configured values, otherwise the default value is used.
+	 */
+	when?: Record<string, unknown> | null;
 }
 
 export type PartialTemplateVariable =
@@ -302,6 +403,15 @@ export type PartialTemplateVariable =
 	| PartialTemplateVariableObjectSetting
 	| PartialTemplateVariableStringSetting;
 
+/** Configures commands to run during the generation lifecycle. */
+export interface PartialTemplateHooksConfig {
+	/**
+	 * A list of commands to run in the destination, after all files
+	 * have been generated. Commands support variable interpolation.
+	 */
+	postGenerate?: string[] | null;
+}
+
 /**
  * Configures a template and its files to be scaffolded.
  * Docs: https://moonrepo.dev/docs/config/template
@@ -317,6 +427,8 @@ export interface PartialTemplateConfig {
 	destination?: string | null;
 	/** Extends one or many other templates. */
 	extends?: Id | Id[] | null;
+	/** Commands to run during the generation lifecycle. */
+	hooks?: PartialTemplateHooksConfig | null;
 	/** Overrides the identifier of the template, instead of using the folder name. */
 	id?: Id | null;
 	/** A human-readable title for the template. */
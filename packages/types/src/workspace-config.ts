@@ -320,6 +320,26 @@ export interface NotifierConfig {
 
 export type PipelineActionSwitch = null | boolean | Id[];
 
+/** Configures the resource capacity that tasks can reserve while running. */
+export interface PipelineResourcesConfig {
+	/**
+	 * The number of CPU cores available to tasks. When not defined,
+	 * the CPU quota of the current cgroup is used, otherwise the
+	 * number of logical cores.
+	 *
+	 * @env MOON_PIPELINE_RESOURCES_CPU
+	 */
+	cpu: number | null;
+	/**
+	 * The amount of memory available to tasks, for example "16gb".
+	 * When not defined, the memory limit of the current cgroup is used,
+	 * otherwise the total system memory.
+	 *
+	 * @env MOON_PIPELINE_RESOURCES_MEMORY
+	 */
+	memory: string | null;
+}
+
 /** Configures aspects of the action pipeline. */
 export interface PipelineConfig {
 	/**
@@ -337,6 +357,15 @@ export interface PipelineConfig {
 	 * @env MOON_PIPELINE_CACHE_LIFETIME
 	 */
 	cacheLifetime?: string;
+	/**
+	 * The lifetime of task run history, before old daily segments
+	 * are pruned from the cache directory.
+	 * @since 2.6.0
+	 *
+	 * @default '30 days'
+	 * @env MOON_PIPELINE_HISTORY_RETENTION
+	 */
+	historyRetention?: string;
 	/**
 	 * Automatically inherit color settings for all tasks being ran.
 	 *
@@ -360,6 +389,12 @@ export interface PipelineConfig {
 	killProcessThreshold?: number;
 	/** Logs the task's command and arguments when running the task. */
 	logRunningCommand?: boolean;
+	/**
+	 * The resource capacity that tasks with `options.resources` reserve
+	 * from, before they are ran.
+	 * @since 2.6.0
+	 */
+	resources: PipelineResourcesConfig;
 	/**
 	 * When creating `SyncProject` actions, recursively create a `SyncProject`
 	 * action for each project dependency, and link them as a relationship.
@@ -411,7 +446,7 @@ export type WorkspaceProjects = WorkspaceProjectsConfig | string[] | Record<Id,
  * The API format of the remote service.
  * @since 1.32.0
  */
-export type RemoteApi = 'grpc' | 'http';
+export type RemoteApi = 'grpc' | 'http' | 's3';
 
 /**
  * Configures basic HTTP authentication.
@@ -434,6 +469,31 @@ export interface RemoteAuthConfig {
  */
 export type RemoteCompression = 'none' | 'zstd';
 
+/**
+ * A rule that determines whether task outputs may be uploaded to the
+ * remote cache. All conditions that are configured must match.
+ * @since 2.6.0
+ */
+export interface RemoteCacheWriteRule {
+	/**
+	 * Allow (`true`) or deny (`false`) uploads when the rule matches.
+	 *
+	 * @default true
+	 */
+	allow?: boolean;
+	/** Match when the current branch matches one of these glob patterns. */
+	branches?: string[];
+	/** Match when running in a CI environment (`true`) or locally (`false`). */
+	ci?: boolean | null;
+	/**
+	 * Match when on the default branch (`true`) or any other branch (`false`).
+	 * Pull requests never match the default branch.
+	 */
+	defaultBranch?: boolean | null;
+	/** Match when the task has at least one of these tags. */
+	tags?: Id[];
+}
+
 /**
  * Configures the action cache (AC) and content-addressable cache (CAS).
  * @since 1.30.0
@@ -472,6 +532,13 @@ export interface RemoteCacheConfig {
 	 * @env MOON_REMOTE_CACHE_VERIFY_INTEGRITY
 	 */
 	verifyIntegrity?: boolean;
+	/**
+	 * A list of rules that determine whether task outputs may be uploaded
+	 * to the remote cache. The first matching rule is applied, and if no
+	 * rules match, uploads are denied. When empty, uploads are allowed.
+	 * @since 2.6.0
+	 */
+	writePolicy?: RemoteCacheWriteRule[];
 }
 
 /**
@@ -515,6 +582,42 @@ export interface RemoteMtlsConfig {
 	domain?: string | null;
 }
 
+/**
+ * Configures an S3-compatible bucket to store blobs in.
+ * Credentials are read from the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`,
+ * and `AWS_SESSION_TOKEN` environment variables.
+ * @since 2.6.0
+ */
+export interface RemoteS3Config {
+	/**
+	 * The name of the bucket.
+	 *
+	 * @env MOON_REMOTE_S3_BUCKET
+	 */
+	bucket: string;
+	/**
+	 * Use path-style requests (`host/bucket/key`) instead of
+	 * virtual-hosted-style requests (`bucket.host/key`). Required
+	 * by most self-hosted S3-compatible services, like MinIO.
+	 *
+	 * @env MOON_REMOTE_S3_PATH_STYLE
+	 */
+	pathStyle?: boolean;
+	/**
+	 * A prefix to prepend to all object keys, for sharing a bucket.
+	 *
+	 * @env MOON_REMOTE_S3_PREFIX
+	 */
+	prefix?: string | null;
+	/**
+	 * The region of the bucket, used for signing requests.
+	 *
+	 * @default 'us-east-1'
+	 * @env MOON_REMOTE_S3_REGION
+	 */
+	region: string;
+}
+
 /**
  * Configures for server-only authentication with TLS.
  * @since 1.30.0
@@ -553,7 +656,7 @@ export interface RemoteConfig {
 	 *
 	 * @default 'grpc'
 	 * @env MOON_REMOTE_API
-	 * @type {'grpc' | 'http'}
+	 * @type {'grpc' | 'http' | 's3'}
 	 */
 	api: RemoteApi;
 	/**
@@ -565,7 +668,8 @@ export interface RemoteConfig {
 	cache: RemoteCacheConfig;
 	/**
 	 * The remote host to connect and send requests to.
-	 * Supports gRPC protocols.
+	 * Supports gRPC protocols. When using the S3 API, this is
+	 * the endpoint of the S3-compatible service.
 	 *
 	 * @env MOON_REMOTE_HOST
 	 */
@@ -575,12 +679,17 @@ export interface RemoteConfig {
 	 * This takes precedence over normal TLS.
 	 */
 	mtls?: RemoteMtlsConfig | null;
+	/**
+	 * Configures the bucket when using the S3 API.
+	 * @since 2.6.0
+	 */
+	s3?: RemoteS3Config | null;
 	/** Connect to the host using server-only authentication with TLS. */
 	tls?: RemoteTlsConfig | null;
 }
 
 /** The VCS being utilized by the repository. */
-export type VcsClient = 'git';
+export type VcsClient = 'git' | 'jujutsu' | 'mercurial' | 'sapling';
 
 /**
  * The format to use for generated VCS hook files.
@@ -600,7 +709,7 @@ export interface VcsConfig {
 	 * The VCS client being utilized by the repository.
 	 *
 	 * @default 'git'
-	 * @type {'git'}
+	 * @type {'git' | 'jujutsu' | 'mercurial' | 'sapling'}
 	 */
 	client: VcsClient;
 	/**
@@ -1033,6 +1142,26 @@ export interface PartialNotifierConfig {
 
 export type PartialPipelineActionSwitch = null | boolean | Id[];
 
+/** Configures the resource capacity that tasks can reserve while running. */
+export interface PartialPipelineResourcesConfig {
+	/**
+	 * The number of CPU cores available to tasks. When not defined,
+	 * the CPU quota of the current cgroup is used, otherwise the
+	 * number of logical cores.
+	 *
+	 * @env MOON_PIPELINE_RESOURCES_CPU
+	 */
+	cpu?: number | null;
+	/**
+	 * The amount of memory available to tasks, for example "16gb".
+	 * When not defined, the memory limit of the current cgroup is used,
+	 * otherwise the total system memory.
+	 *
+	 * @env MOON_PIPELINE_RESOURCES_MEMORY
+	 */
+	memory?: string | null;
+}
+
 /** Configures aspects of the action pipeline. */
 export interface PartialPipelineConfig {
 	/**
@@ -1050,6 +1179,15 @@ export interface PartialPipelineConfig {
 	 * @env MOON_PIPELINE_CACHE_LIFETIME
 	 */
 	cacheLifetime?: string | null;
+	/**
+	 * The lifetime of task run history, before old daily segments
+	 * are pruned from the cache directory.
+	 * @since 2.6.0
+	 *
+	 * @default '30 days'
+	 * @env MOON_PIPELINE_HISTORY_RETENTION
+	 */
+	historyRetention?: string | null;
 	/**
 	 * Automatically inherit color settings for all tasks being ran.
 	 *
@@ -1073,6 +1211,12 @@ export interface PartialPipelineConfig {
 	killProcessThreshold?: number | null;
 	/** Logs the task's command and arguments when running the task. */
 	logRunningCommand?: boolean | null;
+	/**
+	 * The resource capacity that tasks with `options.resources` reserve
+	 * from, before they are ran.
+	 * @since 2.6.0
+	 */
+	resources?: PartialPipelineResourcesConfig | null;
 	/**
 	 * When creating `SyncProject` actions, recursively create a `SyncProject`
 	 * action for each project dependency, and link them as a relationship.
@@ -1134,6 +1278,31 @@ export interface PartialRemoteAuthConfig {
 	token?: string | null;
 }
 
+/**
+ * A rule that determines whether task outputs may be uploaded to the
+ * remote cache. All conditions that are configured must match.
+ * @since 2.6.0
+ */
+export interface PartialRemoteCacheWriteRule {
+	/**
+	 * Allow (`true`) or deny (`false`) uploads when the rule matches.
+	 *
+	 * @default true
+	 */
+	allow?: boolean | null;
+	/** Match when the current branch matches one of these glob patterns. */
+	branches?: string[] | null;
+	/** Match when running in a CI environment (`true`) or locally (`false`). */
+	ci?: boolean | null;
+	/**
+	 * Match when on the default branch (`true`) or any other branch (`false`).
+	 * Pull requests never match the default branch.
+	 */
+	defaultBranch?: boolean | null;
+	/** Match when the task has at least one of these tags. */
+	tags?: Id[] | null;
+}
+
 /**
  * Configures the action cache (AC) and content-addressable cache (CAS).
  * @since 1.30.0
@@ -1171,6 +1340,13 @@ export interface PartialRemoteCacheConfig {
 	 * @env MOON_REMOTE_CACHE_VERIFY_INTEGRITY
 	 */
 	verifyIntegrity?: boolean | null;
+	/**
+	 * A list of rules that determine whether task outputs may be uploaded
+	 * to the remote cache. The first matching rule is applied, and if no
+	 * rules match, uploads are denied. When empty, uploads are allowed.
+	 * @since 2.6.0
+	 */
+	writePolicy?: PartialRemoteCacheWriteRule[] | null;
 }
 
 /**
@@ -1214,6 +1390,42 @@ export interface PartialRemoteMtlsConfig {
 	domain?: string | null;
 }
 
+/**
+ * Configures an S3-compatible bucket to store blobs in.
+ * Credentials are read from the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`,
+ * and `AWS_SESSION_TOKEN` environment variables.
+ * @since 2.6.0
+ */
+export interface PartialRemoteS3Config {
+	/**
+	 * The name of the bucket.
+	 *
+	 * @env MOON_REMOTE_S3_BUCKET
+	 */
+	bucket?: string | null;
+	/**
+	 * Use path-style requests (`host/bucket/key`) instead of
+	 * virtual-hosted-style requests (`bucket.host/key`). Required
+	 * by most self-hosted S3-compatible services, like MinIO.
+	 *
+	 * @env MOON_REMOTE_S3_PATH_STYLE
+	 */
+	pathStyle?: boolean | null;
+	/**
+	 * A prefix to prepend to all object keys, for sharing a bucket.
+	 *
+	 * @env MOON_REMOTE_S3_PREFIX
+	 */
+	prefix?: string | null;
+	/**
+	 * The region of the bucket, used for signing requests.
+	 *
+	 * @default 'us-east-1'
+	 * @env MOON_REMOTE_S3_REGION
+	 */
+	region?: string | null;
+}
+
 /**
  * Configures for server-only authentication with TLS.
  * @since 1.30.0
@@ -1263,7 +1475,8 @@ export interface PartialRemoteConfig {
 	cache?: PartialRemoteCacheConfig | null;
 	/**
 	 * The remote host to connect and send requests to.
-	 * Supports gRPC protocols.
+	 * Supports gRPC protocols. When using the S3 API, this is
+	 * the endpoint of the S3-compatible service.
 	 *
 	 * @env MOON_REMOTE_HOST
 	 */
@@ -1273,6 +1486,11 @@ export interface PartialRemoteConfig {
 	 * This takes precedence over normal TLS.
 	 */
 	mtls?: PartialRemoteMtlsConfig | null;
+	/**
+	 * Configures the bucket when using the S3 API.
+	 * @since 2.6.0
+	 */
+	s3?: PartialRemoteS3Config | null;
 	/** Connect to the host using server-only authentication with TLS. */
 	tls?: PartialRemoteTlsConfig | null;
 }
@@ -101,6 +101,22 @@ const sidebars: SidebarsConfig = {
 				'commands/overview',
 				'commands/action-graph',
 				'commands/bin',
+				{
+					type: 'category',
+					label: 'cache',
+					items: [
+						'commands/cache/remote/get',
+						'commands/cache/remote/ls',
+						'commands/cache/remote/status',
+					],
+					link: {
+						type: 'generated-index',
+						title: 'cache',
+						description: 'Inspect and manage the cache.',
+						slug: '/commands/cache',
+						keywords: ['cli', 'commands', 'cache'],
+					},
+				},
 				'commands/check',
 				'commands/ci',
 				'commands/clean',
@@ -156,6 +172,7 @@ const sidebars: SidebarsConfig = {
 				},
 				'commands/generate',
 				'commands/hash',
+				'commands/history',
 				'commands/init',
 				'commands/mcp',
 				'commands/project',
@@ -167,6 +184,7 @@ const sidebars: SidebarsConfig = {
 					items: [
 						'commands/query/affected',
 						'commands/query/changed-files',
+						'commands/query/path',
 						'commands/query/projects',
 						'commands/query/tasks',
 					],
@@ -201,6 +219,7 @@ const sidebars: SidebarsConfig = {
 				'commands/task',
 				'commands/tasks',
 				'commands/task-graph',
+				'commands/task-outputs',
 				'commands/teardown',
 				'commands/template',
 				'commands/templates',